@@ -0,0 +1,316 @@
+use async_graphql::http::GQLResponse;
+use async_graphql::{EmptyMutation, EmptySubscription, ObjectType, Schema, SubscriptionType};
+use async_trait::async_trait;
+use futures::executor::block_on;
+use futures::stream::{self, StreamExt};
+use graphql_gateway::{Data, Executor, Gateway, QueryBuilder};
+use serde_json::Value;
+use std::env;
+use std::time::{Duration, Instant};
+
+const MAX_SHARDS: usize = 8;
+
+struct LocalExecutor<Q, M, S>(String, Schema<Q, M, S>)
+where
+    Q: ObjectType + Send + Sync + 'static,
+    M: ObjectType + Send + Sync + 'static,
+    S: SubscriptionType + Send + Sync + 'static;
+
+impl<Q, M, S> Clone for LocalExecutor<Q, M, S>
+where
+    Q: ObjectType + Send + Sync + 'static,
+    M: ObjectType + Send + Sync + 'static,
+    S: SubscriptionType + Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        LocalExecutor(self.0.clone(), self.1.clone())
+    }
+}
+
+#[async_trait]
+impl<Q, M, S> Executor for LocalExecutor<Q, M, S>
+where
+    Q: ObjectType + Send + Sync + 'static,
+    M: ObjectType + Send + Sync + 'static,
+    S: SubscriptionType + Send + Sync + 'static,
+{
+    fn name(&self) -> &str {
+        &self.0
+    }
+
+    async fn execute(
+        &self,
+        _ctx: Option<&Data>,
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<Value>,
+    ) -> Result<Value, String> {
+        let mut builder = async_graphql::QueryBuilder::new(query);
+
+        if let Some(operation_name) = operation_name {
+            builder = builder.operator_name(operation_name);
+        }
+
+        if let Some(variables) = variables {
+            if let Ok(variables) = async_graphql::Variables::parse_from_json(variables) {
+                builder = builder.variables(variables);
+            }
+        }
+
+        serde_json::to_value(GQLResponse(builder.execute(&self.1).await)).map_err(|e| e.to_string())
+    }
+}
+
+mod catalog {
+    use async_graphql::ID;
+
+    pub struct Item(usize);
+
+    #[async_graphql::Object]
+    impl Item {
+        #[field]
+        async fn id(&self) -> ID {
+            ID::from(format!("Item:{}", self.0))
+        }
+
+        #[field]
+        async fn name(&self) -> String {
+            format!("Item {}", self.0)
+        }
+
+        #[field]
+        async fn related(&self, first: i32) -> Vec<Item> {
+            (1..=first as usize)
+                .map(|i| Item(self.0 * 10 + i))
+                .collect()
+        }
+    }
+
+    #[async_graphql::Interface(field(name = "id", type = "ID"))]
+    pub struct Node(Item);
+
+    pub struct Query;
+
+    #[async_graphql::Object]
+    impl Query {
+        #[field]
+        async fn items(&self, first: i32) -> Vec<Item> {
+            (0..first as usize).map(Item).collect()
+        }
+
+        #[field]
+        async fn nodes(&self, ids: Vec<ID>) -> Vec<Option<Node>> {
+            ids.iter()
+                .map(|id| super::parse_id(id).map(|id| Item(id).into()))
+                .collect()
+        }
+    }
+}
+
+macro_rules! shard {
+    ($module:ident, $field:ident) => {
+        mod $module {
+            use async_graphql::ID;
+
+            pub struct Item(usize);
+
+            #[async_graphql::Object]
+            impl Item {
+                #[field]
+                async fn id(&self) -> ID {
+                    ID::from(format!("Item:{}", self.0))
+                }
+
+                #[field]
+                async fn $field(&self) -> String {
+                    format!("{}-{}", stringify!($field), self.0)
+                }
+            }
+
+            #[async_graphql::Interface(field(name = "id", type = "ID"))]
+            pub struct Node(Item);
+
+            pub struct Query;
+
+            #[async_graphql::Object]
+            impl Query {
+                #[field]
+                async fn nodes(&self, ids: Vec<ID>) -> Vec<Option<Node>> {
+                    ids.iter()
+                        .map(|id| super::parse_id(id).map(|id| Item(id).into()))
+                        .collect()
+                }
+            }
+        }
+    };
+}
+
+shard!(shard0, attr0);
+shard!(shard1, attr1);
+shard!(shard2, attr2);
+shard!(shard3, attr3);
+shard!(shard4, attr4);
+shard!(shard5, attr5);
+shard!(shard6, attr6);
+shard!(shard7, attr7);
+
+fn parse_id(id: &str) -> Option<usize> {
+    id.strip_prefix("Item:")?.parse().ok()
+}
+
+fn local<Q: ObjectType + Send + Sync + 'static>(name: &str, query: Q) -> impl Executor {
+    LocalExecutor(
+        name.to_owned(),
+        Schema::new(query, EmptyMutation, EmptySubscription),
+    )
+}
+
+struct Options {
+    executors: usize,
+    size: usize,
+    iterations: usize,
+    concurrency: usize,
+}
+
+impl Options {
+    fn from_args() -> Self {
+        let mut options = Options {
+            executors: 4,
+            size: 50,
+            iterations: 200,
+            concurrency: 16,
+        };
+
+        for arg in env::args().skip(1) {
+            let mut parts = arg.trim_start_matches("--").splitn(2, '=');
+            let (key, value) = match (parts.next(), parts.next().and_then(|v| v.parse().ok())) {
+                (Some(key), Some(value)) => (key, value),
+                _ => panic!("Invalid argument \"{}\", expected --key=value", arg),
+            };
+
+            match key {
+                "executors" => options.executors = value,
+                "size" => options.size = value,
+                "iterations" => options.iterations = value,
+                "concurrency" => options.concurrency = value,
+                _ => panic!("Unknown argument \"{}\"", key),
+            }
+        }
+
+        options.executors = options.executors.min(MAX_SHARDS);
+        options
+    }
+}
+
+fn shard<'a>(gateway: Gateway<'a>, index: usize) -> Gateway<'a> {
+    match index {
+        0 => gateway.executor(local("shard0", shard0::Query {})),
+        1 => gateway.executor(local("shard1", shard1::Query {})),
+        2 => gateway.executor(local("shard2", shard2::Query {})),
+        3 => gateway.executor(local("shard3", shard3::Query {})),
+        4 => gateway.executor(local("shard4", shard4::Query {})),
+        5 => gateway.executor(local("shard5", shard5::Query {})),
+        6 => gateway.executor(local("shard6", shard6::Query {})),
+        7 => gateway.executor(local("shard7", shard7::Query {})),
+        _ => gateway,
+    }
+}
+
+async fn gateway<'a>(executors: usize) -> Gateway<'a> {
+    (0..executors)
+        .fold(
+            Gateway::default().executor(local("catalog", catalog::Query {})),
+            shard,
+        )
+        .build()
+        .await
+        .expect("Failed to build gateway")
+}
+
+fn workloads(options: &Options) -> Vec<(&'static str, String)> {
+    let attrs = (0..options.executors)
+        .map(|i| format!("attr{}", i))
+        .collect::<Vec<String>>()
+        .join(" ");
+    let last_attr = format!("attr{}", options.executors.max(1) - 1);
+    let nested_attr = if options.executors > 0 {
+        last_attr.as_str()
+    } else {
+        "name"
+    };
+
+    vec![
+        (
+            "list",
+            format!("{{ items(first: {}) {{ id name }} }}", options.size),
+        ),
+        (
+            "enrichment",
+            format!("{{ items(first: {}) {{ id name {} }} }}", options.size, attrs),
+        ),
+        (
+            "deep",
+            format!(
+                "{{ items(first: {}) {{ related(first: 3) {{ related(first: 3) {{ name {} }} }} }} }}",
+                (options.size / 10).max(1),
+                nested_attr
+            ),
+        ),
+        (
+            "fragments",
+            format!(
+                "query {{ items(first: {}) {{ ...ItemFields }} }} fragment ItemFields on Item {{ id name {} }}",
+                options.size, nested_attr
+            ),
+        ),
+    ]
+}
+
+fn percentile(latencies: &[Duration], p: f64) -> Duration {
+    let i = ((latencies.len() as f64 - 1.0) * p).round() as usize;
+    latencies.get(i).cloned().unwrap_or_default()
+}
+
+fn main() {
+    let options = Options::from_args();
+    let gateway = block_on(gateway(options.executors));
+
+    println!(
+        "executors: {} (+ catalog), size: {}, iterations: {}, concurrency: {}",
+        options.executors, options.size, options.iterations, options.concurrency
+    );
+
+    for (name, source) in workloads(&options) {
+        let started_at = Instant::now();
+        let gateway = &gateway;
+        let source = &source;
+
+        let mut latencies = block_on(
+            stream::iter(0..options.iterations)
+                .map(|_| async move {
+                    let started_at = Instant::now();
+                    let result = QueryBuilder::new(source.as_str()).execute(gateway).await;
+
+                    if let Err(err) = result {
+                        panic!("Workload failed: {:?}", err);
+                    }
+
+                    started_at.elapsed()
+                })
+                .buffer_unordered(options.concurrency.max(1))
+                .collect::<Vec<Duration>>(),
+        );
+
+        let elapsed = started_at.elapsed();
+        latencies.sort();
+
+        println!(
+            "{:<12} {:>10.1} req/s   p50 {:>8.2?}   p95 {:>8.2?}   p99 {:>8.2?}",
+            name,
+            options.iterations as f64 / elapsed.as_secs_f64(),
+            percentile(&latencies, 0.5),
+            percentile(&latencies, 0.95),
+            percentile(&latencies, 0.99),
+        );
+    }
+}
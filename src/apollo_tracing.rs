@@ -0,0 +1,143 @@
+use crate::context::Context;
+use crate::schema::Type;
+use graphql_parser::query::{Selection, Type as AstType};
+use serde_json::{json, Value};
+use std::sync::Mutex;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+pub(crate) struct ApolloTracing {
+    start_time: SystemTime,
+    started_at: Instant,
+    resolvers: Mutex<Vec<Value>>,
+}
+
+impl ApolloTracing {
+    pub(crate) fn new() -> Self {
+        ApolloTracing {
+            start_time: SystemTime::now(),
+            started_at: Instant::now(),
+            resolvers: Mutex::new(vec![]),
+        }
+    }
+
+    pub(crate) fn offset(&self) -> u64 {
+        self.started_at.elapsed().as_nanos() as u64
+    }
+
+    pub(crate) fn fields(
+        &self,
+        context: &Context<'_, '_>,
+        object_type: &Type,
+        selections: &[Selection<'_, String>],
+        paths: &[Vec<Value>],
+        start_offset: u64,
+    ) {
+        let duration = self.offset().saturating_sub(start_offset);
+        let mut resolvers = self
+            .resolvers
+            .lock()
+            .expect("Tracing resolvers lock is poisoned.");
+
+        for path in paths {
+            for selection in selections {
+                let field = match selection {
+                    Selection::Field(field) if field.name != "__typename" => field,
+                    _ => continue,
+                };
+
+                let return_type =
+                    context
+                        .field(object_type, field.name.as_str())
+                        .map(|(_, schema_field)| {
+                            AstType::<String>::from(schema_field.field_type.clone()).to_string()
+                        });
+
+                let mut field_path = path.clone();
+                field_path.push(Value::from(
+                    field.alias.as_ref().unwrap_or(&field.name).as_str(),
+                ));
+
+                resolvers.push(json!({
+                    "path": field_path,
+                    "parentType": object_type.name(),
+                    "fieldName": field.name,
+                    "returnType": return_type,
+                    "startOffset": start_offset,
+                    "duration": duration,
+                }));
+            }
+        }
+    }
+
+    pub(crate) fn downstream<F>(&self, response: &Value, start_offset: u64, map_path: F)
+    where
+        F: Fn(&[Value]) -> Option<Vec<Value>>,
+    {
+        let downstream = response["extensions"]["tracing"]["execution"]["resolvers"].as_array();
+        let mut resolvers = self
+            .resolvers
+            .lock()
+            .expect("Tracing resolvers lock is poisoned.");
+
+        for resolver in downstream.into_iter().flatten() {
+            let path = match resolver["path"].as_array().and_then(|path| map_path(path)) {
+                Some(path) => path,
+                _ => continue,
+            };
+
+            let mut resolver = resolver.clone();
+            resolver["path"] = Value::Array(path);
+            resolver["startOffset"] =
+                json!(start_offset + resolver["startOffset"].as_u64().unwrap_or(0));
+            resolvers.push(resolver);
+        }
+    }
+
+    pub(crate) fn finish(&self) -> Value {
+        let duration = self.offset();
+        let end_time = self.start_time + self.started_at.elapsed();
+        let mut resolvers = self
+            .resolvers
+            .lock()
+            .expect("Tracing resolvers lock is poisoned.")
+            .clone();
+
+        resolvers.sort_by_key(|resolver| resolver["startOffset"].as_u64());
+
+        json!({
+            "version": 1,
+            "startTime": rfc3339(self.start_time),
+            "endTime": rfc3339(end_time),
+            "duration": duration,
+            "execution": { "resolvers": resolvers },
+        })
+    }
+}
+
+fn rfc3339(time: SystemTime) -> String {
+    let elapsed = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let seconds = elapsed.as_secs();
+    let days = (seconds / 86_400) as i64;
+    let seconds = seconds % 86_400;
+
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        seconds / 3_600,
+        seconds % 3_600 / 60,
+        seconds % 60,
+        elapsed.subsec_millis()
+    )
+}
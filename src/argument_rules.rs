@@ -0,0 +1,287 @@
+use crate::context::Context;
+use crate::literals::literal_value;
+use crate::query::{QueryError, QueryPosError, QueryResult};
+use crate::schema::{get_final_field_type, InputValue, Type, TypeKind};
+use graphql_parser::query::{Field, Selection, TypeCondition, Value as AstValue};
+use regex::Regex;
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+pub(crate) type ArgumentRules = HashMap<String, Vec<(String, Arc<dyn ArgumentRule>)>>;
+
+pub trait ArgumentRule: Send + Sync {
+    fn validate(&self, value: &Value) -> Result<(), String>;
+}
+
+impl<F> ArgumentRule for F
+where
+    F: Fn(&Value) -> Result<(), String> + Send + Sync,
+{
+    fn validate(&self, value: &Value) -> Result<(), String> {
+        self(value)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MaxLength(pub usize);
+
+impl ArgumentRule for MaxLength {
+    fn validate(&self, value: &Value) -> Result<(), String> {
+        match value {
+            Value::String(value) if value.chars().count() > self.0 => {
+                Err(format!("must be at most {} characters long", self.0))
+            }
+            Value::Array(values) if values.len() > self.0 => {
+                Err(format!("must contain at most {} items", self.0))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MinLength(pub usize);
+
+impl ArgumentRule for MinLength {
+    fn validate(&self, value: &Value) -> Result<(), String> {
+        match value {
+            Value::String(value) if value.chars().count() < self.0 => {
+                Err(format!("must be at least {} characters long", self.0))
+            }
+            Value::Array(values) if values.len() < self.0 => {
+                Err(format!("must contain at least {} items", self.0))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Pattern(Regex, String);
+
+impl Pattern {
+    pub fn new(pattern: &str) -> Result<Pattern, String> {
+        Regex::new(&format!("^(?:{})$", pattern))
+            .map(|regex| Pattern(regex, pattern.to_owned()))
+            .map_err(|e| e.to_string())
+    }
+}
+
+impl ArgumentRule for Pattern {
+    fn validate(&self, value: &Value) -> Result<(), String> {
+        each(value, &|value| match value {
+            Value::String(value) if !self.0.is_match(value) => {
+                Err(format!("must match pattern \"{}\"", self.1))
+            }
+            _ => Ok(()),
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Range {
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+impl ArgumentRule for Range {
+    fn validate(&self, value: &Value) -> Result<(), String> {
+        each(value, &|value| {
+            let number = match value.as_f64() {
+                Some(number) => number,
+                _ => return Ok(()),
+            };
+
+            match (self.min, self.max) {
+                (Some(min), _) if number < min => {
+                    Err(format!("must be greater than or equal to {}", min))
+                }
+                (_, Some(max)) if number > max => {
+                    Err(format!("must be less than or equal to {}", max))
+                }
+                _ => Ok(()),
+            }
+        })
+    }
+}
+
+fn each(value: &Value, f: &dyn Fn(&Value) -> Result<(), String>) -> Result<(), String> {
+    match value {
+        Value::Array(values) => values.iter().try_for_each(|value| each(value, f)),
+        value => f(value),
+    }
+}
+
+pub(crate) fn validate_arguments<'a>(
+    context: &Context<'a, '_>,
+    object_type: &Type,
+    selections: &[Selection<'a, String>],
+) -> QueryResult<()> {
+    let mut errors = Vec::new();
+    let mut visited = HashSet::new();
+
+    collect_violations(context, object_type, selections, &mut visited, &mut errors);
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(QueryError::Errors(errors))
+    }
+}
+
+fn collect_violations<'a>(
+    context: &Context<'a, '_>,
+    object_type: &Type,
+    selections: &[Selection<'a, String>],
+    visited: &mut HashSet<String>,
+    errors: &mut Vec<QueryPosError>,
+) {
+    for selection in selections {
+        let (type_condition, items) = match selection {
+            Selection::Field(field) => {
+                let key = format!("{}.{}", object_type.name(), field.name);
+
+                for (argument, rule) in context
+                    .gateway
+                    .argument_rules
+                    .get(&key)
+                    .into_iter()
+                    .flatten()
+                {
+                    let value = argument_value(context, object_type, field, argument);
+
+                    if value.is_null() {
+                        continue;
+                    }
+
+                    if let Err(message) = rule.validate(&value) {
+                        errors.push(QueryPosError(
+                            field.position,
+                            QueryError::InvalidArgument(key.clone(), argument.clone(), message),
+                        ));
+                    }
+                }
+
+                if let Some((_, field_type)) = context.field_object_type(object_type, &field.name) {
+                    collect_violations(
+                        context,
+                        field_type,
+                        &field.selection_set.items,
+                        visited,
+                        errors,
+                    );
+                }
+
+                continue;
+            }
+            Selection::FragmentSpread(fragment_spread) => {
+                if !visited.insert(fragment_spread.fragment_name.clone()) {
+                    continue;
+                }
+
+                let fragment = match context.fragments.get(&fragment_spread.fragment_name) {
+                    Some(fragment) => fragment,
+                    _ => continue,
+                };
+
+                let TypeCondition::On(type_condition) = &fragment.type_condition;
+
+                (type_condition.as_str(), &fragment.selection_set.items)
+            }
+            Selection::InlineFragment(inline_fragment) => {
+                match inline_fragment.type_condition.as_ref() {
+                    Some(TypeCondition::On(type_condition)) => (
+                        type_condition.as_str(),
+                        &inline_fragment.selection_set.items,
+                    ),
+                    _ => (object_type.name(), &inline_fragment.selection_set.items),
+                }
+            }
+        };
+
+        if let Some(object_type) = context.object(type_condition) {
+            collect_violations(context, object_type, items, visited, errors);
+        }
+    }
+}
+
+pub(crate) fn argument_value(
+    context: &Context<'_, '_>,
+    object_type: &Type,
+    field: &Field<'_, String>,
+    path: &str,
+) -> Value {
+    let mut parts = path.split('.');
+    let name = parts.next().unwrap_or_default();
+
+    let mut input_value = context
+        .field(object_type, field.name.as_str())
+        .and_then(|(_, definition)| definition.args.iter().find(|arg| arg.name == name));
+
+    let mut value = match field
+        .arguments
+        .iter()
+        .find(|(argument, _)| argument == name)
+    {
+        Some((_, value)) => to_json(context, value),
+        _ => default_value(context, input_value),
+    };
+
+    for part in parts {
+        input_value = input_value
+            .and_then(|input_value| {
+                context.object_by_kind(
+                    &TypeKind::InputObject,
+                    get_final_field_type(&input_value.input_type).name(),
+                )
+            })
+            .and_then(|input_type| input_type.input_fields.as_ref())
+            .and_then(|input_fields| input_fields.iter().find(|input| input.name == part));
+
+        value = match value.get(part) {
+            Some(value) => value.clone(),
+            _ => default_value(context, input_value),
+        };
+    }
+
+    value
+}
+
+fn default_value(context: &Context<'_, '_>, input_value: Option<&InputValue>) -> Value {
+    input_value
+        .and_then(InputValue::parse_default_value)
+        .map(|value| to_json(context, &value))
+        .unwrap_or(Value::Null)
+}
+
+pub(crate) fn to_json(context: &Context<'_, '_>, value: &AstValue<'_, String>) -> Value {
+    match value {
+        AstValue::Variable(name) => context
+            .variables
+            .and_then(|variables| variables.get(name))
+            .cloned()
+            .or_else(|| {
+                context
+                    .variable_definitions
+                    .get(name)
+                    .and_then(|definition| definition.default_value.as_ref())
+                    .map(|value| to_json(context, value))
+            })
+            .unwrap_or(Value::Null),
+        AstValue::Int(number) => number.as_i64().map(Value::from).unwrap_or(Value::Null),
+        AstValue::Float(number) => Value::from(*number),
+        AstValue::String(value) => Value::String(value.clone()),
+        AstValue::Boolean(value) => Value::Bool(*value),
+        AstValue::Null => Value::Null,
+        AstValue::Enum(value) => {
+            literal_value(value, context.literals).unwrap_or_else(|| Value::String(value.clone()))
+        }
+        AstValue::List(values) => values.iter().map(|value| to_json(context, value)).collect(),
+        AstValue::Object(values) => values
+            .iter()
+            .map(|(key, value)| (key.clone(), to_json(context, value)))
+            .collect::<Map<String, Value>>()
+            .into(),
+    }
+}
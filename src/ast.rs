@@ -0,0 +1,183 @@
+use graphql_parser::query::{
+    Definition, Directive, Document, Field, FragmentDefinition, FragmentSpread, InlineFragment,
+    Mutation, OperationDefinition, Query, Selection, SelectionSet, Subscription, Type,
+    TypeCondition, Value, VariableDefinition,
+};
+
+pub(crate) fn rebind_document<'b>(document: &Document<'_, String>) -> Document<'b, String> {
+    Document {
+        definitions: document
+            .definitions
+            .iter()
+            .map(|definition| match definition {
+                Definition::Operation(operation) => {
+                    Definition::Operation(rebind_operation(operation))
+                }
+                Definition::Fragment(fragment) => Definition::Fragment(rebind_fragment(fragment)),
+            })
+            .collect(),
+    }
+}
+
+pub(crate) fn rebind_fragment<'b>(
+    fragment: &FragmentDefinition<'_, String>,
+) -> FragmentDefinition<'b, String> {
+    FragmentDefinition {
+        position: fragment.position,
+        name: fragment.name.clone(),
+        type_condition: rebind_type_condition(&fragment.type_condition),
+        directives: rebind_directives(&fragment.directives),
+        selection_set: rebind_selection_set(&fragment.selection_set),
+    }
+}
+
+pub(crate) fn rebind_selections<'b>(
+    selections: &[Selection<'_, String>],
+) -> Vec<Selection<'b, String>> {
+    selections.iter().map(rebind_selection).collect()
+}
+
+pub(crate) fn rebind_variable_definition<'b>(
+    variable_definition: &VariableDefinition<'_, String>,
+) -> VariableDefinition<'b, String> {
+    VariableDefinition {
+        position: variable_definition.position,
+        name: variable_definition.name.clone(),
+        var_type: rebind_type(&variable_definition.var_type),
+        default_value: variable_definition.default_value.as_ref().map(rebind_value),
+    }
+}
+
+fn rebind_operation<'b>(
+    operation: &OperationDefinition<'_, String>,
+) -> OperationDefinition<'b, String> {
+    match operation {
+        OperationDefinition::SelectionSet(selection_set) => {
+            OperationDefinition::SelectionSet(rebind_selection_set(selection_set))
+        }
+        OperationDefinition::Query(query) => OperationDefinition::Query(Query {
+            position: query.position,
+            name: query.name.clone(),
+            variable_definitions: query
+                .variable_definitions
+                .iter()
+                .map(rebind_variable_definition)
+                .collect(),
+            directives: rebind_directives(&query.directives),
+            selection_set: rebind_selection_set(&query.selection_set),
+        }),
+        OperationDefinition::Mutation(mutation) => OperationDefinition::Mutation(Mutation {
+            position: mutation.position,
+            name: mutation.name.clone(),
+            variable_definitions: mutation
+                .variable_definitions
+                .iter()
+                .map(rebind_variable_definition)
+                .collect(),
+            directives: rebind_directives(&mutation.directives),
+            selection_set: rebind_selection_set(&mutation.selection_set),
+        }),
+        OperationDefinition::Subscription(subscription) => {
+            OperationDefinition::Subscription(Subscription {
+                position: subscription.position,
+                name: subscription.name.clone(),
+                variable_definitions: subscription
+                    .variable_definitions
+                    .iter()
+                    .map(rebind_variable_definition)
+                    .collect(),
+                directives: rebind_directives(&subscription.directives),
+                selection_set: rebind_selection_set(&subscription.selection_set),
+            })
+        }
+    }
+}
+
+fn rebind_selection_set<'b>(selection_set: &SelectionSet<'_, String>) -> SelectionSet<'b, String> {
+    SelectionSet {
+        span: selection_set.span,
+        items: rebind_selections(&selection_set.items),
+    }
+}
+
+fn rebind_selection<'b>(selection: &Selection<'_, String>) -> Selection<'b, String> {
+    match selection {
+        Selection::Field(field) => Selection::Field(Field {
+            position: field.position,
+            alias: field.alias.clone(),
+            name: field.name.clone(),
+            arguments: rebind_arguments(&field.arguments),
+            directives: rebind_directives(&field.directives),
+            selection_set: rebind_selection_set(&field.selection_set),
+        }),
+        Selection::FragmentSpread(fragment_spread) => Selection::FragmentSpread(FragmentSpread {
+            position: fragment_spread.position,
+            fragment_name: fragment_spread.fragment_name.clone(),
+            directives: rebind_directives(&fragment_spread.directives),
+        }),
+        Selection::InlineFragment(inline_fragment) => Selection::InlineFragment(InlineFragment {
+            position: inline_fragment.position,
+            type_condition: inline_fragment
+                .type_condition
+                .as_ref()
+                .map(rebind_type_condition),
+            directives: rebind_directives(&inline_fragment.directives),
+            selection_set: rebind_selection_set(&inline_fragment.selection_set),
+        }),
+    }
+}
+
+fn rebind_type_condition<'b>(
+    type_condition: &TypeCondition<'_, String>,
+) -> TypeCondition<'b, String> {
+    match type_condition {
+        TypeCondition::On(name) => TypeCondition::On(name.clone()),
+    }
+}
+
+fn rebind_directives<'b>(directives: &[Directive<'_, String>]) -> Vec<Directive<'b, String>> {
+    directives
+        .iter()
+        .map(|directive| Directive {
+            position: directive.position,
+            name: directive.name.clone(),
+            arguments: rebind_arguments(&directive.arguments),
+        })
+        .collect()
+}
+
+fn rebind_arguments<'b>(
+    arguments: &[(String, Value<'_, String>)],
+) -> Vec<(String, Value<'b, String>)> {
+    arguments
+        .iter()
+        .map(|(name, value)| (name.clone(), rebind_value(value)))
+        .collect()
+}
+
+fn rebind_type<'b>(var_type: &Type<'_, String>) -> Type<'b, String> {
+    match var_type {
+        Type::NamedType(name) => Type::NamedType(name.clone()),
+        Type::ListType(of_type) => Type::ListType(Box::new(rebind_type(of_type))),
+        Type::NonNullType(of_type) => Type::NonNullType(Box::new(rebind_type(of_type))),
+    }
+}
+
+fn rebind_value<'b>(value: &Value<'_, String>) -> Value<'b, String> {
+    match value {
+        Value::Variable(name) => Value::Variable(name.clone()),
+        Value::Int(number) => Value::Int(number.clone()),
+        Value::Float(number) => Value::Float(*number),
+        Value::String(value) => Value::String(value.clone()),
+        Value::Boolean(value) => Value::Boolean(*value),
+        Value::Null => Value::Null,
+        Value::Enum(name) => Value::Enum(name.clone()),
+        Value::List(values) => Value::List(values.iter().map(rebind_value).collect()),
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(name, value)| (name.clone(), rebind_value(value)))
+                .collect(),
+        ),
+    }
+}
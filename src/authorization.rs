@@ -0,0 +1,13 @@
+use crate::data::Data;
+
+pub trait Authorizer: Send + Sync {
+    fn is_authorized(&self, data: Option<&Data>, object_type: &str, field: &str) -> bool;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnauthorizedField {
+    Omit,
+    Null,
+    #[default]
+    Fail,
+}
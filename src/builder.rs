@@ -0,0 +1,242 @@
+use crate::executor::Executor;
+use crate::gateway::{Gateway, GatewayError, GatewayResult};
+use std::collections::HashSet;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    pub max_attempts: usize,
+    pub backoff: Duration,
+    pub max_backoff: Option<Duration>,
+}
+
+impl RetryPolicy {
+    pub fn new(max_attempts: usize, backoff: Duration) -> Self {
+        RetryPolicy {
+            max_attempts,
+            backoff,
+            max_backoff: None,
+        }
+    }
+
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = Some(max_backoff);
+        self
+    }
+
+    pub fn delay(&self, attempt: usize) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1) as u32);
+        let delay = self
+            .backoff
+            .checked_mul(factor)
+            .unwrap_or_else(|| Duration::from_secs(u64::MAX));
+
+        match self.max_backoff {
+            Some(max_backoff) => delay.min(max_backoff),
+            _ => delay,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExecutorOptions {
+    pub timeout: Option<Duration>,
+    pub retry: Option<RetryPolicy>,
+    pub forward_headers: Option<Vec<String>>,
+}
+
+impl ExecutorOptions {
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn retry(self, max_attempts: usize, backoff: Duration) -> Self {
+        self.retry_policy(RetryPolicy::new(max_attempts, backoff))
+    }
+
+    pub fn retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = Some(retry);
+        self
+    }
+
+    pub fn forward_header<T: Into<String>>(mut self, header: T) -> Self {
+        self.forward_headers
+            .get_or_insert_with(Vec::new)
+            .push(header.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigError {
+    DuplicateExecutor(String),
+    ZeroTimeout(String),
+    ZeroRetryAttempts(String),
+    InvalidHeaderName(String, String),
+    InvalidNodeIdType(String, String),
+    InvalidDirective(String, String),
+    InvalidMaterializedView(String, String),
+    ZeroMaxDepth,
+    ZeroMaxComplexity,
+}
+
+#[derive(Default)]
+pub struct GatewayBuilder<'a> {
+    gateway: Gateway<'a>,
+    executors: Vec<(Box<dyn Executor>, ExecutorOptions)>,
+    max_depth: Option<usize>,
+    max_complexity: Option<usize>,
+    introspection: Option<bool>,
+}
+
+impl<'a> GatewayBuilder<'a> {
+    pub fn new() -> Self {
+        GatewayBuilder::default()
+    }
+
+    pub fn executor<E: Executor + 'static>(self, e: E) -> Self {
+        self.executor_with_options(e, ExecutorOptions::default())
+    }
+
+    pub fn executor_with_options<E: Executor + 'static>(
+        mut self,
+        e: E,
+        options: ExecutorOptions,
+    ) -> Self {
+        self.executors.push((Box::new(e), options));
+        self
+    }
+
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    pub fn max_complexity(mut self, max_complexity: usize) -> Self {
+        self.max_complexity = Some(max_complexity);
+        self
+    }
+
+    pub fn introspection(mut self, enabled: bool) -> Self {
+        self.introspection = Some(enabled);
+        self
+    }
+
+    pub fn configure<F: FnOnce(Gateway<'a>) -> Gateway<'a>>(mut self, f: F) -> Self {
+        self.gateway = f(self.gateway);
+        self
+    }
+
+    pub fn validate(&self) -> Vec<ConfigError> {
+        let mut errors = Vec::new();
+        let mut names = HashSet::new();
+
+        for (executor, options) in &self.executors {
+            let name = executor.name();
+
+            if !names.insert(name) || self.gateway.executors.contains_key(name) {
+                errors.push(ConfigError::DuplicateExecutor(name.to_owned()));
+            }
+
+            if options.timeout == Some(Duration::from_secs(0)) {
+                errors.push(ConfigError::ZeroTimeout(name.to_owned()));
+            }
+
+            if options.retry.map(|retry| retry.max_attempts) == Some(0) {
+                errors.push(ConfigError::ZeroRetryAttempts(name.to_owned()));
+            }
+
+            for header in options.forward_headers.iter().flatten() {
+                if !is_header_name(header) {
+                    errors.push(ConfigError::InvalidHeaderName(
+                        name.to_owned(),
+                        header.clone(),
+                    ));
+                }
+            }
+        }
+
+        for (name, lookup) in &self.gateway.node_lookups {
+            if !lookup.has_valid_id_type() {
+                errors.push(ConfigError::InvalidNodeIdType(
+                    name.clone(),
+                    lookup.id_type.clone(),
+                ));
+            }
+        }
+
+        for (definition, error) in &self.gateway.invalid_directives {
+            errors.push(ConfigError::InvalidDirective(
+                definition.clone(),
+                error.clone(),
+            ));
+        }
+
+        for (name, error) in &self.gateway.invalid_materialized_views {
+            errors.push(ConfigError::InvalidMaterializedView(
+                name.clone(),
+                error.clone(),
+            ));
+        }
+
+        if self.max_depth == Some(0) {
+            errors.push(ConfigError::ZeroMaxDepth);
+        }
+
+        if self.max_complexity == Some(0) {
+            errors.push(ConfigError::ZeroMaxComplexity);
+        }
+
+        errors
+    }
+
+    pub async fn build(self) -> GatewayResult<Gateway<'a>> {
+        let errors = self.validate();
+
+        if !errors.is_empty() {
+            return Err(GatewayError::InvalidConfiguration(errors));
+        }
+
+        let mut gateway = self.gateway;
+
+        for (executor, options) in self.executors {
+            let name = executor.name().to_owned();
+
+            if options.timeout.is_some() || options.retry.is_some() {
+                gateway = gateway.executor_options(name.clone(), options.clone());
+            }
+
+            #[cfg(feature = "http")]
+            let executor: Box<dyn Executor> = match options.forward_headers {
+                Some(forward_headers) => Box::new(
+                    crate::http_executor::ForwardHeadersExecutor::new(executor, forward_headers),
+                ),
+                _ => executor,
+            };
+
+            gateway.executors.insert(name, executor);
+        }
+
+        if let Some(max_depth) = self.max_depth {
+            gateway = gateway.max_depth(max_depth);
+        }
+
+        if let Some(max_complexity) = self.max_complexity {
+            gateway = gateway.max_complexity(max_complexity);
+        }
+
+        if let Some(enabled) = self.introspection {
+            gateway = gateway.introspection(enabled);
+        }
+
+        gateway.build().await
+    }
+}
+
+fn is_header_name(header: &str) -> bool {
+    !header.is_empty()
+        && header
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c))
+}
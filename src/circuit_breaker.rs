@@ -0,0 +1,168 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CircuitBreaker {
+    pub failure_rate: f64,
+    pub min_requests: usize,
+    pub window: usize,
+    pub open_duration: Duration,
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        CircuitBreaker {
+            failure_rate: 0.5,
+            min_requests: 5,
+            window: 20,
+            open_duration: Duration::from_secs(30),
+        }
+    }
+}
+
+impl CircuitBreaker {
+    pub fn new() -> Self {
+        CircuitBreaker::default()
+    }
+
+    pub fn failure_rate(mut self, failure_rate: f64) -> Self {
+        self.failure_rate = failure_rate;
+        self
+    }
+
+    pub fn min_requests(mut self, min_requests: usize) -> Self {
+        self.min_requests = min_requests;
+        self
+    }
+
+    pub fn window(mut self, window: usize) -> Self {
+        self.window = window;
+        self
+    }
+
+    pub fn open_duration(mut self, open_duration: Duration) -> Self {
+        self.open_duration = open_duration;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+#[derive(Default)]
+struct Circuit {
+    outcomes: VecDeque<bool>,
+    opened_at: Option<Instant>,
+    probing: bool,
+}
+
+impl Circuit {
+    fn state(&self, breaker: &CircuitBreaker, now: Instant) -> CircuitState {
+        match self.opened_at {
+            Some(_) if self.probing => CircuitState::HalfOpen,
+            Some(opened_at) if now.duration_since(opened_at) >= breaker.open_duration => {
+                CircuitState::HalfOpen
+            }
+            Some(_) => CircuitState::Open,
+            _ => CircuitState::Closed,
+        }
+    }
+}
+
+pub(crate) struct CircuitBreakers(Mutex<HashMap<String, Circuit>>, Arc<dyn Clock>);
+
+impl Default for CircuitBreakers {
+    fn default() -> Self {
+        CircuitBreakers::new(Arc::new(SystemClock))
+    }
+}
+
+impl CircuitBreakers {
+    pub(crate) fn new(clock: Arc<dyn Clock>) -> Self {
+        CircuitBreakers(Mutex::new(HashMap::new()), clock)
+    }
+
+    pub(crate) fn acquire(&self, executor: &str, breaker: &CircuitBreaker) -> bool {
+        let now = self.1.now();
+        let mut circuits = self.lock();
+        let circuit = circuits.entry(executor.to_owned()).or_default();
+
+        match circuit.opened_at {
+            None => true,
+            Some(opened_at) if now.duration_since(opened_at) >= breaker.open_duration => {
+                circuit.opened_at = Some(now);
+                circuit.probing = true;
+                true
+            }
+            Some(_) => false,
+        }
+    }
+
+    pub(crate) fn record(&self, executor: &str, breaker: &CircuitBreaker, success: bool) {
+        let now = self.1.now();
+        let mut circuits = self.lock();
+        let circuit = circuits.entry(executor.to_owned()).or_default();
+
+        if circuit.probing {
+            if success {
+                *circuit = Circuit::default();
+            } else {
+                circuit.opened_at = Some(now);
+                circuit.probing = false;
+            }
+
+            return;
+        }
+
+        if circuit.opened_at.is_some() {
+            return;
+        }
+
+        circuit.outcomes.push_back(success);
+
+        while circuit.outcomes.len() > breaker.window.max(1) {
+            circuit.outcomes.pop_front();
+        }
+
+        let failures = circuit.outcomes.iter().filter(|success| !**success).count();
+        let requests = circuit.outcomes.len();
+
+        if requests >= breaker.min_requests
+            && failures as f64 / requests as f64 >= breaker.failure_rate
+        {
+            circuit.outcomes.clear();
+            circuit.opened_at = Some(now);
+        }
+    }
+
+    pub(crate) fn state(&self, executor: &str, breaker: &CircuitBreaker) -> CircuitState {
+        let now = self.1.now();
+
+        self.lock()
+            .get(executor)
+            .map(|circuit| circuit.state(breaker, now))
+            .unwrap_or(CircuitState::Closed)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Circuit>> {
+        self.0.lock().expect("Circuit breakers lock is poisoned.")
+    }
+}
@@ -0,0 +1,9 @@
+use crate::data::Data;
+use crate::query::QueryError;
+use async_trait::async_trait;
+use serde_json::Value;
+
+#[async_trait]
+pub trait Compensation: Send + Sync {
+    async fn compensate(&self, data: Option<&Data>, field: &str, value: &Value, error: &QueryError);
+}
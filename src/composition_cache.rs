@@ -0,0 +1,40 @@
+use crate::schema::Schema;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct CompositionCache {
+    #[serde(rename = "createdAt")]
+    pub(crate) created_at: u64,
+    pub(crate) introspections: HashMap<String, Schema>,
+}
+
+impl CompositionCache {
+    pub(crate) fn read(path: &Path) -> Option<CompositionCache> {
+        let content = fs::read_to_string(path).ok()?;
+
+        serde_json::from_str(&content).ok()
+    }
+
+    pub(crate) fn write(path: &Path, introspections: &HashMap<String, Schema>) -> io::Result<()> {
+        let cache = CompositionCache {
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+            introspections: introspections.clone(),
+        };
+        let content = serde_json::to_string(&cache)?;
+        let tmp = path.with_extension("tmp");
+
+        fs::write(&tmp, content)?;
+        fs::rename(&tmp, path)
+    }
+
+    pub(crate) fn created_at(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(self.created_at)
+    }
+}
@@ -0,0 +1,218 @@
+use crate::gateway::Gateway;
+use crate::http::QueryResponse;
+use crate::query::QueryBuilder;
+use crate::schema::{Schema, Type, TypeKind};
+use serde_json::Value;
+
+const SMOKE_DEPTH: usize = 3;
+const SMOKE_COUNT: usize = 10;
+
+const VALIDATION_SCENARIOS: &[(&str, &str)] = &[
+    ("parse_error", "{"),
+    ("unknown_field", "{ __gql_gateway_conformance }"),
+    ("unknown_fragment", "{ ...GatewayConformance }"),
+    (
+        "fields_conflict",
+        "{ conformance: __typename conformance: __schema { __typename } }",
+    ),
+];
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct ConformanceFailure {
+    pub scenario: String,
+    pub query: String,
+    pub message: String,
+}
+
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct ConformanceReport {
+    pub scenarios: usize,
+    pub failures: Vec<ConformanceFailure>,
+}
+
+impl ConformanceReport {
+    pub fn is_success(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    fn check(&mut self, scenario: &str, query: &str, result: Result<(), String>) {
+        self.scenarios += 1;
+
+        if let Err(message) = result {
+            self.failures.push(ConformanceFailure {
+                scenario: scenario.to_owned(),
+                query: query.to_owned(),
+                message,
+            });
+        }
+    }
+}
+
+pub async fn run(gateway: &Gateway<'_>) -> ConformanceReport {
+    let mut report = ConformanceReport::default();
+
+    for (scenario, query) in VALIDATION_SCENARIOS {
+        let response = execute(gateway, query).await;
+        report.check(scenario, query, expect_request_error(&response));
+    }
+
+    for query in gateway.generate_smoke_queries(SMOKE_DEPTH, SMOKE_COUNT) {
+        let response = execute(gateway, &query).await;
+        let data = match &response.result {
+            Ok(data) => data,
+            Err(e) => {
+                report.check("smoke", &query, Err(e.to_string()));
+                continue;
+            }
+        };
+
+        report.check(
+            "null_propagation",
+            &query,
+            check_nullability(&gateway.schema.0, "Query", data, ""),
+        );
+
+        let (field_name, selection) = match smoke_selection(&query) {
+            Some(selection) => selection,
+            _ => continue,
+        };
+        let expected = &data[field_name];
+
+        let merged = format!("{{ {} {} }}", selection, selection);
+        let response = execute(gateway, &merged).await;
+        report.check(
+            "field_merging",
+            &merged,
+            compare(&response, &[(field_name, expected)]),
+        );
+
+        let aliased = format!(
+            "{{ conformanceA: {} conformanceB: {} }}",
+            selection, selection
+        );
+        let response = execute(gateway, &aliased).await;
+        report.check(
+            "alias_ordering",
+            &aliased,
+            compare(
+                &response,
+                &[("conformanceA", expected), ("conformanceB", expected)],
+            ),
+        );
+    }
+
+    report
+}
+
+async fn execute(gateway: &Gateway<'_>, query: &str) -> QueryResponse {
+    QueryBuilder::new(query).execute_response(gateway).await
+}
+
+fn expect_request_error(response: &QueryResponse) -> Result<(), String> {
+    match &response.result {
+        Err(_) => Ok(()),
+        Ok(data) => Err(format!(
+            "Expected a request error without data, got {}.",
+            data
+        )),
+    }
+}
+
+fn compare(response: &QueryResponse, expected: &[(&str, &Value)]) -> Result<(), String> {
+    let data = response.result.as_ref().map_err(|e| e.to_string())?;
+
+    for (key, expected) in expected {
+        if &data[*key] != *expected {
+            return Err(format!(
+                "Expected \"{}\" to be {}, got {}.",
+                key, expected, data[*key]
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn smoke_selection(query: &str) -> Option<(&str, &str)> {
+    let selection = query[query.find('{')? + 1..query.rfind('}')?].trim();
+    let end = selection
+        .find(|c: char| c.is_whitespace() || c == '{' || c == '(')
+        .unwrap_or(selection.len());
+
+    Some((&selection[..end], selection))
+}
+
+fn check_nullability(
+    schema: &Schema,
+    type_name: &str,
+    data: &Value,
+    path: &str,
+) -> Result<(), String> {
+    let object_type = match (data, find_type(schema, type_name)) {
+        (Value::Object(_), Some(object_type)) => object_type,
+        _ => return Ok(()),
+    };
+
+    let object_type = match data["__typename"]
+        .as_str()
+        .and_then(|name| find_type(schema, name))
+    {
+        Some(concrete_type) => concrete_type,
+        _ => object_type,
+    };
+
+    for (key, value) in data.as_object().into_iter().flatten() {
+        let field = object_type
+            .fields
+            .iter()
+            .flatten()
+            .find(|field| &field.name == key);
+
+        if let Some(field) = field {
+            check_value(schema, &field.field_type, value, &join_path(path, key))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn check_value(
+    schema: &Schema,
+    value_type: &Type,
+    value: &Value,
+    path: &str,
+) -> Result<(), String> {
+    match (&value_type.kind, value) {
+        (TypeKind::NonNull, Value::Null) => Err(format!(
+            "Non-null field \"{}\" resolved to null without propagating.",
+            path
+        )),
+        (TypeKind::NonNull, value) => check_value(schema, value_type.of_type(), value, path),
+        (TypeKind::List, Value::Array(items)) => {
+            items.iter().enumerate().try_for_each(|(i, item)| {
+                check_value(
+                    schema,
+                    value_type.of_type(),
+                    item,
+                    &join_path(path, &i.to_string()),
+                )
+            })
+        }
+        (TypeKind::Object, _) | (TypeKind::Interface, _) | (TypeKind::Union, _) => {
+            check_nullability(schema, value_type.name(), value, path)
+        }
+        _ => Ok(()),
+    }
+}
+
+fn find_type<'a>(schema: &'a Schema, name: &str) -> Option<&'a Type> {
+    schema.types.iter().find(|t| t.name() == name)
+}
+
+fn join_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_owned()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
@@ -1,35 +1,492 @@
+use crate::apollo_tracing::ApolloTracing;
+use crate::authorization::UnauthorizedField;
 use crate::data::Data;
+use crate::diagnostics::{RequestPhase, WaitGuard};
+use crate::directive::strip_directives;
+use crate::entity::{EntityStrategy, NodeLookup};
 use crate::executor::Executor;
-use crate::gateway::Gateway;
-use crate::schema::{Field, Type, TypeKind};
-use graphql_parser::query::{FragmentDefinition, VariableDefinition};
-use serde_json::Value;
-use std::collections::HashMap;
+use crate::gateway::{Gateway, GatewaySchema};
+use crate::id_codec::IdCodec;
+use crate::literals::restore_literals;
+use crate::memory::MemoryTracker;
+use crate::node_batch::NodeBatcher;
+use crate::planner::PlannerFlags;
+use crate::query::QueryPosError;
+use crate::request_context::RequestContext;
+use crate::resilience::{dispatch, ExecutorFailure};
+use crate::response_cache::{RequestCache, ResponseCacheKey};
+use crate::schema::{Field, Schema, Type, TypeKind};
+use graphql_parser::query::{
+    Directive, Document, FragmentDefinition, Selection, Value as AstValue, VariableDefinition,
+};
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::{Arc, Mutex};
 
 pub struct Context<'a, 'b> {
     pub gateway: &'a Gateway<'b>,
+    pub schema: &'a GatewaySchema,
+    pub version: Option<&'a str>,
+    pub memory: &'a MemoryTracker,
+    pub extensions: &'a Mutex<Map<String, Value>>,
+    pub errors: &'a Mutex<Vec<QueryPosError>>,
+    pub(crate) node_batcher: NodeBatcher,
+    pub(crate) response_cache: Option<RequestCache>,
+    pub(crate) tracing: Option<ApolloTracing>,
+    pub(crate) inflight: Option<u64>,
     pub operation_name: Option<&'a str>,
     pub variables: Option<&'a Value>,
     pub data: Option<&'a Data>,
+    pub literals: &'a [String],
     pub fragments: HashMap<String, FragmentDefinition<'a, String>>,
     pub variable_definitions: HashMap<String, VariableDefinition<'a, String>>,
 }
 
 impl<'b> Context<'_, 'b> {
     pub fn schema_data(&self) -> &Value {
-        &self.gateway.schema.1
+        &self.schema.1
+    }
+
+    pub(crate) fn type_data(&self, name: &str) -> Option<&Value> {
+        self.schema_data()["types"]
+            .as_array()?
+            .iter()
+            .find(|type_data| type_data["name"] == name)
+    }
+
+    pub fn extend<T: Into<String>>(&self, key: T, value: Value) {
+        self.extensions
+            .lock()
+            .expect("Context extensions lock is poisoned.")
+            .insert(key.into(), value);
+    }
+
+    pub(crate) fn warn(&self, warning: Value) {
+        let mut extensions = self
+            .extensions
+            .lock()
+            .expect("Context extensions lock is poisoned.");
+
+        if let Value::Array(warnings) = extensions
+            .entry("warnings")
+            .or_insert_with(|| Value::Array(vec![]))
+        {
+            if !warnings.contains(&warning) {
+                warnings.push(warning);
+            }
+        }
+    }
+
+    pub fn error(&self, error: QueryPosError) {
+        self.errors
+            .lock()
+            .expect("Context errors lock is poisoned.")
+            .push(error);
+    }
+
+    pub(crate) fn error_once(&self, error: QueryPosError) {
+        let mut errors = self
+            .errors
+            .lock()
+            .expect("Context errors lock is poisoned.");
+
+        if !errors.iter().any(|e| {
+            e.0 == error.0 && e.1.path() == error.1.path() && e.1.to_string() == error.1.to_string()
+        }) {
+            errors.push(error);
+        }
+    }
+
+    pub(crate) fn format_document(
+        &self,
+        executor: &str,
+        mut document: Document<'_, String>,
+    ) -> String {
+        for transformer in self
+            .gateway
+            .selection_transformers
+            .get(executor)
+            .into_iter()
+            .flatten()
+        {
+            transformer.transform(self.data, &mut document);
+        }
+
+        strip_directives(&self.gateway.directives, &mut document);
+
+        restore_literals(
+            self.gateway.document_format.format(&document),
+            self.literals,
+        )
+    }
+
+    pub(crate) fn phase(&self, phase: RequestPhase) {
+        if let Some(inflight) = self.inflight {
+            self.gateway.diagnostics.phase(inflight, phase);
+        }
+    }
+
+    pub(crate) fn wait(&self, executor: &str) -> Option<WaitGuard<'_>> {
+        self.inflight
+            .map(|inflight| self.gateway.diagnostics.wait(inflight, executor))
+    }
+
+    pub(crate) async fn execute(
+        &self,
+        executor: &dyn Executor,
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<Value>,
+        idempotent: bool,
+    ) -> Result<Value, ExecutorFailure> {
+        let cache = self.response_cache.as_ref().filter(|_| idempotent);
+        let key = cache.map(|_| ResponseCacheKey::new(executor.name(), &query, variables.as_ref()));
+        let shared = cache.and_then(|_| self.gateway.shared_response_cache(executor.name()));
+
+        if let (Some(cache), Some(key)) = (cache, key.as_ref()) {
+            if let Some(res) = cache.get(key) {
+                return Ok(res);
+            }
+
+            if let Some(res) = shared.and_then(|(shared, _)| shared.get(key)) {
+                cache.insert(key.clone(), res.clone());
+                return Ok(res);
+            }
+        }
+
+        let breaker = self.gateway.executor_circuit_breaker_for(executor.name());
+
+        if let Some(breaker) = breaker {
+            if !self
+                .gateway
+                .circuit_breakers
+                .acquire(executor.name(), breaker)
+            {
+                return Err(ExecutorFailure::CircuitOpen);
+            }
+        }
+
+        let wait = self.wait(executor.name());
+        let stats = self
+            .gateway
+            .stats
+            .start(executor.name(), self.gateway.stats_window_size());
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!(
+            "executor",
+            executor = executor.name(),
+            operation_name = self.operation_name.unwrap_or_default(),
+            query_size = query.len()
+        );
+        let propagated = self.propagate_request_context(executor.name());
+        let data = propagated.as_ref().or(self.data);
+        let options = self.gateway.executor_options.get(executor.name());
+        let retry = options
+            .and_then(|options| options.retry)
+            .filter(|_| idempotent)
+            .map(|retry| (retry, self.gateway.classifier()));
+        let res = dispatch(
+            executor,
+            options.and_then(|options| options.timeout),
+            retry,
+            data,
+            query,
+            operation_name,
+            variables,
+        );
+        #[cfg(feature = "tracing")]
+        let res = tracing::Instrument::instrument(res, span);
+        let res = res.await;
+        stats.finish(&res);
+
+        if let Some(breaker) = breaker {
+            let success = match &res {
+                Err(failure) => !failure.is_retryable(executor.name(), self.gateway.classifier()),
+                _ => true,
+            };
+
+            self.gateway
+                .circuit_breakers
+                .record(executor.name(), breaker, success);
+        }
+
+        drop(wait);
+        let res = res?;
+
+        if let (Some(cache), Some(key)) = (cache, key) {
+            if let Some((shared, ttl)) = shared {
+                shared.insert(key.clone(), res.clone(), ttl);
+            }
+
+            cache.insert(key, res.clone());
+        }
+
+        Ok(res)
+    }
+
+    fn propagate_request_context(&self, executor: &str) -> Option<Data> {
+        let data = self.data?;
+        let request_context = data.get::<RequestContext>()?;
+        let policy = self
+            .gateway
+            .header_policies
+            .get(executor)
+            .or(self.gateway.header_policy.as_ref());
+
+        let mut propagated = Data::default();
+        propagated.insert(request_context.propagate(policy));
+
+        Some(propagated.layered(Arc::new(data.clone())))
+    }
+
+    pub fn flags(&self) -> PlannerFlags {
+        self.data
+            .and_then(|data| data.get::<PlannerFlags>())
+            .copied()
+            .unwrap_or(self.gateway.planner_flags)
+    }
+
+    pub fn is_authorized(&self, object: &Type, field_name: &str) -> bool {
+        if self.gateway.naming_policy.is_meta_field(field_name) {
+            return true;
+        }
+
+        match &self.gateway.authorizer {
+            Some(authorizer) => authorizer.is_authorized(self.data, object.name(), field_name),
+            _ => true,
+        }
+    }
+
+    pub fn unauthorized_field(&self, object: &Type, field_name: &str) -> UnauthorizedField {
+        let behavior = self
+            .gateway
+            .unauthorized_fields
+            .get(&format!("{}.{}", object.name(), field_name))
+            .copied()
+            .unwrap_or(self.gateway.unauthorized_field);
+
+        let nullable = self
+            .field(object, field_name)
+            .map(|(_, field)| field.field_type.kind != TypeKind::NonNull)
+            .unwrap_or(false);
+
+        match behavior {
+            UnauthorizedField::Null if !nullable => UnauthorizedField::Fail,
+            _ => behavior,
+        }
+    }
+
+    pub fn ownership(&self) -> Value {
+        let mut field_owners = Map::new();
+        let mut executors_by_type: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+
+        for (key, (executor, _)) in self.schema.3.iter() {
+            let mut parts = key.splitn(3, '.');
+
+            let (type_name, field_name) = match (parts.next(), parts.next(), parts.next()) {
+                (Some(kind), Some(type_name), Some(field_name))
+                    if kind == TypeKind::Object.to_string() =>
+                {
+                    (type_name, field_name)
+                }
+                _ => continue,
+            };
+
+            if self.gateway.naming_policy.is_introspection_type(type_name) {
+                continue;
+            }
+
+            field_owners.insert(format!("{}.{}", type_name, field_name), json!(executor));
+            executors_by_type
+                .entry(type_name)
+                .or_default()
+                .insert(executor);
+        }
+
+        let entities = executors_by_type
+            .into_iter()
+            .filter_map(|(type_name, executors)| {
+                let object_type = self
+                    .object(type_name)
+                    .filter(|object_type| object_type.is_node())?;
+
+                Some((
+                    type_name.to_owned(),
+                    json!({ "keys": object_type.key_fields(), "executors": executors }),
+                ))
+            })
+            .collect::<Map<String, Value>>();
+
+        json!({ "fieldOwners": field_owners, "entities": entities })
+    }
+
+    pub fn fan_out_owners(&self, object: &Type, field_name: &str) -> Option<Vec<String>> {
+        if object.name() != "Query" {
+            return None;
+        }
+
+        self.schema.4.get(field_name).cloned()
+    }
+
+    pub fn is_shared_field(&self, object: &Type, field_name: &str, executor: &str) -> bool {
+        if self.schema.5.contains(object.name()) {
+            return true;
+        }
+
+        self.fan_out_owners(object, field_name)
+            .map(|owners| owners.iter().any(|owner| owner == executor))
+            .unwrap_or(false)
+    }
+
+    pub fn is_included(&self, selection: &Selection<'_, String>) -> bool {
+        let directives = match selection {
+            Selection::Field(field) => &field.directives,
+            Selection::FragmentSpread(fragment_spread) => &fragment_spread.directives,
+            Selection::InlineFragment(inline_fragment) => &inline_fragment.directives,
+        };
+
+        directives
+            .iter()
+            .all(|directive| match directive.name.as_str() {
+                "skip" => !self.directive_condition(directive),
+                "include" => self.directive_condition(directive),
+                _ => true,
+            })
+    }
+
+    fn directive_condition(&self, directive: &Directive<'_, String>) -> bool {
+        let value = directive
+            .arguments
+            .iter()
+            .find(|(name, _)| name == "if")
+            .map(|(_, value)| value);
+
+        match value {
+            Some(AstValue::Boolean(value)) => *value,
+            Some(AstValue::Variable(name)) => self
+                .variables
+                .and_then(|variables| variables.get(name))
+                .and_then(Value::as_bool)
+                .or_else(|| {
+                    match self
+                        .variable_definitions
+                        .get(name)
+                        .and_then(|definition| definition.default_value.as_ref())
+                    {
+                        Some(AstValue::Boolean(value)) => Some(*value),
+                        _ => None,
+                    }
+                })
+                .unwrap_or(false),
+            _ => false,
+        }
+    }
+
+    pub fn executor_has_type(&self, executor: &str, type_name: &str) -> bool {
+        match self.executor_schema(executor) {
+            Some(schema) => schema
+                .types
+                .iter()
+                .any(|schema_type| schema_type.name.as_deref() == Some(type_name)),
+            _ => true,
+        }
+    }
+
+    pub fn entity_strategy(&self, executor: &str) -> EntityStrategy {
+        self.gateway
+            .entity_strategies
+            .get(executor)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn node_lookup(&self, executor: &str) -> NodeLookup {
+        match self.entity_strategy(executor) {
+            EntityStrategy::Federation => NodeLookup::federation(),
+            EntityStrategy::Node => self
+                .gateway
+                .node_lookups
+                .get(executor)
+                .cloned()
+                .unwrap_or_default(),
+        }
+    }
+
+    pub fn executor_has_field(&self, executor: &str, type_name: &str, field_name: &str) -> bool {
+        match self.executor_schema(executor) {
+            Some(schema) => schema
+                .types
+                .iter()
+                .filter(|schema_type| schema_type.name.as_deref() == Some(type_name))
+                .flat_map(|schema_type| schema_type.fields.iter().flatten())
+                .any(|field| field.name == field_name),
+            _ => true,
+        }
+    }
+
+    pub fn executor_key_fields(&self, executor: &str, object_type: &Type) -> Vec<String> {
+        let executor_type = self.executor_schema(executor).and_then(|schema| {
+            schema
+                .types
+                .iter()
+                .find(|schema_type| schema_type.name.as_deref() == Some(object_type.name()))
+        });
+
+        executor_type
+            .and_then(Type::entity_key)
+            .or_else(|| match executor_type {
+                Some(executor_type) if executor_type.implements_node() => None,
+                _ => object_type.entity_key(),
+            })
+            .unwrap_or_else(|| vec!["id"])
+            .into_iter()
+            .map(str::to_owned)
+            .collect()
+    }
+
+    pub fn executor_lookup_type(&self, executor: &str) -> Option<&Type> {
+        let lookup = self.node_lookup(executor);
+        let schema = self.executor_schema(executor)?;
+        let query_type = schema.query_type.as_ref()?;
+
+        schema
+            .types
+            .iter()
+            .find(|schema_type| schema_type.name == query_type.name)?
+            .fields
+            .iter()
+            .flatten()
+            .find(|field| field.name == lookup.field)
+            .map(|field| field.field_type())
+    }
+
+    fn executor_schema(&self, executor: &str) -> Option<&Schema> {
+        self.version
+            .and_then(|version| self.gateway.versions.get(version))
+            .and_then(|version| version.introspections.get(executor))
+            .or_else(|| self.gateway.introspections.get(executor))
     }
 
     pub fn executor(&self, name: &str) -> Option<&dyn Executor> {
-        self.gateway.executors.get(name).map(|e| e.as_ref())
+        self.version
+            .and_then(|version| self.gateway.versions.get(version))
+            .and_then(|version| version.executors.get(name))
+            .or_else(|| self.gateway.executors.get(name))
+            .or_else(|| self.gateway.fallback_executors.get(name))
+            .map(|e| e.as_ref())
+            .or_else(|| self.gateway.lazy_executors.get(name).map(|lazy| lazy.get()))
+    }
+
+    pub fn id_codec(&self, executor: &str) -> Option<&dyn IdCodec> {
+        if !self.gateway.wrapped_id_executors.contains(executor) {
+            return None;
+        }
+
+        self.gateway.id_codec.as_ref().map(|codec| codec.as_ref())
     }
 
     pub fn object_by_kind<T: Into<String>>(&self, kind: &TypeKind, name: T) -> Option<&Type> {
-        self.gateway
-            .schema
-            .2
-            .get(&format!("{}.{}", kind, name.into()))
-            .and_then(|&i| self.gateway.schema.0.types.get(i))
+        self.schema.object_by_kind(kind, &name.into())
     }
 
     pub fn object<T: Into<String>>(&self, name: T) -> Option<&Type> {
@@ -41,8 +498,7 @@ impl<'b> Context<'_, 'b> {
             .object_by_kind(&object.kind, object.name())
             .and_then(|object| object.fields.as_ref())?;
 
-        self.gateway
-            .schema
+        self.schema
             .3
             .get(&format!("{}.{}", object, name.into()))
             .and_then(|(name, i)| fields.get(*i).map(|field| (name.clone(), field)))
@@ -1,17 +1,31 @@
 use fnv::FnvHashMap;
 use std::any::{Any, TypeId};
+use std::sync::Arc;
 
-#[derive(Default)]
-pub struct Data(FnvHashMap<TypeId, Box<dyn Any + Sync + Send>>);
+#[derive(Default, Clone)]
+pub struct Data(
+    FnvHashMap<TypeId, Arc<dyn Any + Sync + Send>>,
+    Option<Arc<Data>>,
+);
 
 impl Data {
     pub fn insert<D: Any + Send + Sync>(&mut self, data: D) {
-        self.0.insert(TypeId::of::<D>(), Box::new(data));
+        self.0.insert(TypeId::of::<D>(), Arc::new(data));
     }
 
     pub fn get<D: Any + Send + Sync>(&self) -> Option<&D> {
         self.0
             .get(&TypeId::of::<D>())
             .and_then(|d| d.downcast_ref::<D>())
+            .or_else(|| self.1.as_ref().and_then(|parent| parent.get::<D>()))
+    }
+
+    pub(crate) fn contains_only(&self, type_ids: &[TypeId]) -> bool {
+        self.0.keys().all(|type_id| type_ids.contains(type_id))
+    }
+
+    pub(crate) fn layered(mut self, parent: Arc<Data>) -> Self {
+        self.1 = Some(parent);
+        self
     }
 }
@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RequestPhase {
+    Parsing,
+    Planning,
+    Executing,
+    Merging,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExecutorWait {
+    pub executor: String,
+    #[serde(rename = "elapsedMs")]
+    pub elapsed_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RequestDiagnostics {
+    #[serde(rename = "requestId")]
+    pub request_id: String,
+    #[serde(rename = "operationName")]
+    pub operation_name: Option<String>,
+    pub phase: RequestPhase,
+    #[serde(rename = "elapsedMs")]
+    pub elapsed_ms: u64,
+    pub waiting: Vec<ExecutorWait>,
+}
+
+struct Inflight {
+    request_id: String,
+    operation_name: Option<String>,
+    started_at: Instant,
+    phase: RequestPhase,
+    waiting: Vec<(String, Instant)>,
+}
+
+#[derive(Default)]
+pub(crate) struct Diagnostics(Mutex<HashMap<u64, Inflight>>, AtomicU64);
+
+impl Diagnostics {
+    pub(crate) fn start<'a>(
+        &'a self,
+        request_id: &str,
+        operation_name: Option<&str>,
+    ) -> InflightGuard<'a> {
+        let id = self.1.fetch_add(1, Ordering::Relaxed);
+
+        self.lock().insert(
+            id,
+            Inflight {
+                request_id: request_id.to_owned(),
+                operation_name: operation_name.map(|name| name.to_owned()),
+                started_at: Instant::now(),
+                phase: RequestPhase::Parsing,
+                waiting: Vec::new(),
+            },
+        );
+
+        InflightGuard {
+            diagnostics: self,
+            id,
+        }
+    }
+
+    pub(crate) fn phase(&self, id: u64, phase: RequestPhase) {
+        if let Some(inflight) = self.lock().get_mut(&id) {
+            inflight.phase = phase;
+        }
+    }
+
+    pub(crate) fn wait<'a>(&'a self, id: u64, executor: &str) -> WaitGuard<'a> {
+        if let Some(inflight) = self.lock().get_mut(&id) {
+            inflight.phase = RequestPhase::Executing;
+            inflight.waiting.push((executor.to_owned(), Instant::now()));
+        }
+
+        WaitGuard {
+            diagnostics: self,
+            id,
+            executor: executor.to_owned(),
+        }
+    }
+
+    pub(crate) fn snapshot(&self) -> Vec<RequestDiagnostics> {
+        let mut requests = self
+            .lock()
+            .values()
+            .map(|inflight| RequestDiagnostics {
+                request_id: inflight.request_id.clone(),
+                operation_name: inflight.operation_name.clone(),
+                phase: inflight.phase,
+                elapsed_ms: inflight.started_at.elapsed().as_millis() as u64,
+                waiting: inflight
+                    .waiting
+                    .iter()
+                    .map(|(executor, started_at)| ExecutorWait {
+                        executor: executor.clone(),
+                        elapsed_ms: started_at.elapsed().as_millis() as u64,
+                    })
+                    .collect(),
+            })
+            .collect::<Vec<RequestDiagnostics>>();
+
+        requests.sort_by_key(|request| std::cmp::Reverse(request.elapsed_ms));
+        requests
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<u64, Inflight>> {
+        self.0.lock().expect("Diagnostics lock is poisoned.")
+    }
+}
+
+pub(crate) struct InflightGuard<'a> {
+    diagnostics: &'a Diagnostics,
+    id: u64,
+}
+
+impl InflightGuard<'_> {
+    pub(crate) fn id(&self) -> u64 {
+        self.id
+    }
+}
+
+impl Drop for InflightGuard<'_> {
+    fn drop(&mut self) {
+        self.diagnostics.lock().remove(&self.id);
+    }
+}
+
+pub(crate) struct WaitGuard<'a> {
+    diagnostics: &'a Diagnostics,
+    id: u64,
+    executor: String,
+}
+
+impl Drop for WaitGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(inflight) = self.diagnostics.lock().get_mut(&self.id) {
+            if let Some(i) = inflight
+                .waiting
+                .iter()
+                .position(|(executor, _)| executor == &self.executor)
+            {
+                inflight.waiting.remove(i);
+            }
+
+            if inflight.waiting.is_empty() {
+                inflight.phase = RequestPhase::Merging;
+            }
+        }
+    }
+}
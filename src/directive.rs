@@ -0,0 +1,146 @@
+use crate::argument_rules::to_json;
+use crate::context::Context;
+use crate::data::Data;
+use crate::schema::{Directive, Schema};
+use graphql_parser::query::{
+    Definition, Directive as AstDirective, Document, OperationDefinition, Selection, SelectionSet,
+};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub trait DirectiveHandler: Send + Sync {
+    fn resolve(
+        &self,
+        data: Option<&Data>,
+        arguments: &Map<String, Value>,
+        value: Value,
+    ) -> Result<Value, String>;
+}
+
+impl<F> DirectiveHandler for F
+where
+    F: Fn(Option<&Data>, &Map<String, Value>, Value) -> Result<Value, String> + Send + Sync,
+{
+    fn resolve(
+        &self,
+        data: Option<&Data>,
+        arguments: &Map<String, Value>,
+        value: Value,
+    ) -> Result<Value, String> {
+        self(data, arguments, value)
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct GatewayDirective {
+    pub(crate) definition: Directive,
+    pub(crate) handler: Arc<dyn DirectiveHandler>,
+}
+
+pub(crate) fn parse_directive(definition: &str) -> Result<Directive, String> {
+    let document = graphql_parser::parse_schema::<String>(definition).map_err(|e| e.to_string())?;
+    let name = match document.definitions.as_slice() {
+        [graphql_parser::schema::Definition::DirectiveDefinition(directive)] => {
+            directive.name.clone()
+        }
+        _ => return Err("Expected a single directive definition.".to_owned()),
+    };
+
+    Schema::from_sdl(definition)?
+        .directives
+        .into_iter()
+        .find(|directive| directive.name == name)
+        .ok_or_else(|| format!("Unknown directive \"{}\".", name))
+}
+
+pub(crate) fn resolve_directives(
+    context: &Context<'_, '_>,
+    directives: &[AstDirective<'_, String>],
+    mut value: Value,
+) -> Result<Value, (String, String)> {
+    for directive in directives {
+        let gateway_directive = match context.gateway.directives.get(&directive.name) {
+            Some(gateway_directive) => gateway_directive,
+            _ => continue,
+        };
+
+        let mut arguments = gateway_directive
+            .definition
+            .args
+            .iter()
+            .filter_map(|arg| {
+                arg.parse_default_value()
+                    .map(|value| (arg.name.clone(), to_json(context, &value)))
+            })
+            .collect::<Map<String, Value>>();
+
+        arguments.extend(
+            directive
+                .arguments
+                .iter()
+                .map(|(name, value)| (name.clone(), to_json(context, value))),
+        );
+
+        value = gateway_directive
+            .handler
+            .resolve(context.data, &arguments, value)
+            .map_err(|e| (directive.name.clone(), e))?;
+    }
+
+    Ok(value)
+}
+
+pub(crate) fn strip_directives(
+    directives: &HashMap<String, GatewayDirective>,
+    document: &mut Document<'_, String>,
+) {
+    if directives.is_empty() {
+        return;
+    }
+
+    for definition in document.definitions.iter_mut() {
+        match definition {
+            Definition::Operation(OperationDefinition::SelectionSet(selection_set)) => {
+                strip_selection_set(directives, selection_set)
+            }
+            Definition::Operation(OperationDefinition::Query(query)) => {
+                strip_selection_set(directives, &mut query.selection_set)
+            }
+            Definition::Operation(OperationDefinition::Mutation(mutation)) => {
+                strip_selection_set(directives, &mut mutation.selection_set)
+            }
+            Definition::Operation(OperationDefinition::Subscription(subscription)) => {
+                strip_selection_set(directives, &mut subscription.selection_set)
+            }
+            Definition::Fragment(fragment) => {
+                strip_selection_set(directives, &mut fragment.selection_set)
+            }
+        }
+    }
+}
+
+fn strip_selection_set(
+    directives: &HashMap<String, GatewayDirective>,
+    selection_set: &mut SelectionSet<'_, String>,
+) {
+    for selection in selection_set.items.iter_mut() {
+        match selection {
+            Selection::Field(field) => {
+                field
+                    .directives
+                    .retain(|directive| !directives.contains_key(&directive.name));
+                strip_selection_set(directives, &mut field.selection_set);
+            }
+            Selection::FragmentSpread(fragment_spread) => fragment_spread
+                .directives
+                .retain(|directive| !directives.contains_key(&directive.name)),
+            Selection::InlineFragment(inline_fragment) => {
+                inline_fragment
+                    .directives
+                    .retain(|directive| !directives.contains_key(&directive.name));
+                strip_selection_set(directives, &mut inline_fragment.selection_set);
+            }
+        }
+    }
+}
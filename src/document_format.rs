@@ -0,0 +1,115 @@
+use graphql_parser::query::Document;
+use std::iter::Peekable;
+use std::str::Chars;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DocumentFormat {
+    #[default]
+    Pretty,
+    Minified,
+}
+
+impl DocumentFormat {
+    pub fn format_source(&self, source: &str) -> String {
+        match graphql_parser::parse_query::<String>(source) {
+            Ok(document) => self.format(&document),
+            _ => source.to_owned(),
+        }
+    }
+
+    pub(crate) fn format(&self, document: &Document<'_, String>) -> String {
+        match self {
+            DocumentFormat::Pretty => document.to_string(),
+            DocumentFormat::Minified => minify(&document.to_string()),
+        }
+    }
+}
+
+fn minify(source: &str) -> String {
+    let mut output = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    let mut separated = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            ' ' | '\t' | '\n' | '\r' | ',' => {
+                separated = true;
+                continue;
+            }
+            '#' => {
+                while chars.peek().map(|&c| c != '\n').unwrap_or(false) {
+                    chars.next();
+                }
+                separated = true;
+                continue;
+            }
+            _ => {}
+        }
+
+        if separated {
+            let needs_space = match (output.chars().last(), c) {
+                (Some(last), '"') => last == '"',
+                (Some(last), c) => is_name_char(last) && (is_name_char(c) || c == '-'),
+                _ => false,
+            };
+
+            if needs_space {
+                output.push(' ');
+            }
+
+            separated = false;
+        }
+
+        output.push(c);
+
+        if c == '"' {
+            let block = consume(&mut chars, "\"\"");
+
+            if block {
+                output.push_str("\"\"");
+            }
+
+            copy_string(&mut chars, &mut output, block);
+        }
+    }
+
+    output
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+fn consume(chars: &mut Peekable<Chars<'_>>, prefix: &str) -> bool {
+    let mut lookahead = chars.clone();
+
+    if prefix.chars().all(|c| lookahead.next() == Some(c)) {
+        for _ in prefix.chars() {
+            chars.next();
+        }
+
+        return true;
+    }
+
+    false
+}
+
+fn copy_string(chars: &mut Peekable<Chars<'_>>, output: &mut String, block: bool) {
+    while let Some(c) = chars.next() {
+        output.push(c);
+
+        match c {
+            '\\' => {
+                if let Some(c) = chars.next() {
+                    output.push(c);
+                }
+            }
+            '"' if !block => return,
+            '"' if consume(chars, "\"\"") => {
+                output.push_str("\"\"");
+                return;
+            }
+            _ => {}
+        }
+    }
+}
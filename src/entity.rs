@@ -0,0 +1,105 @@
+use graphql_parser::query::{Definition, OperationDefinition, Type as AstType};
+use serde_json::{json, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EntityStrategy {
+    #[default]
+    Node,
+    Federation,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeLookup {
+    pub field: String,
+    pub argument: String,
+    pub id_type: String,
+}
+
+impl Default for NodeLookup {
+    fn default() -> Self {
+        NodeLookup::new("nodes")
+    }
+}
+
+impl NodeLookup {
+    pub fn new<T: Into<String>>(field: T) -> Self {
+        NodeLookup {
+            field: field.into(),
+            argument: "ids".to_owned(),
+            id_type: "[ID]!".to_owned(),
+        }
+    }
+
+    pub fn argument<T: Into<String>>(mut self, argument: T) -> Self {
+        self.argument = argument.into();
+        self
+    }
+
+    pub fn id_type<T: Into<String>>(mut self, id_type: T) -> Self {
+        self.id_type = id_type.into();
+        self
+    }
+
+    pub(crate) fn federation() -> Self {
+        NodeLookup::new("_entities")
+            .argument("representations")
+            .id_type("[_Any!]!")
+    }
+
+    pub(crate) fn var_type<'a>(&self) -> AstType<'a, String> {
+        self.parse_id_type().unwrap_or_else(|| {
+            AstType::NonNullType(Box::new(AstType::ListType(Box::new(AstType::NamedType(
+                "ID".to_owned(),
+            )))))
+        })
+    }
+
+    pub(crate) fn has_valid_id_type(&self) -> bool {
+        self.parse_id_type().is_some()
+    }
+
+    fn parse_id_type<'a>(&self) -> Option<AstType<'a, String>> {
+        let source = format!("query($ids: {}) {{ __typename }}", self.id_type);
+        let document = graphql_parser::parse_query::<String>(&source).ok()?;
+
+        match document.definitions.into_iter().next()? {
+            Definition::Operation(OperationDefinition::Query(query)) => query
+                .variable_definitions
+                .into_iter()
+                .next()
+                .map(|definition| owned_type(definition.var_type)),
+            _ => None,
+        }
+    }
+}
+
+fn owned_type<'a>(var_type: AstType<'_, String>) -> AstType<'a, String> {
+    match var_type {
+        AstType::NamedType(name) => AstType::NamedType(name),
+        AstType::ListType(of_type) => AstType::ListType(Box::new(owned_type(*of_type))),
+        AstType::NonNullType(of_type) => AstType::NonNullType(Box::new(owned_type(*of_type))),
+    }
+}
+
+impl EntityStrategy {
+    pub(crate) fn argument_value(
+        &self,
+        type_name: &str,
+        key_fields: &[(String, String)],
+        id: &Value,
+    ) -> Value {
+        match self {
+            EntityStrategy::Node => id.clone(),
+            EntityStrategy::Federation => {
+                let mut representation = match (key_fields, id) {
+                    ([(name, _)], _) => json!({ name.as_str(): id }),
+                    (_, Value::Object(fields)) => Value::Object(fields.clone()),
+                    _ => json!({ "id": id }),
+                };
+
+                representation["__typename"] = Value::from(type_name);
+                representation
+            }
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use crate::executor_error::ExecutorError;
+use crate::query::QueryError;
+use serde_json::Value;
+
+const AUTH_CODES: [&str; 3] = ["UNAUTHENTICATED", "UNAUTHORIZED", "FORBIDDEN"];
+const VALIDATION_CODES: [&str; 3] = [
+    "GRAPHQL_PARSE_FAILED",
+    "GRAPHQL_VALIDATION_FAILED",
+    "BAD_USER_INPUT",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorClass {
+    Transient,
+    Validation,
+    Auth,
+    Rejected,
+    Internal,
+}
+
+impl ErrorClass {
+    pub fn is_retryable(&self) -> bool {
+        *self == ErrorClass::Transient
+    }
+
+    pub fn is_definitive(&self) -> bool {
+        !self.is_retryable()
+    }
+}
+
+pub trait ErrorClassifier: Send + Sync {
+    fn classify(&self, error: &QueryError) -> ErrorClass;
+}
+
+#[derive(Clone, Default)]
+pub struct DefaultErrorClassifier;
+
+impl ErrorClassifier for DefaultErrorClassifier {
+    fn classify(&self, error: &QueryError) -> ErrorClass {
+        match error {
+            QueryError::Transport(..)
+            | QueryError::ExecutorTimeout(..)
+            | QueryError::CircuitOpen(..)
+            | QueryError::Unavailable(..) => ErrorClass::Transient,
+            QueryError::ResourceExhausted(..) => ErrorClass::Rejected,
+            QueryError::Executor(value) => classify_response(value),
+            QueryError::Downstream(errors) => {
+                classify_codes(errors.iter().filter_map(ExecutorError::code).collect())
+            }
+            QueryError::Forbidden(..) => ErrorClass::Auth,
+            QueryError::Errors(errors)
+                if errors
+                    .iter()
+                    .any(|error| matches!(error.1, QueryError::Forbidden(..))) =>
+            {
+                ErrorClass::Auth
+            }
+            QueryError::InvalidExecutorResponse
+            | QueryError::Directive(..)
+            | QueryError::Custom(_) => ErrorClass::Internal,
+            _ => ErrorClass::Validation,
+        }
+    }
+}
+
+fn classify_response(value: &Value) -> ErrorClass {
+    let codes = value
+        .get("errors")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|error| error.get("extensions")?.get("code")?.as_str())
+        .collect::<Vec<&str>>();
+
+    classify_codes(codes)
+}
+
+fn classify_codes(codes: Vec<&str>) -> ErrorClass {
+    if codes.iter().any(|code| AUTH_CODES.contains(code)) {
+        ErrorClass::Auth
+    } else if codes.iter().any(|code| VALIDATION_CODES.contains(code)) {
+        ErrorClass::Validation
+    } else {
+        ErrorClass::Rejected
+    }
+}
@@ -0,0 +1,31 @@
+use crate::http::QueryResponse;
+use fnv::FnvHasher;
+use std::hash::Hasher;
+
+#[derive(Clone, Debug)]
+pub struct IfNoneMatch(pub String);
+
+impl IfNoneMatch {
+    pub fn matches(&self, etag: &str) -> bool {
+        self.0.split(',').map(str::trim).any(|candidate| {
+            candidate == "*" || candidate.trim_start_matches("W/") == etag.trim_start_matches("W/")
+        })
+    }
+}
+
+pub(crate) fn apply_etag(response: &mut QueryResponse, if_none_match: Option<&IfNoneMatch>) {
+    let value = match &response.result {
+        Ok(value) if response.errors.is_empty() => value,
+        _ => return,
+    };
+
+    let mut hasher = FnvHasher::default();
+    hasher.write(value.to_string().as_bytes());
+
+    let etag = format!("\"{:016x}\"", hasher.finish());
+
+    response.not_modified = if_none_match
+        .map(|if_none_match| if_none_match.matches(&etag))
+        .unwrap_or(false);
+    response.etag = Some(etag);
+}
@@ -2,6 +2,7 @@ use crate::data::Data;
 use crate::schema::Schema;
 use async_trait::async_trait;
 use serde_json::Value;
+use std::any::Any;
 
 pub const INTROSPECTION_QUERY: &str = r#"
   query IntrospectionQuery {
@@ -106,7 +107,7 @@ pub const INTROSPECTION_QUERY: &str = r#"
 "#;
 
 #[async_trait]
-pub trait Executor: Send + Sync + CloneExecutor {
+pub trait Executor: Send + Sync + CloneExecutor + AsAnyExecutor {
   fn name(&self) -> &str;
 
   async fn execute(
@@ -117,10 +118,10 @@ pub trait Executor: Send + Sync + CloneExecutor {
     variables: Option<Value>,
   ) -> Result<Value, String>;
 
-  async fn introspect(&self) -> Result<(String, Schema), String> {
+  async fn introspect(&self, data: Option<&Data>) -> Result<(String, Schema), String> {
     self
       .execute(
-        None,
+        data,
         INTROSPECTION_QUERY.to_owned(),
         Some("IntrospectionQuery".to_owned()),
         None,
@@ -152,3 +153,21 @@ impl Clone for Box<dyn Executor> {
     self.clone_executor()
   }
 }
+
+pub trait AsAnyExecutor {
+  fn as_any(&self) -> &dyn Any;
+  fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T> AsAnyExecutor for T
+where
+  T: Executor + 'static,
+{
+  fn as_any(&self) -> &dyn Any {
+    self
+  }
+
+  fn as_any_mut(&mut self) -> &mut dyn Any {
+    self
+  }
+}
@@ -0,0 +1,116 @@
+use serde_json::{Map, Value};
+
+const MASKED_MESSAGE: &str = "Downstream service error.";
+const MASKED_CODE: &str = "DOWNSTREAM_SERVICE_ERROR";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorPassthrough {
+    #[default]
+    Expose,
+    Mask,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExecutorError {
+    #[serde(skip)]
+    pub executor: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub locations: Vec<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<Vec<Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extensions: Option<Map<String, Value>>,
+}
+
+impl ExecutorError {
+    pub fn code(&self) -> Option<&str> {
+        self.extensions.as_ref()?.get("code")?.as_str()
+    }
+
+    fn mask(self) -> Self {
+        let mut extensions = Map::new();
+        extensions.insert(
+            "code".to_owned(),
+            Value::from(self.code().unwrap_or(MASKED_CODE)),
+        );
+
+        ExecutorError {
+            executor: self.executor,
+            message: MASKED_MESSAGE.to_owned(),
+            locations: vec![],
+            path: self.path,
+            extensions: Some(extensions),
+        }
+    }
+}
+
+pub(crate) fn executor_errors(
+    executor: &str,
+    response: &Value,
+    passthrough: ErrorPassthrough,
+) -> Vec<ExecutorError> {
+    response
+        .get("errors")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .map(|error| {
+            let error = ExecutorError {
+                executor: executor.to_owned(),
+                message: match error.get("message") {
+                    Some(Value::String(message)) => message.clone(),
+                    Some(message) => message.to_string(),
+                    _ => error.to_string(),
+                },
+                locations: error
+                    .get("locations")
+                    .and_then(Value::as_array)
+                    .cloned()
+                    .unwrap_or_default(),
+                path: error.get("path").and_then(Value::as_array).cloned(),
+                extensions: error.get("extensions").and_then(Value::as_object).cloned(),
+            };
+
+            match passthrough {
+                ErrorPassthrough::Expose => error,
+                ErrorPassthrough::Mask => error.mask(),
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn rewrite_node_paths<F>(
+    errors: Vec<ExecutorError>,
+    field_name: &str,
+    prefix: F,
+) -> Vec<ExecutorError>
+where
+    F: Fn(usize) -> Option<Vec<Value>>,
+{
+    errors
+        .into_iter()
+        .filter_map(|mut error| {
+            let index = match error.path.as_deref() {
+                Some([Value::String(field), Value::Number(index), ..]) if field == field_name => {
+                    index.as_u64()? as usize
+                }
+                _ => return Some(error),
+            };
+
+            let mut path = prefix(index)?;
+            path.extend(error.path.take().into_iter().flatten().skip(2));
+            error.path = Some(path);
+
+            Some(error)
+        })
+        .collect()
+}
+
+pub(crate) fn downstream_message(errors: &[ExecutorError]) -> String {
+    errors
+        .iter()
+        .map(|error| error.message.as_str())
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
@@ -0,0 +1,122 @@
+use crate::naming::NamingPolicy;
+use crate::schema::Schema;
+use fnv::FnvHasher;
+use serde_json::Value;
+use std::hash::Hasher;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ExecutorHealth {
+    Healthy,
+    Unhealthy,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExecutorInfo {
+    pub name: String,
+    #[serde(rename = "schemaHash")]
+    pub schema_hash: Option<String>,
+    #[serde(rename = "typeCount")]
+    pub type_count: usize,
+    #[serde(rename = "fieldCount")]
+    pub field_count: usize,
+    #[serde(rename = "introspectedAt")]
+    pub introspected_at: Option<u64>,
+    pub health: ExecutorHealth,
+    pub error: Option<String>,
+    pub initialized: bool,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct IntrospectionState {
+    pub(crate) at: SystemTime,
+    pub(crate) error: Option<String>,
+    pub(crate) succeeded_at: Option<SystemTime>,
+}
+
+impl IntrospectionState {
+    pub(crate) fn new<T>(result: &Result<T, String>) -> Self {
+        let at = SystemTime::now();
+
+        IntrospectionState {
+            at,
+            error: result.as_ref().err().cloned(),
+            succeeded_at: result.as_ref().ok().map(|_| at),
+        }
+    }
+
+    pub(crate) fn after(mut self, previous: Option<&IntrospectionState>) -> Self {
+        if self.succeeded_at.is_none() {
+            self.succeeded_at = previous.and_then(|previous| previous.succeeded_at);
+        }
+
+        self
+    }
+}
+
+pub(crate) fn executor_info(
+    name: &str,
+    schema: Option<&Schema>,
+    state: Option<&IntrospectionState>,
+    naming_policy: &NamingPolicy,
+    initialized: bool,
+) -> ExecutorInfo {
+    let types = schema
+        .map(|schema| {
+            schema
+                .types
+                .iter()
+                .filter(|t| !naming_policy.is_introspection_type(t.name()))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    let health = match state {
+        Some(state) if state.error.is_some() => ExecutorHealth::Unhealthy,
+        Some(_) => ExecutorHealth::Healthy,
+        _ => ExecutorHealth::Unknown,
+    };
+
+    ExecutorInfo {
+        name: name.to_owned(),
+        schema_hash: schema.map(schema_hash),
+        type_count: types.len(),
+        field_count: types
+            .iter()
+            .map(|t| t.fields.as_ref().map(Vec::len).unwrap_or(0))
+            .sum(),
+        introspected_at: state.map(|state| {
+            state
+                .at
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64
+        }),
+        health,
+        error: state.and_then(|state| state.error.clone()),
+        initialized,
+    }
+}
+
+pub(crate) fn schema_hash(schema: &Schema) -> String {
+    let mut value = serde_json::to_value(schema).unwrap_or_default();
+    canonicalize(&mut value);
+
+    let mut hasher = FnvHasher::default();
+    hasher.write(value.to_string().as_bytes());
+
+    format!("{:016x}", hasher.finish())
+}
+
+fn canonicalize(value: &mut Value) {
+    match value {
+        Value::Array(values) => {
+            values.iter_mut().for_each(canonicalize);
+            values.sort_by_cached_key(Value::to_string);
+        }
+        Value::Object(object) => object.values_mut().for_each(canonicalize),
+        _ => {}
+    }
+}
@@ -0,0 +1,65 @@
+use serde_json::Value;
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MergeStrategy {
+    Concat,
+    SortedBy { key: String, descending: bool },
+}
+
+impl MergeStrategy {
+    pub fn sorted_by<T: Into<String>>(key: T) -> Self {
+        MergeStrategy::SortedBy {
+            key: key.into(),
+            descending: false,
+        }
+    }
+
+    pub fn sorted_by_desc<T: Into<String>>(key: T) -> Self {
+        MergeStrategy::SortedBy {
+            key: key.into(),
+            descending: true,
+        }
+    }
+
+    pub(crate) fn merge(&self, values: Vec<Value>) -> Value {
+        let mut merged = Vec::new();
+
+        for value in values {
+            match value {
+                Value::Array(values) => merged.extend(values),
+                Value::Null => {}
+                value => merged.push(value),
+            }
+        }
+
+        if let MergeStrategy::SortedBy { key, descending } = self {
+            merged.sort_by(|a, b| {
+                let ordering = compare(a.get(key), b.get(key));
+
+                if *descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            });
+        }
+
+        Value::Array(merged)
+    }
+}
+
+fn compare(a: Option<&Value>, b: Option<&Value>) -> Ordering {
+    match (a, b) {
+        (Some(Value::Number(a)), Some(Value::Number(b))) => a
+            .as_f64()
+            .partial_cmp(&b.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (Some(Value::String(a)), Some(Value::String(b))) => a.cmp(b),
+        (Some(Value::Bool(a)), Some(Value::Bool(b))) => a.cmp(b),
+        (Some(Value::Null), Some(Value::Null)) | (None, None) => Ordering::Equal,
+        (None, _) | (Some(Value::Null), _) => Ordering::Greater,
+        (_, None) | (_, Some(Value::Null)) => Ordering::Less,
+        _ => Ordering::Equal,
+    }
+}
@@ -0,0 +1,105 @@
+use crate::context::Context;
+use crate::planner::{plan_operation, PlanStepKind};
+use crate::query::{collect_passthrough, ResolveInfo};
+use crate::schema::Type;
+use crate::static_fields::is_static_field;
+use graphql_parser::query::{Selection, TypeCondition};
+use std::collections::HashMap;
+
+pub(crate) fn single_executor_plan<'a>(
+    context: &Context<'a, '_>,
+    object_type: &Type,
+    selections: &[Selection<'a, String>],
+) -> Option<(String, ResolveInfo<'a>)> {
+    let gateway = context.gateway;
+
+    if !context.flags().fast_path
+        || object_type.name() != "Query"
+        || !gateway.argument_injectors.is_empty()
+        || !gateway.field_fallbacks.is_empty()
+    {
+        return None;
+    }
+
+    let plan = plan_operation(context, object_type, selections).ok()?;
+    let executor = match plan.steps.as_slice() {
+        [step] if step.kind == PlanStepKind::Root => step.executor.clone(),
+        _ => return None,
+    };
+
+    if context.id_codec(&executor).is_some() || !is_pass_through(context, object_type, selections) {
+        return None;
+    }
+
+    let mut fragments = HashMap::new();
+    let mut variable_definitions = HashMap::new();
+    collect_passthrough(
+        context,
+        selections,
+        &mut fragments,
+        &mut variable_definitions,
+    );
+
+    Some((
+        executor,
+        ResolveInfo {
+            selections: selections.to_vec(),
+            fragments,
+            variable_definitions,
+        },
+    ))
+}
+
+fn is_pass_through<'a>(
+    context: &Context<'a, '_>,
+    object_type: &Type,
+    selections: &[Selection<'a, String>],
+) -> bool {
+    selections.iter().all(|selection| {
+        if !context.is_included(selection) {
+            return true;
+        }
+
+        let (type_condition, items) = match selection {
+            Selection::Field(field) => {
+                let is_gateway_field = context.gateway.naming_policy.is_meta_field(&field.name)
+                    || is_static_field(context, object_type.name(), &field.name)
+                    || !context.is_authorized(object_type, &field.name)
+                    || field
+                        .directives
+                        .iter()
+                        .any(|directive| context.gateway.directives.contains_key(&directive.name));
+
+                if is_gateway_field {
+                    return false;
+                }
+
+                return match context.field_object_type(object_type, &field.name) {
+                    Some((_, field_type)) => {
+                        is_pass_through(context, field_type, &field.selection_set.items)
+                    }
+                    _ => false,
+                };
+            }
+            Selection::FragmentSpread(fragment_spread) => {
+                match context.fragments.get(&fragment_spread.fragment_name) {
+                    Some(fragment) => (Some(&fragment.type_condition), &fragment.selection_set),
+                    _ => return false,
+                }
+            }
+            Selection::InlineFragment(inline_fragment) => (
+                inline_fragment.type_condition.as_ref(),
+                &inline_fragment.selection_set,
+            ),
+        };
+
+        let fragment_type = match type_condition {
+            Some(TypeCondition::On(name)) => context.object(name),
+            _ => Some(object_type),
+        };
+
+        fragment_type
+            .map(|fragment_type| is_pass_through(context, fragment_type, &items.items))
+            .unwrap_or(false)
+    })
+}
@@ -0,0 +1,93 @@
+use crate::context::Context;
+use crate::query::{QueryError, QueryPosError, QueryResult};
+use graphql_parser::query::{Field, FragmentDefinition, Selection};
+use std::collections::HashMap;
+
+pub(crate) fn merge_selections<'a>(
+    context: &Context<'a, '_>,
+    selections: &[Selection<'a, String>],
+) -> QueryResult<Vec<Selection<'a, String>>> {
+    let mut items: Vec<Selection<'a, String>> = Vec::with_capacity(selections.len());
+    let mut errors = Vec::new();
+
+    for selection in selections {
+        if !context.is_included(selection) {
+            continue;
+        }
+
+        let field = match selection {
+            Selection::Field(field) => field,
+            Selection::InlineFragment(inline_fragment) => {
+                let mut inline_fragment = inline_fragment.clone();
+                inline_fragment.selection_set.items =
+                    merge_selections(context, &inline_fragment.selection_set.items)?;
+                items.push(Selection::InlineFragment(inline_fragment));
+                continue;
+            }
+            Selection::FragmentSpread(_) => {
+                items.push(selection.clone());
+                continue;
+            }
+        };
+
+        let existing = items.iter_mut().find_map(|item| match item {
+            Selection::Field(existing) if response_key(existing) == response_key(field) => {
+                Some(existing)
+            }
+            _ => None,
+        });
+
+        match existing {
+            Some(existing) if !can_merge(existing, field) => errors.push(QueryPosError(
+                field.position,
+                QueryError::FieldsConflict(response_key(field).to_owned()),
+            )),
+            Some(existing) => existing
+                .selection_set
+                .items
+                .extend(field.selection_set.items.iter().cloned()),
+            _ => items.push(selection.clone()),
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(QueryError::Errors(errors));
+    }
+
+    for item in &mut items {
+        if let Selection::Field(field) = item {
+            if !field.selection_set.items.is_empty() {
+                field.selection_set.items = merge_selections(context, &field.selection_set.items)?;
+            }
+        }
+    }
+
+    Ok(items)
+}
+
+pub(crate) fn merge_fragments<'a>(
+    context: &Context<'a, '_>,
+) -> QueryResult<HashMap<String, FragmentDefinition<'a, String>>> {
+    context
+        .fragments
+        .iter()
+        .map(|(name, fragment)| {
+            let mut fragment = fragment.clone();
+            fragment.selection_set.items =
+                merge_selections(context, &fragment.selection_set.items)?;
+            Ok((name.clone(), fragment))
+        })
+        .collect()
+}
+
+fn response_key<'b>(field: &'b Field<'_, String>) -> &'b str {
+    field.alias.as_ref().unwrap_or(&field.name)
+}
+
+fn can_merge<'a>(a: &Field<'a, String>, b: &Field<'a, String>) -> bool {
+    a.name == b.name
+        && a.arguments.len() == b.arguments.len()
+        && b.arguments
+            .iter()
+            .all(|argument| a.arguments.contains(argument))
+}
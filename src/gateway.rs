@@ -1,11 +1,56 @@
+use crate::argument_rules::{ArgumentRule, ArgumentRules};
+use crate::authorization::{Authorizer, UnauthorizedField};
+use crate::builder::{ConfigError, ExecutorOptions};
+use crate::circuit_breaker::{CircuitBreaker, CircuitBreakers, CircuitState, Clock};
+use crate::compensation::Compensation;
+use crate::composition_cache::CompositionCache;
+use crate::data::Data;
+use crate::diagnostics::{Diagnostics, RequestDiagnostics};
+use crate::directive::{parse_directive, DirectiveHandler, GatewayDirective};
+use crate::document_format::DocumentFormat;
+use crate::entity::{EntityStrategy, NodeLookup};
+use crate::error_class::{DefaultErrorClassifier, ErrorClassifier};
 use crate::executor::Executor;
-use crate::schema::{Schema, Type, TypeKind};
+use crate::executor_error::ErrorPassthrough;
+use crate::executor_info::{executor_info, schema_hash, ExecutorInfo, IntrospectionState};
+use crate::fan_out::MergeStrategy;
+use crate::health::{unix_millis, ExecutorStatus, GatewayHealth};
+use crate::id_codec::{DefaultIdCodec, IdCodec};
+use crate::injection::ArgumentInjector;
+use crate::lazy_executor::{ExecutorFactory, LazyExecutor};
+use crate::limits::{CompositionLimitError, CompositionLimits};
+use crate::materialized::MaterializedView;
+use crate::mode::{Mode, ModeState};
+use crate::naming::{NamingError, NamingPolicy};
+use crate::node_cache::{EntityInvalidator, NodeCache};
+use crate::persisted::PersistedQueries;
+use crate::plan_cache::{PlanCache, PlanCacheStats};
+use crate::planner::PlannerFlags;
+use crate::query::{QueryBuilder, QueryError, QueryResult};
+use crate::request_context::HeaderPolicy;
+use crate::response_cache::ResponseCache;
+use crate::schema::{Directive, Schema, Type, TypeKind};
+use crate::schema_source::{load_schema, FederationSource, SchemaSource, SdlSource};
+use crate::signature::UsageReporter;
+use crate::slo::{Slo, SloAlert, SloStatus, SloTarget, SloTracker};
+use crate::smoke::generate_smoke_queries;
+use crate::snapshot::SchemaHistory;
+use crate::static_fields::{static_fields_schema, STATIC_FIELDS_EXECUTOR};
+use crate::stats::{ExecutorStats, StatsTracker, DEFAULT_STATS_WINDOW};
+use crate::supergraph::supergraph_sdl;
+use crate::transformer::SelectionTransformer;
+use crate::typescript::export_typescript;
 use futures::future;
+use futures_timer::Delay;
 use graphql_parser::schema::{Definition, Document, SchemaDefinition};
 use graphql_parser::Pos;
-use serde_json::{Error as JsonError, Value};
-use std::collections::HashMap;
+use serde_json::{json, Error as JsonError, Value};
+use std::any::Any;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
 
 #[derive(Debug, Error)]
 pub enum GatewayError {
@@ -17,6 +62,16 @@ pub enum GatewayError {
     UnknownExecutor(String),
     #[error("Duplicate object fields: {0:#?}")]
     DuplicateObjectFields(Vec<(String, String, String)>),
+    #[error("Invalid names: {0:#?}")]
+    InvalidNames(Vec<NamingError>),
+    #[error("Invalid configuration: {0:#?}")]
+    InvalidConfiguration(Vec<ConfigError>),
+    #[error("Unknown schema snapshot \"{0}\"")]
+    UnknownSnapshot(String),
+    #[error("Composition failed: {0:#?}")]
+    CompositionFailed(Vec<(String, String)>),
+    #[error("Composition limits exceeded: {0:#?}")]
+    CompositionLimitsExceeded(Vec<CompositionLimitError>),
 }
 
 impl From<String> for GatewayError {
@@ -35,10 +90,86 @@ pub type GatewayResult<T> = Result<T, GatewayError>;
 
 #[derive(Clone, Default)]
 pub struct Gateway<'a> {
-    pub executors: HashMap<String, Box<dyn Executor>>,
+    pub(crate) executors: HashMap<String, Box<dyn Executor>>,
+    pub(crate) lazy_executors: HashMap<String, Arc<LazyExecutor>>,
     pub(crate) introspections: HashMap<String, Schema>,
+    pub(crate) introspection_states: HashMap<String, IntrospectionState>,
+    pub(crate) introspection_data: HashMap<String, Data>,
+    pub(crate) schema_source: Option<Arc<dyn SchemaSource>>,
+    pub(crate) schema_sources: HashMap<String, Arc<dyn SchemaSource>>,
+    pub(crate) data: Arc<Data>,
     pub(crate) schema: GatewaySchema,
+    pub(crate) schema_history: SchemaHistory,
     pub(crate) document: Document<'a, String>,
+    pub(crate) list_parallelism: Option<usize>,
+    pub(crate) batch_parallelism: Option<usize>,
+    pub(crate) document_format: DocumentFormat,
+    pub(crate) planner_flags: PlannerFlags,
+    pub(crate) mode: Arc<RwLock<ModeState>>,
+    pub(crate) node_cache: Option<NodeCache>,
+    pub(crate) node_cache_ttls: HashMap<String, Duration>,
+    pub(crate) node_cache_executor_ttls: HashMap<String, Duration>,
+    pub(crate) id_codec: Option<Arc<dyn IdCodec>>,
+    pub(crate) wrapped_id_executors: HashSet<String>,
+    pub(crate) entity_strategies: HashMap<String, EntityStrategy>,
+    pub(crate) node_lookups: HashMap<String, NodeLookup>,
+    pub(crate) skip_null_keys: bool,
+    pub(crate) header_policy: Option<HeaderPolicy>,
+    pub(crate) header_policies: HashMap<String, HeaderPolicy>,
+    pub(crate) executor_options: HashMap<String, ExecutorOptions>,
+    pub(crate) circuit_breaker: Option<CircuitBreaker>,
+    pub(crate) executor_circuit_breakers: HashMap<String, CircuitBreaker>,
+    pub(crate) circuit_breakers: Arc<CircuitBreakers>,
+    pub(crate) composition_cache: Option<PathBuf>,
+    pub(crate) stale_introspections: HashMap<String, SystemTime>,
+    pub(crate) argument_injectors: HashMap<String, Arc<dyn ArgumentInjector>>,
+    pub(crate) selection_transformers: HashMap<String, Vec<Arc<dyn SelectionTransformer>>>,
+    pub(crate) directives: HashMap<String, GatewayDirective>,
+    pub(crate) invalid_directives: Vec<(String, String)>,
+    pub(crate) invalid_materialized_views: Vec<(String, String)>,
+    pub(crate) argument_rules: ArgumentRules,
+    pub(crate) naming_policy: NamingPolicy,
+    pub(crate) versions: HashMap<String, GatewayVersion>,
+    pub(crate) fallback_executors: HashMap<String, Box<dyn Executor>>,
+    pub(crate) field_fallbacks: HashMap<String, Vec<String>>,
+    pub(crate) catch_all_executor: Option<String>,
+    pub(crate) memory_limit: Option<usize>,
+    pub(crate) max_depth: Option<usize>,
+    pub(crate) max_complexity: Option<usize>,
+    pub(crate) max_deferred: Option<usize>,
+    pub(crate) field_costs: HashMap<String, usize>,
+    pub(crate) composition_limits: CompositionLimits,
+    pub(crate) introspection_disabled: bool,
+    pub(crate) static_fields: bool,
+    pub(crate) etag: bool,
+    pub(crate) apollo_tracing: bool,
+    pub(crate) diagnostics: Arc<Diagnostics>,
+    pub(crate) materialized_views: Vec<MaterializedView>,
+    pub(crate) compensations: HashMap<String, Arc<dyn Compensation>>,
+    pub(crate) fan_out: HashMap<String, MergeStrategy>,
+    pub(crate) error_classifier: Option<Arc<dyn ErrorClassifier>>,
+    pub(crate) error_passthrough: ErrorPassthrough,
+    pub(crate) authorizer: Option<Arc<dyn Authorizer>>,
+    pub(crate) unauthorized_field: UnauthorizedField,
+    pub(crate) unauthorized_fields: HashMap<String, UnauthorizedField>,
+    pub(crate) response_cache: Option<(Arc<dyn ResponseCache>, Duration)>,
+    pub(crate) persisted_queries: Option<PersistedQueries>,
+    pub(crate) plan_cache: Option<Arc<PlanCache>>,
+    pub(crate) response_cache_ttls: HashMap<String, Duration>,
+    pub(crate) slos: HashMap<SloTarget, Slo>,
+    pub(crate) slo_alert: Option<Arc<dyn SloAlert>>,
+    pub(crate) slo_tracker: Arc<SloTracker>,
+    pub(crate) usage_reporter: Option<Arc<dyn UsageReporter>>,
+    pub(crate) stats_window: Option<usize>,
+    pub(crate) stats: Arc<StatsTracker>,
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct GatewayVersion {
+    pub(crate) executors: HashMap<String, Box<dyn Executor>>,
+    pub(crate) introspections: HashMap<String, Schema>,
+    pub(crate) schema: GatewaySchema,
+    pub(crate) deprecation: Option<String>,
 }
 
 impl<'a> Gateway<'a> {
@@ -47,17 +178,764 @@ impl<'a> Gateway<'a> {
         self
     }
 
+    pub fn executor_with_sdl<E: Executor + 'static>(mut self, e: E, sdl: &str) -> Self {
+        self.schema_sources
+            .insert(e.name().to_owned(), Arc::new(SdlSource(sdl.to_owned())));
+        self.executor(e)
+    }
+
+    pub fn lazy_executor<T: Into<String>, F: ExecutorFactory + 'static>(
+        mut self,
+        name: T,
+        factory: F,
+    ) -> Self {
+        self.lazy_executors
+            .insert(name.into(), Arc::new(LazyExecutor::new(factory, None)));
+        self
+    }
+
+    pub fn lazy_executor_with_schema<T: Into<String>, F: ExecutorFactory + 'static>(
+        mut self,
+        name: T,
+        schema: Schema,
+        factory: F,
+    ) -> Self {
+        self.lazy_executors.insert(
+            name.into(),
+            Arc::new(LazyExecutor::new(factory, Some(schema))),
+        );
+        self
+    }
+
+    pub fn executor_version<T: Into<String>, E: Executor + 'static>(
+        mut self,
+        version: T,
+        e: E,
+    ) -> Self {
+        self.versions
+            .entry(version.into())
+            .or_default()
+            .executors
+            .insert(e.name().to_owned(), Box::new(e));
+        self
+    }
+
+    pub fn deprecate_version<T: Into<String>, R: Into<String>>(
+        mut self,
+        version: T,
+        reason: R,
+    ) -> Self {
+        self.versions.entry(version.into()).or_default().deprecation = Some(reason.into());
+        self
+    }
+
+    pub fn versions(&self) -> Vec<&str> {
+        self.versions
+            .keys()
+            .map(|version| version.as_str())
+            .collect()
+    }
+
+    pub fn version_deprecation(&self, version: &str) -> Option<&str> {
+        self.versions
+            .get(version)
+            .and_then(|version| version.deprecation.as_deref())
+    }
+
+    pub fn schema_source<S: SchemaSource + 'static>(mut self, source: S) -> Self {
+        self.schema_source = Some(Arc::new(source));
+        self
+    }
+
+    pub fn schema_history(mut self, limit: usize) -> Self {
+        self.schema_history.limit = limit;
+        self
+    }
+
+    pub fn data<D: Any + Sync + Send>(mut self, data: D) -> Self {
+        Arc::make_mut(&mut self.data).insert(data);
+        self
+    }
+
+    pub fn introspection_data<T: Into<String>, D: Any + Sync + Send>(
+        mut self,
+        executor: T,
+        data: D,
+    ) -> Self {
+        self.introspection_data
+            .entry(executor.into())
+            .or_default()
+            .insert(data);
+        self
+    }
+
+    pub fn fallback_executor<E: Executor + 'static>(mut self, e: E) -> Self {
+        self.fallback_executors
+            .insert(e.name().to_owned(), Box::new(e));
+        self
+    }
+
+    pub fn catch_all_executor<E: Executor + 'static>(mut self, e: E) -> Self {
+        self.catch_all_executor = Some(e.name().to_owned());
+        self.fallback_executors
+            .insert(e.name().to_owned(), Box::new(e));
+        self
+    }
+
+    pub fn field_fallback<T: Into<String>, F: Into<String>, E: Into<String>>(
+        mut self,
+        type_name: T,
+        field_name: F,
+        executor: E,
+    ) -> Self {
+        let key = format!("{}.{}", type_name.into(), field_name.into());
+        let executor = executor.into();
+        let executors = self.field_fallbacks.entry(key).or_default();
+
+        if !executors.contains(&executor) {
+            executors.push(executor);
+        }
+
+        self
+    }
+
+    pub fn compensation<T: Into<String>, C: Compensation + 'static>(
+        mut self,
+        field: T,
+        compensation: C,
+    ) -> Self {
+        self.compensations
+            .insert(field.into(), Arc::new(compensation));
+        self
+    }
+
+    pub fn fan_out<T: Into<String>>(mut self, field: T, strategy: MergeStrategy) -> Self {
+        self.fan_out.insert(field.into(), strategy);
+        self
+    }
+
+    pub fn error_classifier<C: ErrorClassifier + 'static>(mut self, classifier: C) -> Self {
+        self.error_classifier = Some(Arc::new(classifier));
+        self
+    }
+
+    pub(crate) fn classifier(&self) -> &dyn ErrorClassifier {
+        match &self.error_classifier {
+            Some(classifier) => classifier.as_ref(),
+            _ => &DefaultErrorClassifier,
+        }
+    }
+
+    pub fn error_passthrough(mut self, passthrough: ErrorPassthrough) -> Self {
+        self.error_passthrough = passthrough;
+        self
+    }
+
+    pub fn authorizer<A: Authorizer + 'static>(mut self, authorizer: A) -> Self {
+        self.authorizer = Some(Arc::new(authorizer));
+        self
+    }
+
+    pub fn unauthorized_field(mut self, behavior: UnauthorizedField) -> Self {
+        self.unauthorized_field = behavior;
+        self
+    }
+
+    pub fn field_unauthorized<T: Into<String>, F: Into<String>>(
+        mut self,
+        type_name: T,
+        field_name: F,
+        behavior: UnauthorizedField,
+    ) -> Self {
+        let key = format!("{}.{}", type_name.into(), field_name.into());
+        self.unauthorized_fields.insert(key, behavior);
+        self
+    }
+
+    pub fn list_parallelism(mut self, n: usize) -> Self {
+        self.list_parallelism = Some(n);
+        self
+    }
+
+    pub fn batch_parallelism(mut self, n: usize) -> Self {
+        self.batch_parallelism = Some(n);
+        self
+    }
+
+    pub fn document_format(mut self, format: DocumentFormat) -> Self {
+        self.document_format = format;
+        self
+    }
+
+    pub fn planner_flags(mut self, flags: PlannerFlags) -> Self {
+        self.planner_flags = flags;
+        self
+    }
+
+    pub fn memory_limit(mut self, bytes: usize) -> Self {
+        self.memory_limit = Some(bytes);
+        self
+    }
+
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    pub fn max_complexity(mut self, max_complexity: usize) -> Self {
+        self.max_complexity = Some(max_complexity);
+        self
+    }
+
+    pub fn max_deferred(mut self, max_deferred: usize) -> Self {
+        self.max_deferred = Some(max_deferred);
+        self
+    }
+
+    pub fn field_cost<T: Into<String>>(mut self, field: T, cost: usize) -> Self {
+        self.field_costs.insert(field.into(), cost);
+        self
+    }
+
+    pub fn composition_limits(mut self, limits: CompositionLimits) -> Self {
+        self.composition_limits = limits;
+        self
+    }
+
+    pub fn introspection(mut self, enabled: bool) -> Self {
+        self.introspection_disabled = !enabled;
+        self
+    }
+
+    pub fn static_fields(mut self, enabled: bool) -> Self {
+        self.static_fields = enabled;
+        self
+    }
+
+    pub fn etag(mut self, enabled: bool) -> Self {
+        self.etag = enabled;
+        self
+    }
+
+    pub fn apollo_tracing(mut self, enabled: bool) -> Self {
+        self.apollo_tracing = enabled;
+        self
+    }
+
+    pub fn materialized_view<T: Into<String>>(
+        mut self,
+        name: T,
+        query: QueryBuilder,
+        refresh_interval: Duration,
+    ) -> Self {
+        let name = name.into();
+
+        match MaterializedView::new(name.clone(), query, refresh_interval) {
+            Ok(view) => self.materialized_views.push(view),
+            Err(e) => self.invalid_materialized_views.push((name, e)),
+        }
+        self
+    }
+
+    pub fn response_cache<C: ResponseCache + 'static>(mut self, cache: C, ttl: Duration) -> Self {
+        self.response_cache = Some((Arc::new(cache), ttl));
+        self
+    }
+
+    pub fn persisted_queries(mut self, queries: PersistedQueries) -> Self {
+        self.persisted_queries = Some(queries);
+        self
+    }
+
+    pub fn plan_cache(mut self, capacity: usize) -> Self {
+        self.plan_cache = Some(Arc::new(PlanCache::new(capacity)));
+        self
+    }
+
+    pub fn plan_cache_stats(&self) -> Option<PlanCacheStats> {
+        self.plan_cache
+            .as_ref()
+            .map(|plan_cache| plan_cache.stats())
+    }
+
+    pub fn response_cache_ttl<T: Into<String>>(mut self, executor: T, ttl: Duration) -> Self {
+        self.response_cache_ttls.insert(executor.into(), ttl);
+        self
+    }
+
+    pub fn node_cache_ttl(mut self, ttl: Duration) -> Self {
+        let node_cache = NodeCache::new(ttl);
+        Arc::make_mut(&mut self.data).insert(EntityInvalidator(node_cache.clone()));
+        self.node_cache = Some(node_cache);
+        self
+    }
+
+    pub fn node_cache_type_ttl<T: Into<String>>(mut self, type_name: T, ttl: Duration) -> Self {
+        self.node_cache_ttls.insert(type_name.into(), ttl);
+        self
+    }
+
+    pub fn node_cache_executor_ttl<T: Into<String>>(mut self, executor: T, ttl: Duration) -> Self {
+        self.node_cache_executor_ttls.insert(executor.into(), ttl);
+        self
+    }
+
+    pub fn invalidate_entity<T: Into<String>, I: Into<String>>(&self, type_name: T, id: I) {
+        if let Some(node_cache) = &self.node_cache {
+            EntityInvalidator(node_cache.clone()).invalidate(type_name, id);
+        }
+    }
+
+    pub fn id_codec<C: IdCodec + 'static>(mut self, codec: C) -> Self {
+        self.id_codec = Some(Arc::new(codec));
+        self
+    }
+
+    pub fn wrap_ids<T: Into<String>>(mut self, executor: T) -> Self {
+        if self.id_codec.is_none() {
+            self.id_codec = Some(Arc::new(DefaultIdCodec));
+        }
+
+        self.wrapped_id_executors.insert(executor.into());
+        self
+    }
+
+    pub fn entity_strategy<T: Into<String>>(
+        mut self,
+        executor: T,
+        strategy: EntityStrategy,
+    ) -> Self {
+        let executor = executor.into();
+
+        if strategy == EntityStrategy::Federation {
+            self.schema_sources
+                .entry(executor.clone())
+                .or_insert_with(|| Arc::new(FederationSource));
+        }
+
+        self.entity_strategies.insert(executor, strategy);
+        self
+    }
+
+    pub fn node_lookup<T: Into<String>>(mut self, executor: T, lookup: NodeLookup) -> Self {
+        self.node_lookups.insert(executor.into(), lookup);
+        self
+    }
+
+    pub fn skip_null_keys(mut self, enabled: bool) -> Self {
+        self.skip_null_keys = enabled;
+        self
+    }
+
+    pub fn inject_argument<T: Into<String>, I: ArgumentInjector + 'static>(
+        mut self,
+        argument: T,
+        injector: I,
+    ) -> Self {
+        self.argument_injectors
+            .insert(argument.into(), Arc::new(injector));
+        self
+    }
+
+    pub fn header_policy(mut self, policy: HeaderPolicy) -> Self {
+        self.header_policy = Some(policy);
+        self
+    }
+
+    pub fn executor_header_policy<T: Into<String>>(
+        mut self,
+        executor: T,
+        policy: HeaderPolicy,
+    ) -> Self {
+        self.header_policies.insert(executor.into(), policy);
+        self
+    }
+
+    pub fn executor_options<T: Into<String>>(
+        mut self,
+        executor: T,
+        options: ExecutorOptions,
+    ) -> Self {
+        self.executor_options.insert(executor.into(), options);
+        self
+    }
+
+    pub fn composition_cache<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.composition_cache = Some(path.into());
+        self
+    }
+
+    pub fn circuit_breaker(mut self, breaker: CircuitBreaker) -> Self {
+        self.circuit_breaker = Some(breaker);
+        self
+    }
+
+    pub fn circuit_breaker_clock<C: Clock + 'static>(mut self, clock: C) -> Self {
+        self.circuit_breakers = Arc::new(CircuitBreakers::new(Arc::new(clock)));
+        self
+    }
+
+    pub fn executor_circuit_breaker<T: Into<String>>(
+        mut self,
+        executor: T,
+        breaker: CircuitBreaker,
+    ) -> Self {
+        self.executor_circuit_breakers
+            .insert(executor.into(), breaker);
+        self
+    }
+
+    pub fn selection_transformer<T: Into<String>, S: SelectionTransformer + 'static>(
+        mut self,
+        executor: T,
+        transformer: S,
+    ) -> Self {
+        self.selection_transformers
+            .entry(executor.into())
+            .or_default()
+            .push(Arc::new(transformer));
+        self
+    }
+
+    pub fn directive<T: AsRef<str>, H: DirectiveHandler + 'static>(
+        mut self,
+        definition: T,
+        handler: H,
+    ) -> Self {
+        match parse_directive(definition.as_ref()) {
+            Ok(definition) => {
+                self.directives.insert(
+                    definition.name.clone(),
+                    GatewayDirective {
+                        definition,
+                        handler: Arc::new(handler),
+                    },
+                );
+            }
+            Err(e) => self
+                .invalid_directives
+                .push((definition.as_ref().to_owned(), e)),
+        }
+        self
+    }
+
+    pub fn argument_rule<
+        T: Into<String>,
+        F: Into<String>,
+        A: Into<String>,
+        R: ArgumentRule + 'static,
+    >(
+        mut self,
+        type_name: T,
+        field_name: F,
+        argument: A,
+        rule: R,
+    ) -> Self {
+        let key = format!("{}.{}", type_name.into(), field_name.into());
+        self.argument_rules
+            .entry(key)
+            .or_default()
+            .push((argument.into(), Arc::new(rule)));
+        self
+    }
+
+    pub fn slo(mut self, target: SloTarget, slo: Slo) -> Self {
+        self.slos.insert(target, slo);
+        self
+    }
+
+    pub fn slo_alert<A: SloAlert + 'static>(mut self, alert: A) -> Self {
+        self.slo_alert = Some(Arc::new(alert));
+        self
+    }
+
+    pub fn usage_reporter<R: UsageReporter + 'static>(mut self, reporter: R) -> Self {
+        self.usage_reporter = Some(Arc::new(reporter));
+        self
+    }
+
+    pub fn stats_window(mut self, samples: usize) -> Self {
+        self.stats_window = Some(samples);
+        self
+    }
+
+    pub fn naming_policy(mut self, policy: NamingPolicy) -> Self {
+        self.naming_policy = policy;
+        self
+    }
+
+    pub fn generate_smoke_queries(&self, depth: usize, count: usize) -> Vec<String> {
+        generate_smoke_queries(&self.schema, &self.naming_policy, depth, count)
+    }
+
+    pub fn to_sdl(&self) -> String {
+        create_sdl_document(&self.schema.0, &self.naming_policy).to_string()
+    }
+
+    pub fn to_supergraph_sdl(&self) -> String {
+        supergraph_sdl(
+            create_sdl_document(&self.schema.0, &self.naming_policy),
+            &self.schema,
+            &self.introspections,
+            &self.naming_policy,
+        )
+    }
+
+    pub fn export_typescript(&self) -> String {
+        export_typescript(&self.schema.0, &self.naming_policy)
+    }
+
+    pub fn executors_info(&self) -> Vec<ExecutorInfo> {
+        let mut infos = self
+            .executors
+            .keys()
+            .map(|name| (name, true))
+            .chain(
+                self.lazy_executors
+                    .iter()
+                    .map(|(name, lazy)| (name, lazy.is_initialized())),
+            )
+            .map(|(name, initialized)| {
+                executor_info(
+                    name,
+                    self.introspections.get(name),
+                    self.introspection_states.get(name),
+                    &self.naming_policy,
+                    initialized,
+                )
+            })
+            .collect::<Vec<ExecutorInfo>>();
+
+        infos.sort_by(|a, b| a.name.cmp(&b.name));
+        infos
+    }
+
+    pub fn health(&self) -> GatewayHealth {
+        let mut executors = self
+            .executors
+            .keys()
+            .chain(self.lazy_executors.keys())
+            .map(|name| {
+                let state = self.introspection_states.get(name);
+
+                ExecutorStatus {
+                    name: name.clone(),
+                    circuit: self
+                        .executor_circuit_breaker_for(name)
+                        .map(|breaker| self.circuit_breakers.state(name, breaker))
+                        .unwrap_or(CircuitState::Closed),
+                    last_introspection: state.and_then(|state| state.succeeded_at).map(unix_millis),
+                    error: state.and_then(|state| state.error.clone()),
+                    schema_hash: self.introspections.get(name).map(schema_hash),
+                    stale_since: self
+                        .stale_introspections
+                        .get(name)
+                        .copied()
+                        .map(unix_millis),
+                }
+            })
+            .collect::<Vec<ExecutorStatus>>();
+
+        executors.sort_by(|a, b| a.name.cmp(&b.name));
+
+        GatewayHealth {
+            ready: !self.executors.is_empty()
+                && self
+                    .executors
+                    .keys()
+                    .all(|name| self.introspections.contains_key(name)),
+            executors,
+        }
+    }
+
+    pub fn slo_status(&self) -> Vec<SloStatus> {
+        self.slo_tracker.status(&self.slos)
+    }
+
+    pub fn executor_as<E: Executor + 'static>(&self, name: &str) -> Option<&E> {
+        self.executors
+            .get(name)
+            .map(|e| e.as_ref())
+            .or_else(|| self.lazy_executors.get(name).map(|lazy| lazy.get()))
+            .and_then(|e| e.as_any().downcast_ref::<E>())
+    }
+
+    pub fn executor_as_mut<E: Executor + 'static>(&mut self, name: &str) -> Option<&mut E> {
+        self.executors
+            .get_mut(name)
+            .and_then(|e| e.as_mut().as_any_mut().downcast_mut::<E>())
+    }
+
+    pub fn executors_stats(&self) -> Vec<ExecutorStats> {
+        self.stats.snapshot()
+    }
+
+    pub fn executor_stats(&self, name: &str) -> Option<ExecutorStats> {
+        self.stats.get(name)
+    }
+
+    pub fn inflight_requests(&self) -> Vec<RequestDiagnostics> {
+        self.diagnostics.snapshot()
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode_state().mode
+    }
+
+    pub fn set_mode(&self, mode: Mode) {
+        self.set_mode_with(mode, None, None);
+    }
+
+    pub fn set_mode_with(
+        &self,
+        mode: Mode,
+        message: Option<String>,
+        retry_after: Option<Duration>,
+    ) {
+        let mut state = self.mode.write().expect("Gateway mode lock is poisoned.");
+        *state = ModeState {
+            mode,
+            message,
+            retry_after,
+        };
+    }
+
+    pub(crate) fn shared_response_cache(
+        &self,
+        executor: &str,
+    ) -> Option<(&dyn ResponseCache, Duration)> {
+        let (cache, ttl) = self.response_cache.as_ref()?;
+
+        if self.authorizer.is_some() || self.forwards_headers(executor) {
+            return None;
+        }
+
+        let ttl = self
+            .response_cache_ttls
+            .get(executor)
+            .copied()
+            .unwrap_or(*ttl);
+
+        if ttl == Duration::from_secs(0) {
+            return None;
+        }
+
+        Some((cache.as_ref(), ttl))
+    }
+
+    pub(crate) fn forwards_headers(&self, executor: &str) -> bool {
+        self.header_policies.contains_key(executor)
+            || self.header_policy.is_some()
+            || self
+                .executor_options
+                .get(executor)
+                .is_some_and(|options| options.forward_headers.is_some())
+    }
+
+    pub(crate) fn executor_circuit_breaker_for(&self, executor: &str) -> Option<&CircuitBreaker> {
+        self.executor_circuit_breakers
+            .get(executor)
+            .or(self.circuit_breaker.as_ref())
+    }
+
+    pub(crate) fn stats_window_size(&self) -> usize {
+        self.stats_window.unwrap_or(DEFAULT_STATS_WINDOW)
+    }
+
+    pub(crate) fn mode_state(&self) -> ModeState {
+        self.mode
+            .read()
+            .expect("Gateway mode lock is poisoned.")
+            .clone()
+    }
+
     pub async fn build(mut self) -> GatewayResult<Gateway<'a>> {
-        let futures = self.executors.iter().map(|(_, e)| e.introspect());
+        let introspection_data = &self.introspection_data;
+        let schema_source = self.schema_source.as_deref();
+        let names = self.executors.keys().cloned().collect::<Vec<String>>();
+        let schema_sources = &self.schema_sources;
+        let futures = self.executors.values().map(|e| {
+            let schema_source = schema_sources
+                .get(e.name())
+                .map(|s| s.as_ref())
+                .or(schema_source);
 
-        self.introspections = future::join_all(futures)
-            .await
+            load_schema(schema_source, e.as_ref(), introspection_data.get(e.name()))
+        });
+        let results = future::join_all(futures).await;
+
+        self.introspection_states = names
+            .into_iter()
+            .zip(results.iter())
+            .map(|(name, result)| (name, IntrospectionState::new(result)))
+            .collect();
+        self.introspections = results
             .iter()
-            .filter_map(|e| Some(e.as_ref().ok().cloned()?))
+            .filter_map(|e| e.as_ref().ok().cloned())
             .collect::<HashMap<String, Schema>>();
 
-        self.schema = create_schema(&self.introspections)?;
-        self.document = create_document(&self.schema.0);
+        let failed = self
+            .introspection_states
+            .iter()
+            .filter(|(_, state)| state.error.is_some())
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<String>>();
+        let cache = match &self.composition_cache {
+            Some(path) if !failed.is_empty() => CompositionCache::read(path),
+            _ => None,
+        };
+
+        if let Some(mut cache) = cache {
+            for name in failed {
+                if let Some(schema) = cache.introspections.remove(&name) {
+                    self.introspections.insert(name.clone(), schema);
+                    self.stale_introspections.insert(name, cache.created_at());
+                }
+            }
+        }
+
+        for (name, lazy) in &self.lazy_executors {
+            if let Some(schema) = &lazy.schema {
+                self.introspections.insert(name.clone(), schema.clone());
+            }
+        }
+
+        for version in self.versions.values_mut() {
+            let futures = version
+                .executors
+                .values()
+                .map(|e| e.introspect(introspection_data.get(e.name())));
+
+            version.introspections = future::join_all(futures)
+                .await
+                .iter()
+                .filter_map(|e| e.as_ref().ok().cloned())
+                .collect::<HashMap<String, Schema>>();
+        }
+
+        self.schema = create_schema(
+            &self.introspections,
+            &self.naming_policy,
+            &self.fan_out,
+            self.static_fields,
+            &self.composition_limits,
+            &self.node_lookups,
+            &self.directives,
+        )?;
+        self.document = create_document(&self.schema.0, &self.naming_policy);
+        self.compose_versions()?;
+        self.record_snapshot();
+
+        if let Some(path) = &self.composition_cache {
+            let fresh = self
+                .introspection_states
+                .values()
+                .all(|state| state.error.is_none());
+
+            if fresh {
+                let _ = CompositionCache::write(path, &self.introspections);
+            }
+        }
 
         Ok(self)
     }
@@ -67,15 +945,152 @@ impl<'a> Gateway<'a> {
         let executor = self
             .executors
             .get(&name)
-            .ok_or(GatewayError::UnknownExecutor(name))?;
+            .map(|e| e.as_ref())
+            .or_else(|| self.lazy_executors.get(&name).map(|lazy| lazy.get()))
+            .ok_or_else(|| GatewayError::UnknownExecutor(name.clone()))?;
 
-        let (name, schema) = executor.introspect().await?;
+        let schema_source = self
+            .schema_sources
+            .get(&name)
+            .map(|s| s.as_ref())
+            .or(self.schema_source.as_deref());
+
+        let result = load_schema(schema_source, executor, self.introspection_data.get(&name)).await;
+
+        let state = IntrospectionState::new(&result).after(self.introspection_states.get(&name));
+        self.introspection_states.insert(name.clone(), state);
+
+        let (name, schema) = result?;
+
+        if self.schema_history.is_rejected(&name, &schema) {
+            return Ok(());
+        }
 
         let mut introspections = self.introspections.clone();
-        introspections.insert(name, schema);
-        self.schema = create_schema(&introspections)?;
-        self.document = create_document(&self.schema.0);
+        introspections.insert(name.clone(), schema);
+        self.compose(introspections)?;
+        self.stale_introspections.remove(&name);
+
+        Ok(())
+    }
+
+    pub(crate) fn compose(&mut self, introspections: HashMap<String, Schema>) -> GatewayResult<()> {
+        self.schema = create_schema(
+            &introspections,
+            &self.naming_policy,
+            &self.fan_out,
+            self.static_fields,
+            &self.composition_limits,
+            &self.node_lookups,
+            &self.directives,
+        )?;
+        self.document = create_document(&self.schema.0, &self.naming_policy);
         self.introspections = introspections;
+        self.compose_versions()?;
+        self.record_snapshot();
+
+        if let Some(plan_cache) = &self.plan_cache {
+            let schema_hashes = self
+                .versions
+                .values()
+                .map(|version| version.schema.6.as_str())
+                .chain(std::iter::once(self.schema.6.as_str()))
+                .collect::<Vec<&str>>();
+
+            plan_cache.retain(&schema_hashes);
+        }
+
+        Ok(())
+    }
+
+    pub async fn resolve_entity<T: Into<String>, I: Into<String>>(
+        &self,
+        type_name: T,
+        id: I,
+        selection: &str,
+        data: Option<Data>,
+    ) -> QueryResult<Value> {
+        let type_name = type_name.into();
+
+        let is_node = self
+            .schema
+            .2
+            .get(&format!("{}.{}", TypeKind::Object, type_name))
+            .and_then(|&i| self.schema.0.types.get(i))
+            .map(Type::is_node)
+            .unwrap_or(false);
+
+        if !is_node {
+            return Err(QueryError::UnknownEntityType(type_name));
+        }
+
+        let mut query = QueryBuilder::new(format!(
+            "query ResolveEntity($id: ID!) {{ entity: node(id: $id) {{ ... on {} {{ {} }} }} }}",
+            type_name, selection
+        ))
+        .operation_name("ResolveEntity")
+        .variables(json!({ "id": id.into() }));
+        query.ctx_data = data;
+
+        let mut value = query.execute(self).await?;
+
+        Ok(value
+            .get_mut("entity")
+            .map(Value::take)
+            .unwrap_or(Value::Null))
+    }
+
+    pub async fn refresh_materialized_views(&self) -> Vec<(String, QueryResult<()>)> {
+        let mut results = Vec::new();
+
+        for view in self
+            .materialized_views
+            .iter()
+            .filter(|view| view.is_stale())
+        {
+            results.push((view.name.clone(), view.refresh(self).await));
+        }
+
+        results
+    }
+
+    pub async fn run_materialized_views(&self) {
+        if self.materialized_views.is_empty() {
+            return;
+        }
+
+        loop {
+            self.refresh_materialized_views().await;
+
+            let due_in = self
+                .materialized_views
+                .iter()
+                .map(|view| view.due_in())
+                .min()
+                .unwrap_or_default();
+
+            Delay::new(due_in.max(Duration::from_millis(10))).await;
+        }
+    }
+
+    pub(crate) fn version_schema(&self, version: &str) -> Option<&GatewaySchema> {
+        self.versions.get(version).map(|version| &version.schema)
+    }
+
+    fn compose_versions(&mut self) -> GatewayResult<()> {
+        for version in self.versions.values_mut() {
+            let mut introspections = self.introspections.clone();
+            introspections.extend(version.introspections.clone());
+            version.schema = create_schema(
+                &introspections,
+                &self.naming_policy,
+                &self.fan_out,
+                self.static_fields,
+                &self.composition_limits,
+                &self.node_lookups,
+                &self.directives,
+            )?;
+        }
 
         Ok(())
     }
@@ -83,7 +1098,15 @@ impl<'a> Gateway<'a> {
     pub fn validate<T: Into<String>>(&self, name: T, schema: Schema) -> GatewayResult<()> {
         let mut introspections = self.introspections.clone();
         introspections.insert(name.into(), schema);
-        create_schema(&introspections)?;
+        create_schema(
+            &introspections,
+            &self.naming_policy,
+            &self.fan_out,
+            self.static_fields,
+            &self.composition_limits,
+            &self.node_lookups,
+            &self.directives,
+        )?;
 
         Ok(())
     }
@@ -101,17 +1124,101 @@ pub struct GatewaySchema(
     pub(crate) Value,
     pub(crate) HashMap<String, usize>,
     pub(crate) HashMap<String, (String, usize)>,
+    pub(crate) HashMap<String, Vec<String>>,
+    pub(crate) HashSet<String>,
+    pub(crate) String,
 );
 
-fn create_schema(schemas: &HashMap<String, Schema>) -> GatewayResult<GatewaySchema> {
+impl GatewaySchema {
+    pub(crate) fn object_by_kind(&self, kind: &TypeKind, name: &str) -> Option<&Type> {
+        self.2
+            .get(&format!("{}.{}", kind, name))
+            .and_then(|&i| self.0.types.get(i))
+    }
+}
+
+fn create_schema(
+    schemas: &HashMap<String, Schema>,
+    naming_policy: &NamingPolicy,
+    fan_out: &HashMap<String, MergeStrategy>,
+    static_fields: bool,
+    limits: &CompositionLimits,
+    node_lookups: &HashMap<String, NodeLookup>,
+    gateway_directives: &HashMap<String, GatewayDirective>,
+) -> GatewayResult<GatewaySchema> {
+    let limit_errors = schemas
+        .iter()
+        .flat_map(|(executor_name, schema)| limits.check(executor_name, schema, naming_policy))
+        .collect::<Vec<CompositionLimitError>>();
+
+    if !limit_errors.is_empty() {
+        return Err(GatewayError::CompositionLimitsExceeded(limit_errors));
+    }
+
+    if static_fields {
+        let mut schemas = schemas.clone();
+        schemas.insert(STATIC_FIELDS_EXECUTOR.to_owned(), static_fields_schema());
+
+        return create_schema(
+            &schemas,
+            naming_policy,
+            fan_out,
+            false,
+            limits,
+            node_lookups,
+            gateway_directives,
+        );
+    }
+
+    let mut fan_out_owners: HashMap<String, Vec<String>> = HashMap::new();
+    let mut shared_types = HashSet::new();
+
+    for (executor_name, schema) in schemas {
+        let query_fields = schema
+            .types
+            .iter()
+            .filter(|t| t.kind == TypeKind::Object && t.name() == "Query")
+            .flat_map(|t| t.fields.iter().flatten());
+
+        for field in query_fields.filter(|field| fan_out.contains_key(&field.name)) {
+            fan_out_owners
+                .entry(field.name.clone())
+                .or_default()
+                .push(executor_name.clone());
+
+            let field_type = field.field_type();
+
+            if field_type.kind == TypeKind::Object {
+                shared_types.insert(field_type.name().to_owned());
+            }
+        }
+    }
+
+    for owners in fan_out_owners.values_mut() {
+        owners.sort();
+    }
+
     let mut types = vec![];
     let mut types_by_name = HashMap::new();
     let mut type_fields_by_name: HashMap<String, (String, usize)> = HashMap::new();
     let mut duplicate_object_fields = Vec::new();
     let mut possible_types_by_name = HashMap::new();
+    let mut naming_errors = Vec::new();
+    let mut directives: Vec<Directive> = vec![];
 
     for (executor_name, schema) in schemas {
+        for directive in &schema.directives {
+            if directives
+                .iter()
+                .all(|current| current.name != directive.name)
+            {
+                directives.push(directive.clone());
+            }
+        }
+
         for schema_type in schema.types.iter() {
+            naming_errors.extend(naming_policy.check_type(executor_name, schema_type));
+
             let key = schema_type.to_string();
             let current_type = types_by_name.get(&key).and_then(|&i| types.get_mut(i));
 
@@ -131,16 +1238,20 @@ fn create_schema(schemas: &HashMap<String, Schema>) -> GatewayResult<GatewaySche
                 }
             };
 
+            for entity_key in &schema_type.keys {
+                if !current_type.keys.contains(entity_key) {
+                    current_type.keys.push(entity_key.clone());
+                }
+            }
+
             if let Some(possible_types) = &schema_type.possible_types {
-                let mut current_possible_types = current_type
-                    .possible_types
-                    .clone()
-                    .unwrap_or_else(|| vec![]);
+                let mut current_possible_types =
+                    current_type.possible_types.clone().unwrap_or_default();
 
                 for possible_type in possible_types {
                     let possible_type_key = format!("{}.{:#?}", key.clone(), possible_type.name());
 
-                    if possible_types_by_name.get(&possible_type_key).is_some() {
+                    if possible_types_by_name.contains_key(&possible_type_key) {
                         continue;
                     }
 
@@ -153,19 +1264,40 @@ fn create_schema(schemas: &HashMap<String, Schema>) -> GatewayResult<GatewaySche
             }
 
             if let Some(fields) = &schema_type.fields {
-                let mut current_fields = current_type.fields.clone().unwrap_or_else(|| vec![]);
+                let mut current_fields = current_type.fields.clone().unwrap_or_default();
 
                 for field in fields {
                     let field_key = format!("{}.{}", key, &field.name);
 
                     match type_fields_by_name.get(&field_key) {
-                        Some((current_executor_name, _)) => {
+                        Some((current_executor_name, index)) => {
                             let field_type = field.field_type();
 
+                            let is_fan_out = schema_type.name() == "Query"
+                                && fan_out_owners.contains_key(&field.name);
+
+                            let is_lookup = schema_type.name() == "Query"
+                                && (field.name == "node"
+                                    || field.name == "nodes"
+                                    || node_lookups
+                                        .values()
+                                        .any(|lookup| lookup.field == field.name));
+
+                            if is_lookup {
+                                if field_type.kind == TypeKind::Interface {
+                                    current_fields[*index] = field.clone();
+                                }
+
+                                continue;
+                            }
+
                             if field_type.name() == "ID"
+                                || schema_type.key_fields().contains(&field.name.as_str())
                                 || current_type.kind != TypeKind::Object
                                 || field_type.kind == TypeKind::Interface
-                                || schema_type.name().starts_with("__")
+                                || naming_policy.is_introspection_type(schema_type.name())
+                                || is_fan_out
+                                || shared_types.contains(schema_type.name())
                             {
                                 continue;
                             }
@@ -189,6 +1321,10 @@ fn create_schema(schemas: &HashMap<String, Schema>) -> GatewayResult<GatewaySche
         }
     }
 
+    if !naming_errors.is_empty() {
+        return Err(GatewayError::InvalidNames(naming_errors));
+    }
+
     if !duplicate_object_fields.is_empty() {
         return Err(GatewayError::DuplicateObjectFields(duplicate_object_fields));
     }
@@ -205,24 +1341,80 @@ fn create_schema(schemas: &HashMap<String, Schema>) -> GatewayResult<GatewaySche
         ..Type::default()
     });
 
+    for gateway_directive in gateway_directives.values() {
+        directives.retain(|directive| directive.name != gateway_directive.definition.name);
+        directives.push(gateway_directive.definition.clone());
+    }
+
+    directives.sort_by(|a, b| a.name.cmp(&b.name));
+
     let schema = Schema {
         query_type,
         mutation_type,
         types,
+        directives,
         ..Schema::default()
     };
 
     let schema_value = serde_json::to_value(schema.clone())?;
+    let hash = schema_hash(&schema);
 
     Ok(GatewaySchema(
         schema,
         schema_value,
         types_by_name,
         type_fields_by_name,
+        fan_out_owners,
+        shared_types,
+        hash,
     ))
 }
 
-fn create_document<'a>(schema: &Schema) -> Document<'a, String> {
+fn create_document<'a>(schema: &Schema, naming_policy: &NamingPolicy) -> Document<'a, String> {
+    let mut definitions = schema
+        .types
+        .iter()
+        .filter_map(|t| {
+            if naming_policy.is_introspection_type(t.name()) || t.kind == TypeKind::Scalar {
+                None
+            } else {
+                Some(t.clone().into())
+            }
+        })
+        .collect::<Vec<Definition<'a, String>>>();
+
+    definitions.push(schema_definition(schema));
+
+    Document { definitions }
+}
+
+fn create_sdl_document<'a>(schema: &Schema, naming_policy: &NamingPolicy) -> Document<'a, String> {
+    let mut definitions = schema
+        .directives
+        .iter()
+        .filter(|d| !naming_policy.is_builtin_directive(&d.name))
+        .map(|d| d.clone().into())
+        .collect::<Vec<Definition<'a, String>>>();
+
+    definitions.extend(
+        schema
+            .types
+            .iter()
+            .filter(|t| {
+                let is_builtin_scalar =
+                    t.kind == TypeKind::Scalar && naming_policy.is_builtin_scalar(t.name());
+
+                !naming_policy.is_introspection_type(t.name()) && !is_builtin_scalar
+            })
+            .map(|t| t.clone().into()),
+    );
+
+    definitions.push(schema_definition(schema));
+
+    Document { definitions }
+}
+
+fn schema_definition<'a>(schema: &Schema) -> Definition<'a, String> {
     let query = if schema.types.iter().any(|t| t.name() == "Query") {
         Some("Query".to_owned())
     } else {
@@ -235,25 +1427,11 @@ fn create_document<'a>(schema: &Schema) -> Document<'a, String> {
         None
     };
 
-    let mut definitions = schema
-        .types
-        .iter()
-        .filter_map(|t| {
-            if t.name().starts_with("__") || t.kind == TypeKind::Scalar {
-                None
-            } else {
-                Some(t.clone().into())
-            }
-        })
-        .collect::<Vec<Definition<'a, String>>>();
-
-    definitions.push(Definition::SchemaDefinition(SchemaDefinition {
+    Definition::SchemaDefinition(SchemaDefinition {
         position: Pos::default(),
         directives: vec![],
         query,
         mutation,
         subscription: None,
-    }));
-
-    Document { definitions }
+    })
 }
@@ -0,0 +1,245 @@
+use crate::data::Data;
+use crate::executor::Executor;
+use crate::injection::to_ast_value;
+use crate::schema::{Schema, Type};
+use async_trait::async_trait;
+use graphql_parser::query::{
+    Definition, Field, OperationDefinition, Selection, TypeCondition, Value as AstValue,
+};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+const ROOT_TYPES: [(&str, &str); 3] = [
+    ("query_root", "Query"),
+    ("mutation_root", "Mutation"),
+    ("subscription_root", "Subscription"),
+];
+
+#[derive(Clone)]
+pub struct HasuraExecutor {
+    executor: Box<dyn Executor>,
+    entities: HashMap<String, (String, String)>,
+}
+
+impl HasuraExecutor {
+    pub fn new<E: Executor + 'static>(executor: E) -> Self {
+        HasuraExecutor {
+            executor: Box::new(executor),
+            entities: HashMap::new(),
+        }
+    }
+
+    pub fn entity<T: Into<String>, F: Into<String>, K: Into<String>>(
+        mut self,
+        type_name: T,
+        field: F,
+        primary_key: K,
+    ) -> Self {
+        self.entities
+            .insert(type_name.into(), (field.into(), primary_key.into()));
+        self
+    }
+
+    fn pk_lookup(&self, query: &str, variables: Option<&Value>) -> Option<PkLookup> {
+        let mut document = graphql_parser::parse_query::<String>(query).ok()?;
+
+        let operation =
+            document
+                .definitions
+                .iter_mut()
+                .find_map(|definition| match definition {
+                    Definition::Operation(OperationDefinition::Query(operation)) => Some(operation),
+                    _ => None,
+                })?;
+
+        let root = match operation.selection_set.items.as_slice() {
+            [Selection::Field(root)] => root.clone(),
+            _ => return None,
+        };
+
+        let ids_variable = match root.arguments.as_slice() {
+            [(_, AstValue::Variable(name))] => name.clone(),
+            _ => return None,
+        };
+
+        let (type_name, items) = match root.selection_set.items.as_slice() {
+            [Selection::InlineFragment(fragment)] => match &fragment.type_condition {
+                Some(TypeCondition::On(type_name)) => {
+                    (type_name, fragment.selection_set.items.clone())
+                }
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        let (field, primary_key) = self.entities.get(type_name)?;
+        let ids = variables?.get(&ids_variable)?.as_array()?;
+
+        operation.selection_set.items = ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| {
+                let mut node = root.clone();
+                node.alias = Some(format!("node_{}", i));
+                node.name = field.clone();
+                node.arguments = vec![(primary_key.clone(), to_ast_value(id, false))];
+                node.selection_set.items = items.clone();
+
+                Selection::Field(node)
+            })
+            .collect();
+
+        operation
+            .variable_definitions
+            .retain(|definition| definition.name != ids_variable);
+
+        let mut variables = variables.cloned();
+
+        if let Some(Value::Object(variables)) = variables.as_mut() {
+            variables.remove(&ids_variable);
+        }
+
+        Some(PkLookup {
+            query: document.to_string(),
+            variables,
+            field: response_key(&root),
+            count: ids.len(),
+        })
+    }
+}
+
+#[async_trait]
+impl Executor for HasuraExecutor {
+    fn name(&self) -> &str {
+        self.executor.name()
+    }
+
+    async fn execute(
+        &self,
+        data: Option<&Data>,
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<Value>,
+    ) -> Result<Value, String> {
+        let lookup = match self.pk_lookup(&query, variables.as_ref()) {
+            Some(lookup) => lookup,
+            _ => {
+                return self
+                    .executor
+                    .execute(data, query, operation_name, variables)
+                    .await
+            }
+        };
+
+        let response = self
+            .executor
+            .execute(
+                data,
+                lookup.query.clone(),
+                operation_name,
+                lookup.variables.clone(),
+            )
+            .await?;
+
+        Ok(lookup.collect(response))
+    }
+
+    async fn introspect(&self, data: Option<&Data>) -> Result<(String, Schema), String> {
+        let (name, mut schema) = self.executor.introspect(data).await?;
+
+        for schema_type in schema.types.iter_mut() {
+            rename_root_type(schema_type);
+
+            if let Some((_, primary_key)) = schema_type
+                .name
+                .as_ref()
+                .and_then(|name| self.entities.get(name))
+            {
+                schema_type.keys = vec![primary_key.clone()];
+            }
+        }
+
+        for root_type in vec![
+            schema.query_type.as_mut(),
+            schema.mutation_type.as_mut(),
+            schema.subscription_type.as_mut(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            rename_root_type(root_type);
+        }
+
+        Ok((name, schema))
+    }
+}
+
+struct PkLookup {
+    query: String,
+    variables: Option<Value>,
+    field: String,
+    count: usize,
+}
+
+impl PkLookup {
+    fn collect(&self, mut response: Value) -> Value {
+        let mut data = response
+            .get_mut("data")
+            .and_then(Value::as_object_mut)
+            .map(std::mem::take)
+            .unwrap_or_default();
+
+        let nodes = (0..self.count)
+            .map(|i| data.remove(&format!("node_{}", i)).unwrap_or(Value::Null))
+            .collect::<Vec<Value>>();
+
+        let mut result = Map::new();
+        result.insert(self.field.clone(), Value::Array(nodes));
+        response["data"] = Value::Object(result);
+
+        for error in response
+            .get_mut("errors")
+            .and_then(Value::as_array_mut)
+            .into_iter()
+            .flatten()
+        {
+            let path = match error.get_mut("path").and_then(Value::as_array_mut) {
+                Some(path) => path,
+                _ => continue,
+            };
+
+            let index = path
+                .first()
+                .and_then(Value::as_str)
+                .and_then(|alias| alias.strip_prefix("node_"))
+                .and_then(|index| index.parse::<usize>().ok());
+
+            if let Some(index) = index {
+                path[0] = Value::from(self.field.clone());
+                path.insert(1, Value::from(index));
+            }
+        }
+
+        response
+    }
+}
+
+fn response_key(field: &Field<'_, String>) -> String {
+    field.alias.as_ref().unwrap_or(&field.name).to_owned()
+}
+
+fn rename_root_type(schema_type: &mut Type) {
+    if let Some(name) = schema_type.name.as_mut() {
+        if let Some((_, root_name)) = ROOT_TYPES.iter().find(|(hasura, _)| hasura == name) {
+            *name = (*root_name).to_owned();
+        }
+    }
+
+    if let Some(of_type) = schema_type.of_type.as_mut() {
+        rename_root_type(of_type);
+    }
+
+    for field in schema_type.fields.iter_mut().flatten() {
+        rename_root_type(&mut field.field_type);
+    }
+}
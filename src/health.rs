@@ -0,0 +1,27 @@
+use crate::circuit_breaker::CircuitState;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExecutorStatus {
+    pub name: String,
+    pub circuit: CircuitState,
+    #[serde(rename = "lastIntrospection")]
+    pub last_introspection: Option<u64>,
+    pub error: Option<String>,
+    #[serde(rename = "schemaHash")]
+    pub schema_hash: Option<String>,
+    #[serde(rename = "staleSince")]
+    pub stale_since: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GatewayHealth {
+    pub ready: bool,
+    pub executors: Vec<ExecutorStatus>,
+}
+
+pub(crate) fn unix_millis(at: SystemTime) -> u64 {
+    at.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
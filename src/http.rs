@@ -1,57 +1,319 @@
-use crate::query::{QueryBuilder, QueryError, QueryResult};
-use serde::ser::{SerializeMap, SerializeSeq};
+use crate::gateway::Gateway;
+use crate::query::{QueryBuilder, QueryError, QueryPosError, QueryResult};
+use futures::stream::{self, StreamExt};
+use serde::ser::{Error, SerializeMap, SerializeSeq};
 use serde::{Serialize, Serializer};
-use serde_json::Value;
+use serde_json::{Map, Value};
 
 #[derive(Serialize, Deserialize)]
 pub struct GraphQLPayload {
+    #[serde(default)]
     pub query: String,
     #[serde(rename = "operationName")]
     pub operation_name: Option<String>,
     pub variables: Option<Value>,
+    pub extensions: Option<Value>,
 }
 
 impl GraphQLPayload {
     pub fn to_query_builder(&self) -> QueryBuilder {
-        QueryBuilder {
+        let query = QueryBuilder {
             query_source: self.query.clone(),
             operation_name: self.operation_name.clone(),
             variables: self.variables.clone(),
             ctx_data: None,
+            version: None,
+            response_cache: true,
+            prepared: None,
+        };
+
+        match &self.extensions {
+            Some(extensions) => query.extensions(extensions.clone()),
+            _ => query,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum GraphQLBatchPayload {
+    Single(GraphQLPayload),
+    Batch(Vec<GraphQLPayload>),
+}
+
+impl GraphQLBatchPayload {
+    pub async fn execute(&self, gateway: &Gateway<'_>) -> GraphQLBatchResponse {
+        self.execute_with(gateway, |query| query).await
+    }
+
+    pub async fn execute_with<F>(&self, gateway: &Gateway<'_>, f: F) -> GraphQLBatchResponse
+    where
+        F: Fn(QueryBuilder) -> QueryBuilder,
+    {
+        match self {
+            GraphQLBatchPayload::Single(payload) => GraphQLBatchResponse::Single(
+                f(payload.to_query_builder())
+                    .execute_response(gateway)
+                    .await,
+            ),
+            GraphQLBatchPayload::Batch(payloads) => {
+                let parallelism = gateway.batch_parallelism.unwrap_or(1);
+                let queries = payloads
+                    .iter()
+                    .map(|payload| f(payload.to_query_builder()))
+                    .collect::<Vec<QueryBuilder>>();
+                let responses = queries
+                    .iter()
+                    .map(|query| query.execute_response(gateway))
+                    .collect::<Vec<_>>();
+                let responses = stream::iter(responses)
+                    .buffered(parallelism.max(1))
+                    .collect()
+                    .await;
+
+                GraphQLBatchResponse::Batch(responses)
+            }
+        }
+    }
+}
+
+pub enum GraphQLBatchResponse {
+    Single(QueryResponse),
+    Batch(Vec<QueryResponse>),
+}
+
+impl Serialize for GraphQLBatchResponse {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            GraphQLBatchResponse::Single(response) => response.serialize(serializer),
+            GraphQLBatchResponse::Batch(responses) => responses.serialize(serializer),
         }
     }
 }
 
+const GRAPHIQL_SOURCE: &str = r#"<!DOCTYPE html>
+<html>
+  <head>
+    <meta charset="utf-8" />
+    <title>GraphiQL</title>
+    <style>
+      body { height: 100%; margin: 0; width: 100%; overflow: hidden; }
+      #graphiql { height: 100vh; }
+    </style>
+    <link rel="stylesheet" href="https://unpkg.com/graphiql@3/graphiql.min.css" />
+    <script crossorigin src="https://unpkg.com/react@18/umd/react.production.min.js"></script>
+    <script crossorigin src="https://unpkg.com/react-dom@18/umd/react-dom.production.min.js"></script>
+    <script crossorigin src="https://unpkg.com/graphiql@3/graphiql.min.js"></script>
+  </head>
+  <body>
+    <div id="graphiql">Loading...</div>
+    <script>
+      const fetcher = GraphiQL.createFetcher({ url: __ENDPOINT__ });
+      ReactDOM.createRoot(document.getElementById("graphiql")).render(
+        React.createElement(GraphiQL, { fetcher })
+      );
+    </script>
+  </body>
+</html>
+"#;
+
+pub fn graphiql_source(endpoint: &str) -> String {
+    let endpoint = serde_json::to_string(endpoint)
+        .unwrap_or_default()
+        .replace("</", "<\\/");
+
+    GRAPHIQL_SOURCE.replace("__ENDPOINT__", &endpoint)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RequestExtensions(pub Value);
+
 pub struct GraphQLResponse(pub QueryResult<Value>);
 
 impl Serialize for GraphQLResponse {
     fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
-        match &self.0 {
+        ResultRef(&self.0).serialize(serializer)
+    }
+}
+
+pub struct QueryResponse {
+    pub result: QueryResult<Value>,
+    pub errors: Vec<QueryPosError>,
+    pub extensions: Map<String, Value>,
+    pub etag: Option<String>,
+    pub not_modified: bool,
+}
+
+impl Serialize for QueryResponse {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut response = serde_json::to_value(ResultRef(&self.result)).map_err(Error::custom)?;
+
+        if let (Value::Object(response), false) = (&mut response, self.errors.is_empty()) {
+            response.insert(
+                "errors".to_owned(),
+                self.errors.iter().map(pos_error).collect(),
+            );
+        }
+
+        if let (Some(request_id), Some(Value::Array(errors))) =
+            (self.extensions.get("requestId"), response.get_mut("errors"))
+        {
+            for error in errors.iter_mut().filter_map(Value::as_object_mut) {
+                let extensions = error
+                    .entry("extensions")
+                    .or_insert_with(|| Value::Object(Map::new()));
+
+                if let Value::Object(extensions) = extensions {
+                    extensions
+                        .entry("requestId")
+                        .or_insert_with(|| request_id.clone());
+                }
+            }
+        }
+
+        if let (Value::Object(response), false) = (&mut response, self.extensions.is_empty()) {
+            response.insert(
+                "extensions".to_owned(),
+                Value::Object(self.extensions.clone()),
+            );
+        }
+
+        response.serialize(serializer)
+    }
+}
+
+pub const MULTIPART_CONTENT_TYPE: &str = "multipart/mixed; boundary=\"-\"";
+
+pub enum IncrementalPatch {
+    Defer {
+        label: Option<String>,
+        path: Vec<Value>,
+        result: QueryResult<Value>,
+        errors: Vec<QueryPosError>,
+    },
+    Stream {
+        label: Option<String>,
+        path: Vec<Value>,
+        items: Vec<Value>,
+    },
+}
+
+impl Serialize for IncrementalPatch {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let (label, path, mut patch) = match self {
+            IncrementalPatch::Defer {
+                label,
+                path,
+                result,
+                errors,
+            } => {
+                let mut patch = serde_json::to_value(ResultRef(result)).map_err(Error::custom)?;
+
+                if let (Value::Object(patch), false) = (&mut patch, errors.is_empty()) {
+                    patch.insert("errors".to_owned(), errors.iter().map(pos_error).collect());
+                }
+
+                (label, path, patch)
+            }
+            IncrementalPatch::Stream { label, path, items } => {
+                (label, path, serde_json::json!({ "items": items }))
+            }
+        };
+
+        if let Value::Object(patch) = &mut patch {
+            patch.insert("path".to_owned(), Value::Array(path.clone()));
+
+            if let Some(label) = label {
+                patch.insert("label".to_owned(), Value::String(label.clone()));
+            }
+        }
+
+        patch.serialize(serializer)
+    }
+}
+
+pub enum IncrementalPayload {
+    Initial {
+        response: QueryResponse,
+        has_next: bool,
+    },
+    Subsequent {
+        incremental: Vec<IncrementalPatch>,
+        has_next: bool,
+    },
+}
+
+impl IncrementalPayload {
+    pub fn has_next(&self) -> bool {
+        match self {
+            IncrementalPayload::Initial { has_next, .. } => *has_next,
+            IncrementalPayload::Subsequent { has_next, .. } => *has_next,
+        }
+    }
+
+    pub fn to_multipart(&self) -> String {
+        let mut chunk = format!(
+            "\r\n---\r\nContent-Type: application/json; charset=utf-8\r\n\r\n{}",
+            serde_json::to_string(self).unwrap_or_default()
+        );
+
+        if !self.has_next() {
+            chunk.push_str("\r\n-----\r\n");
+        }
+
+        chunk
+    }
+}
+
+impl Serialize for IncrementalPayload {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut payload = match self {
+            IncrementalPayload::Initial { response, .. } => {
+                serde_json::to_value(response).map_err(Error::custom)?
+            }
+            IncrementalPayload::Subsequent { incremental, .. } => {
+                serde_json::json!({ "incremental": incremental })
+            }
+        };
+
+        if let Value::Object(payload) = &mut payload {
+            payload.insert("hasNext".to_owned(), Value::Bool(self.has_next()));
+        }
+
+        payload.serialize(serializer)
+    }
+}
+
+struct ResultRef<'a>(&'a QueryResult<Value>);
+
+impl Serialize for ResultRef<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        match self.0 {
             Ok(data) => {
-                let mut map = serializer.serialize_map(None)?;
                 map.serialize_key("data")?;
                 map.serialize_value(&data)?;
-                map.end()
-            }
-            Err(err) => match err {
-                QueryError::Executor(value) => {
-                    let mut map = serializer.serialize_map(None)?;
-                    if let Value::Object(object) = value {
-                        for (k, v) in object {
-                            map.serialize_key(k)?;
-                            map.serialize_value(&v)?;
-                        }
+            }
+            Err(QueryError::Downstream(errors)) => {
+                map.serialize_key("data")?;
+                map.serialize_value(&Value::Null)?;
+                map.serialize_key("errors")?;
+                map.serialize_value(errors)?;
+            }
+            Err(QueryError::Executor(value)) => {
+                if let Value::Object(object) = value {
+                    for (k, v) in object {
+                        map.serialize_key(k)?;
+                        map.serialize_value(&v)?;
                     }
-                    map.end()
-                }
-                _ => {
-                    let mut map = serializer.serialize_map(None)?;
-                    map.serialize_key("errors")?;
-                    map.serialize_value(&GQLError(err))?;
-                    map.end()
                 }
-            },
+            }
+            Err(err) => {
+                map.serialize_key("errors")?;
+                map.serialize_value(&GQLError(err))?;
+            }
         }
+        map.end()
     }
 }
 
@@ -66,13 +328,52 @@ impl<'a> Serialize for GQLError<'a> {
             QueryError::Errors(errors) => {
                 let mut seq = serializer.serialize_seq(Some(errors.len()))?;
                 for graphql_error in errors {
-                    seq.serialize_element(&serde_json::json!({
-                        "message": graphql_error.1.to_string(),
-                        "locations": [{"line": graphql_error.0.line, "column": graphql_error.0.column}]
-                    }))?;
+                    seq.serialize_element(&pos_error(graphql_error))?;
                 }
                 seq.end()
             }
+            QueryError::Downstream(errors) => errors.serialize(serializer),
+            QueryError::Unavailable(mode, message, retry_after) => {
+                let mut seq = serializer.serialize_seq(Some(1))?;
+                seq.serialize_element(&serde_json::json! ({
+                    "message": message,
+                    "locations": [{"line": 0, "column": 0}],
+                    "extensions": {"code": mode.code(), "retryAfter": retry_after}
+                }))?;
+                seq.end()
+            }
+            QueryError::ResourceExhausted(..) => {
+                let mut seq = serializer.serialize_seq(Some(1))?;
+                seq.serialize_element(&serde_json::json! ({
+                    "message": self.0.to_string(),
+                    "locations": [{"line": 0, "column": 0}],
+                    "extensions": {"code": "RESOURCE_EXHAUSTED"}
+                }))?;
+                seq.end()
+            }
+            QueryError::PersistedQueryNotFound | QueryError::QueryNotAllowed => {
+                let code = match self.0 {
+                    QueryError::PersistedQueryNotFound => "PERSISTED_QUERY_NOT_FOUND",
+                    _ => "QUERY_NOT_ALLOWED",
+                };
+
+                let mut seq = serializer.serialize_seq(Some(1))?;
+                seq.serialize_element(&serde_json::json! ({
+                    "message": self.0.to_string(),
+                    "locations": [{"line": 0, "column": 0}],
+                    "extensions": {"code": code}
+                }))?;
+                seq.end()
+            }
+            QueryError::ExecutorTimeout(executor, _) => {
+                let mut seq = serializer.serialize_seq(Some(1))?;
+                seq.serialize_element(&serde_json::json! ({
+                    "message": self.0.to_string(),
+                    "locations": [{"line": 0, "column": 0}],
+                    "extensions": {"code": "GATEWAY_TIMEOUT", "executor": executor}
+                }))?;
+                seq.end()
+            }
             _ => {
                 let mut seq = serializer.serialize_seq(Some(1))?;
                 seq.serialize_element(&serde_json::json! ({
@@ -84,3 +385,38 @@ impl<'a> Serialize for GQLError<'a> {
         }
     }
 }
+
+fn pos_error(error: &QueryPosError) -> Value {
+    let mut value = serde_json::json!({
+        "message": error.1.to_string(),
+        "locations": [{"line": error.0.line, "column": error.0.column}]
+    });
+
+    match &error.1 {
+        QueryError::DuplicateFragment(_, first) => {
+            value["locations"] = serde_json::json!([
+                {"line": first.line, "column": first.column},
+                {"line": error.0.line, "column": error.0.column}
+            ]);
+        }
+        QueryError::Forbidden(..) => {
+            value["extensions"] = serde_json::json!({"code": "FORBIDDEN"});
+        }
+        QueryError::CircuitOpen(executor) => {
+            value["extensions"] = serde_json::json!({"code": "CIRCUIT_OPEN", "executor": executor});
+        }
+        QueryError::NonNullField(_, _, path) => {
+            value["path"] = Value::Array(path.to_vec());
+        }
+        QueryError::InvalidArgument(field, argument, _) => {
+            value["extensions"] = serde_json::json!({
+                "code": "BAD_USER_INPUT",
+                "field": field,
+                "argument": argument
+            });
+        }
+        _ => {}
+    }
+
+    value
+}
@@ -0,0 +1,167 @@
+use crate::data::Data;
+use crate::executor::Executor;
+use crate::request_context::RequestContext;
+use async_trait::async_trait;
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Clone, Debug, Default)]
+pub struct HttpHeaders(pub Vec<(String, String)>);
+
+#[derive(Clone)]
+pub struct HttpExecutor {
+    name: String,
+    url: String,
+    headers: Vec<(String, String)>,
+    timeout: Option<Duration>,
+    client: Client,
+}
+
+impl HttpExecutor {
+    pub fn new<N: Into<String>, U: Into<String>>(name: N, url: U) -> Self {
+        HttpExecutor {
+            name: name.into(),
+            url: url.into(),
+            headers: Vec::new(),
+            timeout: None,
+            client: Client::new(),
+        }
+    }
+
+    pub fn header<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.headers.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+}
+
+#[async_trait]
+impl Executor for HttpExecutor {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn execute(
+        &self,
+        data: Option<&Data>,
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<Value>,
+    ) -> Result<Value, String> {
+        let mut request = self.client.post(&self.url).json(&json!({
+            "query": query,
+            "operationName": operation_name,
+            "variables": variables,
+        }));
+
+        let data_headers = data
+            .and_then(|data| data.get::<HttpHeaders>())
+            .map(|headers| headers.0.iter())
+            .into_iter()
+            .flatten();
+        let context_headers = data
+            .and_then(|data| data.get::<RequestContext>())
+            .map(|context| context.headers.iter())
+            .into_iter()
+            .flatten();
+
+        for (key, value) in self
+            .headers
+            .iter()
+            .chain(context_headers)
+            .chain(data_headers)
+        {
+            request = request.header(key.as_str(), value.as_str());
+        }
+
+        if let Some(timeout) = self.timeout {
+            request = request.timeout(timeout);
+        }
+
+        let response = request.send().await.map_err(|e| {
+            if e.is_timeout() {
+                format!("Request to {} timed out", self.url)
+            } else {
+                e.to_string()
+            }
+        })?;
+
+        let status = response.status();
+        let body = response.json::<Value>().await;
+
+        match body {
+            Ok(body) if status.is_success() || body.get("errors").is_some() => Ok(body),
+            Ok(_) => Err(format!("{} responded with HTTP {}", self.url, status)),
+            Err(e) if status.is_success() => Err(e.to_string()),
+            Err(_) => Err(format!("{} responded with HTTP {}", self.url, status)),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct ForwardHeadersExecutor {
+    executor: Box<dyn Executor>,
+    forward_headers: Vec<String>,
+}
+
+impl ForwardHeadersExecutor {
+    pub(crate) fn new(executor: Box<dyn Executor>, forward_headers: Vec<String>) -> Self {
+        ForwardHeadersExecutor {
+            executor,
+            forward_headers,
+        }
+    }
+
+    fn forward(&self, data: Option<&Data>) -> Option<Data> {
+        let data = data?;
+        let headers = data
+            .get::<HttpHeaders>()?
+            .0
+            .iter()
+            .filter(|(key, _)| {
+                self.forward_headers
+                    .iter()
+                    .any(|header| header.eq_ignore_ascii_case(key))
+            })
+            .cloned()
+            .collect();
+
+        let mut forwarded = Data::default();
+        forwarded.insert(HttpHeaders(headers));
+
+        Some(forwarded.layered(Arc::new(data.clone())))
+    }
+}
+
+#[async_trait]
+impl Executor for ForwardHeadersExecutor {
+    fn name(&self) -> &str {
+        self.executor.name()
+    }
+
+    async fn execute(
+        &self,
+        data: Option<&Data>,
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<Value>,
+    ) -> Result<Value, String> {
+        let forwarded = self.forward(data);
+        let data = forwarded.as_ref().or(data);
+
+        self.executor
+            .execute(data, query, operation_name, variables)
+            .await
+    }
+}
@@ -0,0 +1,143 @@
+use crate::literals::literal_value;
+use crate::schema::{get_final_field_type, Field};
+use graphql_parser::query::{Type as AstType, Value as AstValue, VariableDefinition};
+use serde_json::Value;
+use std::collections::HashMap;
+
+pub trait IdCodec: Send + Sync {
+    fn encode(&self, type_name: &str, executor: &str, id: &Value) -> Value;
+
+    fn decode(&self, id: &Value) -> Option<(String, String, Value)>;
+}
+
+#[derive(Clone, Default)]
+pub struct DefaultIdCodec;
+
+impl IdCodec for DefaultIdCodec {
+    fn encode(&self, type_name: &str, executor: &str, id: &Value) -> Value {
+        Value::String(base64::encode(format!("{}:{}:{}", type_name, executor, id)))
+    }
+
+    fn decode(&self, id: &Value) -> Option<(String, String, Value)> {
+        let decoded = base64::decode(id.as_str()?).ok()?;
+        let decoded = String::from_utf8(decoded).ok()?;
+        let mut parts = decoded.splitn(3, ':');
+        let type_name = parts.next()?.to_owned();
+        let executor = parts.next()?.to_owned();
+        let id = serde_json::from_str(parts.next()?).ok()?;
+
+        Some((type_name, executor, id))
+    }
+}
+
+pub(crate) fn encode_ids(
+    codec: &dyn IdCodec,
+    type_name: &str,
+    executor: &str,
+    value: &Value,
+) -> Value {
+    match value {
+        Value::Null => Value::Null,
+        Value::Array(values) => Value::Array(
+            values
+                .iter()
+                .map(|value| encode_ids(codec, type_name, executor, value))
+                .collect(),
+        ),
+        _ => codec.encode(type_name, executor, value),
+    }
+}
+
+pub(crate) fn decode_ids(codec: &dyn IdCodec, executor: &str, value: &Value) -> Value {
+    match value {
+        Value::Array(values) => Value::Array(
+            values
+                .iter()
+                .map(|value| decode_ids(codec, executor, value))
+                .collect(),
+        ),
+        _ => match codec.decode(value) {
+            Some((_, id_executor, id)) if id_executor == executor => id,
+            _ => value.clone(),
+        },
+    }
+}
+
+pub(crate) fn decode_variables(
+    codec: &dyn IdCodec,
+    executor: &str,
+    variable_definitions: &HashMap<String, VariableDefinition<'_, String>>,
+    variables: &Value,
+) -> Value {
+    let mut variables = variables.clone();
+
+    if let Value::Object(variables) = &mut variables {
+        for (name, value) in variables.iter_mut() {
+            let is_id = variable_definitions
+                .get(name)
+                .map(|variable_definition| ast_type_name(&variable_definition.var_type) == "ID")
+                .unwrap_or(false);
+
+            if is_id {
+                *value = decode_ids(codec, executor, value);
+            }
+        }
+    }
+
+    variables
+}
+
+pub(crate) fn decode_arguments(
+    codec: &dyn IdCodec,
+    executor: &str,
+    field: &Field,
+    arguments: &mut Vec<(String, AstValue<'_, String>)>,
+    literals: &[String],
+) {
+    for (name, value) in arguments.iter_mut() {
+        let is_id = field
+            .args
+            .iter()
+            .any(|arg| &arg.name == name && get_final_field_type(&arg.input_type).name() == "ID");
+
+        if is_id {
+            *value = decode_ast_ids(codec, executor, value, literals);
+        }
+    }
+}
+
+fn decode_ast_ids<'a>(
+    codec: &dyn IdCodec,
+    executor: &str,
+    value: &AstValue<'a, String>,
+    literals: &[String],
+) -> AstValue<'a, String> {
+    let id = match value {
+        AstValue::List(values) => {
+            return AstValue::List(
+                values
+                    .iter()
+                    .map(|value| decode_ast_ids(codec, executor, value, literals))
+                    .collect(),
+            )
+        }
+        AstValue::String(id) => Value::String(id.clone()),
+        AstValue::Enum(name) => match literal_value(name, literals) {
+            Some(id @ Value::String(_)) => id,
+            _ => return value.clone(),
+        },
+        _ => return value.clone(),
+    };
+
+    match decode_ids(codec, executor, &id) {
+        Value::String(id) => AstValue::String(id),
+        id => AstValue::String(id.to_string()),
+    }
+}
+
+pub(crate) fn ast_type_name<'a>(var_type: &'a AstType<'_, String>) -> &'a str {
+    match var_type {
+        AstType::NamedType(name) => name,
+        AstType::ListType(var_type) | AstType::NonNullType(var_type) => ast_type_name(var_type),
+    }
+}
@@ -0,0 +1,679 @@
+use crate::gateway::Gateway;
+use crate::http::{IncrementalPatch, IncrementalPayload, QueryResponse, RequestExtensions};
+use crate::literals::{preserve_literals, restore_literals};
+use crate::query::{select_operation, QueryBuilder};
+use futures::stream::{self, BoxStream, StreamExt};
+use graphql_parser::query::{
+    Definition, Directive, Document, Field, FragmentDefinition, InlineFragment,
+    OperationDefinition, Query, Selection, SelectionSet, Value as AstValue,
+};
+use graphql_parser::Pos;
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+
+const INCREMENTAL_OPERATION: &str = "IncrementalQuery";
+
+struct DeferredQuery {
+    label: Option<String>,
+    path: Vec<String>,
+    query: QueryBuilder,
+}
+
+struct StreamedField {
+    label: Option<String>,
+    path: Vec<String>,
+    initial_count: usize,
+}
+
+struct IncrementalPlan {
+    initial: QueryBuilder,
+    deferred: Vec<DeferredQuery>,
+    streamed: Vec<StreamedField>,
+}
+
+enum Target {
+    Initial,
+    Deferred(usize),
+}
+
+pub(crate) fn execute_stream<'a>(
+    query: &'a QueryBuilder,
+    gateway: &'a Gateway<'_>,
+) -> BoxStream<'a, IncrementalPayload> {
+    let query = match &gateway.persisted_queries {
+        Some(persisted_queries) => {
+            let extensions = query
+                .ctx_data
+                .as_ref()
+                .and_then(|data| data.get::<RequestExtensions>());
+
+            match persisted_queries.resolve(&query.query_source, extensions) {
+                Ok(None) => query.clone(),
+                Ok(Some(query_source)) => QueryBuilder {
+                    query_source: query_source.to_owned(),
+                    prepared: None,
+                    ..query.clone()
+                },
+                Err(err) => {
+                    let response = QueryResponse {
+                        result: Err(err),
+                        errors: vec![],
+                        extensions: Map::new(),
+                        etag: None,
+                        not_modified: false,
+                    };
+
+                    return stream::once(async move {
+                        IncrementalPayload::Initial {
+                            response,
+                            has_next: false,
+                        }
+                    })
+                    .boxed();
+                }
+            }
+        }
+        _ => query.clone(),
+    };
+
+    let plan = match incremental_plan(&query, gateway.max_deferred.unwrap_or(usize::MAX)) {
+        Some(plan) => plan,
+        _ => {
+            let query = without_incremental_directives(query);
+
+            return stream::once(async move {
+                IncrementalPayload::Initial {
+                    response: query.execute_document(gateway).await,
+                    has_next: false,
+                }
+            })
+            .boxed();
+        }
+    };
+
+    stream::once(execute_plan(plan, gateway)).flatten().boxed()
+}
+
+async fn execute_plan<'a>(
+    plan: IncrementalPlan,
+    gateway: &'a Gateway<'_>,
+) -> BoxStream<'a, IncrementalPayload> {
+    let mut response = plan.initial.execute_document(gateway).await;
+
+    let data = match &mut response.result {
+        Ok(data) => data,
+        _ => {
+            return stream::iter(vec![IncrementalPayload::Initial {
+                response,
+                has_next: false,
+            }])
+            .boxed()
+        }
+    };
+
+    let mut streamed = Vec::new();
+
+    for field in &plan.streamed {
+        streamed.extend(split_stream(data, field));
+    }
+
+    let deferred_count = plan.deferred.len();
+    let mut payloads = vec![IncrementalPayload::Initial {
+        response,
+        has_next: !streamed.is_empty() || deferred_count > 0,
+    }];
+
+    if !streamed.is_empty() {
+        payloads.push(IncrementalPayload::Subsequent {
+            incremental: streamed,
+            has_next: deferred_count > 0,
+        });
+    }
+
+    let deferred = stream::iter(plan.deferred)
+        .map(move |deferred| async move {
+            let response = deferred.query.execute_document(gateway).await;
+            defer_patches(deferred.label, &deferred.path, response)
+        })
+        .buffered(deferred_count.max(1))
+        .enumerate()
+        .map(move |(i, incremental)| IncrementalPayload::Subsequent {
+            incremental,
+            has_next: i + 1 < deferred_count,
+        });
+
+    stream::iter(payloads).chain(deferred).boxed()
+}
+
+fn incremental_plan(query: &QueryBuilder, max_deferred: usize) -> Option<IncrementalPlan> {
+    let (query_source, literals) = preserve_literals(&query.query_source);
+    let document = graphql_parser::parse_query::<String>(&query_source).ok()?;
+    let (name, variable_definitions, directives, selection_set) =
+        match select_operation(&document, query.operation_name.as_deref()).ok()? {
+            OperationDefinition::SelectionSet(selection_set) => {
+                (None, vec![], vec![], selection_set)
+            }
+            OperationDefinition::Query(operation) => (
+                operation.name.clone(),
+                operation.variable_definitions.clone(),
+                operation.directives.clone(),
+                &operation.selection_set,
+            ),
+            _ => return None,
+        };
+
+    let fragments = document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::Fragment(fragment) => Some((fragment.name.as_str(), fragment)),
+            _ => None,
+        })
+        .collect::<HashMap<&str, &FragmentDefinition<'_, String>>>();
+    let selections = inline_fragments(&selection_set.items, &fragments, &mut HashSet::new())?;
+    let variables = query.variables.as_ref();
+
+    let mut deferred = Vec::new();
+    let mut streamed = Vec::new();
+    collect(
+        &selections,
+        &[],
+        false,
+        variables,
+        max_deferred,
+        &mut deferred,
+        &mut streamed,
+    );
+
+    if deferred.is_empty() && streamed.is_empty() {
+        return None;
+    }
+
+    let name = match name {
+        None if !variable_definitions.is_empty() => Some(INCREMENTAL_OPERATION.to_owned()),
+        name => name,
+    };
+    let builder = |target: Target| {
+        let (items, _) = split(&selections, &target, &mut 0, variables, max_deferred);
+        let operation = Query {
+            position: Pos::default(),
+            name: name.clone(),
+            variable_definitions: variable_definitions.clone(),
+            directives: directives.clone(),
+            selection_set: SelectionSet {
+                span: selection_set.span,
+                items: non_empty(items),
+            },
+        };
+        let document = Document {
+            definitions: vec![Definition::Operation(OperationDefinition::Query(operation))],
+        };
+
+        QueryBuilder {
+            query_source: restore_literals(document.to_string(), &literals),
+            operation_name: name.clone(),
+            prepared: None,
+            ..query.clone()
+        }
+    };
+
+    Some(IncrementalPlan {
+        initial: builder(Target::Initial),
+        deferred: deferred
+            .into_iter()
+            .enumerate()
+            .map(|(i, (label, path))| DeferredQuery {
+                label,
+                path,
+                query: builder(Target::Deferred(i)),
+            })
+            .collect(),
+        streamed,
+    })
+}
+
+fn without_incremental_directives(query: QueryBuilder) -> QueryBuilder {
+    let (query_source, literals) = preserve_literals(&query.query_source);
+    let mut document = match graphql_parser::parse_query::<String>(&query_source) {
+        Ok(document) => document,
+        _ => return query,
+    };
+
+    let mut stripped = false;
+
+    for definition in &mut document.definitions {
+        let selection_set = match definition {
+            Definition::Operation(OperationDefinition::SelectionSet(selection_set)) => {
+                selection_set
+            }
+            Definition::Operation(OperationDefinition::Query(operation)) => {
+                &mut operation.selection_set
+            }
+            Definition::Operation(OperationDefinition::Mutation(operation)) => {
+                &mut operation.selection_set
+            }
+            Definition::Operation(OperationDefinition::Subscription(operation)) => {
+                &mut operation.selection_set
+            }
+            Definition::Fragment(fragment) => &mut fragment.selection_set,
+        };
+
+        stripped |= strip_selections(&mut selection_set.items);
+    }
+
+    if !stripped {
+        return query;
+    }
+
+    QueryBuilder {
+        query_source: restore_literals(document.to_string(), &literals),
+        prepared: None,
+        ..query
+    }
+}
+
+fn strip_selections(selections: &mut [Selection<'_, String>]) -> bool {
+    let mut stripped = false;
+
+    for selection in selections {
+        let (directives, items) = match selection {
+            Selection::Field(field) => (&mut field.directives, &mut field.selection_set.items),
+            Selection::InlineFragment(inline_fragment) => (
+                &mut inline_fragment.directives,
+                &mut inline_fragment.selection_set.items,
+            ),
+            Selection::FragmentSpread(fragment_spread) => {
+                let len = fragment_spread.directives.len();
+                fragment_spread.directives = strip_directives(&fragment_spread.directives);
+                stripped |= fragment_spread.directives.len() != len;
+                continue;
+            }
+        };
+
+        let len = directives.len();
+        *directives = strip_directives(directives);
+        stripped |= directives.len() != len;
+        stripped |= strip_selections(items);
+    }
+
+    stripped
+}
+
+fn inline_fragments<'a>(
+    selections: &[Selection<'a, String>],
+    fragments: &HashMap<&str, &FragmentDefinition<'a, String>>,
+    visited: &mut HashSet<String>,
+) -> Option<Vec<Selection<'a, String>>> {
+    let mut items = Vec::with_capacity(selections.len());
+
+    for selection in selections {
+        let item = match selection {
+            Selection::Field(field) => Selection::Field(Field {
+                selection_set: SelectionSet {
+                    span: field.selection_set.span,
+                    items: inline_fragments(&field.selection_set.items, fragments, visited)?,
+                },
+                ..field.clone()
+            }),
+            Selection::InlineFragment(inline_fragment) => {
+                Selection::InlineFragment(InlineFragment {
+                    selection_set: SelectionSet {
+                        span: inline_fragment.selection_set.span,
+                        items: inline_fragments(
+                            &inline_fragment.selection_set.items,
+                            fragments,
+                            visited,
+                        )?,
+                    },
+                    ..inline_fragment.clone()
+                })
+            }
+            Selection::FragmentSpread(fragment_spread) => {
+                let fragment = fragments.get(fragment_spread.fragment_name.as_str())?;
+
+                if !visited.insert(fragment.name.clone()) {
+                    return None;
+                }
+
+                let items = inline_fragments(&fragment.selection_set.items, fragments, visited)?;
+                visited.remove(&fragment.name);
+
+                Selection::InlineFragment(InlineFragment {
+                    position: fragment_spread.position,
+                    type_condition: Some(fragment.type_condition.clone()),
+                    directives: fragment_spread.directives.clone(),
+                    selection_set: SelectionSet {
+                        span: fragment.selection_set.span,
+                        items,
+                    },
+                })
+            }
+        };
+
+        items.push(item);
+    }
+
+    Some(items)
+}
+
+fn collect(
+    selections: &[Selection<'_, String>],
+    path: &[String],
+    in_defer: bool,
+    variables: Option<&Value>,
+    max_deferred: usize,
+    deferred: &mut Vec<(Option<String>, Vec<String>)>,
+    streamed: &mut Vec<StreamedField>,
+) {
+    for selection in selections {
+        match selection {
+            Selection::Field(field) => {
+                let mut field_path = path.to_vec();
+                field_path.push(field.alias.as_ref().unwrap_or(&field.name).clone());
+
+                let stream = find_directive(&field.directives, "stream", variables);
+
+                if let (Some(stream), false) = (stream, in_defer) {
+                    streamed.push(StreamedField {
+                        label: string_argument(stream, "label"),
+                        path: field_path.clone(),
+                        initial_count: int_argument(stream, "initialCount", variables)
+                            .unwrap_or_default(),
+                    });
+                }
+
+                collect(
+                    &field.selection_set.items,
+                    &field_path,
+                    in_defer,
+                    variables,
+                    max_deferred,
+                    deferred,
+                    streamed,
+                );
+            }
+            Selection::InlineFragment(inline_fragment) => {
+                let defer = find_directive(&inline_fragment.directives, "defer", variables)
+                    .filter(|_| deferred.len() < max_deferred);
+
+                if let Some(defer) = defer {
+                    deferred.push((string_argument(defer, "label"), path.to_vec()));
+                }
+
+                collect(
+                    &inline_fragment.selection_set.items,
+                    path,
+                    in_defer || defer.is_some(),
+                    variables,
+                    max_deferred,
+                    deferred,
+                    streamed,
+                );
+            }
+            Selection::FragmentSpread(_) => {}
+        }
+    }
+}
+
+fn split<'a>(
+    selections: &[Selection<'a, String>],
+    target: &Target,
+    next_id: &mut usize,
+    variables: Option<&Value>,
+    max_deferred: usize,
+) -> (Vec<Selection<'a, String>>, bool) {
+    let mut items = Vec::new();
+    let mut found = false;
+
+    for selection in selections {
+        match selection {
+            Selection::Field(field) => {
+                let (children, contains) = split(
+                    &field.selection_set.items,
+                    target,
+                    next_id,
+                    variables,
+                    max_deferred,
+                );
+
+                if let (Target::Deferred(_), false) = (target, contains) {
+                    continue;
+                }
+
+                let children = if field.selection_set.items.is_empty() {
+                    children
+                } else {
+                    non_empty(children)
+                };
+
+                found |= contains;
+                items.push(Selection::Field(Field {
+                    directives: strip_directives(&field.directives),
+                    selection_set: SelectionSet {
+                        span: field.selection_set.span,
+                        items: children,
+                    },
+                    ..field.clone()
+                }));
+            }
+            Selection::InlineFragment(inline_fragment) => {
+                let is_deferred = *next_id < max_deferred
+                    && find_directive(&inline_fragment.directives, "defer", variables).is_some();
+                let id = *next_id;
+
+                if is_deferred {
+                    *next_id += 1;
+                }
+
+                let (children, contains) = match target {
+                    Target::Deferred(target_id) if is_deferred && id == *target_id => {
+                        let (children, _) = split(
+                            &inline_fragment.selection_set.items,
+                            &Target::Initial,
+                            next_id,
+                            variables,
+                            max_deferred,
+                        );
+
+                        (children, true)
+                    }
+                    _ => split(
+                        &inline_fragment.selection_set.items,
+                        target,
+                        next_id,
+                        variables,
+                        max_deferred,
+                    ),
+                };
+
+                let skip = match target {
+                    Target::Initial => is_deferred || children.is_empty(),
+                    Target::Deferred(_) => !contains,
+                };
+
+                if skip {
+                    continue;
+                }
+
+                found |= contains;
+                items.push(Selection::InlineFragment(InlineFragment {
+                    directives: strip_directives(&inline_fragment.directives),
+                    selection_set: SelectionSet {
+                        span: inline_fragment.selection_set.span,
+                        items: children,
+                    },
+                    ..inline_fragment.clone()
+                }));
+            }
+            Selection::FragmentSpread(_) => items.push(selection.clone()),
+        }
+    }
+
+    (items, found)
+}
+
+fn non_empty(mut items: Vec<Selection<'_, String>>) -> Vec<Selection<'_, String>> {
+    if items.is_empty() {
+        items.push(Selection::Field(Field {
+            position: Pos::default(),
+            alias: None,
+            name: "__typename".to_owned(),
+            arguments: vec![],
+            directives: vec![],
+            selection_set: SelectionSet {
+                span: (Pos::default(), Pos::default()),
+                items: vec![],
+            },
+        }));
+    }
+
+    items
+}
+
+fn strip_directives<'a>(directives: &[Directive<'a, String>]) -> Vec<Directive<'a, String>> {
+    directives
+        .iter()
+        .filter(|directive| directive.name != "defer" && directive.name != "stream")
+        .cloned()
+        .collect()
+}
+
+fn find_directive<'d, 'a>(
+    directives: &'d [Directive<'a, String>],
+    name: &str,
+    variables: Option<&Value>,
+) -> Option<&'d Directive<'a, String>> {
+    directives.iter().find(|directive| {
+        directive.name == name
+            && argument_value(directive, "if", variables)
+                .and_then(|value| value.as_bool())
+                .unwrap_or(true)
+    })
+}
+
+fn argument_value(
+    directive: &Directive<'_, String>,
+    name: &str,
+    variables: Option<&Value>,
+) -> Option<Value> {
+    let value = directive
+        .arguments
+        .iter()
+        .find(|(argument, _)| argument == name)
+        .map(|(_, value)| value)?;
+
+    match value {
+        AstValue::Variable(variable) => variables
+            .and_then(|variables| variables.get(variable))
+            .cloned(),
+        AstValue::Boolean(value) => Some(Value::Bool(*value)),
+        AstValue::Int(value) => value.as_i64().map(Value::from),
+        AstValue::String(value) => Some(Value::String(value.clone())),
+        _ => None,
+    }
+}
+
+fn string_argument(directive: &Directive<'_, String>, name: &str) -> Option<String> {
+    argument_value(directive, name, None).and_then(|value| value.as_str().map(|v| v.to_owned()))
+}
+
+fn int_argument(
+    directive: &Directive<'_, String>,
+    name: &str,
+    variables: Option<&Value>,
+) -> Option<usize> {
+    argument_value(directive, name, variables)
+        .and_then(|value| value.as_u64())
+        .map(|value| value as usize)
+}
+
+fn split_stream(data: &mut Value, field: &StreamedField) -> Vec<IncrementalPatch> {
+    let mut patches = Vec::new();
+    let (key, parents) = match field.path.split_last() {
+        Some(path) => path,
+        _ => return patches,
+    };
+
+    visit_mut(data, parents, vec![], &mut |object, mut path| {
+        let items = match object.get_mut(key) {
+            Some(Value::Array(items)) if items.len() > field.initial_count => {
+                items.split_off(field.initial_count)
+            }
+            _ => return,
+        };
+
+        path.push(Value::String(key.clone()));
+        path.push(Value::from(field.initial_count));
+
+        patches.push(IncrementalPatch::Stream {
+            label: field.label.clone(),
+            path,
+            items,
+        });
+    });
+
+    patches
+}
+
+fn visit_mut<F: FnMut(&mut Map<String, Value>, Vec<Value>)>(
+    value: &mut Value,
+    keys: &[String],
+    path: Vec<Value>,
+    f: &mut F,
+) {
+    match value {
+        Value::Array(values) => {
+            for (i, value) in values.iter_mut().enumerate() {
+                let mut item_path = path.clone();
+                item_path.push(Value::from(i));
+                visit_mut(value, keys, item_path, f);
+            }
+        }
+        Value::Object(object) => match keys.split_first() {
+            Some((key, keys)) => {
+                if let Some(value) = object.get_mut(key) {
+                    let mut field_path = path;
+                    field_path.push(Value::String(key.clone()));
+                    visit_mut(value, keys, field_path, f);
+                }
+            }
+            _ => f(object, path),
+        },
+        _ => {}
+    }
+}
+
+fn defer_patches(
+    label: Option<String>,
+    path: &[String],
+    response: QueryResponse,
+) -> Vec<IncrementalPatch> {
+    let mut data = match response.result {
+        Ok(data) => data,
+        Err(err) => {
+            return vec![IncrementalPatch::Defer {
+                label,
+                path: path.iter().map(|key| Value::String(key.clone())).collect(),
+                result: Err(err),
+                errors: response.errors,
+            }]
+        }
+    };
+
+    let mut objects = Vec::new();
+    visit_mut(&mut data, path, vec![], &mut |object, path| {
+        objects.push((path, Value::Object(std::mem::take(object))));
+    });
+
+    let mut errors = Some(response.errors);
+
+    objects
+        .into_iter()
+        .map(|(path, data)| IncrementalPatch::Defer {
+            label: label.clone(),
+            path,
+            result: Ok(data),
+            errors: errors.take().unwrap_or_default(),
+        })
+        .collect()
+}
@@ -0,0 +1,77 @@
+use crate::data::Data;
+use crate::schema::{get_final_field_type, Field, TypeKind};
+use graphql_parser::query::{Number, Value as AstValue};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+pub trait ArgumentInjector: Send + Sync {
+    fn inject(&self, data: Option<&Data>) -> Option<Value>;
+}
+
+impl<F> ArgumentInjector for F
+where
+    F: Fn(Option<&Data>) -> Option<Value> + Send + Sync,
+{
+    fn inject(&self, data: Option<&Data>) -> Option<Value> {
+        self(data)
+    }
+}
+
+pub(crate) fn inject_arguments<'a>(
+    injectors: &HashMap<String, Arc<dyn ArgumentInjector>>,
+    data: Option<&Data>,
+    field_definition: &Field,
+    arguments: &mut Vec<(String, AstValue<'a, String>)>,
+) {
+    for argument_definition in &field_definition.args {
+        if arguments
+            .iter()
+            .any(|(name, _)| name == &argument_definition.name)
+        {
+            continue;
+        }
+
+        let value = match injectors
+            .get(&argument_definition.name)
+            .and_then(|injector| injector.inject(data))
+        {
+            Some(value) => value,
+            _ => continue,
+        };
+
+        let is_enum = get_final_field_type(&argument_definition.input_type).kind == TypeKind::Enum;
+
+        arguments.push((
+            argument_definition.name.clone(),
+            to_ast_value(&value, is_enum),
+        ));
+    }
+}
+
+pub(crate) fn to_ast_value<'a>(value: &Value, is_enum: bool) -> AstValue<'a, String> {
+    match value {
+        Value::Null => AstValue::Null,
+        Value::Bool(value) => AstValue::Boolean(*value),
+        Value::Number(number) => match number.as_i64().map(i32::try_from) {
+            Some(Ok(number)) => AstValue::Int(Number::from(number)),
+            // Serialized verbatim so 64-bit and high-precision numbers skip f64 coercion.
+            _ => AstValue::Enum(number.to_string()),
+        },
+        Value::String(value) if is_enum => AstValue::Enum(value.clone()),
+        Value::String(value) => AstValue::String(value.clone()),
+        Value::Array(values) => AstValue::List(
+            values
+                .iter()
+                .map(|value| to_ast_value(value, is_enum))
+                .collect(),
+        ),
+        Value::Object(object) => AstValue::Object(
+            object
+                .iter()
+                .map(|(key, value)| (key.clone(), to_ast_value(value, false)))
+                .collect(),
+        ),
+    }
+}
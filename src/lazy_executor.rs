@@ -0,0 +1,41 @@
+use crate::executor::Executor;
+use crate::schema::Schema;
+use std::sync::OnceLock;
+
+pub trait ExecutorFactory: Send + Sync {
+    fn create(&self) -> Box<dyn Executor>;
+}
+
+impl<F, E> ExecutorFactory for F
+where
+    F: Fn() -> E + Send + Sync,
+    E: Executor + 'static,
+{
+    fn create(&self) -> Box<dyn Executor> {
+        Box::new(self())
+    }
+}
+
+pub(crate) struct LazyExecutor {
+    factory: Box<dyn ExecutorFactory>,
+    pub(crate) schema: Option<Schema>,
+    instance: OnceLock<Box<dyn Executor>>,
+}
+
+impl LazyExecutor {
+    pub(crate) fn new<F: ExecutorFactory + 'static>(factory: F, schema: Option<Schema>) -> Self {
+        LazyExecutor {
+            factory: Box::new(factory),
+            schema,
+            instance: OnceLock::new(),
+        }
+    }
+
+    pub(crate) fn get(&self) -> &dyn Executor {
+        self.instance.get_or_init(|| self.factory.create()).as_ref()
+    }
+
+    pub(crate) fn is_initialized(&self) -> bool {
+        self.instance.get().is_some()
+    }
+}
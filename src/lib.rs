@@ -4,17 +4,134 @@ extern crate thiserror;
 #[macro_use]
 extern crate serde;
 
+mod apollo_tracing;
+mod argument_rules;
+mod ast;
+mod authorization;
+mod builder;
+mod circuit_breaker;
+mod compensation;
+mod composition_cache;
+pub mod conformance;
 mod context;
 mod data;
+mod diagnostics;
+mod directive;
+mod document_format;
+mod entity;
+mod error_class;
+mod etag;
 mod executor;
+mod executor_error;
+mod executor_info;
+mod fan_out;
+mod fast_path;
+mod field_merge;
 mod gateway;
+mod hasura;
+mod health;
 mod http;
+#[cfg(feature = "http")]
+mod http_executor;
+mod id_codec;
+mod incremental;
+mod injection;
+mod lazy_executor;
+mod limits;
+mod literals;
+mod materialized;
+mod memory;
+mod mode;
+mod naming;
+mod node_batch;
+mod node_cache;
+pub mod ops;
+mod persisted;
+mod plan_cache;
+mod planner;
+mod poller;
+mod prepared;
 mod query;
+mod request_context;
+mod request_id;
+mod resilience;
+mod response_cache;
+#[cfg(feature = "scenario")]
+mod scenario;
 mod schema;
+mod schema_source;
+#[cfg(feature = "axum")]
+mod server;
+mod signature;
+mod slo;
+mod smoke;
+mod snapshot;
+mod static_fields;
+mod stats;
+mod supergraph;
+mod transformer;
+mod typescript;
+mod variables;
 
+pub use crate::argument_rules::{ArgumentRule, MaxLength, MinLength, Pattern, Range};
+pub use crate::authorization::{Authorizer, UnauthorizedField};
+pub use crate::builder::{ConfigError, ExecutorOptions, GatewayBuilder, RetryPolicy};
+pub use crate::circuit_breaker::{CircuitBreaker, CircuitState, Clock, SystemClock};
+pub use crate::compensation::Compensation;
 pub use crate::data::Data;
+pub use crate::diagnostics::{ExecutorWait, RequestDiagnostics, RequestPhase};
+pub use crate::directive::DirectiveHandler;
+pub use crate::document_format::DocumentFormat;
+pub use crate::entity::{EntityStrategy, NodeLookup};
+pub use crate::error_class::{DefaultErrorClassifier, ErrorClass, ErrorClassifier};
+pub use crate::etag::IfNoneMatch;
 pub use crate::executor::{Executor, INTROSPECTION_QUERY};
+pub use crate::executor_error::{ErrorPassthrough, ExecutorError};
+pub use crate::executor_info::{ExecutorHealth, ExecutorInfo};
+pub use crate::fan_out::MergeStrategy;
 pub use crate::gateway::{Gateway, GatewayError};
-pub use crate::http::{GraphQLPayload, GraphQLResponse};
-pub use crate::query::{QueryBuilder, QueryError};
+pub use crate::hasura::HasuraExecutor;
+pub use crate::health::{ExecutorStatus, GatewayHealth};
+pub use crate::http::{
+    graphiql_source, GraphQLBatchPayload, GraphQLBatchResponse, GraphQLPayload, GraphQLResponse,
+    IncrementalPatch, IncrementalPayload, QueryResponse, RequestExtensions, MULTIPART_CONTENT_TYPE,
+};
+#[cfg(feature = "http")]
+pub use crate::http_executor::{HttpExecutor, HttpHeaders};
+pub use crate::id_codec::{DefaultIdCodec, IdCodec};
+pub use crate::injection::ArgumentInjector;
+pub use crate::lazy_executor::ExecutorFactory;
+pub use crate::limits::{CompositionLimit, CompositionLimitError, CompositionLimits};
+pub use crate::mode::Mode;
+pub use crate::naming::{
+    is_injected_variable, NamingError, NamingPolicy, NamingViolation, INJECTED_VARIABLE_PREFIX,
+};
+pub use crate::node_cache::EntityInvalidator;
+pub use crate::persisted::PersistedQueries;
+pub use crate::plan_cache::PlanCacheStats;
+pub use crate::planner::{PlanStep, PlanStepKind, PlannerFlags, QueryPlan};
+pub use crate::poller::{GatewayHandle, SchemaChanged};
+pub use crate::prepared::PreparedQuery;
+pub use crate::query::{ParsedQuery, QueryBuilder, QueryError};
+pub use crate::request_context::{HeaderPolicy, RequestContext};
+pub use crate::request_id::RequestId;
+pub use crate::response_cache::{LruResponseCache, ResponseCache, ResponseCacheKey};
+#[cfg(feature = "scenario")]
+pub use crate::scenario::{
+    Scenario, ScenarioCall, ScenarioExecutor, ScenarioOutcome, ScenarioResponse,
+};
 pub use crate::schema::{Schema, TypeKind};
+pub use crate::schema_source::{
+    FederationSource, IntrospectionSource, RegistrySource, SchemaSource,
+};
+#[cfg(feature = "axum")]
+pub use crate::server::{graphiql, router};
+pub use crate::signature::{
+    operation_hash, operation_signature, sha256_hex, stats_report_key, OperationUsage,
+    UsageReporter,
+};
+pub use crate::slo::{Slo, SloAlert, SloStatus, SloTarget, SloViolation};
+pub use crate::snapshot::SchemaSnapshot;
+pub use crate::stats::ExecutorStats;
+pub use crate::transformer::SelectionTransformer;
+pub use crate::variables::Variables;
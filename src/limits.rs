@@ -0,0 +1,269 @@
+use crate::argument_rules::argument_value;
+use crate::context::Context;
+use crate::naming::NamingPolicy;
+use crate::query::{QueryError, QueryResult};
+use crate::schema::{Schema, Type, TypeKind};
+use graphql_parser::query::{FragmentDefinition, Selection, TypeCondition};
+use std::collections::{HashMap, HashSet};
+
+pub(crate) const INTROSPECTION_FIELDS: [&str; 2] = ["__schema", "__type"];
+
+const LIST_SIZE_ARGUMENTS: [&str; 3] = ["first", "last", "limit"];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CompositionLimits {
+    pub max_types: Option<usize>,
+    pub max_fields_per_type: Option<usize>,
+    pub max_enum_values: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CompositionLimit {
+    Types,
+    FieldsPerType,
+    EnumValues,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompositionLimitError {
+    pub executor: String,
+    pub name: Option<String>,
+    pub limit: CompositionLimit,
+    pub count: usize,
+    pub max: usize,
+}
+
+pub(crate) fn validate_depth(
+    selections: &[Selection<'_, String>],
+    fragments: &HashMap<String, FragmentDefinition<'_, String>>,
+    max_depth: usize,
+) -> QueryResult<()> {
+    let depth = depth(selections, fragments, &mut vec![]);
+
+    if depth > max_depth {
+        return Err(QueryError::MaxDepthExceeded(depth, max_depth));
+    }
+
+    Ok(())
+}
+
+pub(crate) fn validate_introspection(
+    selections: &[Selection<'_, String>],
+    fragments: &HashMap<String, FragmentDefinition<'_, String>>,
+) -> QueryResult<()> {
+    if has_introspection(selections, fragments, &mut vec![]) {
+        return Err(QueryError::IntrospectionDisabled);
+    }
+
+    Ok(())
+}
+
+pub(crate) fn validate_complexity<'a>(
+    context: &Context<'a, '_>,
+    object_type: &Type,
+    selections: &[Selection<'a, String>],
+    max_complexity: usize,
+) -> QueryResult<()> {
+    let complexity = complexity(context, object_type, selections, &mut HashSet::new());
+
+    if complexity > max_complexity {
+        return Err(QueryError::MaxComplexityExceeded(
+            complexity,
+            max_complexity,
+        ));
+    }
+
+    Ok(())
+}
+
+fn has_introspection<'s>(
+    selections: &'s [Selection<'_, String>],
+    fragments: &'s HashMap<String, FragmentDefinition<'_, String>>,
+    visited: &mut Vec<&'s str>,
+) -> bool {
+    selections.iter().any(|selection| match selection {
+        Selection::Field(field) => INTROSPECTION_FIELDS.contains(&field.name.as_str()),
+        Selection::InlineFragment(fragment) => {
+            has_introspection(&fragment.selection_set.items, fragments, visited)
+        }
+        Selection::FragmentSpread(spread) => {
+            let fragment = match fragments.get(&spread.fragment_name) {
+                Some(fragment) if !visited.contains(&spread.fragment_name.as_str()) => fragment,
+                _ => return false,
+            };
+
+            visited.push(&spread.fragment_name);
+            has_introspection(&fragment.selection_set.items, fragments, visited)
+        }
+    })
+}
+
+fn depth<'s>(
+    selections: &'s [Selection<'_, String>],
+    fragments: &'s HashMap<String, FragmentDefinition<'_, String>>,
+    visited: &mut Vec<&'s str>,
+) -> usize {
+    selections
+        .iter()
+        .map(|selection| match selection {
+            Selection::Field(field) if field.selection_set.items.is_empty() => 1,
+            Selection::Field(field) => 1 + depth(&field.selection_set.items, fragments, visited),
+            Selection::InlineFragment(fragment) => {
+                depth(&fragment.selection_set.items, fragments, visited)
+            }
+            Selection::FragmentSpread(spread) => {
+                let fragment = match fragments.get(&spread.fragment_name) {
+                    Some(fragment) if !visited.contains(&spread.fragment_name.as_str()) => fragment,
+                    _ => return 0,
+                };
+
+                visited.push(&spread.fragment_name);
+                let depth = depth(&fragment.selection_set.items, fragments, visited);
+                visited.pop();
+                depth
+            }
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+fn complexity<'a>(
+    context: &Context<'a, '_>,
+    object_type: &Type,
+    selections: &[Selection<'a, String>],
+    visited: &mut HashSet<String>,
+) -> usize {
+    selections
+        .iter()
+        .map(|selection| {
+            let (type_condition, items) = match selection {
+                Selection::Field(field) => {
+                    let definition = context.field(object_type, field.name.as_str());
+                    let weight = context
+                        .gateway
+                        .field_costs
+                        .get(&format!("{}.{}", object_type.name(), field.name))
+                        .copied()
+                        .or_else(|| definition.as_ref().and_then(|(_, field)| field.cost))
+                        .unwrap_or(1);
+
+                    let field_type = match context.field_object_type(object_type, &field.name) {
+                        Some((_, field_type)) => field_type,
+                        _ => return weight,
+                    };
+
+                    let is_list = definition
+                        .map(|(_, definition)| is_list(&definition.field_type))
+                        .unwrap_or(false);
+
+                    let multiplier = if is_list {
+                        LIST_SIZE_ARGUMENTS
+                            .iter()
+                            .find_map(|argument| {
+                                argument_value(context, object_type, field, argument).as_u64()
+                            })
+                            .unwrap_or(1) as usize
+                    } else {
+                        1
+                    };
+
+                    let children =
+                        complexity(context, field_type, &field.selection_set.items, visited);
+
+                    return weight + multiplier * children;
+                }
+                Selection::InlineFragment(fragment) => match &fragment.type_condition {
+                    Some(TypeCondition::On(type_condition)) => {
+                        (type_condition.as_str(), &fragment.selection_set.items)
+                    }
+                    _ => (object_type.name(), &fragment.selection_set.items),
+                },
+                Selection::FragmentSpread(spread) => {
+                    let fragment = match context.fragments.get(&spread.fragment_name) {
+                        Some(fragment) if visited.insert(spread.fragment_name.clone()) => fragment,
+                        _ => return 0,
+                    };
+
+                    let TypeCondition::On(type_condition) = &fragment.type_condition;
+
+                    (type_condition.as_str(), &fragment.selection_set.items)
+                }
+            };
+
+            let complexity = context
+                .object(type_condition)
+                .map(|object_type| complexity(context, object_type, items, visited))
+                .unwrap_or(0);
+
+            if let Selection::FragmentSpread(spread) = selection {
+                visited.remove(&spread.fragment_name);
+            }
+
+            complexity
+        })
+        .sum()
+}
+
+fn is_list(field_type: &Type) -> bool {
+    match field_type.kind {
+        TypeKind::List => true,
+        TypeKind::NonNull => is_list(field_type.of_type()),
+        _ => false,
+    }
+}
+
+impl CompositionLimits {
+    pub(crate) fn check(
+        &self,
+        executor: &str,
+        schema: &Schema,
+        naming_policy: &NamingPolicy,
+    ) -> Vec<CompositionLimitError> {
+        let types = schema
+            .types
+            .iter()
+            .filter(|t| !naming_policy.is_introspection_type(t.name()))
+            .collect::<Vec<&Type>>();
+
+        let mut errors = vec![];
+        let mut push = |name: Option<&str>, limit, count, max| {
+            errors.push(CompositionLimitError {
+                executor: executor.to_owned(),
+                name: name.map(|name| name.to_owned()),
+                limit,
+                count,
+                max,
+            })
+        };
+
+        if let Some(max) = self.max_types.filter(|&max| types.len() > max) {
+            push(None, CompositionLimit::Types, types.len(), max);
+        }
+
+        for schema_type in types {
+            let fields = schema_type.fields.as_ref().map(Vec::len).unwrap_or(0)
+                + schema_type.input_fields.as_ref().map(Vec::len).unwrap_or(0);
+            let enum_values = schema_type.enum_values.as_ref().map(Vec::len).unwrap_or(0);
+
+            if let Some(max) = self.max_fields_per_type.filter(|&max| fields > max) {
+                push(
+                    Some(schema_type.name()),
+                    CompositionLimit::FieldsPerType,
+                    fields,
+                    max,
+                );
+            }
+
+            if let Some(max) = self.max_enum_values.filter(|&max| enum_values > max) {
+                push(
+                    Some(schema_type.name()),
+                    CompositionLimit::EnumValues,
+                    enum_values,
+                    max,
+                );
+            }
+        }
+
+        errors
+    }
+}
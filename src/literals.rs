@@ -0,0 +1,151 @@
+use crate::query::find_operation;
+use graphql_parser::query::{Selection, Value as AstValue};
+use serde_json::Value;
+
+const LITERAL_PREFIX: &str = "__gql_gateway_literal_";
+
+pub(crate) fn preserve_literals(source: &str) -> (String, Vec<String>) {
+    let chars = source.char_indices().collect::<Vec<(usize, char)>>();
+    let mut output = String::with_capacity(source.len());
+    let mut literals = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let (start, c) = chars[i];
+
+        let end = match c {
+            '#' => scan_while(&chars, i, |c| c != '\n'),
+            '"' => scan_string(&chars, i),
+            c if c == '_' || c.is_ascii_alphabetic() => {
+                scan_while(&chars, i, |c| c == '_' || c.is_ascii_alphanumeric())
+            }
+            '-' if chars.get(i + 1).map(|&(_, c)| c.is_ascii_digit()) == Some(true) => {
+                scan_number(&chars, i)
+            }
+            c if c.is_ascii_digit() => scan_number(&chars, i),
+            _ => i + 1,
+        };
+
+        let end_offset = chars
+            .get(end)
+            .map(|&(offset, _)| offset)
+            .unwrap_or(source.len());
+        let token = &source[start..end_offset];
+
+        if is_lossy(c, token) {
+            output.push_str(&format!("{}{}_", LITERAL_PREFIX, literals.len()));
+            literals.push(token.to_owned());
+        } else {
+            output.push_str(token);
+        }
+
+        i = end.max(i + 1);
+    }
+
+    (output, literals)
+}
+
+pub(crate) fn restore_literals(mut source: String, literals: &[String]) -> String {
+    for (i, literal) in literals.iter().enumerate() {
+        source = source.replace(&format!("{}{}_", LITERAL_PREFIX, i), literal);
+    }
+
+    source
+}
+
+pub(crate) fn literal_source<'a>(name: &str, literals: &'a [String]) -> Option<&'a str> {
+    let index = name
+        .strip_prefix(LITERAL_PREFIX)?
+        .strip_suffix('_')?
+        .parse::<usize>()
+        .ok()?;
+
+    literals.get(index).map(String::as_str)
+}
+
+pub(crate) fn literal_value(name: &str, literals: &[String]) -> Option<Value> {
+    let query = format!("{{ literal(value: {}) }}", literal_source(name, literals)?);
+    let document = graphql_parser::parse_query::<String>(&query).ok()?;
+    let (_, selections, _) = find_operation(&document, None).ok()?;
+
+    let value = match selections.first()? {
+        Selection::Field(field) => &field.arguments.first()?.1,
+        _ => return None,
+    };
+
+    match value {
+        AstValue::String(value) => Some(Value::String(value.clone())),
+        AstValue::Int(number) => Some(number.as_i64().map(Value::from).unwrap_or(Value::Null)),
+        AstValue::Float(number) => Some(Value::from(*number)),
+        _ => None,
+    }
+}
+
+fn is_lossy(first: char, token: &str) -> bool {
+    match first {
+        '"' => token.starts_with("\"\"\"") || token.contains('\\'),
+        '-' if token.len() == 1 => false,
+        c if c == '-' || c.is_ascii_digit() => {
+            if token.contains(['.', 'e', 'E']) {
+                token.parse::<f64>().map(|v| v.to_string()) != Ok(token.to_owned())
+            } else {
+                token.parse::<i64>().map(|v| v.to_string()) != Ok(token.to_owned())
+            }
+        }
+        _ => false,
+    }
+}
+
+pub(crate) fn scan_while<F: Fn(char) -> bool>(
+    chars: &[(usize, char)],
+    start: usize,
+    f: F,
+) -> usize {
+    let mut i = start + 1;
+
+    while i < chars.len() && f(chars[i].1) {
+        i += 1;
+    }
+
+    i
+}
+
+fn scan_number(chars: &[(usize, char)], start: usize) -> usize {
+    let mut i = start + 1;
+    let mut previous = chars[start].1;
+
+    while let Some(&(_, c)) = chars.get(i) {
+        let accepted = c.is_ascii_digit()
+            || c == '.'
+            || c == 'e'
+            || c == 'E'
+            || ((c == '+' || c == '-') && (previous == 'e' || previous == 'E'));
+
+        if !accepted {
+            break;
+        }
+
+        previous = c;
+        i += 1;
+    }
+
+    i
+}
+
+pub(crate) fn scan_string(chars: &[(usize, char)], start: usize) -> usize {
+    let is_quote = |i: usize| chars.get(i).map(|&(_, c)| c == '"').unwrap_or(false);
+    let block = is_quote(start + 1) && is_quote(start + 2);
+    let mut i = if block { start + 3 } else { start + 1 };
+
+    while i < chars.len() {
+        match chars[i].1 {
+            '\\' if block && is_quote(i + 1) && is_quote(i + 2) && is_quote(i + 3) => i += 4,
+            '\\' if !block => i += 2,
+            '"' if !block => return i + 1,
+            '"' if is_quote(i + 1) && is_quote(i + 2) => return i + 3,
+            _ => i += 1,
+        }
+    }
+
+    i
+}
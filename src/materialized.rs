@@ -0,0 +1,182 @@
+use crate::data::Data;
+use crate::etag::IfNoneMatch;
+use crate::gateway::Gateway;
+use crate::http::RequestExtensions;
+use crate::memory::MemoryTracker;
+use crate::query::{definition_name, QueryBuilder, QueryResult};
+use crate::request_id::RequestId;
+use graphql_parser::query::{Definition, OperationDefinition};
+use serde_json::{json, Map, Value};
+use std::any::TypeId;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+#[derive(Clone)]
+struct Materialized {
+    refreshed_at: Instant,
+    refreshed_at_system: SystemTime,
+    value: Value,
+}
+
+#[derive(Clone)]
+pub(crate) struct MaterializedView {
+    pub(crate) name: String,
+    query_source: String,
+    operation_name: Option<String>,
+    variables: Option<Value>,
+    refresh_interval: Duration,
+    state: Arc<RwLock<Option<Materialized>>>,
+}
+
+impl MaterializedView {
+    pub(crate) fn new(
+        name: String,
+        query: QueryBuilder,
+        refresh_interval: Duration,
+    ) -> Result<Self, String> {
+        if !is_query_operation(&query.query_source, query.operation_name.as_deref()) {
+            return Err("Materialized views only support query operations.".to_owned());
+        }
+
+        Ok(MaterializedView {
+            name,
+            query_source: normalize_query(&query.query_source),
+            operation_name: query.operation_name,
+            variables: query.variables,
+            refresh_interval,
+            state: Arc::new(RwLock::new(None)),
+        })
+    }
+
+    pub(crate) fn matches(&self, gateway: &Gateway<'_>, query: &QueryBuilder) -> bool {
+        gateway.authorizer.is_none()
+            && query.ctx_data.as_ref().is_none_or(is_shared_data)
+            && query.version.is_none()
+            && self.operation_name == query.operation_name
+            && self.variables == query.variables
+            && self.query_source == normalize_query(&query.query_source)
+    }
+
+    pub(crate) fn is_stale(&self) -> bool {
+        match self
+            .state
+            .read()
+            .expect("Materialized view lock is poisoned.")
+            .as_ref()
+        {
+            Some(materialized) => materialized.refreshed_at.elapsed() >= self.refresh_interval,
+            _ => true,
+        }
+    }
+
+    pub(crate) fn due_in(&self) -> Duration {
+        match self
+            .state
+            .read()
+            .expect("Materialized view lock is poisoned.")
+            .as_ref()
+        {
+            Some(materialized) => self
+                .refresh_interval
+                .checked_sub(materialized.refreshed_at.elapsed())
+                .unwrap_or_default(),
+            _ => Duration::default(),
+        }
+    }
+
+    pub(crate) fn get(&self) -> Option<(Value, Value)> {
+        let state = self
+            .state
+            .read()
+            .expect("Materialized view lock is poisoned.");
+        let materialized = state.as_ref()?;
+        let refreshed_at = materialized
+            .refreshed_at_system
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+
+        let metadata = json!({
+            "name": self.name,
+            "refreshedAt": refreshed_at.as_millis() as u64,
+            "age": materialized.refreshed_at.elapsed().as_millis() as u64,
+        });
+
+        Some((materialized.value.clone(), metadata))
+    }
+
+    pub(crate) async fn refresh(&self, gateway: &Gateway<'_>) -> QueryResult<()> {
+        let query = QueryBuilder {
+            query_source: self.query_source.clone(),
+            operation_name: self.operation_name.clone(),
+            variables: self.variables.clone(),
+            ctx_data: None,
+            version: None,
+            response_cache: true,
+            prepared: None,
+        };
+
+        let mut data = Data::default().layered(gateway.data.clone());
+        data.insert(RequestId::generate());
+
+        let memory = MemoryTracker::new(gateway.memory_limit);
+        let value = query
+            .execute_with(
+                gateway,
+                &data,
+                &memory,
+                &Mutex::new(Map::new()),
+                &Mutex::new(Vec::new()),
+            )
+            .await?;
+
+        *self
+            .state
+            .write()
+            .expect("Materialized view lock is poisoned.") = Some(Materialized {
+            refreshed_at: Instant::now(),
+            refreshed_at_system: SystemTime::now(),
+            value,
+        });
+
+        Ok(())
+    }
+}
+
+fn is_shared_data(data: &Data) -> bool {
+    data.contains_only(&[
+        TypeId::of::<RequestId>(),
+        TypeId::of::<IfNoneMatch>(),
+        TypeId::of::<RequestExtensions>(),
+    ])
+}
+
+fn is_query_operation(query_source: &str, operation_name: Option<&str>) -> bool {
+    let document = match graphql_parser::parse_query::<String>(query_source) {
+        Ok(document) => document,
+        _ => return true,
+    };
+
+    document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::Operation(operation) => Some(operation),
+            _ => None,
+        })
+        .filter(|operation| {
+            operation_name.is_none() || definition_name(operation) == operation_name
+        })
+        .all(|operation| {
+            matches!(
+                operation,
+                OperationDefinition::Query(_) | OperationDefinition::SelectionSet(_)
+            )
+        })
+}
+
+fn normalize_query(query_source: &str) -> String {
+    match graphql_parser::parse_query::<String>(query_source) {
+        Ok(document) => document.to_string(),
+        _ => query_source.to_owned(),
+    }
+}
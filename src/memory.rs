@@ -0,0 +1,51 @@
+use crate::query::{QueryError, QueryResult};
+use serde_json::Value;
+use std::mem::size_of;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[derive(Debug, Default)]
+pub(crate) struct MemoryTracker {
+    used: AtomicUsize,
+    limit: Option<usize>,
+}
+
+impl MemoryTracker {
+    pub(crate) fn new(limit: Option<usize>) -> Self {
+        MemoryTracker {
+            used: AtomicUsize::new(0),
+            limit,
+        }
+    }
+
+    pub(crate) fn used(&self) -> usize {
+        self.used.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    pub(crate) fn track(&self, value: &Value) -> QueryResult<()> {
+        let size = value_size(value);
+        let used = self.used.fetch_add(size, Ordering::SeqCst) + size;
+
+        match self.limit {
+            Some(limit) if used > limit => Err(QueryError::ResourceExhausted(used, limit)),
+            _ => Ok(()),
+        }
+    }
+}
+
+pub(crate) fn value_size(value: &Value) -> usize {
+    let size = match value {
+        Value::String(value) => value.len(),
+        Value::Array(values) => values.iter().map(value_size).sum(),
+        Value::Object(object) => object
+            .iter()
+            .map(|(key, value)| size_of::<String>() + key.len() + value_size(value))
+            .sum(),
+        _ => 0,
+    };
+
+    size_of::<Value>() + size
+}
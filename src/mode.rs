@@ -0,0 +1,49 @@
+use std::fmt;
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Mode {
+    #[default]
+    Normal,
+    ReadOnly,
+    Maintenance,
+}
+
+impl Mode {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Mode::Normal => "NORMAL",
+            Mode::ReadOnly => "READ_ONLY",
+            Mode::Maintenance => "MAINTENANCE",
+        }
+    }
+
+    fn default_message(&self) -> &'static str {
+        match self {
+            Mode::Normal => "",
+            Mode::ReadOnly => "Gateway is in read-only mode, mutations are not allowed.",
+            Mode::Maintenance => "Gateway is under maintenance.",
+        }
+    }
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ModeState {
+    pub(crate) mode: Mode,
+    pub(crate) message: Option<String>,
+    pub(crate) retry_after: Option<Duration>,
+}
+
+impl ModeState {
+    pub(crate) fn message(&self) -> String {
+        self.message
+            .clone()
+            .unwrap_or_else(|| self.mode.default_message().to_owned())
+    }
+}
@@ -0,0 +1,165 @@
+use crate::schema::{Type, TypeKind};
+
+const INTROSPECTION_TYPES: [&str; 8] = [
+    "__Schema",
+    "__Type",
+    "__Field",
+    "__InputValue",
+    "__EnumValue",
+    "__Directive",
+    "__TypeKind",
+    "__DirectiveLocation",
+];
+
+const BUILTIN_SCALARS: [&str; 5] = ["String", "Int", "Float", "Boolean", "ID"];
+
+pub const INJECTED_VARIABLE_PREFIX: &str = "__gql_gateway_";
+
+const BUILTIN_DIRECTIVES: [&str; 4] = ["skip", "include", "deprecated", "specifiedBy"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NamingViolation {
+    ReservedPrefix,
+    InvalidCharacters,
+    BuiltinScalarConflict,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamingError {
+    pub executor: String,
+    pub name: String,
+    pub violation: NamingViolation,
+}
+
+#[derive(Debug, Clone)]
+pub struct NamingPolicy {
+    pub reserved_prefixes: Vec<String>,
+    pub builtin_scalars: Vec<String>,
+}
+
+impl Default for NamingPolicy {
+    fn default() -> Self {
+        NamingPolicy {
+            reserved_prefixes: vec!["__".to_owned()],
+            builtin_scalars: BUILTIN_SCALARS.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+impl NamingPolicy {
+    pub fn reserved_prefix<T: Into<String>>(mut self, prefix: T) -> Self {
+        self.reserved_prefixes.push(prefix.into());
+        self
+    }
+
+    pub fn is_introspection_type(&self, name: &str) -> bool {
+        INTROSPECTION_TYPES.contains(&name)
+    }
+
+    pub fn is_builtin_scalar(&self, name: &str) -> bool {
+        self.builtin_scalars.iter().any(|scalar| scalar == name)
+    }
+
+    pub fn is_builtin_directive(&self, name: &str) -> bool {
+        BUILTIN_DIRECTIVES.contains(&name)
+    }
+
+    pub fn is_meta_field(&self, name: &str) -> bool {
+        name.starts_with("__")
+    }
+
+    pub fn is_reserved(&self, name: &str) -> bool {
+        self.reserved_prefixes
+            .iter()
+            .any(|prefix| name.starts_with(prefix.as_str()))
+    }
+
+    pub(crate) fn check_type(&self, executor: &str, schema_type: &Type) -> Vec<NamingError> {
+        let name = schema_type.name();
+
+        if self.is_introspection_type(name) {
+            return vec![];
+        }
+
+        let mut errors = vec![];
+        let mut push = |name: &str, violation| {
+            errors.push(NamingError {
+                executor: executor.to_owned(),
+                name: name.to_owned(),
+                violation,
+            })
+        };
+
+        if self.is_reserved(name) {
+            push(name, NamingViolation::ReservedPrefix);
+        } else if !is_valid_name(name) {
+            push(name, NamingViolation::InvalidCharacters);
+        }
+
+        if self.is_builtin_scalar(name) && schema_type.kind != TypeKind::Scalar {
+            push(name, NamingViolation::BuiltinScalarConflict);
+        }
+
+        let field_names = schema_type
+            .fields
+            .iter()
+            .flatten()
+            .map(|field| field.name.as_str())
+            .chain(
+                schema_type
+                    .input_fields
+                    .iter()
+                    .flatten()
+                    .map(|input_value| input_value.name.as_str()),
+            );
+
+        for field_name in field_names {
+            let key = format!("{}.{}", name, field_name);
+
+            if self.is_reserved(field_name) {
+                push(&key, NamingViolation::ReservedPrefix);
+            } else if !is_valid_name(field_name) {
+                push(&key, NamingViolation::InvalidCharacters);
+            }
+        }
+
+        errors
+    }
+}
+
+pub fn is_injected_variable(name: &str) -> bool {
+    name.starts_with(INJECTED_VARIABLE_PREFIX)
+}
+
+pub(crate) fn injected_variable<F: Fn(&str) -> bool>(name: &str, is_declared: F) -> String {
+    let base = format!("{}{}", INJECTED_VARIABLE_PREFIX, name);
+    let mut variable = base.clone();
+    let mut suffix = 0;
+
+    while is_declared(&variable) {
+        suffix += 1;
+        variable = format!("{}_{}", base, suffix);
+    }
+
+    variable
+}
+
+pub(crate) fn pascal_case(name: &str) -> String {
+    let mut chars = name.chars();
+
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        _ => String::new(),
+    }
+}
+
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    match chars.next() {
+        Some(c) if c == '_' || c.is_ascii_alphabetic() => {
+            chars.all(|c| c == '_' || c.is_ascii_alphanumeric())
+        }
+        _ => false,
+    }
+}
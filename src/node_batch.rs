@@ -0,0 +1,106 @@
+use crate::resilience::ExecutorFailure;
+use futures::lock::Mutex as AsyncMutex;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+type BatchKey = (String, String, String);
+
+#[derive(Default)]
+pub(crate) struct NodeBatch {
+    ids: Mutex<Vec<Value>>,
+    response: AsyncMutex<Option<Arc<Result<Value, ExecutorFailure>>>>,
+}
+
+#[derive(Default)]
+pub(crate) struct NodeBatcher(Mutex<HashMap<BatchKey, Arc<NodeBatch>>>);
+
+impl NodeBatcher {
+    pub(crate) fn enqueue(
+        &self,
+        executor: &str,
+        query_source: &str,
+        variables: &Map<String, Value>,
+        ids: &[Value],
+    ) -> (Arc<NodeBatch>, Vec<usize>) {
+        let key = (
+            executor.to_owned(),
+            query_source.to_owned(),
+            Value::Object(variables.clone()).to_string(),
+        );
+
+        let mut batches = self.0.lock().expect("Node batcher lock is poisoned.");
+        let batch = batches.entry(key).or_default().clone();
+        let mut batch_ids = batch.ids.lock().expect("Node batch lock is poisoned.");
+
+        let positions = ids
+            .iter()
+            .map(
+                |id| match batch_ids.iter().position(|batch_id| batch_id == id) {
+                    Some(position) => position,
+                    _ => {
+                        batch_ids.push(id.clone());
+                        batch_ids.len() - 1
+                    }
+                },
+            )
+            .collect();
+
+        drop(batch_ids);
+
+        (batch, positions)
+    }
+
+    pub(crate) async fn load<F, R>(
+        &self,
+        batch: &Arc<NodeBatch>,
+        fetch: F,
+    ) -> (Arc<Result<Value, ExecutorFailure>>, bool)
+    where
+        F: FnOnce(Vec<Value>) -> R,
+        R: Future<Output = Result<Value, ExecutorFailure>>,
+    {
+        YieldNow(false).await;
+
+        let mut response = batch.response.lock().await;
+
+        if let Some(response) = response.as_ref() {
+            return (response.clone(), false);
+        }
+
+        self.0
+            .lock()
+            .expect("Node batcher lock is poisoned.")
+            .retain(|_, pending| !Arc::ptr_eq(pending, batch));
+
+        let ids = batch
+            .ids
+            .lock()
+            .expect("Node batch lock is poisoned.")
+            .clone();
+
+        let fetched = Arc::new(fetch(ids).await);
+        *response = Some(fetched.clone());
+
+        (fetched, true)
+    }
+}
+
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            return Poll::Ready(());
+        }
+
+        self.0 = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
@@ -0,0 +1,118 @@
+use serde_json::Value;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const MAX_ENTRIES: usize = 10_000;
+
+type NodeCacheKey = (String, String, String, u64);
+
+#[derive(Clone)]
+pub(crate) struct NodeCache {
+    pub(crate) ttl: Duration,
+    entries: Arc<Mutex<HashMap<NodeCacheKey, (Instant, Value)>>>,
+}
+
+#[derive(Clone)]
+pub struct EntityInvalidator(pub(crate) NodeCache);
+
+impl EntityInvalidator {
+    pub fn invalidate<T: Into<String>, I: Into<String>>(&self, type_name: T, id: I) {
+        self.0
+            .invalidate(&type_name.into(), &Value::String(id.into()));
+    }
+}
+
+impl NodeCache {
+    pub(crate) fn new(ttl: Duration) -> Self {
+        NodeCache {
+            ttl,
+            entries: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub(crate) fn selection_hash(query_source: &str, variables: Option<&Value>) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        query_source.hash(&mut hasher);
+        variables.map(|v| v.to_string()).hash(&mut hasher);
+        hasher.finish()
+    }
+
+    pub(crate) fn get(
+        &self,
+        executor: &str,
+        type_name: &str,
+        id: &Value,
+        selection_hash: u64,
+    ) -> Option<Value> {
+        let key = (
+            executor.to_owned(),
+            type_name.to_owned(),
+            id.to_string(),
+            selection_hash,
+        );
+        let mut entries = self.entries.lock().expect("Node cache lock is poisoned.");
+
+        match entries.get(&key) {
+            Some((expires_at, value)) if *expires_at > Instant::now() => Some(value.clone()),
+            Some(_) => {
+                entries.remove(&key);
+                None
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) fn insert(
+        &self,
+        executor: &str,
+        type_name: &str,
+        id: &Value,
+        selection_hash: u64,
+        value: Value,
+        ttl: Duration,
+    ) {
+        if ttl == Duration::from_secs(0) {
+            return;
+        }
+
+        let key = (
+            executor.to_owned(),
+            type_name.to_owned(),
+            id.to_string(),
+            selection_hash,
+        );
+        let mut entries = self.entries.lock().expect("Node cache lock is poisoned.");
+
+        if entries.len() >= MAX_ENTRIES {
+            let now = Instant::now();
+            entries.retain(|_, (expires_at, _)| *expires_at > now);
+        }
+
+        while entries.len() >= MAX_ENTRIES && !entries.contains_key(&key) {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, (expires_at, _))| *expires_at)
+                .map(|(key, _)| key.clone());
+
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+
+        entries.insert(key, (Instant::now() + ttl, value));
+    }
+
+    pub(crate) fn invalidate(&self, type_name: &str, id: &Value) {
+        let id = id.to_string();
+
+        self.entries
+            .lock()
+            .expect("Node cache lock is poisoned.")
+            .retain(|(_, entry_type_name, entry_id, _), _| {
+                entry_type_name != type_name || *entry_id != id
+            });
+    }
+}
@@ -0,0 +1,184 @@
+use crate::builder::GatewayBuilder;
+use crate::gateway::{Gateway, GatewayError, GatewayResult};
+use crate::query::{ParsedQuery, QueryBuilder, QueryResult};
+use crate::schema::{Field, Schema, Type};
+use graphql_parser::schema;
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "change", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SchemaChange {
+    TypeAdded {
+        name: String,
+    },
+    TypeRemoved {
+        name: String,
+    },
+    TypeKindChanged {
+        name: String,
+        from: String,
+        to: String,
+    },
+    FieldAdded {
+        coordinate: String,
+    },
+    FieldRemoved {
+        coordinate: String,
+    },
+    FieldTypeChanged {
+        coordinate: String,
+        from: String,
+        to: String,
+    },
+}
+
+impl SchemaChange {
+    pub fn is_breaking(&self) -> bool {
+        !matches!(
+            self,
+            SchemaChange::TypeAdded { .. } | SchemaChange::FieldAdded { .. }
+        )
+    }
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaChange::TypeAdded { name } => write!(f, "Type {} was added", name),
+            SchemaChange::TypeRemoved { name } => write!(f, "Type {} was removed", name),
+            SchemaChange::TypeKindChanged { name, from, to } => {
+                write!(f, "Type {} changed from {} to {}", name, from, to)
+            }
+            SchemaChange::FieldAdded { coordinate } => write!(f, "Field {} was added", coordinate),
+            SchemaChange::FieldRemoved { coordinate } => {
+                write!(f, "Field {} was removed", coordinate)
+            }
+            SchemaChange::FieldTypeChanged {
+                coordinate,
+                from,
+                to,
+            } => write!(
+                f,
+                "Field {} changed type from {} to {}",
+                coordinate, from, to
+            ),
+        }
+    }
+}
+
+pub async fn compose(builder: GatewayBuilder<'_>) -> GatewayResult<Gateway<'_>> {
+    let gateway = builder.build().await?;
+    let errors = gateway
+        .executors_info()
+        .into_iter()
+        .filter_map(|info| Some((info.name, info.error?)))
+        .collect::<Vec<_>>();
+
+    if !errors.is_empty() {
+        return Err(GatewayError::CompositionFailed(errors));
+    }
+
+    Ok(gateway)
+}
+
+pub fn print_sdl(gateway: &Gateway<'_>) -> String {
+    gateway.to_sdl()
+}
+
+pub fn diff(previous: &Gateway<'_>, next: &Gateway<'_>) -> Vec<SchemaChange> {
+    let previous_types = types(&previous.schema.0);
+    let next_types = types(&next.schema.0);
+    let mut changes = vec![];
+
+    for (name, previous_type) in &previous_types {
+        let next_type = match next_types.get(name) {
+            Some(next_type) => next_type,
+            _ => {
+                changes.push(SchemaChange::TypeRemoved {
+                    name: name.to_string(),
+                });
+                continue;
+            }
+        };
+
+        if previous_type.kind != next_type.kind {
+            changes.push(SchemaChange::TypeKindChanged {
+                name: name.to_string(),
+                from: previous_type.kind.to_string(),
+                to: next_type.kind.to_string(),
+            });
+            continue;
+        }
+
+        let previous_fields = fields(previous_type);
+        let next_fields = fields(next_type);
+
+        for (field_name, previous_field) in &previous_fields {
+            let coordinate = format!("{}.{}", name, field_name);
+
+            match next_fields.get(field_name) {
+                Some(next_field) => {
+                    let from = type_ref(previous_field);
+                    let to = type_ref(next_field);
+
+                    if from != to {
+                        changes.push(SchemaChange::FieldTypeChanged {
+                            coordinate,
+                            from,
+                            to,
+                        });
+                    }
+                }
+                _ => changes.push(SchemaChange::FieldRemoved { coordinate }),
+            }
+        }
+
+        for field_name in next_fields.keys() {
+            if !previous_fields.contains_key(field_name) {
+                changes.push(SchemaChange::FieldAdded {
+                    coordinate: format!("{}.{}", name, field_name),
+                });
+            }
+        }
+    }
+
+    for name in next_types.keys() {
+        if !previous_types.contains_key(name) {
+            changes.push(SchemaChange::TypeAdded {
+                name: name.to_string(),
+            });
+        }
+    }
+
+    changes
+}
+
+pub fn check_query(gateway: &Gateway<'_>, source: &str) -> QueryResult<ParsedQuery> {
+    let query = QueryBuilder::new(source);
+
+    query.validate(gateway)?;
+    query.parse()
+}
+
+fn types(schema: &Schema) -> BTreeMap<&str, &Type> {
+    schema
+        .types
+        .iter()
+        .filter(|schema_type| !schema_type.name().starts_with("__"))
+        .map(|schema_type| (schema_type.name(), schema_type))
+        .collect()
+}
+
+fn fields(schema_type: &Type) -> BTreeMap<&str, &Field> {
+    schema_type
+        .fields
+        .iter()
+        .flatten()
+        .map(|field| (field.name.as_str(), field))
+        .collect()
+}
+
+fn type_ref(field: &Field) -> String {
+    schema::Type::from(field.field_type.clone()).to_string()
+}
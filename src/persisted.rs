@@ -0,0 +1,99 @@
+use crate::http::RequestExtensions;
+use crate::query::{QueryError, QueryResult};
+use crate::signature::sha256_hex;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Manifest {
+    Apollo { operations: Vec<ManifestOperation> },
+    Map(HashMap<String, String>),
+}
+
+#[derive(Deserialize)]
+struct ManifestOperation {
+    id: String,
+    body: String,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct PersistedQueries {
+    queries: HashMap<String, String>,
+    hashes: HashSet<String>,
+}
+
+impl PersistedQueries {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn query<T: Into<String>>(self, query: T) -> Self {
+        let query = query.into();
+        let id = sha256_hex(&query);
+
+        self.query_with_id(id, query)
+    }
+
+    pub fn query_with_id<I: Into<String>, T: Into<String>>(mut self, id: I, query: T) -> Self {
+        let query = query.into();
+
+        self.hashes.insert(sha256_hex(&query));
+        self.queries.insert(id.into(), query);
+        self
+    }
+
+    pub fn from_json(source: &str) -> Result<PersistedQueries, String> {
+        let manifest = serde_json::from_str(source).map_err(|e| e.to_string())?;
+        let operations = match manifest {
+            Manifest::Apollo { operations } => operations
+                .into_iter()
+                .map(|operation| (operation.id, operation.body))
+                .collect(),
+            Manifest::Map(queries) => queries.into_iter().collect::<Vec<(String, String)>>(),
+        };
+
+        Ok(operations
+            .into_iter()
+            .fold(PersistedQueries::new(), |queries, (id, query)| {
+                queries.query_with_id(id, query)
+            }))
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<PersistedQueries, String> {
+        let source = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+
+        PersistedQueries::from_json(&source)
+    }
+
+    pub fn len(&self) -> usize {
+        self.queries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queries.is_empty()
+    }
+
+    pub(crate) fn resolve(
+        &self,
+        query_source: &str,
+        extensions: Option<&RequestExtensions>,
+    ) -> QueryResult<Option<&str>> {
+        if !query_source.trim().is_empty() {
+            return if self.hashes.contains(&sha256_hex(query_source)) {
+                Ok(None)
+            } else {
+                Err(QueryError::QueryNotAllowed)
+            };
+        }
+
+        let id = extensions
+            .and_then(|extensions| extensions.0["persistedQuery"]["sha256Hash"].as_str())
+            .ok_or(QueryError::QueryNotAllowed)?;
+
+        self.queries
+            .get(id)
+            .map(|query| Some(query.as_str()))
+            .ok_or(QueryError::PersistedQueryNotFound)
+    }
+}
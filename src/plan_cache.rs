@@ -0,0 +1,277 @@
+use crate::ast::{rebind_document, rebind_fragment, rebind_selections, rebind_variable_definition};
+use crate::context::Context;
+use crate::planner::PlannerFlags;
+use crate::query::{ResolveInfo, RootPlans};
+use graphql_parser::query::{
+    Definition, Directive, Document, FragmentDefinition, OperationDefinition, Selection,
+    Value as AstValue,
+};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub struct PlanCacheStats {
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct PlanKey {
+    pub(crate) schema_hash: String,
+    pub(crate) version: Option<String>,
+    pub(crate) operation_name: Option<String>,
+    pub(crate) query: String,
+    pub(crate) flags: PlannerFlags,
+}
+
+pub(crate) struct CachedOperation {
+    pub(crate) document: Document<'static, String>,
+    pub(crate) literals: Vec<String>,
+    pub(crate) plan: Option<OperationPlan<'static>>,
+}
+
+pub(crate) struct OperationPlan<'a> {
+    pub(crate) fragments: HashMap<String, FragmentDefinition<'a, String>>,
+    pub(crate) selections: Vec<Selection<'a, String>>,
+    pub(crate) root: Option<RootPlan<'a>>,
+}
+
+impl OperationPlan<'_> {
+    pub(crate) fn rebind<'b>(&self) -> OperationPlan<'b> {
+        OperationPlan {
+            fragments: rebind_fragments(&self.fragments),
+            selections: rebind_selections(&self.selections),
+            root: self.root.as_ref().map(RootPlan::rebind),
+        }
+    }
+}
+
+pub(crate) enum RootPlan<'a> {
+    FastPath(String, ResolveInfo<'a>),
+    Executors(RootPlans<'a>),
+}
+
+impl RootPlan<'_> {
+    pub(crate) fn rebind<'b>(&self) -> RootPlan<'b> {
+        match self {
+            RootPlan::FastPath(executor, resolve_info) => {
+                RootPlan::FastPath(executor.clone(), rebind_resolve_info(resolve_info))
+            }
+            RootPlan::Executors((plans, fan_out_fields)) => RootPlan::Executors((
+                plans
+                    .iter()
+                    .map(|(executor, resolve_info)| {
+                        (executor.clone(), rebind_resolve_info(resolve_info))
+                    })
+                    .collect(),
+                rebind_selections(fan_out_fields),
+            )),
+        }
+    }
+}
+
+#[derive(Default)]
+struct PlanCacheState {
+    entries: HashMap<PlanKey, (Arc<CachedOperation>, u64)>,
+    recency: BTreeMap<u64, PlanKey>,
+    clock: u64,
+    hits: u64,
+    misses: u64,
+}
+
+impl PlanCacheState {
+    fn remove(&mut self, key: &PlanKey) {
+        if let Some((_, used_at)) = self.entries.remove(key) {
+            self.recency.remove(&used_at);
+        }
+    }
+}
+
+pub(crate) struct PlanCache {
+    capacity: usize,
+    state: Mutex<PlanCacheState>,
+}
+
+impl PlanCache {
+    pub(crate) fn new(capacity: usize) -> Self {
+        PlanCache {
+            capacity,
+            state: Mutex::new(PlanCacheState::default()),
+        }
+    }
+
+    pub(crate) fn get(&self, key: &PlanKey) -> Option<Arc<CachedOperation>> {
+        let mut state = self.state.lock().expect("Plan cache lock is poisoned.");
+        state.clock += 1;
+        let clock = state.clock;
+
+        let (operation, used_at) = match state.entries.get_mut(key) {
+            Some((operation, used_at)) => {
+                let previous = *used_at;
+                *used_at = clock;
+                (operation.clone(), previous)
+            }
+            _ => {
+                state.misses += 1;
+                return None;
+            }
+        };
+
+        state.recency.remove(&used_at);
+        state.recency.insert(clock, key.clone());
+        state.hits += 1;
+
+        Some(operation)
+    }
+
+    pub(crate) fn insert(&self, key: PlanKey, operation: CachedOperation) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut state = self.state.lock().expect("Plan cache lock is poisoned.");
+        state.remove(&key);
+
+        while state.entries.len() >= self.capacity {
+            let oldest = match state.recency.values().next() {
+                Some(oldest) => oldest.clone(),
+                _ => break,
+            };
+
+            state.remove(&oldest);
+        }
+
+        state.clock += 1;
+        let used_at = state.clock;
+
+        state.recency.insert(used_at, key.clone());
+        state.entries.insert(key, (Arc::new(operation), used_at));
+    }
+
+    pub(crate) fn retain(&self, schema_hashes: &[&str]) {
+        let mut state = self.state.lock().expect("Plan cache lock is poisoned.");
+        let stale = state
+            .entries
+            .keys()
+            .filter(|key| !schema_hashes.contains(&key.schema_hash.as_str()))
+            .cloned()
+            .collect::<Vec<PlanKey>>();
+
+        for key in stale {
+            state.remove(&key);
+        }
+    }
+
+    pub(crate) fn stats(&self) -> PlanCacheStats {
+        let state = self.state.lock().expect("Plan cache lock is poisoned.");
+
+        PlanCacheStats {
+            entries: state.entries.len(),
+            hits: state.hits,
+            misses: state.misses,
+        }
+    }
+}
+
+pub(crate) type PlanParts<'p, 'a> = (
+    &'p HashMap<String, FragmentDefinition<'a, String>>,
+    &'p [Selection<'a, String>],
+    Option<&'p RootPlan<'a>>,
+);
+
+pub(crate) fn cached_operation(
+    document: &Document<'_, String>,
+    literals: &[String],
+    plan: Option<PlanParts<'_, '_>>,
+) -> CachedOperation {
+    CachedOperation {
+        document: rebind_document(document),
+        literals: literals.to_vec(),
+        plan: plan.map(|(fragments, selections, root)| OperationPlan {
+            fragments: rebind_fragments(fragments),
+            selections: rebind_selections(selections),
+            root: root.map(RootPlan::rebind),
+        }),
+    }
+}
+
+pub(crate) fn is_plan_stable(context: &Context<'_, '_>, document: &Document<'_, String>) -> bool {
+    if context.gateway.authorizer.is_some() || !context.gateway.argument_injectors.is_empty() {
+        return false;
+    }
+
+    document
+        .definitions
+        .iter()
+        .all(|definition| match definition {
+            Definition::Fragment(fragment) => {
+                is_stable_selections(&fragment.selection_set.items)
+                    && is_stable_directives(&fragment.directives)
+            }
+            Definition::Operation(OperationDefinition::SelectionSet(selection_set)) => {
+                is_stable_selections(&selection_set.items)
+            }
+            Definition::Operation(OperationDefinition::Query(query)) => {
+                is_stable_selections(&query.selection_set.items)
+            }
+            Definition::Operation(OperationDefinition::Mutation(mutation)) => {
+                is_stable_selections(&mutation.selection_set.items)
+            }
+            Definition::Operation(OperationDefinition::Subscription(subscription)) => {
+                is_stable_selections(&subscription.selection_set.items)
+            }
+        })
+}
+
+fn is_stable_selections(selections: &[Selection<'_, String>]) -> bool {
+    selections.iter().all(|selection| match selection {
+        Selection::Field(field) => {
+            is_stable_directives(&field.directives)
+                && is_stable_selections(&field.selection_set.items)
+        }
+        Selection::FragmentSpread(fragment_spread) => {
+            is_stable_directives(&fragment_spread.directives)
+        }
+        Selection::InlineFragment(inline_fragment) => {
+            is_stable_directives(&inline_fragment.directives)
+                && is_stable_selections(&inline_fragment.selection_set.items)
+        }
+    })
+}
+
+fn is_stable_directives(directives: &[Directive<'_, String>]) -> bool {
+    directives.iter().all(|directive| {
+        (directive.name != "skip" && directive.name != "include")
+            || directive
+                .arguments
+                .iter()
+                .all(|(_, value)| !matches!(value, AstValue::Variable(_)))
+    })
+}
+
+fn rebind_resolve_info<'b>(resolve_info: &ResolveInfo<'_>) -> ResolveInfo<'b> {
+    ResolveInfo {
+        selections: rebind_selections(&resolve_info.selections),
+        fragments: rebind_fragments(&resolve_info.fragments),
+        variable_definitions: resolve_info
+            .variable_definitions
+            .iter()
+            .map(|(name, variable_definition)| {
+                (
+                    name.clone(),
+                    rebind_variable_definition(variable_definition),
+                )
+            })
+            .collect(),
+    }
+}
+
+fn rebind_fragments<'b>(
+    fragments: &HashMap<String, FragmentDefinition<'_, String>>,
+) -> HashMap<String, FragmentDefinition<'b, String>> {
+    fragments
+        .iter()
+        .map(|(name, fragment)| (name.clone(), rebind_fragment(fragment)))
+        .collect()
+}
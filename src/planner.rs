@@ -0,0 +1,356 @@
+use crate::context::Context;
+use crate::query::{
+    mutation_steps, node_document, resolve_executor, resolve_executors, root_document, root_plans,
+    QueryResult, ResolveInfo,
+};
+use crate::schema::Type;
+use graphql_parser::query::{FragmentDefinition, Selection, TypeCondition};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PlannerFlags {
+    pub batch_nodes: bool,
+    pub parallel_root: bool,
+    pub strict_validation: bool,
+    pub allow_duplicate_fragments: bool,
+    pub fast_path: bool,
+}
+
+impl Default for PlannerFlags {
+    fn default() -> Self {
+        PlannerFlags {
+            batch_nodes: true,
+            parallel_root: true,
+            strict_validation: false,
+            allow_duplicate_fragments: false,
+            fast_path: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PlanStepKind {
+    Root,
+    Node,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlanStep {
+    pub id: usize,
+    pub kind: PlanStepKind,
+    pub executor: String,
+    #[serde(rename = "typeName")]
+    pub type_name: String,
+    pub path: Vec<String>,
+    pub document: String,
+    #[serde(rename = "dependsOn")]
+    pub depends_on: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QueryPlan {
+    #[serde(rename = "parallelRoot")]
+    pub parallel_root: bool,
+    pub steps: Vec<PlanStep>,
+}
+
+impl QueryPlan {
+    pub fn executors(&self) -> Vec<&str> {
+        let mut executors = Vec::new();
+
+        for step in &self.steps {
+            if !executors.contains(&step.executor.as_str()) {
+                executors.push(step.executor.as_str());
+            }
+        }
+
+        executors
+    }
+
+    pub fn dependents(&self, id: usize) -> Vec<&PlanStep> {
+        self.steps
+            .iter()
+            .filter(|step| step.depends_on == Some(id))
+            .collect()
+    }
+}
+
+#[derive(Default)]
+struct Shape(HashMap<String, (usize, Shape)>);
+
+impl Shape {
+    fn new<'a>(
+        selections: &[Selection<'a, String>],
+        fragments: &HashMap<String, FragmentDefinition<'a, String>>,
+        step: usize,
+    ) -> Shape {
+        let mut shape = Shape::default();
+
+        for selection in selections {
+            match selection {
+                Selection::Field(field) => {
+                    let field_name = field.alias.as_ref().unwrap_or(&field.name);
+                    let field_shape = Shape::new(&field.selection_set.items, fragments, step);
+
+                    shape.0.insert(field_name.clone(), (step, field_shape));
+                }
+                Selection::FragmentSpread(fragment_spread) => {
+                    if let Some(fragment) = fragments.get(&fragment_spread.fragment_name) {
+                        shape.merge(Shape::new(&fragment.selection_set.items, fragments, step));
+                    }
+                }
+                Selection::InlineFragment(inline_fragment) => {
+                    shape.merge(Shape::new(
+                        &inline_fragment.selection_set.items,
+                        fragments,
+                        step,
+                    ));
+                }
+            }
+        }
+
+        shape
+    }
+
+    fn merge(&mut self, other: Shape) {
+        self.0.extend(other.0);
+    }
+
+    fn to_value(&self) -> Value {
+        self.0
+            .iter()
+            .map(|(key, (_, shape))| {
+                let value = if shape.0.is_empty() {
+                    Value::Null
+                } else {
+                    shape.to_value()
+                };
+
+                (key.clone(), value)
+            })
+            .collect::<Map<String, Value>>()
+            .into()
+    }
+}
+
+struct Planner<'c, 'a, 'b> {
+    context: &'c Context<'a, 'b>,
+    steps: Vec<PlanStep>,
+}
+
+impl<'a> Planner<'_, 'a, '_> {
+    fn push(
+        &mut self,
+        executor: String,
+        object_type: &Type,
+        path: &[String],
+        depends_on: Option<usize>,
+        resolve_info: ResolveInfo<'a>,
+        shape: &mut Shape,
+    ) {
+        let id = self.steps.len();
+        shape.merge(Shape::new(
+            &resolve_info.selections,
+            &resolve_info.fragments,
+            id,
+        ));
+
+        let (kind, document) = match depends_on {
+            Some(_) => (
+                PlanStepKind::Node,
+                node_document(self.context, &executor, object_type, resolve_info),
+            ),
+            _ => (
+                PlanStepKind::Root,
+                root_document(self.context, &executor, object_type, resolve_info),
+            ),
+        };
+
+        self.steps.push(PlanStep {
+            id,
+            kind,
+            executor,
+            type_name: object_type.name().to_owned(),
+            path: path.to_vec(),
+            document,
+            depends_on,
+        });
+    }
+
+    fn walk(
+        &mut self,
+        object_type: &Type,
+        shape: &mut Shape,
+        provider: Option<usize>,
+        path: &[String],
+        selections: &[Selection<'a, String>],
+    ) -> QueryResult<()> {
+        let context = self.context;
+
+        if selections.is_empty() {
+            return Ok(());
+        }
+
+        if let (Some(provider), true) = (provider, object_type.is_node()) {
+            let data = shape.to_value();
+
+            for executor in resolve_executors(context, object_type, Some(&data), selections)? {
+                let resolve_info =
+                    resolve_executor(context, object_type, selections.to_vec(), executor.clone())?;
+
+                if resolve_info.selections.is_empty() {
+                    continue;
+                }
+
+                self.push(
+                    executor,
+                    object_type,
+                    path,
+                    Some(provider),
+                    resolve_info,
+                    shape,
+                );
+            }
+        }
+
+        for selection in selections {
+            if !context.is_included(selection) {
+                continue;
+            }
+
+            let (object_type, items) = match selection {
+                Selection::Field(field) => {
+                    if context.gateway.naming_policy.is_meta_field(&field.name)
+                        || !context.is_authorized(object_type, &field.name)
+                    {
+                        continue;
+                    }
+
+                    let field_type = match context.field_object_type(object_type, &field.name) {
+                        Some((_, field_type)) => field_type,
+                        _ => continue,
+                    };
+
+                    let field_name = field.alias.as_ref().unwrap_or(&field.name);
+                    let (field_provider, field_shape) = match shape.0.get_mut(field_name) {
+                        Some((field_provider, field_shape)) => (*field_provider, field_shape),
+                        _ => continue,
+                    };
+
+                    let mut field_path = path.to_vec();
+                    field_path.push(field_name.clone());
+
+                    self.walk(
+                        field_type,
+                        field_shape,
+                        Some(field_provider),
+                        &field_path,
+                        &field.selection_set.items,
+                    )?;
+
+                    continue;
+                }
+                Selection::FragmentSpread(fragment_spread) => {
+                    let fragment = match context.fragments.get(&fragment_spread.fragment_name) {
+                        Some(fragment) => fragment,
+                        _ => continue,
+                    };
+
+                    let TypeCondition::On(type_condition) = &fragment.type_condition;
+
+                    (type_condition.as_str(), &fragment.selection_set.items)
+                }
+                Selection::InlineFragment(inline_fragment) => {
+                    match inline_fragment.type_condition.as_ref() {
+                        Some(TypeCondition::On(type_condition)) => (
+                            type_condition.as_str(),
+                            &inline_fragment.selection_set.items,
+                        ),
+                        _ => (object_type.name(), &inline_fragment.selection_set.items),
+                    }
+                }
+            };
+
+            if let Some(object_type) = context.object(object_type) {
+                self.walk(object_type, shape, provider, path, items)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) fn plan_operation<'a>(
+    context: &Context<'a, '_>,
+    object_type: &Type,
+    selections: &[Selection<'a, String>],
+) -> QueryResult<QueryPlan> {
+    let mut planner = Planner {
+        context,
+        steps: Vec::new(),
+    };
+    let mut shape = Shape::default();
+
+    if object_type.name() == "Mutation" {
+        for (selection, executors) in mutation_steps(context, object_type, selections)? {
+            let selections = std::slice::from_ref(selection);
+
+            for executor in executors {
+                let resolve_info =
+                    resolve_executor(context, object_type, selections.to_vec(), executor.clone())?;
+
+                if resolve_info.selections.is_empty() {
+                    continue;
+                }
+
+                planner.push(executor, object_type, &[], None, resolve_info, &mut shape);
+            }
+
+            planner.walk(object_type, &mut shape, None, &[], selections)?;
+        }
+
+        return Ok(QueryPlan {
+            parallel_root: false,
+            steps: planner.steps,
+        });
+    }
+
+    let (mut plans, fan_out_fields) = root_plans(context, object_type, selections)?;
+
+    for selection in fan_out_fields {
+        let field = match &selection {
+            Selection::Field(field) => field,
+            _ => continue,
+        };
+
+        for executor in context
+            .fan_out_owners(object_type, &field.name)
+            .unwrap_or_default()
+        {
+            let resolve_info = resolve_executor(
+                context,
+                object_type,
+                vec![selection.clone()],
+                executor.clone(),
+            )?;
+            plans.push((executor, resolve_info));
+        }
+    }
+
+    for (executor, resolve_info) in plans {
+        if resolve_info.selections.is_empty() {
+            continue;
+        }
+
+        planner.push(executor, object_type, &[], None, resolve_info, &mut shape);
+    }
+
+    planner.walk(object_type, &mut shape, None, &[], selections)?;
+
+    Ok(QueryPlan {
+        parallel_root: context.flags().parallel_root,
+        steps: planner.steps,
+    })
+}
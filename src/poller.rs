@@ -0,0 +1,181 @@
+use crate::executor_info::schema_hash;
+use crate::gateway::{Gateway, GatewayResult};
+use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use futures::future::BoxFuture;
+use futures_timer::Delay;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaChanged {
+    pub executor: String,
+    pub previous_hash: Option<String>,
+    pub hash: Option<String>,
+}
+
+struct HandleState<'a> {
+    gateway: RwLock<Arc<Gateway<'a>>>,
+    subscribers: Mutex<Vec<UnboundedSender<SchemaChanged>>>,
+}
+
+#[derive(Clone)]
+pub struct GatewayHandle<'a>(Arc<HandleState<'a>>);
+
+impl<'a> GatewayHandle<'a> {
+    pub fn new(gateway: Gateway<'a>) -> Self {
+        GatewayHandle(Arc::new(HandleState {
+            gateway: RwLock::new(Arc::new(gateway)),
+            subscribers: Mutex::new(Vec::new()),
+        }))
+    }
+
+    pub fn load(&self) -> Arc<Gateway<'a>> {
+        self.0
+            .gateway
+            .read()
+            .expect("Gateway handle lock is poisoned.")
+            .clone()
+    }
+
+    pub fn schema_changes(&self) -> UnboundedReceiver<SchemaChanged> {
+        let (sender, receiver) = unbounded();
+
+        self.0
+            .subscribers
+            .lock()
+            .expect("Gateway handle lock is poisoned.")
+            .push(sender);
+
+        receiver
+    }
+
+    pub async fn poll(&self) -> Vec<SchemaChanged> {
+        let mut gateway = Gateway::clone(&self.load());
+        let mut names = gateway
+            .executors
+            .keys()
+            .chain(
+                gateway
+                    .lazy_executors
+                    .iter()
+                    .filter(|(_, lazy)| lazy.is_initialized())
+                    .map(|(name, _)| name),
+            )
+            .cloned()
+            .collect::<Vec<String>>();
+        names.sort();
+
+        let mut changes = Vec::new();
+
+        for name in names {
+            let previous_hash = gateway.introspections.get(&name).map(schema_hash);
+
+            if gateway.pull(name.as_str()).await.is_err() {
+                continue;
+            }
+
+            let hash = gateway.introspections.get(&name).map(schema_hash);
+
+            if hash != previous_hash {
+                changes.push(SchemaChanged {
+                    executor: name,
+                    previous_hash,
+                    hash,
+                });
+            }
+        }
+
+        self.swap(gateway, &changes);
+
+        changes
+    }
+
+    pub fn rollback(&self, n: usize) -> GatewayResult<Vec<SchemaChanged>> {
+        self.restore(|gateway| gateway.rollback(n))
+    }
+
+    pub fn rollback_to(&self, hash: &str) -> GatewayResult<Vec<SchemaChanged>> {
+        self.restore(|gateway| gateway.rollback_to(hash))
+    }
+
+    fn restore<F>(&self, f: F) -> GatewayResult<Vec<SchemaChanged>>
+    where
+        F: FnOnce(&mut Gateway<'a>) -> GatewayResult<()>,
+    {
+        let previous = self.load();
+        let mut gateway = Gateway::clone(&previous);
+        f(&mut gateway)?;
+
+        let mut names = previous
+            .introspections
+            .keys()
+            .chain(gateway.introspections.keys())
+            .cloned()
+            .collect::<Vec<String>>();
+        names.sort();
+        names.dedup();
+
+        let changes = names
+            .into_iter()
+            .filter_map(|name| {
+                let previous_hash = previous.introspections.get(&name).map(schema_hash);
+                let hash = gateway.introspections.get(&name).map(schema_hash);
+
+                if hash == previous_hash {
+                    return None;
+                }
+
+                Some(SchemaChanged {
+                    executor: name,
+                    previous_hash,
+                    hash,
+                })
+            })
+            .collect::<Vec<SchemaChanged>>();
+
+        self.swap(gateway, &changes);
+
+        Ok(changes)
+    }
+
+    fn swap(&self, gateway: Gateway<'a>, changes: &[SchemaChanged]) {
+        *self
+            .0
+            .gateway
+            .write()
+            .expect("Gateway handle lock is poisoned.") = Arc::new(gateway);
+
+        self.0
+            .subscribers
+            .lock()
+            .expect("Gateway handle lock is poisoned.")
+            .retain(|subscriber| {
+                changes
+                    .iter()
+                    .all(|change| subscriber.unbounded_send(change.clone()).is_ok())
+            });
+    }
+}
+
+impl Gateway<'static> {
+    pub fn spawn_poller<S>(self, interval: Duration, spawn: S) -> GatewayHandle<'static>
+    where
+        S: FnOnce(BoxFuture<'static, ()>),
+    {
+        let handle = GatewayHandle::new(self);
+        let state = Arc::downgrade(&handle.0);
+
+        spawn(Box::pin(async move {
+            loop {
+                Delay::new(interval).await;
+
+                match state.upgrade() {
+                    Some(state) => GatewayHandle(state).poll().await,
+                    _ => break,
+                };
+            }
+        }));
+
+        handle
+    }
+}
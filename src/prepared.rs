@@ -0,0 +1,42 @@
+use crate::gateway::Gateway;
+use crate::literals::preserve_literals;
+use crate::plan_cache::{cached_operation, CachedOperation, PlanCache, PlanCacheStats};
+use crate::query::{find_fragments, QueryBuilder, QueryResult};
+use std::sync::Arc;
+
+const PREPARED_PLANS: usize = 8;
+
+#[derive(Clone)]
+pub struct PreparedQuery {
+    pub(crate) query_source: String,
+    pub(crate) operation: Arc<CachedOperation>,
+    pub(crate) plans: Arc<PlanCache>,
+}
+
+impl PreparedQuery {
+    pub fn prepare<T: Into<String>>(gateway: &Gateway<'_>, source: T) -> QueryResult<Self> {
+        let source = source.into();
+        let (query_source, literals) = preserve_literals(&source);
+        let document = graphql_parser::parse_query::<String>(&query_source)?;
+
+        find_fragments(&document, gateway.planner_flags)?;
+
+        Ok(PreparedQuery {
+            query_source: source,
+            operation: Arc::new(cached_operation(&document, &literals, None)),
+            plans: Arc::new(PlanCache::new(PREPARED_PLANS)),
+        })
+    }
+
+    pub fn query(&self) -> QueryBuilder {
+        QueryBuilder::from_prepared(self)
+    }
+
+    pub fn source(&self) -> &str {
+        &self.query_source
+    }
+
+    pub fn plan_cache_stats(&self) -> PlanCacheStats {
+        self.plans.stats()
+    }
+}
@@ -1,23 +1,67 @@
+use crate::apollo_tracing::ApolloTracing;
+use crate::argument_rules::validate_arguments;
+use crate::ast::rebind_document;
+use crate::authorization::UnauthorizedField;
 use crate::context::Context;
 use crate::data::Data;
-use crate::gateway::Gateway;
-use crate::schema::Type;
-use futures::future::{BoxFuture, FutureExt};
+use crate::diagnostics::{InflightGuard, RequestPhase};
+use crate::directive::{resolve_directives, GatewayDirective};
+use crate::entity::{EntityStrategy, NodeLookup};
+use crate::etag::{apply_etag, IfNoneMatch};
+use crate::executor_error::{
+    downstream_message, executor_errors, rewrite_node_paths, ExecutorError,
+};
+use crate::fan_out::MergeStrategy;
+use crate::fast_path::single_executor_plan;
+use crate::field_merge::{merge_fragments, merge_selections};
+use crate::gateway::{Gateway, GatewaySchema};
+use crate::http::{IncrementalPayload, QueryResponse, RequestExtensions};
+use crate::id_codec::{decode_arguments, decode_variables, encode_ids};
+use crate::incremental::execute_stream;
+use crate::injection::inject_arguments;
+use crate::limits::{
+    validate_complexity, validate_depth, validate_introspection, INTROSPECTION_FIELDS,
+};
+use crate::literals::{literal_value, preserve_literals};
+use crate::materialized::MaterializedView;
+use crate::memory::MemoryTracker;
+use crate::mode::{Mode, ModeState};
+use crate::naming::injected_variable;
+use crate::node_batch::NodeBatcher;
+use crate::node_cache::NodeCache;
+use crate::plan_cache::{cached_operation, is_plan_stable, OperationPlan, PlanKey, RootPlan};
+use crate::planner::{plan_operation, PlannerFlags, QueryPlan};
+use crate::prepared::PreparedQuery;
+use crate::request_context::RequestContext;
+use crate::request_id::RequestId;
+use crate::resilience::is_executor_error;
+use crate::response_cache::RequestCache;
+use crate::schema::{Type, TypeKind};
+use crate::signature::{sha256_hex, stats_report_key, OperationUsage};
+use crate::static_fields::{is_static_field, static_field_value};
+use crate::variables::{
+    coerce_variables, validate_declared_variables, validate_variables, with_default_variables,
+};
+use futures::future::{self, BoxFuture, FutureExt};
+use futures::stream::{self, BoxStream, StreamExt, TryStreamExt};
 use graphql_parser::query::{
     Definition, Document, Field, FragmentDefinition, InlineFragment, Mutation, OperationDefinition,
-    ParseError as QueryParseError, Query, Selection, SelectionSet, Type as AstType, TypeCondition,
+    ParseError as QueryParseError, Query, Selection, SelectionSet, TypeCondition,
     Value as AstValue, VariableDefinition,
 };
 use graphql_parser::Pos;
-use serde_json::{Map, Value};
+use serde_json::{json, Map, Value};
 use std::any::Any;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 #[derive(Debug, Clone)]
-struct ResolveInfo<'a> {
-    selections: Vec<Selection<'a, String>>,
-    fragments: HashMap<String, FragmentDefinition<'a, String>>,
-    variable_definitions: HashMap<String, VariableDefinition<'a, String>>,
+pub(crate) struct ResolveInfo<'a> {
+    pub(crate) selections: Vec<Selection<'a, String>>,
+    pub(crate) fragments: HashMap<String, FragmentDefinition<'a, String>>,
+    pub(crate) variable_definitions: HashMap<String, VariableDefinition<'a, String>>,
 }
 
 #[derive(Debug)]
@@ -35,34 +79,105 @@ pub enum QueryError {
     FieldNotFound(String, String),
     #[error("Cannot get field data \"{1}\" on type \"{0}\".")]
     FieldDataNotFound(String, String),
+    #[error("Not authorized to query field \"{1}\" on type \"{0}\".")]
+    Forbidden(String, String),
     #[error("Cannot query field \"id\" on type \"{0}\".")]
     FieldIdNotFound(String),
     #[error("\"__typename\" must be an existing string")]
     TypeNameNotExists(String),
-    #[error("Missing type condition on inline fragment.")]
-    MissingTypeConditionInlineFragment,
+    #[error("Variable \"${0}\" of required type \"{1}\" was not provided.")]
+    VariableNotProvided(String, String),
+    #[error("Variable \"${0}\" is not defined by the operation.")]
+    UnknownVariable(String),
+    #[error("Invalid value for argument \"{1}\" on field \"{0}\": {2}.")]
+    InvalidArgument(String, String, String),
+    #[error("Variables must be an object.")]
+    InvalidVariables,
+    #[error("Variable \"${0}\" got invalid value {1}; {2}")]
+    InvalidVariableValue(String, String, String),
+    #[error("Must provide operation name if query contains multiple operations.")]
+    OperationNameRequired,
+    #[error("Unknown operation named \"{0}\".")]
+    UnknownOperation(String),
+    #[error("There can be only one operation named \"{0}\".")]
+    DuplicateOperation(String),
     #[error("Unknown fragment \"{0}\".")]
     UnknownFragment(String),
+    #[error("There can be only one fragment named \"{0}\".")]
+    DuplicateFragment(String, Pos),
+    #[error("Fields \"{0}\" conflict because they have differing names or arguments.")]
+    FieldsConflict(String),
+    #[error("Unknown entity type \"{0}\".")]
+    UnknownEntityType(String),
     #[error("Unknown executor \"{0}\".")]
     UnknownExecutor(String),
+    #[error("Unknown version \"{0}\".")]
+    UnknownVersion(String),
     #[error("Invalid executor response")]
     InvalidExecutorResponse,
     #[error("Executor error: {0}")]
     Executor(Value),
+    #[error("{}", downstream_message(.0))]
+    Downstream(Vec<ExecutorError>),
+    #[error("{1}")]
+    Transport(String, String),
+    #[error("Executor \"{0}\" timed out after {1}ms")]
+    ExecutorTimeout(String, u64),
+    #[error("Executor \"{0}\" is unavailable, its circuit breaker is open.")]
+    CircuitOpen(String),
     #[error("Parse error: {0}")]
     QueryParse(QueryParseError),
     #[error("Query errors.")]
     Errors(Vec<QueryPosError>),
+    #[error("{1}")]
+    Unavailable(Mode, String, Option<u64>),
+    #[error("Request exceeded the memory limit of {1} bytes ({0} bytes used).")]
+    ResourceExhausted(usize, usize),
+    #[error("Query depth {0} exceeds the maximum depth of {1}.")]
+    MaxDepthExceeded(usize, usize),
+    #[error("Query complexity {0} exceeds the maximum complexity of {1}.")]
+    MaxComplexityExceeded(usize, usize),
+    #[error("Introspection is disabled.")]
+    IntrospectionDisabled,
+    #[error("PersistedQueryNotFound")]
+    PersistedQueryNotFound,
+    #[error("Query is not in the persisted query allowlist.")]
+    QueryNotAllowed,
+    #[error("Query plan is stale, plan the operation again.")]
+    StalePlan,
+    #[error("Directive \"@{0}\" failed: {1}")]
+    Directive(String, String),
+    #[error("Cannot return null for non-nullable field \"{0}.{1}\".")]
+    NonNullField(String, String, Vec<Value>),
     #[error("{0}")]
     Custom(String),
 }
 
+impl QueryError {
+    pub(crate) fn path(&self) -> Option<&[Value]> {
+        match self {
+            QueryError::NonNullField(_, _, path) => Some(path),
+            _ => None,
+        }
+    }
+}
+
 impl From<QueryParseError> for QueryError {
     fn from(e: QueryParseError) -> QueryError {
         QueryError::QueryParse(e)
     }
 }
 
+impl From<ModeState> for QueryError {
+    fn from(state: ModeState) -> QueryError {
+        QueryError::Unavailable(
+            state.mode,
+            state.message(),
+            state.retry_after.map(|retry_after| retry_after.as_secs()),
+        )
+    }
+}
+
 impl From<String> for QueryError {
     fn from(e: String) -> QueryError {
         QueryError::Custom(e)
@@ -71,11 +186,27 @@ impl From<String> for QueryError {
 
 pub type QueryResult<T> = Result<T, QueryError>;
 
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParsedQuery {
+    #[serde(rename = "operationType")]
+    pub operation_type: String,
+    #[serde(rename = "operationName")]
+    pub operation_name: Option<String>,
+    pub variables: Vec<String>,
+    pub fragments: Vec<String>,
+    #[serde(rename = "rootFields")]
+    pub root_fields: Vec<String>,
+}
+
+#[derive(Clone)]
 pub struct QueryBuilder {
     pub(crate) query_source: String,
     pub(crate) operation_name: Option<String>,
     pub(crate) variables: Option<Value>,
     pub(crate) ctx_data: Option<Data>,
+    pub(crate) version: Option<String>,
+    pub(crate) response_cache: bool,
+    pub(crate) prepared: Option<PreparedQuery>,
 }
 
 impl QueryBuilder {
@@ -85,6 +216,16 @@ impl QueryBuilder {
             operation_name: None,
             variables: None,
             ctx_data: None,
+            version: None,
+            response_cache: true,
+            prepared: None,
+        }
+    }
+
+    pub fn from_prepared(prepared: &PreparedQuery) -> Self {
+        QueryBuilder {
+            prepared: Some(prepared.clone()),
+            ..QueryBuilder::new(prepared.query_source.clone())
         }
     }
 
@@ -93,11 +234,29 @@ impl QueryBuilder {
         self
     }
 
-    pub fn variables(mut self, e: Value) -> Self {
-        self.variables = Some(e);
+    pub fn variables<T: Into<Value>>(mut self, e: T) -> Self {
+        self.variables = Some(e.into());
+        self
+    }
+
+    pub fn version<T: Into<String>>(mut self, e: T) -> Self {
+        self.version = Some(e.into());
+        self
+    }
+
+    pub fn response_cache(mut self, enabled: bool) -> Self {
+        self.response_cache = enabled;
         self
     }
 
+    pub fn extensions<T: Into<Value>>(self, e: T) -> Self {
+        self.data(RequestExtensions(e.into()))
+    }
+
+    pub fn request_context(self, e: RequestContext) -> Self {
+        self.data(e)
+    }
+
     pub fn data<T: Any + Sync + Send>(mut self, e: T) -> Self {
         if let Some(ctx_data) = &mut self.ctx_data {
             ctx_data.insert(e);
@@ -110,76 +269,736 @@ impl QueryBuilder {
     }
 
     pub async fn execute(&self, gateway: &Gateway<'_>) -> QueryResult<Value> {
-        let document = graphql_parser::parse_query::<String>(&self.query_source)?;
+        self.execute_response(gateway).await.result
+    }
 
-        let fragments = document
-            .definitions
-            .iter()
-            .filter_map(|definition| match definition {
-                Definition::Fragment(fragment) => Some((fragment.name.clone(), fragment.clone())),
-                _ => None,
-            })
-            .collect::<HashMap<String, FragmentDefinition<'_, String>>>();
+    pub async fn execute_response(&self, gateway: &Gateway<'_>) -> QueryResponse {
+        let persisted_queries = match &gateway.persisted_queries {
+            Some(persisted_queries) => persisted_queries,
+            _ => return self.execute_document(gateway).await,
+        };
 
-        let (object_type_name, selections, variable_definitions) = document
-            .definitions
-            .iter()
-            .find_map(|definition| match definition {
-                Definition::Operation(operation) => match operation {
-                    OperationDefinition::SelectionSet(selection_set) => {
-                        Some(("Query", selection_set.items.clone(), vec![]))
-                    }
-                    OperationDefinition::Query(query) => Some((
-                        "Query",
-                        query.selection_set.items.clone(),
-                        query.variable_definitions.clone(),
-                    )),
-                    OperationDefinition::Mutation(mutation) => Some((
-                        "Mutation",
-                        mutation.selection_set.items.clone(),
-                        mutation.variable_definitions.clone(),
-                    )),
-                    _ => None,
-                },
-                _ => None,
-            })
-            .ok_or(QueryError::NotSupported)?;
+        let extensions = self
+            .ctx_data
+            .as_ref()
+            .and_then(|data| data.get::<RequestExtensions>());
+
+        match persisted_queries.resolve(&self.query_source, extensions) {
+            Ok(None) => self.execute_document(gateway).await,
+            Ok(Some(query_source)) => {
+                let query = QueryBuilder {
+                    query_source: query_source.to_owned(),
+                    prepared: None,
+                    ..self.clone()
+                };
+
+                query.execute_document(gateway).await
+            }
+            Err(err) => QueryResponse {
+                result: Err(err),
+                errors: vec![],
+                extensions: Map::new(),
+                etag: None,
+                not_modified: false,
+            },
+        }
+    }
+
+    pub fn execute_stream<'a>(
+        &'a self,
+        gateway: &'a Gateway<'_>,
+    ) -> BoxStream<'a, IncrementalPayload> {
+        execute_stream(self, gateway)
+    }
+
+    pub(crate) async fn execute_document(&self, gateway: &Gateway<'_>) -> QueryResponse {
+        let started_at = Instant::now();
+        let mut data = self
+            .ctx_data
+            .clone()
+            .unwrap_or_default()
+            .layered(gateway.data.clone());
+        let request_id = match data.get::<RequestId>() {
+            Some(request_id) => request_id.clone(),
+            _ => {
+                let request_id = RequestId::generate();
+                data.insert(request_id.clone());
+                request_id
+            }
+        };
+
+        let mut extensions = Map::new();
+        extensions.insert("requestId".to_owned(), json!(request_id.as_str()));
+
+        if let Some((version, reason)) = self.version.as_deref().and_then(|version| {
+            gateway
+                .version_deprecation(version)
+                .map(|reason| (version, reason))
+        }) {
+            extensions.insert(
+                "deprecation".to_owned(),
+                json!({ "version": version, "reason": reason }),
+            );
+        }
+
+        if let Some(view) = self.materialized_view(gateway) {
+            let refreshed = if view.is_stale() {
+                view.refresh(gateway).await
+            } else {
+                Ok(())
+            };
+
+            if let Some((value, metadata)) = view.get() {
+                extensions.insert("materialized".to_owned(), metadata);
+
+                let mut response = QueryResponse {
+                    result: Ok(value),
+                    errors: vec![],
+                    extensions,
+                    etag: None,
+                    not_modified: false,
+                };
+
+                if gateway.etag {
+                    apply_etag(&mut response, data.get::<IfNoneMatch>());
+                }
+
+                self.observe_slos(gateway, &response, started_at);
+                self.report_usage(gateway, &response, started_at);
+
+                return response;
+            }
+
+            if let Err(err) = refreshed {
+                return QueryResponse {
+                    result: Err(err),
+                    errors: vec![],
+                    extensions,
+                    etag: None,
+                    not_modified: false,
+                };
+            }
+        }
+
+        let memory = MemoryTracker::new(gateway.memory_limit);
+        let context_extensions = Mutex::new(Map::new());
+        let context_errors = Mutex::new(Vec::new());
+        let result = self.execute_with(
+            gateway,
+            &data,
+            &memory,
+            &context_extensions,
+            &context_errors,
+        );
+        #[cfg(feature = "tracing")]
+        let result = tracing::Instrument::instrument(
+            result,
+            tracing::info_span!(
+                "query",
+                operation_name = self.operation_name.as_deref().unwrap_or_default(),
+                request_id = request_id.as_str()
+            ),
+        );
+        let result = result.await;
+
+        if let Err(err) = &result {
+            let class = gateway.classifier().classify(err);
+
+            extensions.insert("errorClass".to_owned(), json!(class));
+        }
+
+        extensions.extend(
+            context_extensions
+                .into_inner()
+                .expect("Context extensions lock is poisoned."),
+        );
+
+        extensions.insert(
+            "memory".to_owned(),
+            json!({ "used": memory.used(), "limit": memory.limit() }),
+        );
+
+        let errors = match result {
+            Ok(_) => context_errors
+                .into_inner()
+                .expect("Context errors lock is poisoned."),
+            _ => vec![],
+        };
+
+        let mut response = QueryResponse {
+            result,
+            errors,
+            extensions,
+            etag: None,
+            not_modified: false,
+        };
+
+        if gateway.etag {
+            apply_etag(&mut response, data.get::<IfNoneMatch>());
+        }
+
+        self.observe_slos(gateway, &response, started_at);
+        self.report_usage(gateway, &response, started_at);
+
+        response
+    }
+
+    fn observe_slos(&self, gateway: &Gateway<'_>, response: &QueryResponse, started_at: Instant) {
+        if gateway.slos.is_empty() {
+            return;
+        }
 
-        let variable_definitions = variable_definitions
+        let violations = gateway.slo_tracker.observe(
+            &gateway.slos,
+            &self.query_source,
+            self.operation_name.as_deref(),
+            response.result.as_ref().ok(),
+            started_at.elapsed(),
+        );
+
+        if let Some(alert) = &gateway.slo_alert {
+            for violation in &violations {
+                alert.alert(violation);
+            }
+        }
+    }
+
+    fn report_usage(&self, gateway: &Gateway<'_>, response: &QueryResponse, started_at: Instant) {
+        let reporter = match &gateway.usage_reporter {
+            Some(reporter) => reporter,
+            _ => return,
+        };
+
+        let operation_name = self.operation_name.as_deref();
+        let stats_report_key = match stats_report_key(&self.query_source, operation_name) {
+            Ok(key) => key,
+            _ => return,
+        };
+
+        reporter.report(&OperationUsage {
+            operation_hash: sha256_hex(
+                stats_report_key
+                    .split_once('\n')
+                    .map(|(_, signature)| signature)
+                    .unwrap_or_default(),
+            ),
+            stats_report_key,
+            duration: started_at.elapsed(),
+            has_errors: response.result.is_err() || !response.errors.is_empty(),
+        });
+    }
+
+    fn materialized_view<'a>(&self, gateway: &'a Gateway<'_>) -> Option<&'a MaterializedView> {
+        if gateway.materialized_views.is_empty() || gateway.mode() == Mode::Maintenance {
+            return None;
+        }
+
+        gateway
+            .materialized_views
             .iter()
-            .map(|variable_definition| {
-                (
-                    variable_definition.name.clone(),
-                    variable_definition.clone(),
-                )
-            })
-            .collect();
+            .find(|view| view.matches(gateway, self))
+    }
 
-        let context = Context {
+    pub(crate) async fn execute_with(
+        &self,
+        gateway: &Gateway<'_>,
+        data: &Data,
+        memory: &MemoryTracker,
+        extensions: &Mutex<Map<String, Value>>,
+        errors: &Mutex<Vec<QueryPosError>>,
+    ) -> QueryResult<Value> {
+        let mode_state = gateway.mode_state();
+
+        if mode_state.mode == Mode::Maintenance {
+            return Err(mode_state.into());
+        }
+
+        let inflight = data.get::<RequestId>().map(|request_id| {
+            gateway
+                .diagnostics
+                .start(request_id.as_str(), self.operation_name.as_deref())
+        });
+
+        let plan_cache = match &self.prepared {
+            Some(prepared) => Some(&prepared.plans),
+            _ => gateway.plan_cache.as_ref(),
+        };
+        let plan_key = match plan_cache {
+            Some(_) => Some(self.plan_key(gateway, data)?),
+            _ => None,
+        };
+        let hit = match (plan_cache, &plan_key) {
+            (Some(plan_cache), Some(plan_key)) => plan_cache.get(plan_key),
+            _ => None,
+        };
+        let cached = hit.clone().or_else(|| {
+            self.prepared
+                .as_ref()
+                .map(|prepared| prepared.operation.clone())
+        });
+
+        let (query_source, literals) = match &cached {
+            Some(cached) => (String::new(), cached.literals.clone()),
+            _ => preserve_literals(&self.query_source),
+        };
+        let document = match &cached {
+            Some(cached) => rebind_document(&cached.document),
+            _ => graphql_parser::parse_query::<String>(&query_source)?,
+        };
+
+        let fragments = find_fragments(&document, planner_flags(gateway, data))?;
+        let (object_type_name, selections, variable_definitions) =
+            find_operation(&document, self.operation_name.as_deref())?;
+
+        if mode_state.mode == Mode::ReadOnly && object_type_name == "Mutation" {
+            return Err(mode_state.into());
+        }
+
+        let variables = self.validate_operation(
             gateway,
-            data: self.ctx_data.as_ref(),
-            operation_name: self.operation_name.as_ref().map(|e| e.as_str()),
-            variables: self.variables.as_ref(),
+            data,
+            &fragments,
+            &selections,
+            &variable_definitions,
+            &literals,
+        )?;
+
+        let mut context = Context {
+            gateway,
+            schema: self.schema(gateway)?,
+            version: self.version.as_deref(),
+            memory,
+            extensions,
+            errors,
+            node_batcher: NodeBatcher::default(),
+            response_cache: if self.response_cache {
+                Some(RequestCache::default())
+            } else {
+                None
+            },
+            tracing: if gateway.apollo_tracing {
+                Some(ApolloTracing::new())
+            } else {
+                None
+            },
+            inflight: inflight.as_ref().map(InflightGuard::id),
+            data: Some(data),
+            literals: &literals,
+            operation_name: self.operation_name.as_deref(),
+            variables: variables.as_ref(),
             fragments,
-            variable_definitions,
+            variable_definitions: variable_definitions_by_name(&variable_definitions),
         };
 
-        let object_type = match context.object(object_type_name) {
-            Some(object_type) => object_type,
+        let cached_plan = cached
+            .as_ref()
+            .and_then(|cached| cached.plan.as_ref())
+            .map(OperationPlan::rebind);
+
+        let (selections, root) = match cached_plan {
+            Some(plan) => {
+                context.fragments = plan.fragments;
+                (plan.selections, plan.root)
+            }
             _ => {
-                let err = match object_type_name {
-                    "Query" => QueryError::NotConfiguredQueries,
-                    "Mutation" => QueryError::NotConfiguredMutations,
-                    _ => QueryError::NotSupported,
+                context.fragments = merge_fragments(&context)?;
+                (merge_selections(&context, &selections)?, None)
+            }
+        };
+
+        let object_type = context
+            .object(object_type_name)
+            .ok_or_else(|| root_type_error(object_type_name))?;
+
+        if !gateway.argument_rules.is_empty() {
+            validate_arguments(&context, object_type, &selections)?;
+        }
+
+        if let Some(max_complexity) = gateway.max_complexity {
+            validate_complexity(&context, object_type, &selections, max_complexity)?;
+        }
+
+        context.phase(RequestPhase::Planning);
+
+        let root = match root {
+            Some(root) => Some(root),
+            _ => plan_root(&context, object_type, &selections)?,
+        };
+
+        if let (None, Some(plan_cache), Some(plan_key)) = (&hit, plan_cache, plan_key) {
+            let plan = Some((&context.fragments, selections.as_slice(), root.as_ref()))
+                .filter(|_| is_plan_stable(&context, &document));
+
+            plan_cache.insert(plan_key, cached_operation(&document, &literals, plan));
+        }
+
+        let result = match root {
+            Some(RootPlan::FastPath(executor, resolve_info)) => {
+                match get_executor_root_data(&context, object_type, resolve_info, executor).await {
+                    Ok(data) if data.values().any(contains_null) => {
+                        resolve(&context, object_type, data.into(), &selections, vec![]).await
+                    }
+                    data => data.map(Value::Object),
+                }
+            }
+            None if object_type.name() == "Mutation" => {
+                resolve_mutation(&context, object_type, &selections).await
+            }
+            root => {
+                let data = match root {
+                    Some(RootPlan::Executors(plans)) => {
+                        get_planned_root_data(&context, object_type, plans).await
+                    }
+                    _ => get_root_data(&context, object_type, &selections).await,
                 };
 
-                return Err(err);
+                match data {
+                    Ok(data) => resolve(&context, object_type, data, &selections, vec![]).await,
+                    Err(e) => Err(e),
+                }
             }
         };
 
-        let data = get_root_data(&context, object_type, &selections).await?;
+        if let Some(tracing) = &context.tracing {
+            context.extend("tracing", tracing.finish());
+        }
+
+        result
+    }
+
+    pub fn parse(&self) -> QueryResult<ParsedQuery> {
+        let (query_source, _) = preserve_literals(&self.query_source);
+        let document = graphql_parser::parse_query::<String>(&query_source)?;
+        let operation_name = self.operation_name.as_deref();
+        let (object_type_name, selections, variable_definitions) =
+            find_operation(&document, operation_name)?;
+        let operation_name = select_operation(&document, operation_name)
+            .ok()
+            .and_then(definition_name)
+            .map(|name| name.to_owned());
+
+        Ok(ParsedQuery {
+            operation_type: object_type_name.to_owned(),
+            operation_name,
+            variables: variable_definitions
+                .iter()
+                .map(|variable_definition| variable_definition.name.clone())
+                .collect(),
+            fragments: document
+                .definitions
+                .iter()
+                .filter_map(|definition| match definition {
+                    Definition::Fragment(fragment) => Some(fragment.name.clone()),
+                    _ => None,
+                })
+                .collect(),
+            root_fields: selections
+                .iter()
+                .filter_map(|selection| match selection {
+                    Selection::Field(field) => Some(field.name.clone()),
+                    _ => None,
+                })
+                .collect(),
+        })
+    }
+
+    pub fn validate(&self, gateway: &Gateway<'_>) -> QueryResult<()> {
+        self.prepare(gateway, true).map(|_| ())
+    }
+
+    pub fn plan(&self, gateway: &Gateway<'_>) -> QueryResult<QueryPlan> {
+        self.prepare(gateway, false)
+    }
+
+    pub async fn execute_plan(
+        &self,
+        gateway: &Gateway<'_>,
+        plan: &QueryPlan,
+    ) -> QueryResult<Value> {
+        if &self.plan(gateway)? != plan {
+            return Err(QueryError::StalePlan);
+        }
+
+        self.execute(gateway).await
+    }
+
+    fn prepare(&self, gateway: &Gateway<'_>, validate: bool) -> QueryResult<QueryPlan> {
+        let data = self
+            .ctx_data
+            .clone()
+            .unwrap_or_default()
+            .layered(gateway.data.clone());
+
+        let (query_source, literals) = preserve_literals(&self.query_source);
+        let document = graphql_parser::parse_query::<String>(&query_source)?;
+        let fragments = find_fragments(&document, planner_flags(gateway, &data))?;
+        let (object_type_name, selections, variable_definitions) =
+            find_operation(&document, self.operation_name.as_deref())?;
+
+        let variables = if validate {
+            self.validate_operation(
+                gateway,
+                &data,
+                &fragments,
+                &selections,
+                &variable_definitions,
+                &literals,
+            )?
+        } else {
+            with_default_variables(&variable_definitions, self.variables.as_ref(), &literals)
+        };
+
+        let mut context = Context {
+            gateway,
+            schema: self.schema(gateway)?,
+            version: self.version.as_deref(),
+            memory: &MemoryTracker::default(),
+            extensions: &Mutex::new(Map::new()),
+            errors: &Mutex::new(Vec::new()),
+            node_batcher: NodeBatcher::default(),
+            response_cache: None,
+            tracing: None,
+            inflight: None,
+            data: Some(&data),
+            literals: &literals,
+            operation_name: self.operation_name.as_deref(),
+            variables: variables.as_ref(),
+            fragments,
+            variable_definitions: variable_definitions_by_name(&variable_definitions),
+        };
+
+        context.fragments = merge_fragments(&context)?;
+        let selections = merge_selections(&context, &selections)?;
+
+        let object_type = context
+            .object(object_type_name)
+            .ok_or_else(|| root_type_error(object_type_name))?;
+
+        if validate && !gateway.argument_rules.is_empty() {
+            validate_arguments(&context, object_type, &selections)?;
+        }
+
+        if let (true, Some(max_complexity)) = (validate, gateway.max_complexity) {
+            validate_complexity(&context, object_type, &selections, max_complexity)?;
+        }
+
+        plan_operation(&context, object_type, &selections)
+    }
+
+    fn validate_operation(
+        &self,
+        gateway: &Gateway<'_>,
+        data: &Data,
+        fragments: &HashMap<String, FragmentDefinition<'_, String>>,
+        selections: &[Selection<'_, String>],
+        variable_definitions: &[VariableDefinition<'_, String>],
+        literals: &[String],
+    ) -> QueryResult<Option<Value>> {
+        let variables =
+            with_default_variables(variable_definitions, self.variables.as_ref(), literals);
+
+        validate_variables(variable_definitions, variables.as_ref())?;
+
+        let variables = coerce_variables(
+            &self.schema(gateway)?.0,
+            variable_definitions,
+            variables.as_ref(),
+        )?;
+
+        if gateway.introspection_disabled {
+            validate_introspection(selections, fragments)?;
+        }
+
+        if let Some(max_depth) = gateway.max_depth {
+            validate_depth(selections, fragments, max_depth)?;
+        }
+
+        if planner_flags(gateway, data).strict_validation {
+            validate_declared_variables(variable_definitions, self.variables.as_ref())?;
+        }
+
+        Ok(variables)
+    }
+
+    fn plan_key(&self, gateway: &Gateway<'_>, data: &Data) -> QueryResult<PlanKey> {
+        Ok(PlanKey {
+            schema_hash: self.schema(gateway)?.6.clone(),
+            version: self.version.clone(),
+            operation_name: self.operation_name.clone(),
+            query: self.query_source.trim().to_owned(),
+            flags: planner_flags(gateway, data),
+        })
+    }
+
+    fn schema<'a>(&self, gateway: &'a Gateway<'_>) -> QueryResult<&'a GatewaySchema> {
+        match self.version.as_deref() {
+            Some(version) => gateway
+                .version_schema(version)
+                .ok_or_else(|| QueryError::UnknownVersion(version.to_owned())),
+            _ => Ok(&gateway.schema),
+        }
+    }
+}
+
+fn plan_root<'a>(
+    context: &Context<'a, '_>,
+    object_type: &Type,
+    selections: &[Selection<'a, String>],
+) -> QueryResult<Option<RootPlan<'a>>> {
+    if let Some((executor, resolve_info)) = single_executor_plan(context, object_type, selections) {
+        return Ok(Some(RootPlan::FastPath(executor, resolve_info)));
+    }
+
+    if object_type.name() == "Mutation" {
+        return Ok(None);
+    }
+
+    root_plans(context, object_type, selections).map(|plans| Some(RootPlan::Executors(plans)))
+}
+
+fn root_type_error(object_type_name: &str) -> QueryError {
+    match object_type_name {
+        "Query" => QueryError::NotConfiguredQueries,
+        "Mutation" => QueryError::NotConfiguredMutations,
+        _ => QueryError::NotSupported,
+    }
+}
+
+fn planner_flags(gateway: &Gateway<'_>, data: &Data) -> PlannerFlags {
+    data.get::<PlannerFlags>()
+        .copied()
+        .unwrap_or(gateway.planner_flags)
+}
+
+pub(crate) fn find_fragments<'a>(
+    document: &Document<'a, String>,
+    flags: PlannerFlags,
+) -> QueryResult<HashMap<String, FragmentDefinition<'a, String>>> {
+    let mut fragments: HashMap<String, FragmentDefinition<'a, String>> = HashMap::new();
+    let mut errors = Vec::new();
+
+    for definition in &document.definitions {
+        let fragment = match definition {
+            Definition::Fragment(fragment) => fragment,
+            _ => continue,
+        };
+
+        match fragments.get(&fragment.name) {
+            Some(first) if !flags.allow_duplicate_fragments => errors.push(QueryPosError(
+                fragment.position,
+                QueryError::DuplicateFragment(fragment.name.clone(), first.position),
+            )),
+            _ => {
+                fragments.insert(fragment.name.clone(), fragment.clone());
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(fragments)
+    } else {
+        Err(QueryError::Errors(errors))
+    }
+}
+
+fn variable_definitions_by_name<'a>(
+    variable_definitions: &[VariableDefinition<'a, String>],
+) -> HashMap<String, VariableDefinition<'a, String>> {
+    variable_definitions
+        .iter()
+        .map(|variable_definition| {
+            (
+                variable_definition.name.clone(),
+                variable_definition.clone(),
+            )
+        })
+        .collect()
+}
+
+pub(crate) type OperationInfo<'a> = (
+    &'static str,
+    Vec<Selection<'a, String>>,
+    Vec<VariableDefinition<'a, String>>,
+);
+
+pub(crate) fn find_operation<'a>(
+    document: &Document<'a, String>,
+    operation_name: Option<&str>,
+) -> QueryResult<OperationInfo<'a>> {
+    match select_operation(document, operation_name)? {
+        OperationDefinition::SelectionSet(selection_set) => {
+            Ok(("Query", selection_set.items.clone(), vec![]))
+        }
+        OperationDefinition::Query(query) => Ok((
+            "Query",
+            query.selection_set.items.clone(),
+            query.variable_definitions.clone(),
+        )),
+        OperationDefinition::Mutation(mutation) => Ok((
+            "Mutation",
+            mutation.selection_set.items.clone(),
+            mutation.variable_definitions.clone(),
+        )),
+        _ => Err(QueryError::NotSupported),
+    }
+}
+
+pub(crate) fn select_operation<'a, 'b>(
+    document: &'b Document<'a, String>,
+    operation_name: Option<&str>,
+) -> QueryResult<&'b OperationDefinition<'a, String>> {
+    let mut operations = document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::Operation(operation) => Some(operation),
+            _ => None,
+        })
+        .filter(|operation| {
+            operation_name.is_none() || definition_name(operation) == operation_name
+        });
+
+    match (operations.next(), operations.next(), operation_name) {
+        (Some(operation), None, _) => Ok(operation),
+        (Some(_), Some(_), Some(name)) => Err(QueryError::DuplicateOperation(name.to_owned())),
+        (Some(_), Some(_), None) => Err(QueryError::OperationNameRequired),
+        (None, _, Some(name)) => Err(QueryError::UnknownOperation(name.to_owned())),
+        (None, _, None) => Err(QueryError::NotSupported),
+    }
+}
+
+fn string_argument(
+    context: &Context<'_, '_>,
+    field: &Field<'_, String>,
+    name: &str,
+) -> Option<String> {
+    let (_, value) = field
+        .arguments
+        .iter()
+        .find(|(argument, _)| argument == name)?;
+
+    match value {
+        AstValue::String(value) => Some(value.clone()),
+        AstValue::Enum(value) => literal_value(value, context.literals)?
+            .as_str()
+            .map(|value| value.to_owned()),
+        AstValue::Variable(variable) => context
+            .variables?
+            .get(variable)?
+            .as_str()
+            .map(|value| value.to_owned()),
+        _ => None,
+    }
+}
 
-        Ok(resolve(&context, object_type, data, &selections).await?)
+pub(crate) fn definition_name<'b>(
+    operation: &'b OperationDefinition<'_, String>,
+) -> Option<&'b str> {
+    match operation {
+        OperationDefinition::SelectionSet(_) => None,
+        OperationDefinition::Query(query) => query.name.as_deref(),
+        OperationDefinition::Mutation(mutation) => mutation.name.as_deref(),
+        OperationDefinition::Subscription(subscription) => subscription.name.as_deref(),
     }
 }
 
@@ -188,6 +1007,7 @@ fn resolve<'a, 'b>(
     object_type: &'a Type,
     data: Value,
     selections: &'a [Selection<'a, String>],
+    path: Vec<Value>,
 ) -> BoxFuture<'a, QueryResult<Value>> {
     async move {
         if data.is_null() || selections.is_empty() {
@@ -200,65 +1020,185 @@ fn resolve<'a, 'b>(
             }
         }
 
-        let data = get_node_data(context, object_type, &data, selections).await?;
+        let data = if data.is_array() && !context.flags().batch_nodes {
+            data
+        } else {
+            let paths = match &data {
+                Value::Array(values) => (0..values.len()).map(|i| item_path(&path, i)).collect(),
+                _ => vec![path.clone()],
+            };
+            let data = get_node_data(context, object_type, &data, selections, paths).await?;
+            get_fallback_data(context, object_type, data, selections, &path).await?
+        };
+
+        if let Value::Array(mut values) = data {
+            if context.flags().batch_nodes {
+                let items = values
+                    .iter_mut()
+                    .enumerate()
+                    .map(|(i, value)| (item_path(&path, i), value))
+                    .collect();
 
-        if let Value::Array(values) = &data {
-            let futures = values
-                .iter()
-                .map(|value| resolve(context, object_type, value.clone(), selections))
-                .collect::<Vec<BoxFuture<'a, QueryResult<Value>>>>();
+                prefetch_nodes(context, object_type, items, selections).await?;
+            }
+
+            let parallelism = context.gateway.list_parallelism.unwrap_or(values.len());
+            let values = stream::iter(values.into_iter().enumerate())
+                .map(|(i, value)| {
+                    resolve(context, object_type, value, selections, item_path(&path, i))
+                })
+                .buffered(parallelism.max(1))
+                .try_collect::<Vec<Value>>()
+                .await?;
 
-            let values = futures::future::try_join_all(futures).await?;
             return Ok(Value::Array(values));
         }
 
         let mut errors = Vec::new();
         let mut map = Map::new();
+        let mut propagate_null = false;
 
         for selection in selections {
+            if !context.is_included(selection) {
+                continue;
+            }
+
             match selection {
                 Selection::Field(field) => {
                     let field_name = field.alias.as_ref().unwrap_or(&field.name);
-                    let (field_type, field_data) = if field.name == "__schema" {
-                        (context.object("__Schema"), Some(context.schema_data()))
-                    } else {
-                        let field_type = context
-                            .field_object_type(object_type, field.name.as_str())
-                            .map(|(_, field_type)| field_type);
-                        (field_type, data.get(&field_name))
-                    };
 
-                    let field_data = match field_data {
-                        Some(field_data) => field_data,
-                        _ => {
-                            errors.push(QueryPosError(
-                                field.position,
-                                QueryError::FieldDataNotFound(
-                                    object_type.name().to_owned(),
-                                    field_name.to_string(),
-                                ),
-                            ));
-                            continue;
+                    if !context.is_authorized(object_type, &field.name) {
+                        let error = QueryPosError(
+                            field.position,
+                            QueryError::Forbidden(
+                                object_type.name().to_owned(),
+                                field.name.clone(),
+                            ),
+                        );
+
+                        match context.unauthorized_field(object_type, &field.name) {
+                            UnauthorizedField::Omit => {}
+                            UnauthorizedField::Null => {
+                                map.insert(field_name.clone(), Value::Null);
+                                context.error(error);
+                            }
+                            UnauthorizedField::Fail => errors.push(error),
+                        }
+
+                        continue;
+                    }
+
+                    let (field_type, field_data) = match field.name.as_str() {
+                        "__schema" => {
+                            context.extend("gateway", context.ownership());
+                            (context.object("__Schema"), Some(context.schema_data()))
+                        }
+                        "__type" => {
+                            let type_data = string_argument(context, field, "name")
+                                .and_then(|name| context.type_data(&name));
+
+                            match type_data {
+                                Some(type_data) => (context.object("__Type"), Some(type_data)),
+                                _ => {
+                                    map.insert(field_name.clone(), Value::Null);
+                                    continue;
+                                }
+                            }
+                        }
+                        "__typename" => {
+                            map.insert(field_name.clone(), typename_value(&data, object_type));
+                            continue;
+                        }
+                        name if is_static_field(context, object_type.name(), name) => {
+                            map.insert(field_name.clone(), static_field_value(context, name));
+                            continue;
+                        }
+                        _ => {
+                            let field_type = context
+                                .field_object_type(object_type, field.name.as_str())
+                                .map(|(_, field_type)| field_type);
+                            (field_type, data.get(field_name))
                         }
                     };
 
+                    let field_data = match field_data {
+                        Some(field_data) => field_data,
+                        _ => {
+                            errors.push(QueryPosError(
+                                field.position,
+                                QueryError::FieldDataNotFound(
+                                    object_type.name().to_owned(),
+                                    field_name.to_string(),
+                                ),
+                            ));
+                            continue;
+                        }
+                    };
+
+                    let mut field_path = path.clone();
+                    field_path.push(Value::String(field_name.clone()));
+
                     let field_type = match field_type {
                         Some(field_type) => field_type,
                         _ => {
-                            map.insert(field_name.clone(), field_data.clone());
+                            match field_directives(context, field, field_data.clone()) {
+                                Ok(data) => {
+                                    match complete_field(
+                                        context,
+                                        object_type,
+                                        field,
+                                        field_data,
+                                        data,
+                                        field_path,
+                                    ) {
+                                        Some(data) => {
+                                            map.insert(field_name.clone(), data);
+                                        }
+                                        _ => propagate_null = true,
+                                    }
+                                }
+                                Err(error) => errors.push(error),
+                            }
                             continue;
                         }
                     };
 
-                    let data = resolve(
+                    let mut data = resolve(
                         context,
                         field_type,
                         field_data.clone(),
                         &field.selection_set.items,
+                        field_path.clone(),
                     )
                     .await?;
 
-                    map.insert(field_name.clone(), data.clone());
+                    if field_type.name() == "ID" {
+                        if let Some((executor, _)) = context.field(object_type, field.name.as_str())
+                        {
+                            if let Some(codec) = context.id_codec(&executor) {
+                                data = encode_ids(codec, object_type.name(), &executor, &data);
+                            }
+                        }
+                    }
+
+                    match field_directives(context, field, data) {
+                        Ok(data) => {
+                            match complete_field(
+                                context,
+                                object_type,
+                                field,
+                                field_data,
+                                data,
+                                field_path,
+                            ) {
+                                Some(data) => {
+                                    map.insert(field_name.clone(), data);
+                                }
+                                _ => propagate_null = true,
+                            }
+                        }
+                        Err(error) => errors.push(error),
+                    }
                 }
                 Selection::FragmentSpread(fragment_spread) => {
                     let fragment = match context.fragments.get(&fragment_spread.fragment_name) {
@@ -285,32 +1225,28 @@ fn resolve<'a, 'b>(
                         },
                     };
 
+                    if !is_typename(&data, object_type) {
+                        continue;
+                    }
+
                     let data = resolve(
                         context,
                         object_type,
                         data.clone(),
                         &fragment.selection_set.items,
+                        path.clone(),
                     )
                     .await?;
 
-                    if let Value::Object(object) = data {
-                        map.extend(object);
+                    match data {
+                        Value::Object(object) => merge_object(&mut map, object),
+                        Value::Null => propagate_null = true,
+                        _ => {}
                     }
                 }
                 Selection::InlineFragment(inline_fragment) => {
-                    let type_condition = match inline_fragment.type_condition.as_ref() {
-                        Some(type_condition) => type_condition,
-                        _ => {
-                            errors.push(QueryPosError(
-                                inline_fragment.position,
-                                QueryError::MissingTypeConditionInlineFragment,
-                            ));
-                            continue;
-                        }
-                    };
-
-                    let object_type = match type_condition {
-                        TypeCondition::On(v) => match context.object(v) {
+                    let object_type = match inline_fragment.type_condition.as_ref() {
+                        Some(TypeCondition::On(v)) => match context.object(v) {
                             Some(object_type) => object_type,
                             _ => {
                                 errors.push(QueryPosError(
@@ -320,48 +1256,388 @@ fn resolve<'a, 'b>(
                                 continue;
                             }
                         },
+                        _ => object_type,
                     };
 
+                    if !is_typename(&data, object_type) {
+                        continue;
+                    }
+
                     let data = resolve(
                         context,
                         object_type,
                         data.clone(),
                         &inline_fragment.selection_set.items,
+                        path.clone(),
                     )
                     .await?;
 
-                    if let Value::Object(object) = data {
-                        map.extend(object);
+                    match data {
+                        Value::Object(object) => merge_object(&mut map, object),
+                        Value::Null => propagate_null = true,
+                        _ => {}
                     }
                 }
             };
         }
 
-        if errors.is_empty() {
-            Ok(map.into())
-        } else {
+        if !errors.is_empty() {
             Err(QueryError::Errors(errors))
+        } else if propagate_null {
+            Ok(Value::Null)
+        } else {
+            Ok(map.into())
         }
     }
     .boxed()
 }
 
+fn complete_field(
+    context: &Context<'_, '_>,
+    object_type: &Type,
+    field: &Field<'_, String>,
+    field_data: &Value,
+    data: Value,
+    path: Vec<Value>,
+) -> Option<Value> {
+    let field_type = match context.field(object_type, field.name.as_str()) {
+        Some((_, schema_field)) => &schema_field.field_type,
+        _ => return Some(data),
+    };
+
+    if has_null_violation(field_type, field_data) && has_null_violation(field_type, &data) {
+        context.error_once(QueryPosError(
+            field.position,
+            QueryError::NonNullField(object_type.name().to_owned(), field.name.clone(), path),
+        ));
+    }
+
+    complete_value(field_type, data)
+}
+
+fn complete_value(value_type: &Type, value: Value) -> Option<Value> {
+    match value_type.kind {
+        TypeKind::NonNull => {
+            complete_value(value_type.of_type(), value).filter(|value| !value.is_null())
+        }
+        TypeKind::List => match value {
+            Value::Array(items) => Some(
+                items
+                    .into_iter()
+                    .map(|item| complete_value(value_type.of_type(), item))
+                    .collect::<Option<Vec<_>>>()
+                    .map(Value::Array)
+                    .unwrap_or(Value::Null),
+            ),
+            value => Some(value),
+        },
+        _ => Some(value),
+    }
+}
+
+fn has_null_violation(value_type: &Type, value: &Value) -> bool {
+    match (&value_type.kind, value) {
+        (TypeKind::NonNull, Value::Null) => true,
+        (TypeKind::NonNull, value) => has_null_violation(value_type.of_type(), value),
+        (TypeKind::List, Value::Array(items)) => items
+            .iter()
+            .any(|item| has_null_violation(value_type.of_type(), item)),
+        _ => false,
+    }
+}
+
+fn contains_null(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Array(items) => items.iter().any(contains_null),
+        Value::Object(object) => object.values().any(contains_null),
+        _ => false,
+    }
+}
+
+fn field_directives(
+    context: &Context<'_, '_>,
+    field: &Field<'_, String>,
+    value: Value,
+) -> Result<Value, QueryPosError> {
+    resolve_directives(context, &field.directives, value)
+        .map_err(|(name, e)| QueryPosError(field.position, QueryError::Directive(name, e)))
+}
+
+fn item_path(path: &[Value], index: usize) -> Vec<Value> {
+    let mut path = path.to_vec();
+    path.push(Value::from(index));
+    path
+}
+
+fn typename_value(data: &Value, object_type: &Type) -> Value {
+    match data.get("__typename") {
+        Some(Value::String(type_name)) => Value::String(type_name.clone()),
+        _ if object_type.kind == TypeKind::Object => Value::String(object_type.name().to_owned()),
+        _ => Value::Null,
+    }
+}
+
+fn is_typename(data: &Value, object_type: &Type) -> bool {
+    data.get("__typename")
+        .and_then(Value::as_str)
+        .map(|type_name| type_name == object_type.name())
+        .unwrap_or(true)
+}
+
 async fn get_root_data<'a, 'b>(
     context: &'a Context<'a, 'b>,
     object_type: &'a Type,
     selections: &'a [Selection<'a, String>],
+) -> QueryResult<Value> {
+    get_planned_root_data(
+        context,
+        object_type,
+        root_plans(context, object_type, selections)?,
+    )
+    .await
+}
+
+async fn get_planned_root_data<'a, 'b>(
+    context: &'a Context<'a, 'b>,
+    object_type: &'a Type,
+    (plans, fan_out_fields): RootPlans<'a>,
 ) -> QueryResult<Value> {
     let mut map = Map::new();
-    let executors = resolve_executors(context, object_type, None, selections)?;
 
-    for executor in executors {
-        let result = resolve_executor(context, object_type, selections.to_vec(), executor.clone())?;
-        let data = get_executor_root_data(context, object_type, result, executor).await?;
+    let mut requests = Vec::new();
+
+    for (executor, result) in plans {
+        requests.push(get_executor_root_data(
+            context,
+            object_type,
+            result,
+            executor,
+        ));
+    }
 
+    for data in dispatch_root_requests(context, requests).await? {
         merge_object(&mut map, data);
     }
 
-    Ok(map.into())
+    for selection in fan_out_fields {
+        let field = match &selection {
+            Selection::Field(field) => field,
+            _ => continue,
+        };
+
+        let field_name = field.alias.as_ref().unwrap_or(&field.name);
+        let owners = context
+            .fan_out_owners(object_type, &field.name)
+            .unwrap_or_default();
+        let mut requests = Vec::new();
+
+        for executor in owners {
+            let result = resolve_executor(
+                context,
+                object_type,
+                vec![selection.clone()],
+                executor.clone(),
+            )?;
+
+            if result.selections.is_empty() {
+                continue;
+            }
+
+            requests.push(get_executor_root_data(
+                context,
+                object_type,
+                result,
+                executor,
+            ));
+        }
+
+        let values = dispatch_root_requests(context, requests)
+            .await?
+            .into_iter()
+            .map(|mut data| data.remove(field_name).unwrap_or(Value::Null))
+            .collect();
+
+        let value = match context.gateway.fan_out.get(&field.name) {
+            Some(strategy) => strategy.merge(values),
+            _ => MergeStrategy::Concat.merge(values),
+        };
+
+        map.insert(field_name.clone(), value);
+    }
+
+    let data = map.into();
+    context.memory.track(&data)?;
+
+    Ok(data)
+}
+
+async fn dispatch_root_requests<T, F: Future<Output = QueryResult<T>>>(
+    context: &Context<'_, '_>,
+    requests: Vec<F>,
+) -> QueryResult<Vec<T>> {
+    if context.flags().parallel_root {
+        return future::try_join_all(requests).await;
+    }
+
+    let mut results = Vec::new();
+
+    for request in requests {
+        results.push(request.await?);
+    }
+
+    Ok(results)
+}
+
+pub(crate) type RootPlans<'a> = (Vec<(String, ResolveInfo<'a>)>, Vec<Selection<'a, String>>);
+
+pub(crate) fn root_plans<'a>(
+    context: &Context<'a, '_>,
+    object_type: &Type,
+    selections: &[Selection<'a, String>],
+) -> QueryResult<RootPlans<'a>> {
+    let (fan_out_fields, selections): (Vec<_>, Vec<_>) = selections
+        .iter()
+        .filter(|selection| context.is_included(selection))
+        .cloned()
+        .partition(|selection| match selection {
+            Selection::Field(field) => context.fan_out_owners(object_type, &field.name).is_some(),
+            _ => false,
+        });
+
+    let mut plans = Vec::new();
+
+    for executor in resolve_executors(context, object_type, None, &selections)? {
+        let result = resolve_executor(context, object_type, selections.clone(), executor.clone())?;
+        plans.push((executor, result));
+    }
+
+    Ok((plans, fan_out_fields))
+}
+
+pub(crate) fn mutation_steps<'a, 's>(
+    context: &Context<'a, '_>,
+    object_type: &Type,
+    selections: &'s [Selection<'a, String>],
+) -> QueryResult<Vec<(&'s Selection<'a, String>, Vec<String>)>> {
+    let mut steps = Vec::new();
+
+    for selection in selections {
+        if !context.is_included(selection) {
+            continue;
+        }
+
+        let executors =
+            resolve_executors(context, object_type, None, std::slice::from_ref(selection))?;
+
+        steps.push((selection, executors));
+    }
+
+    Ok(steps)
+}
+
+async fn resolve_mutation<'a, 'b>(
+    context: &'a Context<'a, 'b>,
+    object_type: &'a Type,
+    selections: &'a [Selection<'a, String>],
+) -> QueryResult<Value> {
+    let mut map = Map::new();
+    let mut root_data = Map::new();
+    let mut completed = Vec::new();
+    let mut errors = Vec::new();
+    let mut failure = None;
+    let mut propagate_null = false;
+
+    for (selection, executors) in mutation_steps(context, object_type, selections)? {
+        let mut step_data = Map::new();
+
+        for executor in executors {
+            let result = resolve_executor(
+                context,
+                object_type,
+                vec![selection.clone()],
+                executor.clone(),
+            )?;
+
+            if result.selections.is_empty() {
+                continue;
+            }
+
+            match get_executor_root_data(context, object_type, result, executor).await {
+                Ok(data) => merge_object(&mut step_data, data),
+                Err(err) => {
+                    compensate(context, completed, &root_data, &err).await;
+                    return Err(err);
+                }
+            };
+        }
+
+        if let Selection::Field(field) = selection {
+            completed.push(field.clone());
+        }
+
+        merge_object(&mut root_data, step_data.clone());
+
+        let data = resolve(
+            context,
+            object_type,
+            step_data.into(),
+            std::slice::from_ref(selection),
+            vec![],
+        )
+        .await;
+
+        match data {
+            Ok(Value::Object(data)) => merge_object(&mut map, data),
+            Ok(Value::Null) => {
+                propagate_null = true;
+                break;
+            }
+            Ok(_) => {}
+            Err(QueryError::Errors(step_errors)) => errors.extend(step_errors),
+            Err(err) => {
+                failure.get_or_insert(err);
+            }
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(QueryError::Errors(errors));
+    }
+
+    if let Some(err) = failure {
+        return Err(err);
+    }
+
+    if propagate_null {
+        return Ok(Value::Null);
+    }
+
+    let data = map.into();
+    context.memory.track(&data)?;
+
+    Ok(data)
+}
+
+async fn compensate<'a, 'b>(
+    context: &Context<'a, 'b>,
+    fields: Vec<Field<'a, String>>,
+    data: &Map<String, Value>,
+    error: &QueryError,
+) {
+    for field in fields.iter().rev() {
+        let compensation = match context.gateway.compensations.get(&field.name) {
+            Some(compensation) => compensation,
+            _ => continue,
+        };
+
+        let field_name = field.alias.as_ref().unwrap_or(&field.name);
+        let value = data.get(field_name).unwrap_or(&Value::Null);
+
+        compensation
+            .compensate(context.data, &field.name, value, error)
+            .await;
+    }
 }
 
 async fn get_executor_root_data<'a, 'b, T: Into<String>>(
@@ -370,12 +1646,66 @@ async fn get_executor_root_data<'a, 'b, T: Into<String>>(
     resolve_info: ResolveInfo<'a>,
     executor: T,
 ) -> QueryResult<Map<String, Value>> {
+    let executor = executor.into();
+    let variables = context
+        .variables
+        .map(|_| executor_variables(context, &executor, &resolve_info.variable_definitions).into());
+    let traced_selections = context
+        .tracing
+        .as_ref()
+        .map(|tracing| (tracing.offset(), resolve_info.selections.clone()));
+    let circuit_selections = context
+        .gateway
+        .executor_circuit_breaker_for(&executor)
+        .map(|_| resolve_info.selections.clone());
+    let query_source = root_document(context, &executor, object_type, resolve_info);
+
+    let executor = context
+        .executor(&executor)
+        .ok_or(QueryError::UnknownExecutor(executor))?;
+
+    let res = match context
+        .execute(
+            executor,
+            query_source,
+            context.operation_name.map(|e| e.to_owned()),
+            variables,
+            object_type.name() != "Mutation",
+        )
+        .await
+        .map_err(|e| e.query_error(executor.name()))
+    {
+        Err(err @ QueryError::CircuitOpen(_)) => {
+            let selections = circuit_selections.unwrap_or_default();
+
+            return Ok(circuit_open_data(context, err, &selections));
+        }
+        res => res?,
+    };
+
+    if let (Some(tracing), Some((start_offset, selections))) = (&context.tracing, traced_selections)
+    {
+        tracing.fields(context, object_type, &selections, &[vec![]], start_offset);
+        tracing.downstream(&res, start_offset, |path| {
+            Some(path.to_vec()).filter(|path| path.len() > 1)
+        });
+    }
+
+    context.memory.track(&res)?;
+    check_executor_response(context, executor.name(), res)
+}
+
+pub(crate) fn root_document<'a>(
+    context: &Context<'a, '_>,
+    executor: &str,
+    object_type: &Type,
+    resolve_info: ResolveInfo<'a>,
+) -> String {
     let variable_definitions = resolve_info
         .variable_definitions
         .values()
         .cloned()
         .collect::<_>();
-    let executor = executor.into();
     let operation = match object_type.name() {
         "Query" => OperationDefinition::Query(Query {
             position: Pos::default(),
@@ -402,29 +1732,97 @@ async fn get_executor_root_data<'a, 'b, T: Into<String>>(
 
     let mut definitions = resolve_info
         .fragments
-        .into_iter()
-        .map(|(_, fragment)| Definition::Fragment(fragment))
+        .into_values()
+        .map(Definition::Fragment)
         .collect::<Vec<Definition<'a, String>>>();
 
     definitions.push(Definition::Operation(operation));
 
-    let document = Document { definitions };
-    let query_source = document.to_string();
+    context.format_document(executor, Document { definitions })
+}
 
-    let executor = context
-        .executor(&executor)
-        .ok_or(QueryError::UnknownExecutor(executor))?;
+fn prefetch_nodes<'a, 'b, 'c>(
+    context: &'a Context<'a, 'b>,
+    object_type: &'a Type,
+    mut items: Vec<(Vec<Value>, &'c mut Value)>,
+    selections: &'a [Selection<'a, String>],
+) -> BoxFuture<'c, QueryResult<()>>
+where
+    'a: 'c,
+{
+    async move {
+        for selection in selections {
+            let field = match selection {
+                Selection::Field(field)
+                    if context.is_included(selection)
+                        && context.is_authorized(object_type, &field.name) =>
+                {
+                    field
+                }
+                _ => continue,
+            };
 
-    let res = executor
-        .execute(
-            context.data,
-            query_source,
-            context.operation_name.map(|e| e.to_owned()),
-            context.variables.cloned(),
-        )
-        .await?;
+            let field_type = match context.field_object_type(object_type, &field.name) {
+                Some((_, field_type))
+                    if field_type.kind == TypeKind::Object && field_type.is_node() =>
+                {
+                    field_type
+                }
+                _ => continue,
+            };
 
-    check_executor_response(res)
+            let field_name = field.alias.as_ref().unwrap_or(&field.name);
+            let mut children = Vec::new();
+
+            for (path, item) in items.iter_mut() {
+                let mut field_path = path.clone();
+                field_path.push(Value::String(field_name.clone()));
+
+                match item.get_mut(field_name) {
+                    Some(Value::Array(values)) => {
+                        for (i, value) in values.iter_mut().enumerate() {
+                            if value.is_object() {
+                                children.push((item_path(&field_path, i), value));
+                            }
+                        }
+                    }
+                    Some(value) if value.is_object() => children.push((field_path, value)),
+                    _ => {}
+                }
+            }
+
+            if children.is_empty() {
+                continue;
+            }
+
+            let data = Value::Array(
+                children
+                    .iter()
+                    .map(|(_, value)| (**value).clone())
+                    .collect(),
+            );
+            let paths = children.iter().map(|(path, _)| path.clone()).collect();
+
+            if let Value::Array(values) = get_node_data(
+                context,
+                field_type,
+                &data,
+                &field.selection_set.items,
+                paths,
+            )
+            .await?
+            {
+                for ((_, child), value) in children.iter_mut().zip(values) {
+                    **child = value;
+                }
+            }
+
+            prefetch_nodes(context, field_type, children, &field.selection_set.items).await?;
+        }
+
+        Ok(())
+    }
+    .boxed()
 }
 
 async fn get_node_data<'a, 'b>(
@@ -432,6 +1830,7 @@ async fn get_node_data<'a, 'b>(
     object_type: &'a Type,
     data: &Value,
     selections: &'a [Selection<'a, String>],
+    paths: Vec<Vec<Value>>,
 ) -> QueryResult<Value> {
     if !object_type.is_node() {
         return Ok(data.clone());
@@ -452,8 +1851,54 @@ async fn get_node_data<'a, 'b>(
 
     for executor in executors {
         let result = resolve_executor(context, object_type, selections.to_vec(), executor.clone())?;
+        let paths = paths.clone();
+        let fallback_selections = if has_field_fallback(context, object_type, &result.selections) {
+            let key_fields = object_type.key_fields();
+
+            Some(
+                result
+                    .selections
+                    .iter()
+                    .filter(|selection| match selection {
+                        Selection::Field(field) => !key_fields.contains(&field.name.as_str()),
+                        _ => true,
+                    })
+                    .cloned()
+                    .collect::<Vec<_>>(),
+            )
+        } else {
+            None
+        };
         let node_data =
-            get_executor_node_data(context, object_type, data, result, executor).await?;
+            match get_executor_node_data(context, object_type, data, result, executor, paths).await
+            {
+                Err(err) if is_executor_error(&err) => {
+                    let selections = match fallback_selections {
+                        Some(selections) => selections,
+                        _ => return Err(err),
+                    };
+                    let position = selections
+                        .iter()
+                        .find_map(|selection| match selection {
+                            Selection::Field(field) => Some(field.position),
+                            _ => None,
+                        })
+                        .unwrap_or_default();
+
+                    context.error_once(QueryPosError(position, err));
+
+                    let node = Value::Object(null_node(context, &selections));
+                    let nodes = match data {
+                        Value::Array(values) => vec![node; values.len()],
+                        _ => vec![node],
+                    };
+
+                    let mut node_data = Map::new();
+                    node_data.insert("nodes".to_owned(), Value::Array(nodes));
+                    node_data
+                }
+                res => res?,
+            };
 
         merge_object(&mut map, node_data);
     }
@@ -468,6 +1913,140 @@ async fn get_node_data<'a, 'b>(
     let mut data = data.clone();
 
     merge_value(&mut data, node_data);
+    context.memory.track(&data)?;
+
+    Ok(data)
+}
+
+fn has_field_fallback(
+    context: &Context<'_, '_>,
+    object_type: &Type,
+    selections: &[Selection<'_, String>],
+) -> bool {
+    !context.gateway.field_fallbacks.is_empty()
+        && selections.iter().any(|selection| match selection {
+            Selection::Field(field) => context.gateway.field_fallbacks.contains_key(&format!(
+                "{}.{}",
+                object_type.name(),
+                field.name
+            )),
+            _ => false,
+        })
+}
+
+async fn get_fallback_data<'a, 'b>(
+    context: &Context<'a, 'b>,
+    object_type: &'a Type,
+    mut data: Value,
+    selections: &'a [Selection<'a, String>],
+    path: &[Value],
+) -> QueryResult<Value> {
+    if context.gateway.field_fallbacks.is_empty() || !object_type.is_node() {
+        return Ok(data);
+    }
+
+    for selection in selections {
+        if !context.is_included(selection) {
+            continue;
+        }
+
+        let field = match selection {
+            Selection::Field(field) if !object_type.key_fields().contains(&field.name.as_str()) => {
+                field
+            }
+            _ => continue,
+        };
+
+        let key = format!("{}.{}", object_type.name(), field.name);
+        let fallbacks = match context.gateway.field_fallbacks.get(&key) {
+            Some(fallbacks) => fallbacks,
+            _ => continue,
+        };
+
+        let field_name = field.alias.as_ref().unwrap_or(&field.name);
+        let mut visited = context
+            .field(object_type, field.name.as_str())
+            .map(|(executor, _)| executor)
+            .into_iter()
+            .collect::<HashSet<String>>();
+
+        for executor in fallbacks {
+            if !visited.insert(executor.clone()) {
+                continue;
+            }
+
+            let (paths, missing): (Vec<Vec<Value>>, Vec<&mut Value>) = match &mut data {
+                Value::Array(values) => values
+                    .iter_mut()
+                    .enumerate()
+                    .map(|(i, value)| (item_path(path, i), value))
+                    .collect::<Vec<_>>(),
+                value => vec![(path.to_vec(), value)],
+            }
+            .into_iter()
+            .filter(|(_, value)| {
+                value.is_object() && value.get(field_name).is_none_or(Value::is_null)
+            })
+            .unzip();
+
+            if missing.is_empty() {
+                break;
+            }
+
+            let mut selections = node_key_selections(context, object_type, executor, selections);
+            selections.push(Selection::Field(field.clone()));
+
+            let mut fragments = HashMap::new();
+            let mut variable_definitions = HashMap::new();
+
+            collect_passthrough(
+                context,
+                std::slice::from_ref(selection),
+                &mut fragments,
+                &mut variable_definitions,
+            );
+
+            let resolve_info = ResolveInfo {
+                selections,
+                fragments,
+                variable_definitions,
+            };
+
+            let values = Value::Array(missing.iter().map(|value| (*value).clone()).collect());
+            let mut node_data = match get_executor_node_data(
+                context,
+                object_type,
+                &values,
+                resolve_info,
+                executor.as_str(),
+                paths,
+            )
+            .await
+            {
+                Err(err) if is_executor_error(&err) => {
+                    context.error_once(QueryPosError(field.position, err));
+                    continue;
+                }
+                res => res?,
+            };
+
+            let nodes = match node_data.remove("nodes") {
+                Some(Value::Array(nodes)) => nodes,
+                _ => return Err(QueryError::InvalidExecutorResponse),
+            };
+
+            for (value, node) in missing.into_iter().zip(nodes) {
+                let field_data = match node.get(field_name) {
+                    Some(field_data) if !field_data.is_null() => field_data.clone(),
+                    _ => continue,
+                };
+
+                if let Value::Object(object) = value {
+                    object.insert(field_name.clone(), field_data);
+                }
+            }
+        }
+    }
 
     Ok(data)
 }
@@ -478,46 +2057,297 @@ async fn get_executor_node_data<'a, 'b, T: Into<String>>(
     data: &Value,
     resolve_info: ResolveInfo<'a>,
     executor: T,
+    paths: Vec<Vec<Value>>,
 ) -> QueryResult<Map<String, Value>> {
-    let var_name_node_ids = "__gql_gateway_ids";
     let executor = executor.into();
 
-    let field_id = resolve_info
-        .selections
-        .iter()
-        .find_map(|selection| match selection {
-            Selection::Field(field) => {
-                if field.name == "id" {
-                    Some(field.alias.as_ref().unwrap_or(&field.name).to_owned())
-                } else {
-                    None
+    let key_fields = context
+        .executor_key_fields(&executor, object_type)
+        .into_iter()
+        .map(|name| {
+            let response_key = resolve_info
+                .selections
+                .iter()
+                .find_map(|selection| match selection {
+                    Selection::Field(field) if field.name == name => {
+                        Some(field.alias.as_ref().unwrap_or(&field.name).to_owned())
+                    }
+                    _ => None,
+                })
+                .unwrap_or_else(|| name.clone());
+
+            (name, response_key)
+        })
+        .collect::<Vec<(String, String)>>();
+
+    let skip_null_keys = context.gateway.skip_null_keys;
+    let values = match data {
+        Value::Array(values) => values.iter().collect(),
+        _ => vec![data],
+    };
+    let mut ids = Vec::new();
+
+    for (i, value) in values.into_iter().enumerate() {
+        let id = match node_key(value, &key_fields) {
+            Some(id) if !(skip_null_keys && is_null_key(&id)) => id,
+            _ if skip_null_keys => {
+                context.warn(json!({
+                    "message": format!(
+                        "Skipped enrichment of \"{}\" with a null id.",
+                        object_type.name()
+                    ),
+                    "path": paths.get(i),
+                }));
+
+                Value::Null
+            }
+            _ => return Err(QueryError::FieldIdNotFound(object_type.name().to_owned())),
+        };
+
+        ids.push(id);
+    }
+
+    let skipped_node = if skip_null_keys {
+        null_node(context, &resolve_info.selections)
+    } else {
+        Map::new()
+    };
+    let ctx_variables = executor_variables(context, &executor, &resolve_info.variable_definitions);
+    let traced_selections = context.tracing.as_ref().map(|_| {
+        resolve_info
+            .selections
+            .iter()
+            .filter(|selection| match selection {
+                Selection::Field(field) => !key_fields.iter().any(|(name, _)| name == &field.name),
+                _ => true,
+            })
+            .cloned()
+            .collect::<Vec<Selection<'a, String>>>()
+    });
+    let circuit_selections = context
+        .gateway
+        .executor_circuit_breaker_for(&executor)
+        .map(|_| {
+            resolve_info
+                .selections
+                .iter()
+                .filter(|selection| match selection {
+                    Selection::Field(field) => {
+                        !key_fields.iter().any(|(name, _)| name == &field.name)
+                    }
+                    _ => true,
+                })
+                .cloned()
+                .collect::<Vec<Selection<'a, String>>>()
+        });
+    let query_source = node_document(context, &executor, object_type, resolve_info);
+
+    let node_cache = context.gateway.node_cache.as_ref().filter(|_| {
+        context.gateway.authorizer.is_none() && !context.gateway.forwards_headers(&executor)
+    });
+    let type_name = object_type.name();
+    let selection_hash =
+        NodeCache::selection_hash(&query_source, Some(&ctx_variables.clone().into()));
+    let mut nodes = vec![Value::Null; ids.len()];
+    let mut missing_ids = Vec::new();
+
+    for (i, id) in ids.iter().enumerate() {
+        if skip_null_keys && id.is_null() {
+            nodes[i] = Value::Object(skipped_node.clone());
+            continue;
+        }
+
+        let cached = node_cache
+            .and_then(|node_cache| node_cache.get(&executor, type_name, id, selection_hash));
+
+        match cached {
+            Some(node) => nodes[i] = node,
+            _ => missing_ids.push(i),
+        }
+    }
+
+    if !missing_ids.is_empty() {
+        let strategy = context.entity_strategy(&executor);
+        let missing = missing_ids
+            .iter()
+            .map(|&i| strategy.argument_value(type_name, &key_fields, &ids[i]))
+            .collect();
+        let missing_paths = missing_ids
+            .iter()
+            .map(|&i| paths.get(i).cloned().unwrap_or_default())
+            .collect::<Vec<Vec<Value>>>();
+        let start_offset = context.tracing.as_ref().map(ApolloTracing::offset);
+        let fetched_nodes = match fetch_nodes(
+            context,
+            &executor,
+            query_source,
+            ctx_variables,
+            missing,
+            &missing_paths,
+        )
+        .await
+        {
+            Err(QueryError::Downstream(errors)) => {
+                return Err(QueryError::Downstream(rewrite_node_paths(
+                    errors,
+                    "nodes",
+                    |j| missing_ids.get(j).and_then(|&i| paths.get(i)).cloned(),
+                )))
+            }
+            Err(err @ QueryError::CircuitOpen(_)) => {
+                let selections = circuit_selections.unwrap_or_default();
+                let node = Value::Object(circuit_open_data(context, err, &selections));
+
+                for i in missing_ids {
+                    nodes[i] = node.clone();
                 }
+
+                let mut map = Map::new();
+                map.insert("nodes".to_owned(), Value::Array(nodes));
+
+                return Ok(map);
             }
-            _ => None,
+            res => res?,
+        };
+
+        if let (Some(tracing), Some(start_offset), Some(selections)) =
+            (&context.tracing, start_offset, &traced_selections)
+        {
+            tracing.fields(
+                context,
+                object_type,
+                selections,
+                &missing_paths,
+                start_offset,
+            );
+        }
+
+        let missing = missing_ids
+            .iter()
+            .map(|&i| ids[i].clone())
+            .collect::<Vec<Value>>();
+        let fetched_nodes = match_nodes(&key_fields, &missing, fetched_nodes);
+
+        for (i, node) in missing_ids.into_iter().zip(fetched_nodes) {
+            if let Some(node_cache) = node_cache {
+                let ttl = context
+                    .gateway
+                    .node_cache_ttls
+                    .get(type_name)
+                    .or_else(|| context.gateway.node_cache_executor_ttls.get(&executor))
+                    .copied()
+                    .unwrap_or(node_cache.ttl);
+
+                node_cache.insert(
+                    &executor,
+                    type_name,
+                    &ids[i],
+                    selection_hash,
+                    node.clone(),
+                    ttl,
+                );
+            }
+
+            nodes[i] = node;
+        }
+    }
+
+    let mut map = Map::new();
+    map.insert("nodes".to_owned(), Value::Array(nodes));
+
+    Ok(map)
+}
+
+fn node_key(value: &Value, key_fields: &[(String, String)]) -> Option<Value> {
+    match key_fields {
+        [(_, response_key)] => value.get(response_key).cloned(),
+        _ => key_fields
+            .iter()
+            .map(|(name, response_key)| Some((name.clone(), value.get(response_key)?.clone())))
+            .collect::<Option<Map<String, Value>>>()
+            .map(Value::Object),
+    }
+}
+
+fn is_null_key(id: &Value) -> bool {
+    match id {
+        Value::Object(fields) => fields.values().any(Value::is_null),
+        _ => id.is_null(),
+    }
+}
+
+fn match_nodes(key_fields: &[(String, String)], ids: &[Value], nodes: Vec<Value>) -> Vec<Value> {
+    let is_match = |node: &Value, id: &Value| node_key(node, key_fields).as_ref() == Some(id);
+
+    ids.iter()
+        .enumerate()
+        .map(|(i, id)| match nodes.get(i) {
+            Some(node) if is_match(node, id) => node.clone(),
+            positional => nodes
+                .iter()
+                .find(|node| is_match(node, id))
+                .or(positional)
+                .cloned()
+                .unwrap_or(Value::Null),
         })
-        .unwrap_or_else(|| "id".to_owned());
+        .collect()
+}
+
+// Open circuits and skipped null keys fill fields with these placeholders. They complete like any
+// other value, so a placeholder in a non-null field bubbles up to the nearest nullable parent.
+fn null_node(
+    context: &Context<'_, '_>,
+    selections: &[Selection<'_, String>],
+) -> Map<String, Value> {
+    let mut node = Map::new();
+
+    for selection in selections {
+        match selection {
+            Selection::Field(field) => {
+                node.insert(
+                    field.alias.as_ref().unwrap_or(&field.name).to_owned(),
+                    Value::Null,
+                );
+            }
+            Selection::InlineFragment(inline_fragment) => {
+                node.extend(null_node(context, &inline_fragment.selection_set.items))
+            }
+            Selection::FragmentSpread(fragment_spread) => {
+                if let Some(fragment) = context.fragments.get(&fragment_spread.fragment_name) {
+                    node.extend(null_node(context, &fragment.selection_set.items));
+                }
+            }
+        }
+    }
 
-    let ids = match data {
-        Value::Array(values) => {
-            let mut ids = Vec::new();
+    node
+}
 
-            for value in values {
-                ids.push(
-                    value
-                        .get(&field_id)
-                        .ok_or_else(|| QueryError::FieldIdNotFound(object_type.name().to_owned()))?
-                        .clone(),
-                );
-            }
+fn circuit_open_data(
+    context: &Context<'_, '_>,
+    error: QueryError,
+    selections: &[Selection<'_, String>],
+) -> Map<String, Value> {
+    let position = selections
+        .iter()
+        .find_map(|selection| match selection {
+            Selection::Field(field) => Some(field.position),
+            _ => None,
+        })
+        .unwrap_or_default();
 
-            ids
-        }
-        _ => vec![data
-            .get(&field_id)
-            .ok_or_else(|| QueryError::FieldIdNotFound(object_type.name().to_owned()))?
-            .clone()],
-    };
+    context.error(QueryPosError(position, error));
+    null_node(context, selections)
+}
 
+pub(crate) fn node_document<'a>(
+    context: &Context<'a, '_>,
+    executor: &str,
+    object_type: &Type,
+    resolve_info: ResolveInfo<'a>,
+) -> String {
+    let lookup = context.node_lookup(executor);
+    let var_name_node_ids = node_ids_variable(context, &lookup);
     let mut variable_definitions = resolve_info
         .variable_definitions
         .values()
@@ -525,23 +2355,31 @@ async fn get_executor_node_data<'a, 'b, T: Into<String>>(
         .collect::<Vec<VariableDefinition<'a, String>>>();
 
     variable_definitions.push(VariableDefinition {
-        var_type: AstType::NonNullType(Box::new(AstType::ListType(Box::new(AstType::NamedType(
-            "ID".to_owned(),
-        ))))),
+        var_type: lookup.var_type(),
         position: Pos::default(),
-        name: var_name_node_ids.to_owned(),
+        name: var_name_node_ids.clone(),
         default_value: None,
     });
 
-    let node_items = vec![Selection::InlineFragment(InlineFragment {
-        position: Pos::default(),
-        type_condition: Some(TypeCondition::On(object_type.name().to_owned())),
-        directives: vec![],
-        selection_set: SelectionSet {
-            span: (Pos::default(), Pos::default()),
-            items: resolve_info.selections,
-        },
-    })];
+    let is_concrete_lookup = context.entity_strategy(executor) == EntityStrategy::Node
+        && context
+            .executor_lookup_type(executor)
+            .map(|lookup_type| lookup_type.kind == TypeKind::Object)
+            .unwrap_or(false);
+
+    let node_items = if is_concrete_lookup {
+        resolve_info.selections
+    } else {
+        vec![Selection::InlineFragment(InlineFragment {
+            position: Pos::default(),
+            type_condition: Some(TypeCondition::On(object_type.name().to_owned())),
+            directives: vec![],
+            selection_set: SelectionSet {
+                span: (Pos::default(), Pos::default()),
+                items: resolve_info.selections,
+            },
+        })]
+    };
 
     let operation = OperationDefinition::Query(Query {
         position: Pos::default(),
@@ -552,12 +2390,9 @@ async fn get_executor_node_data<'a, 'b, T: Into<String>>(
             span: (Pos::default(), Pos::default()),
             items: vec![Selection::Field(Field {
                 alias: None,
-                arguments: vec![(
-                    "ids".to_owned(),
-                    AstValue::Variable(var_name_node_ids.to_owned()),
-                )],
+                arguments: vec![(lookup.argument, AstValue::Variable(var_name_node_ids))],
                 directives: vec![],
-                name: "nodes".to_owned(),
+                name: lookup.field,
                 position: Pos::default(),
                 selection_set: SelectionSet {
                     span: (Pos::default(), Pos::default()),
@@ -567,46 +2402,138 @@ async fn get_executor_node_data<'a, 'b, T: Into<String>>(
         },
     });
 
-    let mut variables = Map::new();
-    variables.insert(var_name_node_ids.to_owned(), Value::Array(ids));
-
-    if let Some(ctx_variables) = context
-        .variables
-        .and_then(|variables| variables.as_object())
-    {
-        variables.extend(ctx_variables.clone());
-    }
-
     let mut definitions = resolve_info
         .fragments
-        .into_iter()
-        .map(|(_, fragment)| Definition::Fragment(fragment))
+        .into_values()
+        .map(Definition::Fragment)
         .collect::<Vec<Definition<'a, String>>>();
 
     definitions.push(Definition::Operation(operation));
 
-    let document = Document { definitions };
-    let query_source = document.to_string();
+    context.format_document(executor, Document { definitions })
+}
 
-    let executor = context
-        .executor(&executor)
-        .ok_or(QueryError::UnknownExecutor(executor))?;
+fn node_ids_variable(context: &Context<'_, '_>, lookup: &NodeLookup) -> String {
+    injected_variable(&lookup.argument, |name| {
+        context.variable_definitions.contains_key(name)
+    })
+}
 
-    let res = executor
-        .execute(
-            context.data,
-            query_source,
-            Some("NodeQuery".to_owned()),
-            Some(variables.into()),
+async fn fetch_nodes<'a, 'b>(
+    context: &Context<'a, 'b>,
+    executor: &str,
+    query_source: String,
+    ctx_variables: Map<String, Value>,
+    ids: Vec<Value>,
+    paths: &[Vec<Value>],
+) -> QueryResult<Vec<Value>> {
+    let start_offset = context.tracing.as_ref().map(ApolloTracing::offset);
+    let lookup = context.node_lookup(executor);
+    let var_name_node_ids = node_ids_variable(context, &lookup);
+    let executor_ref = context
+        .executor(executor)
+        .ok_or_else(|| QueryError::UnknownExecutor(executor.to_owned()))?;
+
+    let batch = if context.flags().batch_nodes {
+        Some(
+            context
+                .node_batcher
+                .enqueue(executor, &query_source, &ctx_variables, &ids),
         )
-        .await?;
+    } else {
+        None
+    };
+
+    let execute = |ids: Vec<Value>| async move {
+        let mut variables = Map::new();
+        variables.insert(var_name_node_ids, Value::Array(ids));
+        variables.extend(ctx_variables);
+
+        context
+            .execute(
+                executor_ref,
+                query_source,
+                Some("NodeQuery".to_owned()),
+                Some(variables.into()),
+                true,
+            )
+            .await
+    };
+
+    let (response, positions, fetched) = match batch {
+        Some((batch, positions)) => {
+            let (response, fetched) = context.node_batcher.load(&batch, execute).await;
+            (response, positions, fetched)
+        }
+        _ => {
+            let positions = (0..ids.len()).collect::<Vec<usize>>();
+            (Arc::new(execute(ids).await), positions, true)
+        }
+    };
+
+    let res = match response.as_ref() {
+        Ok(res) => res,
+        Err(e) => return Err(e.query_error(executor)),
+    };
+
+    if fetched {
+        context.memory.track(res)?;
+    }
+
+    if let (Some(tracing), Some(start_offset)) = (&context.tracing, start_offset) {
+        tracing.downstream(res, start_offset, |path| match path {
+            [_, Value::Number(position), rest @ ..] if rest.len() > 1 => {
+                let j = positions
+                    .iter()
+                    .position(|&p| Some(p as u64) == position.as_u64())?;
+                let mut path = paths.get(j)?.clone();
+                path.extend_from_slice(rest);
+                Some(path)
+            }
+            _ => None,
+        });
+    }
+
+    if res.get("errors").is_some() {
+        let errors = rewrite_node_paths(
+            executor_errors(executor, res, context.gateway.error_passthrough),
+            &lookup.field,
+            |position| {
+                positions
+                    .iter()
+                    .position(|&p| p == position)
+                    .map(|j| vec![Value::from("nodes"), Value::from(j)])
+            },
+        );
+
+        if !errors.is_empty() {
+            return Err(QueryError::Downstream(errors));
+        }
+    }
 
-    check_executor_response(res)
+    let fetched_nodes = res
+        .get("data")
+        .and_then(|data| data.get(&lookup.field))
+        .and_then(Value::as_array)
+        .ok_or(QueryError::InvalidExecutorResponse)?;
+
+    Ok(positions
+        .into_iter()
+        .map(|i| fetched_nodes.get(i).cloned().unwrap_or(Value::Null))
+        .collect())
 }
 
-fn check_executor_response(res: Value) -> QueryResult<Map<String, Value>> {
+fn check_executor_response(
+    context: &Context<'_, '_>,
+    executor: &str,
+    res: Value,
+) -> QueryResult<Map<String, Value>> {
     if res.get("errors").is_some() {
-        Err(QueryError::Executor(res))
+        Err(QueryError::Downstream(executor_errors(
+            executor,
+            &res,
+            context.gateway.error_passthrough,
+        )))
     } else {
         Ok(res
             .get("data")
@@ -617,20 +2544,35 @@ fn check_executor_response(res: Value) -> QueryResult<Map<String, Value>> {
     }
 }
 
-fn resolve_executors<'a, 'b>(
+pub(crate) fn resolve_executors<'a, 'b>(
     context: &Context<'a, 'b>,
     object_type: &Type,
     data: Option<&Value>,
     selections: &[Selection<'a, String>],
 ) -> QueryResult<Vec<String>> {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!(
+        "resolve_executors",
+        object_type = object_type.name(),
+        operation_name = context.operation_name.unwrap_or_default()
+    )
+    .entered();
+
     let mut executors = vec![];
     let mut cache = HashMap::new();
     let mut errors = Vec::new();
 
     for selection in selections {
+        if !context.is_included(selection) {
+            continue;
+        }
+
         match selection {
             Selection::Field(field) => {
-                if field.name.starts_with("__") {
+                if context.gateway.naming_policy.is_meta_field(&field.name)
+                    || is_static_field(context, object_type.name(), &field.name)
+                    || !context.is_authorized(object_type, &field.name)
+                {
                     continue;
                 }
 
@@ -638,6 +2580,18 @@ fn resolve_executors<'a, 'b>(
                     match context.field_object_type(object_type, &field.name) {
                         Some(field_type) => field_type,
                         _ => {
+                            if let Some(catch_all) = &context.gateway.catch_all_executor {
+                                let field_name = field.alias.as_ref().unwrap_or(&field.name);
+                                let field_data = data.and_then(|data| data.get(field_name));
+
+                                if !cache.contains_key(catch_all) && field_data.is_none() {
+                                    cache.insert(catch_all.clone(), true);
+                                    executors.push(catch_all.clone());
+                                }
+
+                                continue;
+                            }
+
                             errors.push(QueryPosError(
                                 field.position,
                                 QueryError::FieldNotFound(
@@ -710,19 +2664,8 @@ fn resolve_executors<'a, 'b>(
                 }
             }
             Selection::InlineFragment(inline_fragment) => {
-                let type_condition = match inline_fragment.type_condition.as_ref() {
-                    Some(type_condition) => type_condition,
-                    _ => {
-                        errors.push(QueryPosError(
-                            inline_fragment.position,
-                            QueryError::MissingTypeConditionInlineFragment,
-                        ));
-                        continue;
-                    }
-                };
-
-                let object_type = match type_condition {
-                    TypeCondition::On(v) => match context.object(v) {
+                let object_type = match inline_fragment.type_condition.as_ref() {
+                    Some(TypeCondition::On(v)) => match context.object(v) {
                         Some(object_type) => object_type,
                         _ => {
                             errors.push(QueryPosError(
@@ -732,6 +2675,7 @@ fn resolve_executors<'a, 'b>(
                             continue;
                         }
                     },
+                    _ => object_type,
                 };
 
                 let fragment_executors = resolve_executors(
@@ -761,7 +2705,7 @@ fn resolve_executors<'a, 'b>(
     }
 }
 
-fn resolve_executor<'a, 'b>(
+pub(crate) fn resolve_executor<'a, 'b>(
     context: &Context<'a, 'b>,
     object_type: &Type,
     selections: Vec<Selection<'a, String>>,
@@ -773,56 +2717,83 @@ fn resolve_executor<'a, 'b>(
     let mut errors = Vec::new();
 
     if !selections.is_empty() && object_type.is_node() {
-        let selection_field_id = selections
-            .iter()
-            .find_map(|selection| match selection {
-                Selection::Field(field) => {
-                    if field.name == "id" {
-                        Some(field.clone())
-                    } else {
-                        None
-                    }
-                }
-                _ => None,
-            })
-            .unwrap_or(Field {
-                position: Pos::default(),
-                alias: None,
-                name: "id".to_owned(),
-                arguments: vec![],
-                directives: vec![],
-                selection_set: SelectionSet {
-                    span: (Pos::default(), Pos::default()),
-                    items: vec![],
-                },
-            });
-
-        items.push(Selection::Field(selection_field_id));
+        items.extend(node_key_selections(
+            context,
+            object_type,
+            &executor,
+            &selections,
+        ));
     }
 
     for selection in selections {
+        if !context.is_included(&selection) {
+            continue;
+        }
+
         match selection {
             Selection::Field(field) => {
-                if field.name == "id" {
+                if field.name == "__typename" {
+                    if object_type.is_interface() || object_type.is_union() {
+                        push_typename(&mut items);
+                    }
+
                     continue;
                 }
 
-                let (mut field_executor, field_type) =
-                    match context.field_object_type(object_type, field.name.as_str()) {
-                        Some(field_type) => field_type,
-                        _ => {
-                            errors.push(QueryPosError(
-                                field.position,
-                                QueryError::FieldNotFound(
-                                    object_type.name().to_owned(),
-                                    field.name.clone(),
-                                ),
-                            ));
+                if (object_type.is_node()
+                    && object_type.key_fields().contains(&field.name.as_str()))
+                    || INTROSPECTION_FIELDS.contains(&field.name.as_str())
+                    || is_static_field(context, object_type.name(), &field.name)
+                    || !context.is_authorized(object_type, &field.name)
+                {
+                    continue;
+                }
+
+                let (mut field_executor, field_type) = match context
+                    .field_object_type(object_type, field.name.as_str())
+                {
+                    Some(field_type) => field_type,
+                    _ => {
+                        let catch_all =
+                            context.gateway.catch_all_executor.as_ref().filter(|_| {
+                                !context.gateway.naming_policy.is_meta_field(&field.name)
+                            });
+
+                        if let Some(catch_all) = catch_all {
+                            if catch_all == &executor {
+                                collect_passthrough(
+                                    context,
+                                    &field.selection_set.items,
+                                    &mut fragments,
+                                    &mut variable_definitions,
+                                );
+
+                                for (_, argument) in
+                                    field_arguments(&context.gateway.directives, &field)
+                                {
+                                    collect_variables(context, argument, &mut variable_definitions);
+                                }
+
+                                items.push(Selection::Field(field));
+                            }
+
                             continue;
                         }
-                    };
 
-                if field_type.is_interface() {
+                        errors.push(QueryPosError(
+                            field.position,
+                            QueryError::FieldNotFound(
+                                object_type.name().to_owned(),
+                                field.name.clone(),
+                            ),
+                        ));
+                        continue;
+                    }
+                };
+
+                if field_type.is_interface()
+                    || context.is_shared_field(object_type, &field.name, &executor)
+                {
                     field_executor = executor.clone();
                 }
 
@@ -830,19 +2801,41 @@ fn resolve_executor<'a, 'b>(
                     continue;
                 }
 
-                let field_variable_definitions = field
-                    .arguments
-                    .iter()
-                    .filter_map(|(name, argument)| match argument {
-                        AstValue::Variable(variable) => {
-                            let variable = context.variable_definitions.get(variable)?;
-                            Some((name.clone(), variable.clone()))
-                        }
-                        _ => None,
-                    })
-                    .collect::<HashMap<String, VariableDefinition<'a, String>>>();
+                let mut field_variable_definitions = HashMap::new();
+
+                for (_, argument) in field_arguments(&context.gateway.directives, &field) {
+                    collect_variables(context, argument, &mut field_variable_definitions);
+                }
 
                 let mut field = field.clone();
+
+                if let Some(codec) = context.id_codec(&executor) {
+                    if let Some((_, field_definition)) =
+                        context.field(object_type, field.name.as_str())
+                    {
+                        decode_arguments(
+                            codec,
+                            &executor,
+                            field_definition,
+                            &mut field.arguments,
+                            context.literals,
+                        );
+                    }
+                }
+
+                if !context.gateway.argument_injectors.is_empty() {
+                    if let Some((_, field_definition)) =
+                        context.field(object_type, field.name.as_str())
+                    {
+                        inject_arguments(
+                            &context.gateway.argument_injectors,
+                            context.data,
+                            field_definition,
+                            &mut field.arguments,
+                        );
+                    }
+                }
+
                 if !field.selection_set.items.is_empty() {
                     let result = resolve_executor(
                         context,
@@ -856,6 +2849,11 @@ fn resolve_executor<'a, 'b>(
                     }
 
                     field.selection_set.items = result.selections;
+
+                    if field_type.is_union() {
+                        push_typename(&mut field.selection_set.items);
+                    }
+
                     fragments.extend(result.fragments);
                     variable_definitions.extend(result.variable_definitions);
                 }
@@ -874,9 +2872,9 @@ fn resolve_executor<'a, 'b>(
                     }
                 };
 
-                let object_type = match &fragment.type_condition {
+                let fragment_type = match &fragment.type_condition {
                     TypeCondition::On(v) => match context.object(v) {
-                        Some(object_type) => object_type,
+                        Some(fragment_type) => fragment_type,
                         _ => {
                             errors.push(QueryPosError(
                                 fragment_spread.position,
@@ -887,14 +2885,38 @@ fn resolve_executor<'a, 'b>(
                     },
                 };
 
+                if !context.executor_has_type(&executor, fragment_type.name()) {
+                    continue;
+                }
+
                 let resolve_info = resolve_executor(
                     context,
-                    object_type,
+                    fragment_type,
                     fragment.selection_set.items.clone(),
                     executor.clone(),
                 )?;
 
-                if resolve_info.selections.len() <= 1 {
+                if resolve_info.selections.len() <= key_selections(fragment_type)
+                    && !object_type.is_union()
+                    && !object_type.is_interface()
+                {
+                    continue;
+                }
+
+                if fragment_type.name() != object_type.name() {
+                    fragments.extend(resolve_info.fragments);
+                    variable_definitions.extend(resolve_info.variable_definitions);
+
+                    items.push(Selection::InlineFragment(InlineFragment {
+                        position: fragment_spread.position,
+                        type_condition: Some(fragment.type_condition.clone()),
+                        directives: fragment_spread.directives,
+                        selection_set: SelectionSet {
+                            span: fragment.selection_set.span,
+                            items: resolve_info.selections,
+                        },
+                    }));
+
                     continue;
                 }
 
@@ -911,20 +2933,9 @@ fn resolve_executor<'a, 'b>(
                 variable_definitions.extend(resolve_info.variable_definitions);
             }
             Selection::InlineFragment(inline_fragment) => {
-                let type_condition = match inline_fragment.type_condition.as_ref() {
-                    Some(type_condition) => type_condition,
-                    _ => {
-                        errors.push(QueryPosError(
-                            inline_fragment.position,
-                            QueryError::MissingTypeConditionInlineFragment,
-                        ));
-                        continue;
-                    }
-                };
-
-                let object_type = match type_condition {
-                    TypeCondition::On(v) => match context.object(v) {
-                        Some(object_type) => object_type,
+                let fragment_type = match inline_fragment.type_condition.as_ref() {
+                    Some(TypeCondition::On(v)) => match context.object(v) {
+                        Some(fragment_type) => fragment_type,
                         _ => {
                             errors.push(QueryPosError(
                                 inline_fragment.position,
@@ -933,16 +2944,24 @@ fn resolve_executor<'a, 'b>(
                             continue;
                         }
                     },
+                    _ => object_type,
                 };
 
+                if !context.executor_has_type(&executor, fragment_type.name()) {
+                    continue;
+                }
+
                 let resolve_info = resolve_executor(
                     context,
-                    object_type,
+                    fragment_type,
                     inline_fragment.selection_set.items.clone(),
                     executor.clone(),
                 )?;
 
-                if resolve_info.selections.len() <= 1 {
+                if resolve_info.selections.len() <= key_selections(fragment_type)
+                    && !object_type.is_union()
+                    && !object_type.is_interface()
+                {
                     continue;
                 }
 
@@ -951,6 +2970,12 @@ fn resolve_executor<'a, 'b>(
                 fragments.extend(resolve_info.fragments);
                 variable_definitions.extend(resolve_info.variable_definitions);
 
+                for directive in &inline_fragment.directives {
+                    for (_, argument) in &directive.arguments {
+                        collect_variables(context, argument, &mut variable_definitions);
+                    }
+                }
+
                 items.push(Selection::InlineFragment(inline_fragment));
             }
         };
@@ -967,6 +2992,201 @@ fn resolve_executor<'a, 'b>(
     }
 }
 
+fn key_selections(object_type: &Type) -> usize {
+    if object_type.is_node() {
+        1
+    } else {
+        0
+    }
+}
+
+fn is_typename_selection(selection: &Selection<'_, String>) -> bool {
+    match selection {
+        Selection::Field(field) => field.name == "__typename" && field.alias.is_none(),
+        _ => false,
+    }
+}
+
+fn push_typename(selections: &mut Vec<Selection<'_, String>>) {
+    if !selections.iter().any(is_typename_selection) {
+        selections.push(Selection::Field(typename_field()));
+    }
+}
+
+fn typename_field<'a>() -> Field<'a, String> {
+    Field {
+        position: Pos::default(),
+        alias: None,
+        name: "__typename".to_owned(),
+        arguments: vec![],
+        directives: vec![],
+        selection_set: SelectionSet {
+            span: (Pos::default(), Pos::default()),
+            items: vec![],
+        },
+    }
+}
+
+fn node_key_selections<'a>(
+    context: &Context<'a, '_>,
+    object_type: &Type,
+    executor: &str,
+    selections: &[Selection<'a, String>],
+) -> Vec<Selection<'a, String>> {
+    object_type
+        .key_fields()
+        .into_iter()
+        .filter(|name| context.executor_has_field(executor, object_type.name(), name))
+        .map(|name| Selection::Field(node_key_field(selections, name)))
+        .collect()
+}
+
+fn node_key_field<'a>(selections: &[Selection<'a, String>], name: &str) -> Field<'a, String> {
+    selections
+        .iter()
+        .find_map(|selection| match selection {
+            Selection::Field(field) => {
+                if field.name == name {
+                    Some(field.clone())
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        })
+        .unwrap_or(Field {
+            position: Pos::default(),
+            alias: None,
+            name: name.to_owned(),
+            arguments: vec![],
+            directives: vec![],
+            selection_set: SelectionSet {
+                span: (Pos::default(), Pos::default()),
+                items: vec![],
+            },
+        })
+}
+
+fn field_arguments<'a, 'b>(
+    directives: &'b HashMap<String, GatewayDirective>,
+    field: &'b Field<'a, String>,
+) -> impl Iterator<Item = &'b (String, AstValue<'a, String>)> {
+    field.arguments.iter().chain(
+        field
+            .directives
+            .iter()
+            .filter(move |directive| !directives.contains_key(&directive.name))
+            .flat_map(|directive| directive.arguments.iter()),
+    )
+}
+
+pub(crate) fn collect_passthrough<'a>(
+    context: &Context<'a, '_>,
+    selections: &[Selection<'a, String>],
+    fragments: &mut HashMap<String, FragmentDefinition<'a, String>>,
+    variable_definitions: &mut HashMap<String, VariableDefinition<'a, String>>,
+) {
+    for selection in selections {
+        match selection {
+            Selection::Field(field) => {
+                for (_, argument) in field_arguments(&context.gateway.directives, field) {
+                    collect_variables(context, argument, variable_definitions);
+                }
+
+                collect_passthrough(
+                    context,
+                    &field.selection_set.items,
+                    fragments,
+                    variable_definitions,
+                );
+            }
+            Selection::FragmentSpread(fragment_spread) => {
+                for directive in &fragment_spread.directives {
+                    for (_, argument) in &directive.arguments {
+                        collect_variables(context, argument, variable_definitions);
+                    }
+                }
+
+                let fragment = match context.fragments.get(&fragment_spread.fragment_name) {
+                    Some(fragment) if !fragments.contains_key(&fragment.name) => fragment,
+                    _ => continue,
+                };
+
+                fragments.insert(fragment.name.clone(), fragment.clone());
+                collect_passthrough(
+                    context,
+                    &fragment.selection_set.items,
+                    fragments,
+                    variable_definitions,
+                );
+            }
+            Selection::InlineFragment(inline_fragment) => {
+                for directive in &inline_fragment.directives {
+                    for (_, argument) in &directive.arguments {
+                        collect_variables(context, argument, variable_definitions);
+                    }
+                }
+
+                collect_passthrough(
+                    context,
+                    &inline_fragment.selection_set.items,
+                    fragments,
+                    variable_definitions,
+                )
+            }
+        }
+    }
+}
+
+fn collect_variables<'a>(
+    context: &Context<'a, '_>,
+    value: &AstValue<'a, String>,
+    variable_definitions: &mut HashMap<String, VariableDefinition<'a, String>>,
+) {
+    match value {
+        AstValue::Variable(name) => {
+            if let Some(variable_definition) = context.variable_definitions.get(name) {
+                variable_definitions.insert(name.clone(), variable_definition.clone());
+            }
+        }
+        AstValue::List(values) => {
+            for value in values {
+                collect_variables(context, value, variable_definitions);
+            }
+        }
+        AstValue::Object(values) => {
+            for value in values.values() {
+                collect_variables(context, value, variable_definitions);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn executor_variables(
+    context: &Context<'_, '_>,
+    executor: &str,
+    variable_definitions: &HashMap<String, VariableDefinition<'_, String>>,
+) -> Map<String, Value> {
+    let variables = match (context.id_codec(executor), context.variables) {
+        (Some(codec), Some(variables)) => Some(decode_variables(
+            codec,
+            executor,
+            &context.variable_definitions,
+            variables,
+        )),
+        (_, variables) => variables.cloned(),
+    };
+
+    match variables {
+        Some(Value::Object(variables)) => variables
+            .into_iter()
+            .filter(|(name, _)| variable_definitions.contains_key(name))
+            .collect(),
+        _ => Map::new(),
+    }
+}
+
 fn merge_object(a: &mut Map<String, Value>, b: Map<String, Value>) {
     for (key, value) in b {
         match a.get_mut(&key) {
@@ -0,0 +1,99 @@
+use serde_json::{Map, Value};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RequestContext {
+    pub headers: Vec<(String, String)>,
+    pub metadata: Map<String, Value>,
+}
+
+impl RequestContext {
+    pub fn new() -> Self {
+        RequestContext::default()
+    }
+
+    pub fn header<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.headers.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn metadata<K: Into<String>, V: Into<Value>>(mut self, key: K, value: V) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn get_header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub(crate) fn propagate(&self, policy: Option<&HeaderPolicy>) -> RequestContext {
+        RequestContext {
+            headers: policy
+                .map(|policy| policy.apply(&self.headers))
+                .unwrap_or_default(),
+            metadata: self.metadata.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HeaderPolicy {
+    pub allow: Vec<String>,
+    pub rename: Vec<(String, String)>,
+    pub inject: Vec<(String, String)>,
+}
+
+impl HeaderPolicy {
+    pub fn new() -> Self {
+        HeaderPolicy::default()
+    }
+
+    pub fn allow<T: Into<String>>(mut self, header: T) -> Self {
+        self.allow.push(header.into());
+        self
+    }
+
+    pub fn rename<F: Into<String>, T: Into<String>>(mut self, from: F, to: T) -> Self {
+        self.rename.push((from.into(), to.into()));
+        self
+    }
+
+    pub fn inject<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.inject.push((key.into(), value.into()));
+        self
+    }
+
+    pub fn apply(&self, headers: &[(String, String)]) -> Vec<(String, String)> {
+        let mut propagated = headers
+            .iter()
+            .filter_map(|(key, value)| {
+                let renamed = self
+                    .rename
+                    .iter()
+                    .find(|(from, _)| from.eq_ignore_ascii_case(key))
+                    .map(|(_, to)| to.clone());
+
+                match renamed {
+                    Some(key) => Some((key, value.clone())),
+                    _ if self
+                        .allow
+                        .iter()
+                        .any(|header| header.eq_ignore_ascii_case(key)) =>
+                    {
+                        Some((key.clone(), value.clone()))
+                    }
+                    _ => None,
+                }
+            })
+            .collect::<Vec<(String, String)>>();
+
+        for (key, value) in &self.inject {
+            propagated.retain(|(current, _)| !current.eq_ignore_ascii_case(key));
+            propagated.push((key.clone(), value.clone()));
+        }
+
+        propagated
+    }
+}
@@ -0,0 +1,102 @@
+use crate::builder::RetryPolicy;
+use crate::data::Data;
+use crate::error_class::ErrorClassifier;
+use crate::executor::Executor;
+use crate::query::QueryError;
+use futures::future::{self, Either};
+use futures_timer::Delay;
+use serde_json::Value;
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ExecutorFailure {
+    Error(String),
+    Timeout(Duration),
+    CircuitOpen,
+}
+
+impl ExecutorFailure {
+    pub(crate) fn query_error(&self, executor: &str) -> QueryError {
+        match self {
+            ExecutorFailure::Error(e) => QueryError::Transport(executor.to_owned(), e.clone()),
+            ExecutorFailure::Timeout(timeout) => {
+                QueryError::ExecutorTimeout(executor.to_owned(), timeout.as_millis() as u64)
+            }
+            ExecutorFailure::CircuitOpen => QueryError::CircuitOpen(executor.to_owned()),
+        }
+    }
+
+    pub(crate) fn is_retryable(&self, executor: &str, classifier: &dyn ErrorClassifier) -> bool {
+        classifier
+            .classify(&self.query_error(executor))
+            .is_retryable()
+    }
+}
+
+pub(crate) fn is_executor_error(error: &QueryError) -> bool {
+    matches!(
+        error,
+        QueryError::Transport(..)
+            | QueryError::ExecutorTimeout(..)
+            | QueryError::CircuitOpen(..)
+            | QueryError::Executor(_)
+            | QueryError::Downstream(_)
+            | QueryError::InvalidExecutorResponse
+    )
+}
+
+pub(crate) async fn dispatch(
+    executor: &dyn Executor,
+    timeout: Option<Duration>,
+    retry: Option<(RetryPolicy, &dyn ErrorClassifier)>,
+    data: Option<&Data>,
+    query: String,
+    operation_name: Option<String>,
+    variables: Option<Value>,
+) -> Result<Value, ExecutorFailure> {
+    let mut attempt = 1;
+
+    loop {
+        let result = execute_once(
+            executor,
+            timeout,
+            data,
+            query.clone(),
+            operation_name.clone(),
+            variables.clone(),
+        )
+        .await;
+
+        match (retry, &result) {
+            (Some((retry, classifier)), Err(failure))
+                if attempt < retry.max_attempts
+                    && failure.is_retryable(executor.name(), classifier) =>
+            {
+                Delay::new(retry.delay(attempt)).await;
+                attempt += 1;
+            }
+            _ => return result,
+        }
+    }
+}
+
+async fn execute_once(
+    executor: &dyn Executor,
+    timeout: Option<Duration>,
+    data: Option<&Data>,
+    query: String,
+    operation_name: Option<String>,
+    variables: Option<Value>,
+) -> Result<Value, ExecutorFailure> {
+    let execute = executor.execute(data, query, operation_name, variables);
+
+    let timeout = match timeout {
+        Some(timeout) => timeout,
+        _ => return execute.await.map_err(ExecutorFailure::Error),
+    };
+
+    match future::select(execute, Delay::new(timeout)).await {
+        Either::Left((result, _)) => result.map_err(ExecutorFailure::Error),
+        Either::Right(_) => Err(ExecutorFailure::Timeout(timeout)),
+    }
+}
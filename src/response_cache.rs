@@ -0,0 +1,190 @@
+use crate::literals::{scan_string, scan_while};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ResponseCacheKey {
+    pub executor: String,
+    pub query: String,
+    pub variables: String,
+}
+
+impl ResponseCacheKey {
+    pub(crate) fn new(executor: &str, query: &str, variables: Option<&Value>) -> Self {
+        ResponseCacheKey {
+            executor: executor.to_owned(),
+            query: normalize_whitespace(query),
+            variables: variables.map(Value::to_string).unwrap_or_default(),
+        }
+    }
+}
+
+fn normalize_whitespace(query: &str) -> String {
+    let chars = query.char_indices().collect::<Vec<(usize, char)>>();
+    let mut output = String::with_capacity(query.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let (start, c) = chars[i];
+
+        if c.is_whitespace() {
+            i = scan_while(&chars, i, char::is_whitespace);
+
+            if !output.is_empty() && i < chars.len() {
+                output.push(' ');
+            }
+
+            continue;
+        }
+
+        let end = if c == '"' {
+            scan_string(&chars, i)
+        } else {
+            i + 1
+        };
+        let end_offset = chars
+            .get(end)
+            .map(|&(offset, _)| offset)
+            .unwrap_or(query.len());
+
+        output.push_str(&query[start..end_offset]);
+        i = end;
+    }
+
+    output
+}
+
+pub trait ResponseCache: Send + Sync {
+    fn get(&self, key: &ResponseCacheKey) -> Option<Value>;
+    fn insert(&self, key: ResponseCacheKey, value: Value, ttl: Duration);
+}
+
+struct LruEntry {
+    value: Value,
+    expires_at: Instant,
+    used_at: u64,
+}
+
+#[derive(Default)]
+struct LruState {
+    entries: HashMap<ResponseCacheKey, LruEntry>,
+    recency: BTreeMap<u64, ResponseCacheKey>,
+    clock: u64,
+}
+
+impl LruState {
+    fn touch(&mut self, key: &ResponseCacheKey) {
+        self.clock += 1;
+        let clock = self.clock;
+
+        if let Some(entry) = self.entries.get_mut(key) {
+            self.recency.remove(&entry.used_at);
+            self.recency.insert(clock, key.clone());
+            entry.used_at = clock;
+        }
+    }
+
+    fn remove(&mut self, key: &ResponseCacheKey) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.recency.remove(&entry.used_at);
+        }
+    }
+}
+
+pub struct LruResponseCache {
+    capacity: usize,
+    state: Mutex<LruState>,
+}
+
+impl LruResponseCache {
+    pub fn new(capacity: usize) -> Self {
+        LruResponseCache {
+            capacity,
+            state: Mutex::new(LruState::default()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.state
+            .lock()
+            .expect("Response cache lock is poisoned.")
+            .entries
+            .len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl ResponseCache for LruResponseCache {
+    fn get(&self, key: &ResponseCacheKey) -> Option<Value> {
+        let mut state = self.state.lock().expect("Response cache lock is poisoned.");
+
+        match state.entries.get(key) {
+            Some(entry) if entry.expires_at > Instant::now() => {
+                let value = entry.value.clone();
+                state.touch(key);
+                Some(value)
+            }
+            Some(_) => {
+                state.remove(key);
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn insert(&self, key: ResponseCacheKey, value: Value, ttl: Duration) {
+        if self.capacity == 0 || ttl == Duration::from_secs(0) {
+            return;
+        }
+
+        let mut state = self.state.lock().expect("Response cache lock is poisoned.");
+        state.remove(&key);
+
+        while state.entries.len() >= self.capacity {
+            let oldest = match state.recency.values().next() {
+                Some(oldest) => oldest.clone(),
+                _ => break,
+            };
+
+            state.remove(&oldest);
+        }
+
+        state.clock += 1;
+        let used_at = state.clock;
+
+        state.recency.insert(used_at, key.clone());
+        state.entries.insert(
+            key,
+            LruEntry {
+                value,
+                expires_at: Instant::now() + ttl,
+                used_at,
+            },
+        );
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct RequestCache(Mutex<HashMap<ResponseCacheKey, Value>>);
+
+impl RequestCache {
+    pub(crate) fn get(&self, key: &ResponseCacheKey) -> Option<Value> {
+        self.0
+            .lock()
+            .expect("Response cache lock is poisoned.")
+            .get(key)
+            .cloned()
+    }
+
+    pub(crate) fn insert(&self, key: ResponseCacheKey, value: Value) {
+        self.0
+            .lock()
+            .expect("Response cache lock is poisoned.")
+            .insert(key, value);
+    }
+}
@@ -0,0 +1,220 @@
+use crate::data::Data;
+use crate::executor::Executor;
+use crate::gateway::Gateway;
+use crate::http::GraphQLResponse;
+use crate::query::QueryBuilder;
+use crate::schema::Schema;
+use async_trait::async_trait;
+use serde_json::Value;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct Scenario {
+    pub name: String,
+    pub executors: Vec<ScenarioExecutor>,
+    pub query: String,
+    #[serde(rename = "operationName")]
+    pub operation_name: Option<String>,
+    pub variables: Option<Value>,
+    pub expected: Value,
+    pub plan: Option<Vec<ScenarioCall>>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct ScenarioExecutor {
+    pub name: String,
+    pub schema: Schema,
+    #[serde(default)]
+    pub responses: Vec<ScenarioResponse>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct ScenarioResponse {
+    pub query: Option<String>,
+    pub response: Value,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ScenarioCall {
+    pub executor: String,
+    pub query: String,
+    pub variables: Option<Value>,
+}
+
+#[derive(Debug)]
+pub struct ScenarioOutcome {
+    pub response: Value,
+    pub calls: Vec<ScenarioCall>,
+}
+
+impl Scenario {
+    pub fn from_json(source: &str) -> Result<Scenario, String> {
+        serde_json::from_str(source).map_err(|e| e.to_string())
+    }
+
+    pub fn from_yaml(source: &str) -> Result<Scenario, String> {
+        serde_yaml::from_str(source).map_err(|e| e.to_string())
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Scenario, String> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("yaml") | Some("yml") => Scenario::from_yaml(&source),
+            _ => Scenario::from_json(&source),
+        }
+    }
+
+    pub async fn run(&self) -> Result<ScenarioOutcome, String> {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let mut gateway = Gateway::default();
+
+        for executor in &self.executors {
+            gateway = gateway.executor(FixtureExecutor {
+                fixture: Arc::new(executor.clone()),
+                calls: calls.clone(),
+                position: Arc::new(AtomicUsize::new(0)),
+            });
+        }
+
+        let gateway = gateway.build().await.map_err(|e| e.to_string())?;
+        let mut query = QueryBuilder::new(self.query.as_str());
+
+        if let Some(operation_name) = &self.operation_name {
+            query = query.operation_name(operation_name.as_str());
+        }
+
+        if let Some(variables) = &self.variables {
+            query = query.variables(variables.clone());
+        }
+
+        let response = serde_json::to_value(GraphQLResponse(query.execute(&gateway).await))
+            .map_err(|e| e.to_string())?;
+        let calls = calls
+            .lock()
+            .expect("Scenario calls lock is poisoned.")
+            .clone();
+
+        Ok(ScenarioOutcome { response, calls })
+    }
+
+    pub async fn check(&self) -> Result<ScenarioOutcome, String> {
+        let outcome = self.run().await?;
+
+        if outcome.response != self.expected {
+            return Err(format!(
+                "Scenario \"{}\": expected response {} but got {}",
+                self.name, self.expected, outcome.response
+            ));
+        }
+
+        let plan = match &self.plan {
+            Some(plan) => plan,
+            _ => return Ok(outcome),
+        };
+
+        if plan.len() != outcome.calls.len() {
+            return Err(format!(
+                "Scenario \"{}\": expected {} executor calls but got {}: {:#?}",
+                self.name,
+                plan.len(),
+                outcome.calls.len(),
+                outcome.calls
+            ));
+        }
+
+        for (expected, call) in plan.iter().zip(outcome.calls.iter()) {
+            let variables_match = match &expected.variables {
+                Some(variables) => Some(variables) == call.variables.as_ref(),
+                _ => true,
+            };
+
+            if expected.executor != call.executor
+                || normalize_query(&expected.query) != normalize_query(&call.query)
+                || !variables_match
+            {
+                return Err(format!(
+                    "Scenario \"{}\": expected executor call {:#?} but got {:#?}",
+                    self.name, expected, call
+                ));
+            }
+        }
+
+        Ok(outcome)
+    }
+}
+
+#[derive(Clone)]
+struct FixtureExecutor {
+    fixture: Arc<ScenarioExecutor>,
+    calls: Arc<Mutex<Vec<ScenarioCall>>>,
+    position: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl Executor for FixtureExecutor {
+    fn name(&self) -> &str {
+        &self.fixture.name
+    }
+
+    async fn execute(
+        &self,
+        _data: Option<&Data>,
+        query: String,
+        _operation_name: Option<String>,
+        variables: Option<Value>,
+    ) -> Result<Value, String> {
+        self.calls
+            .lock()
+            .expect("Scenario calls lock is poisoned.")
+            .push(ScenarioCall {
+                executor: self.fixture.name.clone(),
+                query: query.clone(),
+                variables,
+            });
+
+        let normalized_query = normalize_query(&query);
+        let response = self
+            .fixture
+            .responses
+            .iter()
+            .find(|response| {
+                response
+                    .query
+                    .as_ref()
+                    .map(|query| normalize_query(query) == normalized_query)
+                    .unwrap_or(false)
+            })
+            .or_else(|| {
+                let position = self.position.fetch_add(1, Ordering::SeqCst);
+
+                self.fixture
+                    .responses
+                    .iter()
+                    .filter(|response| response.query.is_none())
+                    .nth(position)
+            })
+            .ok_or_else(|| {
+                format!(
+                    "No canned response for executor \"{}\": {}",
+                    self.fixture.name, query
+                )
+            })?;
+
+        Ok(response.response.clone())
+    }
+
+    async fn introspect(&self, _data: Option<&Data>) -> Result<(String, Schema), String> {
+        Ok((self.fixture.name.clone(), self.fixture.schema.clone()))
+    }
+}
+
+fn normalize_query(query: &str) -> String {
+    match graphql_parser::parse_query::<String>(query) {
+        Ok(document) => document.to_string(),
+        _ => query.split_whitespace().collect::<Vec<&str>>().join(" "),
+    }
+}
@@ -1,4 +1,6 @@
+use graphql_parser::query::{Definition, OperationDefinition, Selection, Value};
 use graphql_parser::{schema, Pos};
+use std::collections::HashMap;
 use std::fmt;
 
 #[derive(Serialize, Deserialize, Clone, Default, Debug)]
@@ -29,6 +31,8 @@ pub struct Type {
   pub input_fields: Option<Vec<InputValue>>,
   #[serde(rename = "ofType")]
   pub of_type: Option<Box<Type>>,
+  #[serde(skip)]
+  pub keys: Vec<String>,
 }
 
 impl Type {
@@ -48,7 +52,41 @@ impl Type {
     self.kind == TypeKind::Interface
   }
 
+  pub fn is_union(&self) -> bool {
+    self.kind == TypeKind::Union
+  }
+
   pub fn is_node(&self) -> bool {
+    self.entity_key().is_some() || self.implements_node()
+  }
+
+  pub fn entity_key(&self) -> Option<Vec<&str>> {
+    self
+      .keys
+      .iter()
+      .find(|key| !key.contains('{'))
+      .map(|key| key.split_whitespace().collect())
+  }
+
+  pub fn key_fields(&self) -> Vec<&str> {
+    let mut fields = vec![];
+
+    if self.implements_node() {
+      fields.push("id");
+    }
+
+    for key in self.keys.iter().filter(|key| !key.contains('{')) {
+      for field in key.split_whitespace() {
+        if !fields.contains(&field) {
+          fields.push(field);
+        }
+      }
+    }
+
+    fields
+  }
+
+  pub fn implements_node(&self) -> bool {
     match self.interfaces.as_ref() {
       Some(interfaces) => interfaces
         .iter()
@@ -75,6 +113,8 @@ pub struct Field {
   pub is_deprecated: bool,
   #[serde(rename = "deprecationReason")]
   pub deprecation_reason: Option<String>,
+  #[serde(skip)]
+  pub cost: Option<usize>,
 }
 
 impl Field {
@@ -89,7 +129,7 @@ impl fmt::Display for Field {
   }
 }
 
-fn get_final_field_type(field_type: &Type) -> &Type {
+pub(crate) fn get_final_field_type(field_type: &Type) -> &Type {
   match field_type.kind {
     TypeKind::List | TypeKind::NonNull => get_final_field_type(field_type.of_type()),
     _ => field_type,
@@ -106,6 +146,42 @@ pub struct InputValue {
   pub default_value: Option<String>,
 }
 
+impl InputValue {
+  pub(crate) fn parse_default_value<'a>(&self) -> Option<Value<'a, String>> {
+    let source = format!("{{ field(value: {}) }}", self.default_value.as_ref()?);
+    let document = graphql_parser::parse_query::<String>(&source).ok()?;
+
+    let selection_set = match document.definitions.first()? {
+      Definition::Operation(OperationDefinition::SelectionSet(selection_set)) => selection_set,
+      _ => return None,
+    };
+
+    match selection_set.items.first()? {
+      Selection::Field(field) => field.arguments.first().map(|(_, value)| owned_value(value)),
+      _ => None,
+    }
+  }
+}
+
+fn owned_value<'a>(value: &Value<'_, String>) -> Value<'a, String> {
+  match value {
+    Value::Variable(name) => Value::Variable(name.clone()),
+    Value::Int(number) => Value::Int(number.clone()),
+    Value::Float(number) => Value::Float(*number),
+    Value::String(value) => Value::String(value.clone()),
+    Value::Boolean(value) => Value::Boolean(*value),
+    Value::Null => Value::Null,
+    Value::Enum(value) => Value::Enum(value.clone()),
+    Value::List(values) => Value::List(values.iter().map(owned_value).collect()),
+    Value::Object(values) => Value::Object(
+      values
+        .iter()
+        .map(|(key, value)| (key.clone(), owned_value(value)))
+        .collect(),
+    ),
+  }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct EnumValue {
   pub name: String,
@@ -116,9 +192,10 @@ pub struct EnumValue {
   pub deprecation_reason: Option<String>,
 }
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
 pub enum TypeKind {
   #[serde(rename = "SCALAR")]
+  #[default]
   Scalar,
   #[serde(rename = "OBJECT")]
   Object,
@@ -136,12 +213,6 @@ pub enum TypeKind {
   NonNull,
 }
 
-impl Default for TypeKind {
-  fn default() -> Self {
-    TypeKind::Scalar
-  }
-}
-
 impl fmt::Display for TypeKind {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     match self {
@@ -205,28 +276,28 @@ pub enum DirectiveLocation {
   InputFieldDefinition,
 }
 
-impl<'a> Into<schema::Definition<'a, String>> for Type {
-  fn into(self) -> schema::Definition<'a, String> {
-    let name = self.name.expect("Type name does not exist.");
-    let type_definition = match self.kind {
+impl<'a> From<Type> for schema::Definition<'a, String> {
+  fn from(value: Type) -> schema::Definition<'a, String> {
+    let name = value.name.expect("Type name does not exist.");
+    let type_definition = match value.kind {
       TypeKind::Scalar => schema::TypeDefinition::Scalar(schema::ScalarType {
         position: Pos::default(),
-        description: self.description,
+        description: value.description,
         name,
         directives: vec![],
       }),
       TypeKind::Object => schema::TypeDefinition::Object(schema::ObjectType {
         position: Pos::default(),
-        description: self.description,
+        description: value.description,
         name,
-        implements_interfaces: self
+        implements_interfaces: value
           .interfaces
           .expect("Type interfaces does not exist.")
           .into_iter()
           .map(|interface| interface.name().to_owned())
           .collect(),
         directives: vec![],
-        fields: self
+        fields: value
           .fields
           .expect("Type fields does not exist.")
           .into_iter()
@@ -235,10 +306,10 @@ impl<'a> Into<schema::Definition<'a, String>> for Type {
       }),
       TypeKind::Interface => schema::TypeDefinition::Interface(schema::InterfaceType {
         position: Pos::default(),
-        description: self.description,
+        description: value.description,
         name,
         directives: vec![],
-        fields: self
+        fields: value
           .fields
           .expect("Type fields does not exist.")
           .into_iter()
@@ -247,10 +318,10 @@ impl<'a> Into<schema::Definition<'a, String>> for Type {
       }),
       TypeKind::InputObject => schema::TypeDefinition::InputObject(schema::InputObjectType {
         position: Pos::default(),
-        description: self.description,
+        description: value.description,
         name,
         directives: vec![],
-        fields: self
+        fields: value
           .input_fields
           .expect("Type input_fields does not exist.")
           .into_iter()
@@ -259,10 +330,10 @@ impl<'a> Into<schema::Definition<'a, String>> for Type {
       }),
       TypeKind::Enum => schema::TypeDefinition::Enum(schema::EnumType {
         position: Pos::default(),
-        description: self.description,
+        description: value.description,
         name,
         directives: vec![],
-        values: self
+        values: value
           .enum_values
           .expect("Type enum_values does not exist.")
           .into_iter()
@@ -271,10 +342,10 @@ impl<'a> Into<schema::Definition<'a, String>> for Type {
       }),
       TypeKind::Union => schema::TypeDefinition::Union(schema::UnionType {
         position: Pos::default(),
-        description: self.description,
+        description: value.description,
         name,
         directives: vec![],
-        types: self
+        types: value
           .possible_types
           .expect("Type possible_types does not exist.")
           .into_iter()
@@ -288,49 +359,584 @@ impl<'a> Into<schema::Definition<'a, String>> for Type {
   }
 }
 
-impl<'a> Into<schema::Field<'a, String>> for Field {
-  fn into(self) -> schema::Field<'a, String> {
+impl<'a> From<Field> for schema::Field<'a, String> {
+  fn from(value: Field) -> schema::Field<'a, String> {
     schema::Field {
       position: Pos::default(),
-      description: self.description,
-      name: self.name,
-      directives: vec![],
-      field_type: self.field_type.into(),
-      arguments: self.args.into_iter().map(|arg| arg.into()).collect(),
+      description: value.description,
+      name: value.name,
+      directives: deprecated_directive(value.is_deprecated, value.deprecation_reason),
+      field_type: value.field_type.into(),
+      arguments: value.args.into_iter().map(|arg| arg.into()).collect(),
     }
   }
 }
 
-impl<'a> Into<schema::InputValue<'a, String>> for InputValue {
-  fn into(self) -> schema::InputValue<'a, String> {
+impl<'a> From<InputValue> for schema::InputValue<'a, String> {
+  fn from(value: InputValue) -> schema::InputValue<'a, String> {
+    let default_value = value.parse_default_value();
+
     schema::InputValue {
       position: Pos::default(),
-      description: self.description,
-      name: self.name,
+      description: value.description,
+      name: value.name,
       directives: vec![],
-      value_type: self.input_type.into(),
-      default_value: None,
+      value_type: value.input_type.into(),
+      default_value,
     }
   }
 }
 
-impl<'a> Into<schema::Type<'a, String>> for Type {
-  fn into(self) -> schema::Type<'a, String> {
-    match self.kind {
-      TypeKind::NonNull => schema::Type::NonNullType(Box::new(self.of_type().clone().into())),
-      TypeKind::List => schema::Type::ListType(Box::new(self.of_type().clone().into())),
-      _ => schema::Type::NamedType(self.name().to_owned()),
+impl<'a> From<Type> for schema::Type<'a, String> {
+  fn from(value: Type) -> schema::Type<'a, String> {
+    match value.kind {
+      TypeKind::NonNull => schema::Type::NonNullType(Box::new(value.of_type().clone().into())),
+      TypeKind::List => schema::Type::ListType(Box::new(value.of_type().clone().into())),
+      _ => schema::Type::NamedType(value.name().to_owned()),
     }
   }
 }
 
-impl<'a> Into<schema::EnumValue<'a, String>> for EnumValue {
-  fn into(self) -> schema::EnumValue<'a, String> {
+impl<'a> From<EnumValue> for schema::EnumValue<'a, String> {
+  fn from(value: EnumValue) -> schema::EnumValue<'a, String> {
     schema::EnumValue {
       position: Pos::default(),
-      description: self.description,
-      name: self.name,
-      directives: vec![],
+      description: value.description,
+      name: value.name,
+      directives: deprecated_directive(value.is_deprecated, value.deprecation_reason),
+    }
+  }
+}
+
+impl<'a> From<Directive> for schema::Definition<'a, String> {
+  fn from(value: Directive) -> schema::Definition<'a, String> {
+    schema::Definition::DirectiveDefinition(schema::DirectiveDefinition {
+      position: Pos::default(),
+      description: value.description,
+      name: value.name,
+      arguments: value.args.into_iter().map(|arg| arg.into()).collect(),
+      locations: value
+        .locations
+        .into_iter()
+        .map(|location| location.into())
+        .collect(),
+    })
+  }
+}
+
+impl From<DirectiveLocation> for schema::DirectiveLocation {
+  fn from(value: DirectiveLocation) -> schema::DirectiveLocation {
+    match value {
+      DirectiveLocation::Query => schema::DirectiveLocation::Query,
+      DirectiveLocation::Mutation => schema::DirectiveLocation::Mutation,
+      DirectiveLocation::Subscription => schema::DirectiveLocation::Subscription,
+      DirectiveLocation::Field => schema::DirectiveLocation::Field,
+      DirectiveLocation::FragmentDefinition => schema::DirectiveLocation::FragmentDefinition,
+      DirectiveLocation::FragmentSpread => schema::DirectiveLocation::FragmentSpread,
+      DirectiveLocation::InlineFragment => schema::DirectiveLocation::InlineFragment,
+      DirectiveLocation::Schema => schema::DirectiveLocation::Schema,
+      DirectiveLocation::Scalar => schema::DirectiveLocation::Scalar,
+      DirectiveLocation::Object => schema::DirectiveLocation::Object,
+      DirectiveLocation::FieldDefinition => schema::DirectiveLocation::FieldDefinition,
+      DirectiveLocation::ArgumentDefinition => schema::DirectiveLocation::ArgumentDefinition,
+      DirectiveLocation::Interface => schema::DirectiveLocation::Interface,
+      DirectiveLocation::Union => schema::DirectiveLocation::Union,
+      DirectiveLocation::Enum => schema::DirectiveLocation::Enum,
+      DirectiveLocation::EnumValue => schema::DirectiveLocation::EnumValue,
+      DirectiveLocation::InputObject => schema::DirectiveLocation::InputObject,
+      DirectiveLocation::InputFieldDefinition => schema::DirectiveLocation::InputFieldDefinition,
     }
   }
 }
+
+fn deprecated_directive<'a>(
+  is_deprecated: bool,
+  reason: Option<String>,
+) -> Vec<schema::Directive<'a, String>> {
+  if !is_deprecated {
+    return vec![];
+  }
+
+  vec![schema::Directive {
+    position: Pos::default(),
+    name: "deprecated".to_owned(),
+    arguments: reason
+      .map(|reason| vec![("reason".to_owned(), Value::String(reason))])
+      .unwrap_or_default(),
+  }]
+}
+
+impl From<schema::DirectiveLocation> for DirectiveLocation {
+  fn from(value: schema::DirectiveLocation) -> DirectiveLocation {
+    match value {
+      schema::DirectiveLocation::Query => DirectiveLocation::Query,
+      schema::DirectiveLocation::Mutation => DirectiveLocation::Mutation,
+      schema::DirectiveLocation::Subscription => DirectiveLocation::Subscription,
+      schema::DirectiveLocation::Field => DirectiveLocation::Field,
+      schema::DirectiveLocation::FragmentDefinition => DirectiveLocation::FragmentDefinition,
+      schema::DirectiveLocation::FragmentSpread => DirectiveLocation::FragmentSpread,
+      schema::DirectiveLocation::InlineFragment => DirectiveLocation::InlineFragment,
+      schema::DirectiveLocation::Schema => DirectiveLocation::Schema,
+      schema::DirectiveLocation::Scalar => DirectiveLocation::Scalar,
+      schema::DirectiveLocation::Object => DirectiveLocation::Object,
+      schema::DirectiveLocation::FieldDefinition => DirectiveLocation::FieldDefinition,
+      schema::DirectiveLocation::ArgumentDefinition => DirectiveLocation::ArgumentDefinition,
+      schema::DirectiveLocation::Interface => DirectiveLocation::Interface,
+      schema::DirectiveLocation::Union => DirectiveLocation::Union,
+      schema::DirectiveLocation::Enum => DirectiveLocation::Enum,
+      schema::DirectiveLocation::EnumValue => DirectiveLocation::EnumValue,
+      schema::DirectiveLocation::InputObject => DirectiveLocation::InputObject,
+      schema::DirectiveLocation::InputFieldDefinition => DirectiveLocation::InputFieldDefinition,
+    }
+  }
+}
+
+const BUILTIN_SDL: &str = r#"
+scalar String
+scalar Int
+scalar Float
+scalar Boolean
+scalar ID
+
+directive @skip(if: Boolean!) on FIELD | FRAGMENT_SPREAD | INLINE_FRAGMENT
+directive @include(if: Boolean!) on FIELD | FRAGMENT_SPREAD | INLINE_FRAGMENT
+directive @deprecated(reason: String = "No longer supported") on FIELD_DEFINITION | ENUM_VALUE
+
+type __Schema {
+  types: [__Type!]!
+  queryType: __Type!
+  mutationType: __Type
+  subscriptionType: __Type
+  directives: [__Directive!]!
+}
+
+type __Type {
+  kind: __TypeKind!
+  name: String
+  description: String
+  fields(includeDeprecated: Boolean = false): [__Field!]
+  interfaces: [__Type!]
+  possibleTypes: [__Type!]
+  enumValues(includeDeprecated: Boolean = false): [__EnumValue!]
+  inputFields: [__InputValue!]
+  ofType: __Type
+}
+
+type __Field {
+  name: String!
+  description: String
+  args: [__InputValue!]!
+  type: __Type!
+  isDeprecated: Boolean!
+  deprecationReason: String
+}
+
+type __InputValue {
+  name: String!
+  description: String
+  type: __Type!
+  defaultValue: String
+}
+
+type __EnumValue {
+  name: String!
+  description: String
+  isDeprecated: Boolean!
+  deprecationReason: String
+}
+
+type __Directive {
+  name: String!
+  description: String
+  locations: [__DirectiveLocation!]!
+  args: [__InputValue!]!
+}
+
+enum __TypeKind {
+  SCALAR
+  OBJECT
+  INTERFACE
+  UNION
+  ENUM
+  INPUT_OBJECT
+  LIST
+  NON_NULL
+}
+
+enum __DirectiveLocation {
+  QUERY
+  MUTATION
+  SUBSCRIPTION
+  FIELD
+  FRAGMENT_DEFINITION
+  FRAGMENT_SPREAD
+  INLINE_FRAGMENT
+  SCHEMA
+  SCALAR
+  OBJECT
+  FIELD_DEFINITION
+  ARGUMENT_DEFINITION
+  INTERFACE
+  UNION
+  ENUM
+  ENUM_VALUE
+  INPUT_OBJECT
+  INPUT_FIELD_DEFINITION
+}
+"#;
+
+type SdlDefinitions<'a> = (
+  Vec<schema::TypeDefinition<'a, String>>,
+  Vec<schema::DirectiveDefinition<'a, String>>,
+  Option<schema::SchemaDefinition<'a, String>>,
+);
+
+impl Schema {
+  pub fn from_sdl(sdl: &str) -> Result<Schema, String> {
+    let builtin = graphql_parser::parse_schema::<String>(BUILTIN_SDL).map_err(|e| e.to_string())?;
+    let document = graphql_parser::parse_schema::<String>(sdl).map_err(|e| e.to_string())?;
+    let (mut type_definitions, mut directive_definitions, schema_definition) =
+      sdl_definitions(document.definitions)?;
+    let (builtin_types, builtin_directives, _) = sdl_definitions(builtin.definitions)?;
+
+    for builtin_type in builtin_types {
+      if !type_definitions.iter().any(|t| sdl_name(t) == sdl_name(&builtin_type)) {
+        type_definitions.push(builtin_type);
+      }
+    }
+
+    for builtin_directive in builtin_directives {
+      if !directive_definitions.iter().any(|d| d.name == builtin_directive.name) {
+        directive_definitions.push(builtin_directive);
+      }
+    }
+
+    let kinds = type_definitions
+      .iter()
+      .map(|t| (sdl_name(t).to_owned(), sdl_kind(t)))
+      .collect::<HashMap<String, TypeKind>>();
+
+    let root_type = |name: Option<String>, default: &str| {
+      name
+        .or_else(|| Some(default.to_owned()).filter(|name| kinds.contains_key(name)))
+        .map(|name| Type {
+          kind: TypeKind::Object,
+          name: Some(name),
+          ..Type::default()
+        })
+    };
+
+    let (query, mutation, subscription) = match schema_definition {
+      Some(definition) => (definition.query, definition.mutation, definition.subscription),
+      _ => (None, None, None),
+    };
+
+    let types = type_definitions
+      .iter()
+      .map(|t| sdl_type(t, &type_definitions, &kinds))
+      .collect::<Result<Vec<Type>, String>>()?;
+
+    let directives = directive_definitions
+      .into_iter()
+      .map(|d| {
+        Ok(Directive {
+          name: d.name,
+          description: d.description,
+          locations: d.locations.into_iter().map(|l| l.into()).collect(),
+          args: sdl_input_values(&d.arguments, &kinds)?,
+        })
+      })
+      .collect::<Result<Vec<Directive>, String>>()?;
+
+    Ok(Schema {
+      description: None,
+      types,
+      query_type: root_type(query, "Query"),
+      mutation_type: root_type(mutation, "Mutation"),
+      subscription_type: root_type(subscription, "Subscription"),
+      directives,
+    })
+  }
+}
+
+fn sdl_definitions(definitions: Vec<schema::Definition<'_, String>>) -> Result<SdlDefinitions<'_>, String> {
+  let mut type_definitions = vec![];
+  let mut directive_definitions = vec![];
+  let mut schema_definition = None;
+  let mut type_extensions = vec![];
+
+  for definition in definitions {
+    match definition {
+      schema::Definition::SchemaDefinition(definition) => schema_definition = Some(definition),
+      schema::Definition::TypeDefinition(definition) => type_definitions.push(definition),
+      schema::Definition::DirectiveDefinition(definition) => directive_definitions.push(definition),
+      schema::Definition::TypeExtension(extension) => type_extensions.push(extension),
+    }
+  }
+
+  for extension in type_extensions {
+    if let schema::TypeExtension::Object(extension) = &extension {
+      let is_entity_stub =
+        extension.name == "Query" || !sdl_keys(&extension.directives).is_empty();
+
+      if is_entity_stub && !type_definitions.iter().any(|t| sdl_name(t) == extension.name) {
+        let mut object = schema::ObjectType::new(extension.name.clone());
+        object.position = extension.position;
+        type_definitions.push(schema::TypeDefinition::Object(object));
+      }
+    }
+
+    let (name, implements_interfaces, directives, fields) = match extension {
+      schema::TypeExtension::Object(extension) => (
+        extension.name,
+        extension.implements_interfaces,
+        extension.directives,
+        extension.fields,
+      ),
+      schema::TypeExtension::Interface(extension) => {
+        (extension.name, vec![], vec![], extension.fields)
+      }
+      _ => return Err("Only object and interface type extensions are supported.".to_owned()),
+    };
+
+    match type_definitions.iter_mut().find(|t| sdl_name(t) == name) {
+      Some(schema::TypeDefinition::Object(object)) => {
+        object.implements_interfaces.extend(implements_interfaces);
+        object.directives.extend(directives);
+        object.fields.extend(fields);
+      }
+      Some(schema::TypeDefinition::Interface(interface)) => interface.fields.extend(fields),
+      _ => return Err(format!("Cannot extend unknown type \"{}\".", name)),
+    }
+  }
+
+  Ok((type_definitions, directive_definitions, schema_definition))
+}
+
+fn sdl_name<'a>(definition: &'a schema::TypeDefinition<'_, String>) -> &'a str {
+  match definition {
+    schema::TypeDefinition::Scalar(scalar) => &scalar.name,
+    schema::TypeDefinition::Object(object) => &object.name,
+    schema::TypeDefinition::Interface(interface) => &interface.name,
+    schema::TypeDefinition::Union(union) => &union.name,
+    schema::TypeDefinition::Enum(enum_type) => &enum_type.name,
+    schema::TypeDefinition::InputObject(input_object) => &input_object.name,
+  }
+}
+
+fn sdl_kind(definition: &schema::TypeDefinition<'_, String>) -> TypeKind {
+  match definition {
+    schema::TypeDefinition::Scalar(_) => TypeKind::Scalar,
+    schema::TypeDefinition::Object(_) => TypeKind::Object,
+    schema::TypeDefinition::Interface(_) => TypeKind::Interface,
+    schema::TypeDefinition::Union(_) => TypeKind::Union,
+    schema::TypeDefinition::Enum(_) => TypeKind::Enum,
+    schema::TypeDefinition::InputObject(_) => TypeKind::InputObject,
+  }
+}
+
+fn sdl_type(
+  definition: &schema::TypeDefinition<'_, String>,
+  definitions: &[schema::TypeDefinition<'_, String>],
+  kinds: &HashMap<String, TypeKind>,
+) -> Result<Type, String> {
+  let mut value = Type {
+    kind: sdl_kind(definition),
+    name: Some(sdl_name(definition).to_owned()),
+    ..Type::default()
+  };
+
+  match definition {
+    schema::TypeDefinition::Scalar(scalar) => value.description = scalar.description.clone(),
+    schema::TypeDefinition::Object(object) => {
+      value.description = object.description.clone();
+      value.keys = sdl_keys(&object.directives);
+
+      let fields = object
+        .fields
+        .iter()
+        .filter(|field| {
+          !field.directives.iter().any(|directive| directive.name == "external")
+            || value.keys.iter().any(|key| key.split_whitespace().any(|name| name == field.name))
+        })
+        .cloned()
+        .collect::<Vec<schema::Field<'_, String>>>();
+
+      value.fields = Some(sdl_fields(&fields, kinds)?);
+      value.interfaces = Some(
+        object
+          .implements_interfaces
+          .iter()
+          .map(|name| sdl_named_type(name, kinds))
+          .collect::<Result<Vec<Type>, String>>()?,
+      );
+    }
+    schema::TypeDefinition::Interface(interface) => {
+      value.description = interface.description.clone();
+      value.fields = Some(sdl_fields(&interface.fields, kinds)?);
+      value.possible_types = Some(
+        definitions
+          .iter()
+          .filter_map(|t| match t {
+            schema::TypeDefinition::Object(object)
+              if object.implements_interfaces.contains(&interface.name) =>
+            {
+              Some(sdl_named_type(&object.name, kinds))
+            }
+            _ => None,
+          })
+          .collect::<Result<Vec<Type>, String>>()?,
+      );
+    }
+    schema::TypeDefinition::Union(union) => {
+      value.description = union.description.clone();
+      value.possible_types = Some(
+        union
+          .types
+          .iter()
+          .map(|name| sdl_named_type(name, kinds))
+          .collect::<Result<Vec<Type>, String>>()?,
+      );
+    }
+    schema::TypeDefinition::Enum(enum_type) => {
+      value.description = enum_type.description.clone();
+      value.enum_values = Some(
+        enum_type
+          .values
+          .iter()
+          .map(|enum_value| {
+            let (is_deprecated, deprecation_reason) = sdl_deprecation(&enum_value.directives);
+
+            EnumValue {
+              name: enum_value.name.clone(),
+              description: enum_value.description.clone(),
+              is_deprecated,
+              deprecation_reason,
+            }
+          })
+          .collect(),
+      );
+    }
+    schema::TypeDefinition::InputObject(input_object) => {
+      value.description = input_object.description.clone();
+      value.input_fields = Some(sdl_input_values(&input_object.fields, kinds)?);
+    }
+  }
+
+  Ok(value)
+}
+
+fn sdl_fields(
+  fields: &[schema::Field<'_, String>],
+  kinds: &HashMap<String, TypeKind>,
+) -> Result<Vec<Field>, String> {
+  fields
+    .iter()
+    .map(|field| {
+      let (is_deprecated, deprecation_reason) = sdl_deprecation(&field.directives);
+
+      Ok(Field {
+        name: field.name.clone(),
+        description: field.description.clone(),
+        args: sdl_input_values(&field.arguments, kinds)?,
+        field_type: sdl_type_ref(&field.field_type, kinds)?,
+        is_deprecated,
+        deprecation_reason,
+        cost: sdl_cost(&field.directives),
+      })
+    })
+    .collect()
+}
+
+fn sdl_input_values(
+  input_values: &[schema::InputValue<'_, String>],
+  kinds: &HashMap<String, TypeKind>,
+) -> Result<Vec<InputValue>, String> {
+  input_values
+    .iter()
+    .map(|input_value| {
+      Ok(InputValue {
+        name: input_value.name.clone(),
+        description: input_value.description.clone(),
+        input_type: sdl_type_ref(&input_value.value_type, kinds)?,
+        default_value: input_value.default_value.as_ref().map(|value| value.to_string()),
+      })
+    })
+    .collect()
+}
+
+fn sdl_type_ref(
+  type_ref: &schema::Type<'_, String>,
+  kinds: &HashMap<String, TypeKind>,
+) -> Result<Type, String> {
+  let (kind, of_type) = match type_ref {
+    schema::Type::NamedType(name) => return sdl_named_type(name, kinds),
+    schema::Type::ListType(of_type) => (TypeKind::List, of_type),
+    schema::Type::NonNullType(of_type) => (TypeKind::NonNull, of_type),
+  };
+
+  Ok(Type {
+    kind,
+    of_type: Some(Box::new(sdl_type_ref(of_type, kinds)?)),
+    ..Type::default()
+  })
+}
+
+fn sdl_named_type(name: &str, kinds: &HashMap<String, TypeKind>) -> Result<Type, String> {
+  let kind = kinds
+    .get(name)
+    .cloned()
+    .ok_or_else(|| format!("Unknown type \"{}\".", name))?;
+
+  Ok(Type {
+    kind,
+    name: Some(name.to_owned()),
+    ..Type::default()
+  })
+}
+
+fn sdl_deprecation(directives: &[schema::Directive<'_, String>]) -> (bool, Option<String>) {
+  let directive = match directives.iter().find(|directive| directive.name == "deprecated") {
+    Some(directive) => directive,
+    _ => return (false, None),
+  };
+
+  let reason = directive
+    .arguments
+    .iter()
+    .find(|(name, _)| name == "reason")
+    .and_then(|(_, value)| match value {
+      Value::String(reason) => Some(reason.clone()),
+      _ => None,
+    })
+    .unwrap_or_else(|| "No longer supported".to_owned());
+
+  (true, Some(reason))
+}
+
+fn sdl_keys(directives: &[schema::Directive<'_, String>]) -> Vec<String> {
+  directives
+    .iter()
+    .filter(|directive| directive.name == "key")
+    .filter_map(|directive| {
+      directive.arguments.iter().find_map(|(name, value)| match value {
+        Value::String(fields) if name == "fields" => {
+          Some(fields.split_whitespace().collect::<Vec<&str>>().join(" "))
+        }
+        _ => None,
+      })
+    })
+    .collect()
+}
+
+fn sdl_cost(directives: &[schema::Directive<'_, String>]) -> Option<usize> {
+  directives
+    .iter()
+    .find(|directive| directive.name == "cost")?
+    .arguments
+    .iter()
+    .find_map(|(name, value)| match value {
+      Value::Int(weight) if name == "weight" => weight.as_i64().map(|weight| weight as usize),
+      _ => None,
+    })
+}
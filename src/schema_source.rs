@@ -0,0 +1,141 @@
+use crate::data::Data;
+use crate::executor::Executor;
+use crate::schema::Schema;
+use async_trait::async_trait;
+use serde_json::Value;
+use std::path::PathBuf;
+
+const FEDERATION_TYPES: [&str; 4] = ["_Any", "_Entity", "_Service", "_FieldSet"];
+
+#[async_trait]
+pub trait SchemaSource: Send + Sync {
+    async fn load(&self, executor: &dyn Executor, data: Option<&Data>) -> Result<Schema, String>;
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IntrospectionSource;
+
+#[async_trait]
+impl SchemaSource for IntrospectionSource {
+    async fn load(&self, executor: &dyn Executor, data: Option<&Data>) -> Result<Schema, String> {
+        executor.introspect(data).await.map(|(_, schema)| schema)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum RegistrySource {
+    File(PathBuf),
+    #[cfg(feature = "http")]
+    Endpoint(String),
+}
+
+impl RegistrySource {
+    pub fn file<P: Into<PathBuf>>(path: P) -> Self {
+        RegistrySource::File(path.into())
+    }
+
+    #[cfg(feature = "http")]
+    pub fn endpoint<U: Into<String>>(url: U) -> Self {
+        RegistrySource::Endpoint(url.into())
+    }
+
+    async fn fetch(&self) -> Result<Value, String> {
+        match self {
+            RegistrySource::File(path) => {
+                let source = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+                serde_json::from_str(&source).map_err(|e| e.to_string())
+            }
+            #[cfg(feature = "http")]
+            RegistrySource::Endpoint(url) => {
+                let response = reqwest::get(url).await.map_err(|e| e.to_string())?;
+                let status = response.status();
+
+                if !status.is_success() {
+                    return Err(format!("{} responded with HTTP {}", url, status));
+                }
+
+                response.json::<Value>().await.map_err(|e| e.to_string())
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl SchemaSource for RegistrySource {
+    async fn load(&self, executor: &dyn Executor, _data: Option<&Data>) -> Result<Schema, String> {
+        let mut registry = self.fetch().await?;
+        let schema = registry
+            .get_mut(executor.name())
+            .map(Value::take)
+            .ok_or_else(|| {
+                format!(
+                    "Executor \"{}\" is not in the schema registry.",
+                    executor.name()
+                )
+            })?;
+
+        serde_json::from_value(schema).map_err(|e| e.to_string())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FederationSource;
+
+#[async_trait]
+impl SchemaSource for FederationSource {
+    async fn load(&self, executor: &dyn Executor, data: Option<&Data>) -> Result<Schema, String> {
+        let response = executor
+            .execute(
+                data,
+                "query ServiceQuery { _service { sdl } }".to_owned(),
+                Some("ServiceQuery".to_owned()),
+                None,
+            )
+            .await?;
+
+        let sdl = response
+            .get("data")
+            .and_then(|data| data.get("_service"))
+            .and_then(|service| service.get("sdl"))
+            .and_then(Value::as_str)
+            .ok_or_else(|| "data._service.sdl does not exist.".to_owned())?;
+
+        let mut schema = Schema::from_sdl(sdl)?;
+
+        schema
+            .types
+            .retain(|schema_type| !FEDERATION_TYPES.contains(&schema_type.name()));
+
+        for schema_type in schema.types.iter_mut() {
+            if schema_type.name() == "Query" {
+                if let Some(fields) = schema_type.fields.as_mut() {
+                    fields.retain(|field| field.name != "_service" && field.name != "_entities");
+                }
+            }
+        }
+
+        Ok(schema)
+    }
+}
+
+pub(crate) struct SdlSource(pub(crate) String);
+
+#[async_trait]
+impl SchemaSource for SdlSource {
+    async fn load(&self, _executor: &dyn Executor, _data: Option<&Data>) -> Result<Schema, String> {
+        Schema::from_sdl(&self.0)
+    }
+}
+
+pub(crate) async fn load_schema(
+    source: Option<&dyn SchemaSource>,
+    executor: &dyn Executor,
+    data: Option<&Data>,
+) -> Result<(String, Schema), String> {
+    let schema = match source {
+        Some(source) => source.load(executor, data).await?,
+        _ => IntrospectionSource.load(executor, data).await?,
+    };
+
+    Ok((executor.name().to_owned(), schema))
+}
@@ -0,0 +1,195 @@
+use crate::etag::IfNoneMatch;
+use crate::http::{
+    graphiql_source, GraphQLBatchPayload, GraphQLBatchResponse, GraphQLPayload,
+    MULTIPART_CONTENT_TYPE,
+};
+use crate::poller::GatewayHandle;
+use crate::request_context::RequestContext;
+use axum::body::Body;
+use axum::extract::{Query, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{Html, IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use futures::channel::mpsc;
+use futures::future::{self, FutureExt};
+use futures::stream::{self, StreamExt};
+use serde_json::Value;
+use std::convert::Infallible;
+
+#[derive(Deserialize)]
+struct GetPayload {
+    #[serde(default)]
+    query: String,
+    #[serde(rename = "operationName")]
+    operation_name: Option<String>,
+    variables: Option<String>,
+    extensions: Option<String>,
+}
+
+impl GetPayload {
+    fn into_payload(self) -> Result<GraphQLPayload, String> {
+        Ok(GraphQLPayload {
+            query: self.query,
+            operation_name: self.operation_name,
+            variables: json_param("variables", self.variables)?,
+            extensions: json_param("extensions", self.extensions)?,
+        })
+    }
+}
+
+pub fn router(path: &str, gateway: GatewayHandle<'static>) -> Router {
+    Router::new()
+        .route(path, get(get_query).post(post_query))
+        .with_state(gateway)
+}
+
+pub fn graphiql(path: &str, endpoint: &str) -> Router {
+    let source = graphiql_source(endpoint);
+
+    Router::new().route(path, get(move || async move { Html(source) }))
+}
+
+async fn get_query(
+    State(gateway): State<GatewayHandle<'static>>,
+    headers: HeaderMap,
+    Query(payload): Query<GetPayload>,
+) -> Response {
+    let payload = match payload.into_payload() {
+        Ok(payload) => payload,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+
+    let is_mutation = payload
+        .to_query_builder()
+        .parse()
+        .map(|parsed| parsed.operation_type == "Mutation")
+        .unwrap_or(false);
+
+    if is_mutation {
+        return (
+            StatusCode::METHOD_NOT_ALLOWED,
+            [(header::ALLOW, "POST")],
+            "Mutations can only be executed over POST.",
+        )
+            .into_response();
+    }
+
+    if accepts_multipart(&headers) {
+        return stream_response(&gateway, &headers, &payload);
+    }
+
+    into_response(execute(&gateway, &headers, &GraphQLBatchPayload::Single(payload)).await)
+}
+
+async fn post_query(
+    State(gateway): State<GatewayHandle<'static>>,
+    headers: HeaderMap,
+    Json(payload): Json<GraphQLBatchPayload>,
+) -> Response {
+    if let (GraphQLBatchPayload::Single(payload), true) = (&payload, accepts_multipart(&headers)) {
+        return stream_response(&gateway, &headers, payload);
+    }
+
+    into_response(execute(&gateway, &headers, &payload).await)
+}
+
+fn stream_response(
+    gateway: &GatewayHandle<'static>,
+    headers: &HeaderMap,
+    payload: &GraphQLPayload,
+) -> Response {
+    let gateway = gateway.load();
+    let query = payload
+        .to_query_builder()
+        .request_context(request_context(headers));
+    let (sender, receiver) = mpsc::unbounded();
+
+    let driver = async move {
+        let mut payloads = query.execute_stream(&gateway);
+
+        while let Some(payload) = payloads.next().await {
+            if sender.unbounded_send(payload.to_multipart()).is_err() {
+                break;
+            }
+        }
+    };
+    let body = stream::select(
+        driver.into_stream().filter_map(|_| future::ready(None)),
+        receiver,
+    )
+    .map(Ok::<String, Infallible>);
+
+    (
+        [(header::CONTENT_TYPE, MULTIPART_CONTENT_TYPE)],
+        Body::from_stream(body),
+    )
+        .into_response()
+}
+
+fn accepts_multipart(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.contains("multipart/mixed"))
+        .unwrap_or(false)
+}
+
+async fn execute(
+    gateway: &GatewayHandle<'static>,
+    headers: &HeaderMap,
+    payload: &GraphQLBatchPayload,
+) -> GraphQLBatchResponse {
+    let if_none_match = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok());
+
+    payload
+        .execute_with(&gateway.load(), |query| {
+            let query = query.request_context(request_context(headers));
+
+            match if_none_match {
+                Some(if_none_match) => query.data(IfNoneMatch(if_none_match.to_owned())),
+                _ => query,
+            }
+        })
+        .await
+}
+
+fn into_response(response: GraphQLBatchResponse) -> Response {
+    let response = match response {
+        GraphQLBatchResponse::Single(response) => response,
+        response => return Json(response).into_response(),
+    };
+    let etag = response.etag.clone();
+
+    let mut response = if response.not_modified {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        Json(response).into_response()
+    };
+
+    if let Some(value) = etag.and_then(|etag| etag.parse().ok()) {
+        response.headers_mut().insert(header::ETAG, value);
+    }
+
+    response
+}
+
+fn request_context(headers: &HeaderMap) -> RequestContext {
+    headers
+        .iter()
+        .filter_map(|(key, value)| Some((key.as_str(), value.to_str().ok()?)))
+        .fold(RequestContext::new(), |context, (key, value)| {
+            context.header(key, value)
+        })
+}
+
+fn json_param(name: &str, value: Option<String>) -> Result<Option<Value>, String> {
+    match value {
+        Some(value) => serde_json::from_str(&value)
+            .map(Some)
+            .map_err(|e| format!("Invalid \"{}\" parameter: {}", name, e)),
+        _ => Ok(None),
+    }
+}
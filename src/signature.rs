@@ -0,0 +1,339 @@
+use crate::literals::{literal_source, preserve_literals};
+use crate::query::{definition_name, select_operation, QueryResult};
+use graphql_parser::query::{
+    Definition, Directive, FragmentDefinition, OperationDefinition, Selection, SelectionSet,
+    TypeCondition, Value as AstValue, VariableDefinition,
+};
+use regex::Regex;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+pub fn operation_signature(query: &str, operation_name: Option<&str>) -> QueryResult<String> {
+    signature(query, operation_name).map(|(_, signature)| signature)
+}
+
+pub fn operation_hash(query: &str, operation_name: Option<&str>) -> QueryResult<String> {
+    operation_signature(query, operation_name).map(|signature| sha256_hex(&signature))
+}
+
+pub fn stats_report_key(query: &str, operation_name: Option<&str>) -> QueryResult<String> {
+    let (name, signature) = signature(query, operation_name)?;
+
+    Ok(format!(
+        "# {}\n{}",
+        name.as_deref().unwrap_or("-"),
+        signature
+    ))
+}
+
+pub fn sha256_hex(input: &str) -> String {
+    Sha256::digest(input.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+fn signature(query: &str, operation_name: Option<&str>) -> QueryResult<(Option<String>, String)> {
+    let (source, literals) = preserve_literals(query);
+    let document = graphql_parser::parse_query::<String>(&source)?;
+    let operation = select_operation(&document, operation_name)?;
+
+    let fragments = document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::Fragment(fragment) => Some((fragment.name.as_str(), fragment)),
+            _ => None,
+        })
+        .collect::<HashMap<&str, &FragmentDefinition<'_, String>>>();
+
+    let mut used = HashSet::new();
+    collect_fragments(operation_selection_set(operation), &fragments, &mut used);
+
+    let mut used = used.into_iter().collect::<Vec<&str>>();
+    used.sort_unstable();
+
+    let printer = Printer {
+        literals: &literals,
+    };
+    let mut definitions = used
+        .into_iter()
+        .filter_map(|name| fragments.get(name))
+        .map(|fragment| printer.fragment(fragment))
+        .collect::<Vec<String>>();
+    definitions.push(printer.operation(operation));
+
+    Ok((
+        definition_name(operation).map(|name| name.to_owned()),
+        reduce_whitespace(&definitions.join(" ")),
+    ))
+}
+
+fn operation_selection_set<'a, 'b>(
+    operation: &'b OperationDefinition<'a, String>,
+) -> &'b SelectionSet<'a, String> {
+    match operation {
+        OperationDefinition::SelectionSet(selection_set) => selection_set,
+        OperationDefinition::Query(query) => &query.selection_set,
+        OperationDefinition::Mutation(mutation) => &mutation.selection_set,
+        OperationDefinition::Subscription(subscription) => &subscription.selection_set,
+    }
+}
+
+fn collect_fragments<'b>(
+    selection_set: &'b SelectionSet<'_, String>,
+    fragments: &HashMap<&'b str, &'b FragmentDefinition<'_, String>>,
+    used: &mut HashSet<&'b str>,
+) {
+    for selection in &selection_set.items {
+        match selection {
+            Selection::Field(field) => collect_fragments(&field.selection_set, fragments, used),
+            Selection::InlineFragment(fragment) => {
+                collect_fragments(&fragment.selection_set, fragments, used)
+            }
+            Selection::FragmentSpread(spread) => {
+                if let Some((&name, fragment)) =
+                    fragments.get_key_value(spread.fragment_name.as_str())
+                {
+                    if used.insert(name) {
+                        collect_fragments(&fragment.selection_set, fragments, used);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn reduce_whitespace(source: &str) -> String {
+    let source = Regex::new(r"\s+").unwrap().replace_all(source, " ");
+    let source = Regex::new(r"([^_a-zA-Z0-9]) ")
+        .unwrap()
+        .replace_all(&source, "$1");
+    let source = Regex::new(r" ([^_a-zA-Z0-9])")
+        .unwrap()
+        .replace_all(&source, "$1");
+
+    source.trim().to_owned()
+}
+
+struct Printer<'l> {
+    literals: &'l [String],
+}
+
+impl Printer<'_> {
+    fn operation(&self, operation: &OperationDefinition<'_, String>) -> String {
+        let (kind, name, variable_definitions, directives, selection_set) = match operation {
+            OperationDefinition::SelectionSet(selection_set) => {
+                return self.selection_set(selection_set)
+            }
+            OperationDefinition::Query(query) => (
+                "query",
+                &query.name,
+                &query.variable_definitions,
+                &query.directives,
+                &query.selection_set,
+            ),
+            OperationDefinition::Mutation(mutation) => (
+                "mutation",
+                &mutation.name,
+                &mutation.variable_definitions,
+                &mutation.directives,
+                &mutation.selection_set,
+            ),
+            OperationDefinition::Subscription(subscription) => (
+                "subscription",
+                &subscription.name,
+                &subscription.variable_definitions,
+                &subscription.directives,
+                &subscription.selection_set,
+            ),
+        };
+
+        if kind == "query"
+            && name.is_none()
+            && variable_definitions.is_empty()
+            && directives.is_empty()
+        {
+            return self.selection_set(selection_set);
+        }
+
+        let mut variable_definitions = variable_definitions.iter().collect::<Vec<_>>();
+        variable_definitions.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let variables = if variable_definitions.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "({})",
+                variable_definitions
+                    .iter()
+                    .map(|definition| self.variable_definition(definition))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )
+        };
+
+        join(&[
+            kind.to_owned(),
+            format!("{}{}", name.as_deref().unwrap_or_default(), variables),
+            self.directives(directives, false),
+            self.selection_set(selection_set),
+        ])
+    }
+
+    fn fragment(&self, fragment: &FragmentDefinition<'_, String>) -> String {
+        let TypeCondition::On(type_condition) = &fragment.type_condition;
+
+        join(&[
+            format!("fragment {} on {}", fragment.name, type_condition),
+            self.directives(&fragment.directives, true),
+            self.selection_set(&fragment.selection_set),
+        ])
+    }
+
+    fn variable_definition(&self, definition: &VariableDefinition<'_, String>) -> String {
+        let default_value = definition
+            .default_value
+            .as_ref()
+            .map(|value| format!(" = {}", self.value(value)))
+            .unwrap_or_default();
+
+        format!(
+            "${}: {}{}",
+            definition.name, definition.var_type, default_value
+        )
+    }
+
+    fn selection_set(&self, selection_set: &SelectionSet<'_, String>) -> String {
+        let mut selections = selection_set.items.iter().collect::<Vec<_>>();
+        selections.sort_by_key(|selection| match selection {
+            Selection::Field(field) => (0, Some(field.name.as_str())),
+            Selection::FragmentSpread(spread) => (1, Some(spread.fragment_name.as_str())),
+            Selection::InlineFragment(_) => (2, None),
+        });
+
+        format!(
+            "{{ {} }}",
+            selections
+                .into_iter()
+                .map(|selection| self.selection(selection))
+                .collect::<Vec<String>>()
+                .join(" ")
+        )
+    }
+
+    fn selection(&self, selection: &Selection<'_, String>) -> String {
+        match selection {
+            Selection::Field(field) => {
+                let selection_set = if field.selection_set.items.is_empty() {
+                    String::new()
+                } else {
+                    self.selection_set(&field.selection_set)
+                };
+
+                join(&[
+                    format!("{}{}", field.name, self.arguments(&field.arguments)),
+                    self.directives(&field.directives, false),
+                    selection_set,
+                ])
+            }
+            Selection::FragmentSpread(spread) => join(&[
+                format!("...{}", spread.fragment_name),
+                self.directives(&spread.directives, true),
+            ]),
+            Selection::InlineFragment(fragment) => join(&[
+                "...".to_owned(),
+                fragment
+                    .type_condition
+                    .as_ref()
+                    .map(|TypeCondition::On(name)| format!("on {}", name))
+                    .unwrap_or_default(),
+                self.directives(&fragment.directives, true),
+                self.selection_set(&fragment.selection_set),
+            ]),
+        }
+    }
+
+    fn directives(&self, directives: &[Directive<'_, String>], sorted: bool) -> String {
+        let mut directives = directives.iter().collect::<Vec<_>>();
+
+        if sorted {
+            directives.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+
+        directives
+            .into_iter()
+            .map(|directive| {
+                format!(
+                    "@{}{}",
+                    directive.name,
+                    self.arguments(&directive.arguments)
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    fn arguments(&self, arguments: &[(String, AstValue<'_, String>)]) -> String {
+        if arguments.is_empty() {
+            return String::new();
+        }
+
+        let mut arguments = arguments.iter().collect::<Vec<_>>();
+        arguments.sort_by(|a, b| a.0.cmp(&b.0));
+
+        format!(
+            "({})",
+            arguments
+                .into_iter()
+                .map(|(name, value)| format!("{}: {}", name, self.value(value)))
+                .collect::<Vec<String>>()
+                .join(", ")
+        )
+    }
+
+    fn value(&self, value: &AstValue<'_, String>) -> String {
+        match value {
+            AstValue::Variable(name) => format!("${}", name),
+            AstValue::Int(_) | AstValue::Float(_) => "0".to_owned(),
+            AstValue::String(_) => "\"\"".to_owned(),
+            AstValue::Boolean(value) => value.to_string(),
+            AstValue::Null => "null".to_owned(),
+            AstValue::Enum(name) if literal_source(name, self.literals).is_some() => "0".to_owned(),
+            AstValue::Enum(name) => name.clone(),
+            AstValue::List(_) => "[]".to_owned(),
+            AstValue::Object(_) => "{}".to_owned(),
+        }
+    }
+}
+
+fn join(parts: &[String]) -> String {
+    parts
+        .iter()
+        .filter(|part| !part.is_empty())
+        .cloned()
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OperationUsage {
+    pub stats_report_key: String,
+    pub operation_hash: String,
+    pub duration: Duration,
+    pub has_errors: bool,
+}
+
+pub trait UsageReporter: Send + Sync {
+    fn report(&self, usage: &OperationUsage);
+}
+
+impl<F> UsageReporter for F
+where
+    F: Fn(&OperationUsage) + Send + Sync,
+{
+    fn report(&self, usage: &OperationUsage) {
+        self(usage)
+    }
+}
@@ -0,0 +1,209 @@
+use crate::literals::preserve_literals;
+use crate::query::find_operation;
+use graphql_parser::query::Selection;
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::Duration;
+
+const LATENCY_WINDOW: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SloTarget {
+    Operation(String),
+    RootField(String),
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Slo {
+    pub p95_latency: Option<Duration>,
+    pub max_size: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SloViolation {
+    Latency {
+        target: SloTarget,
+        p95: Duration,
+        threshold: Duration,
+    },
+    Size {
+        target: SloTarget,
+        size: usize,
+        threshold: usize,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct SloStatus {
+    pub target: SloTarget,
+    pub samples: usize,
+    pub p95_latency: Option<Duration>,
+    pub last_size: Option<usize>,
+    pub violating: bool,
+}
+
+pub trait SloAlert: Send + Sync {
+    fn alert(&self, violation: &SloViolation);
+}
+
+impl<F> SloAlert for F
+where
+    F: Fn(&SloViolation) + Send + Sync,
+{
+    fn alert(&self, violation: &SloViolation) {
+        self(violation)
+    }
+}
+
+#[derive(Default)]
+struct SloState {
+    latencies: VecDeque<Duration>,
+    last_size: Option<usize>,
+    latency_violated: bool,
+    size_violated: bool,
+}
+
+impl SloState {
+    fn p95_latency(&self) -> Option<Duration> {
+        let mut latencies = self.latencies.iter().copied().collect::<Vec<Duration>>();
+        latencies.sort();
+
+        let i = ((latencies.len() as f64 * 0.95).ceil() as usize).max(1) - 1;
+        latencies.get(i).copied()
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct SloTracker(Mutex<HashMap<SloTarget, SloState>>);
+
+impl SloTracker {
+    pub(crate) fn observe(
+        &self,
+        slos: &HashMap<SloTarget, Slo>,
+        query_source: &str,
+        operation_name: Option<&str>,
+        result: Option<&Value>,
+        elapsed: Duration,
+    ) -> Vec<SloViolation> {
+        let mut observations = Vec::new();
+
+        if let Some(operation_name) = operation_name {
+            observations.push((
+                SloTarget::Operation(operation_name.to_owned()),
+                result.map(size),
+            ));
+        }
+
+        if slos
+            .keys()
+            .any(|target| matches!(target, SloTarget::RootField(_)))
+        {
+            observations.extend(root_fields(query_source, operation_name).into_iter().map(
+                |(name, alias)| {
+                    let field_size = result.and_then(|result| result.get(&alias)).map(size);
+                    (SloTarget::RootField(name), field_size)
+                },
+            ));
+        }
+
+        let mut states = self.0.lock().expect("SLO tracker lock is poisoned.");
+        let mut violations = Vec::new();
+
+        for (target, size) in observations {
+            let slo = match slos.get(&target) {
+                Some(slo) => slo,
+                _ => continue,
+            };
+
+            let state = states.entry(target.clone()).or_default();
+
+            if state.latencies.len() == LATENCY_WINDOW {
+                state.latencies.pop_front();
+            }
+
+            state.latencies.push_back(elapsed);
+
+            if let (Some(threshold), Some(p95)) = (slo.p95_latency, state.p95_latency()) {
+                let violated = p95 > threshold;
+
+                if violated && !state.latency_violated {
+                    violations.push(SloViolation::Latency {
+                        target: target.clone(),
+                        p95,
+                        threshold,
+                    });
+                }
+
+                state.latency_violated = violated;
+            }
+
+            if let Some(size) = size {
+                state.last_size = Some(size);
+                state.size_violated = false;
+
+                if let Some(threshold) = slo.max_size.filter(|&threshold| size > threshold) {
+                    state.size_violated = true;
+                    violations.push(SloViolation::Size {
+                        target,
+                        size,
+                        threshold,
+                    });
+                }
+            }
+        }
+
+        violations
+    }
+
+    pub(crate) fn status(&self, slos: &HashMap<SloTarget, Slo>) -> Vec<SloStatus> {
+        let states = self.0.lock().expect("SLO tracker lock is poisoned.");
+        let mut status = slos
+            .keys()
+            .map(|target| {
+                let state = states.get(target);
+
+                SloStatus {
+                    target: target.clone(),
+                    samples: state.map(|state| state.latencies.len()).unwrap_or(0),
+                    p95_latency: state.and_then(SloState::p95_latency),
+                    last_size: state.and_then(|state| state.last_size),
+                    violating: state
+                        .map(|state| state.latency_violated || state.size_violated)
+                        .unwrap_or(false),
+                }
+            })
+            .collect::<Vec<SloStatus>>();
+
+        status.sort_by(|a, b| a.target.cmp(&b.target));
+        status
+    }
+}
+
+fn size(value: &Value) -> usize {
+    value.to_string().len()
+}
+
+fn root_fields(query_source: &str, operation_name: Option<&str>) -> Vec<(String, String)> {
+    let (query_source, _) = preserve_literals(query_source);
+    let document = match graphql_parser::parse_query::<String>(&query_source) {
+        Ok(document) => document,
+        _ => return vec![],
+    };
+
+    let selections = match find_operation(&document, operation_name) {
+        Ok((_, selections, _)) => selections,
+        _ => return vec![],
+    };
+
+    selections
+        .iter()
+        .filter_map(|selection| match selection {
+            Selection::Field(field) => Some((
+                field.name.clone(),
+                field.alias.clone().unwrap_or_else(|| field.name.clone()),
+            )),
+            _ => None,
+        })
+        .collect()
+}
@@ -0,0 +1,122 @@
+use crate::gateway::GatewaySchema;
+use crate::naming::{pascal_case, NamingPolicy};
+use crate::schema::{Field, Type, TypeKind};
+use std::collections::BTreeSet;
+
+struct SmokeQuery {
+    source: String,
+    executors: BTreeSet<String>,
+}
+
+pub(crate) fn generate_smoke_queries(
+    schema: &GatewaySchema,
+    naming_policy: &NamingPolicy,
+    depth: usize,
+    count: usize,
+) -> Vec<String> {
+    let query_type = match schema.object_by_kind(&TypeKind::Object, "Query") {
+        Some(query_type) => query_type,
+        _ => return vec![],
+    };
+
+    let mut candidates = selectable_fields(query_type)
+        .into_iter()
+        .filter(|field| !naming_policy.is_meta_field(&field.name))
+        .filter_map(|field| {
+            let mut executors = BTreeSet::new();
+            let selection = select_field(schema, query_type, field, depth, &mut executors)?;
+
+            Some(SmokeQuery {
+                source: format!(
+                    "query Smoke{} {{ {} }}",
+                    pascal_case(&field.name),
+                    selection
+                ),
+                executors,
+            })
+        })
+        .collect::<Vec<SmokeQuery>>();
+
+    let mut covered = BTreeSet::new();
+    let mut queries = Vec::new();
+
+    while queries.len() < count {
+        let best = candidates
+            .iter()
+            .enumerate()
+            .map(|(i, candidate)| (i, candidate.executors.difference(&covered).count()))
+            .filter(|(_, uncovered)| *uncovered > 0)
+            .max_by(|(a, a_uncovered), (b, b_uncovered)| {
+                a_uncovered.cmp(b_uncovered).then(b.cmp(a))
+            });
+
+        let i = match best {
+            Some((i, _)) => i,
+            _ => break,
+        };
+
+        let candidate = candidates.remove(i);
+        covered.extend(candidate.executors);
+        queries.push(candidate.source);
+    }
+
+    let remaining = count.saturating_sub(queries.len());
+    queries.extend(
+        candidates
+            .into_iter()
+            .take(remaining)
+            .map(|candidate| candidate.source),
+    );
+
+    queries
+}
+
+fn select_field(
+    schema: &GatewaySchema,
+    object_type: &Type,
+    field: &Field,
+    depth: usize,
+    executors: &mut BTreeSet<String>,
+) -> Option<String> {
+    let field_type = field.field_type();
+    let selection = match field_type.kind {
+        TypeKind::Scalar | TypeKind::Enum => field.name.clone(),
+        _ if depth == 0 => return None,
+        _ => {
+            let field_type = schema.object_by_kind(&field_type.kind, field_type.name())?;
+            let selections = selectable_fields(field_type)
+                .into_iter()
+                .filter_map(|child| select_field(schema, field_type, child, depth - 1, executors))
+                .collect::<Vec<String>>();
+
+            if selections.is_empty() {
+                return None;
+            }
+
+            format!("{} {{ {} }}", field.name, selections.join(" "))
+        }
+    };
+
+    if let Some((executor, _)) = schema.3.get(&format!("{}.{}", object_type, field.name)) {
+        executors.insert(executor.clone());
+    }
+
+    Some(selection)
+}
+
+fn selectable_fields(object_type: &Type) -> Vec<&Field> {
+    let mut fields = object_type
+        .fields
+        .iter()
+        .flatten()
+        .filter(|field| {
+            field
+                .args
+                .iter()
+                .all(|arg| arg.input_type.kind != TypeKind::NonNull || arg.default_value.is_some())
+        })
+        .collect::<Vec<&Field>>();
+
+    fields.sort_by(|a, b| a.name.cmp(&b.name));
+    fields
+}
@@ -0,0 +1,98 @@
+use crate::executor_info::schema_hash;
+use crate::gateway::{Gateway, GatewayError, GatewayResult};
+use crate::schema::Schema;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::SystemTime;
+
+#[derive(Debug, Clone)]
+pub struct SchemaSnapshot {
+    pub hash: String,
+    pub introspections: HashMap<String, Schema>,
+    pub created_at: SystemTime,
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct SchemaHistory {
+    pub(crate) limit: usize,
+    snapshots: VecDeque<SchemaSnapshot>,
+    rejected: HashMap<String, HashSet<String>>,
+}
+
+impl SchemaHistory {
+    pub(crate) fn is_rejected(&self, executor: &str, schema: &Schema) -> bool {
+        self.rejected
+            .get(executor)
+            .map(|hashes| hashes.contains(&schema_hash(schema)))
+            .unwrap_or(false)
+    }
+}
+
+impl<'a> Gateway<'a> {
+    pub fn schema_snapshots(&self) -> Vec<&SchemaSnapshot> {
+        self.schema_history.snapshots.iter().collect()
+    }
+
+    pub fn rollback(&mut self, n: usize) -> GatewayResult<()> {
+        let index = self
+            .schema_history
+            .snapshots
+            .len()
+            .checked_sub(n + 1)
+            .ok_or_else(|| GatewayError::UnknownSnapshot(n.to_string()))?;
+
+        self.restore(index)
+    }
+
+    pub fn rollback_to(&mut self, hash: &str) -> GatewayResult<()> {
+        let index = self
+            .schema_history
+            .snapshots
+            .iter()
+            .position(|snapshot| snapshot.hash == hash)
+            .ok_or_else(|| GatewayError::UnknownSnapshot(hash.to_owned()))?;
+
+        self.restore(index)
+    }
+
+    pub(crate) fn record_snapshot(&mut self) {
+        if self.schema_history.limit == 0 {
+            return;
+        }
+
+        let hash = schema_hash(&self.schema.0);
+        let history = &mut self.schema_history;
+
+        if history.snapshots.back().map(|snapshot| &snapshot.hash) == Some(&hash) {
+            return;
+        }
+
+        history.snapshots.push_back(SchemaSnapshot {
+            hash,
+            introspections: self.introspections.clone(),
+            created_at: SystemTime::now(),
+        });
+
+        while history.snapshots.len() > history.limit {
+            history.snapshots.pop_front();
+        }
+    }
+
+    fn restore(&mut self, index: usize) -> GatewayResult<()> {
+        let snapshot = self.schema_history.snapshots[index].clone();
+
+        for (name, schema) in &self.introspections {
+            let hash = schema_hash(schema);
+
+            if snapshot.introspections.get(name).map(schema_hash) != Some(hash.clone()) {
+                self.schema_history
+                    .rejected
+                    .entry(name.clone())
+                    .or_default()
+                    .insert(hash);
+            }
+        }
+
+        self.schema_history.snapshots.truncate(index + 1);
+        self.compose(snapshot.introspections)
+    }
+}
@@ -0,0 +1,51 @@
+use crate::context::Context;
+use crate::executor_info::{schema_hash, ExecutorHealth};
+use crate::schema::Schema;
+use serde_json::Value;
+
+pub(crate) const STATIC_FIELDS_EXECUTOR: &str = "__gateway";
+
+const STATIC_FIELDS: [&str; 3] = ["_gatewayVersion", "_schemaHash", "_health"];
+
+const STATIC_FIELDS_SDL: &str = r#"
+type Query {
+  _gatewayVersion: String!
+  _schemaHash: String!
+  _health: String!
+}
+"#;
+
+pub(crate) fn static_fields_schema() -> Schema {
+    let mut schema = Schema::from_sdl(STATIC_FIELDS_SDL).expect("Static fields SDL is invalid.");
+    schema
+        .types
+        .retain(|schema_type| matches!(schema_type.name(), "Query" | "String"));
+    schema.directives.clear();
+    schema
+}
+
+pub(crate) fn is_static_field(context: &Context<'_, '_>, object_type: &str, name: &str) -> bool {
+    context.gateway.static_fields && object_type == "Query" && STATIC_FIELDS.contains(&name)
+}
+
+pub(crate) fn static_field_value(context: &Context<'_, '_>, name: &str) -> Value {
+    match name {
+        "_gatewayVersion" => Value::from(env!("CARGO_PKG_VERSION")),
+        "_schemaHash" => Value::from(schema_hash(&context.schema.0)),
+        "_health" => {
+            let healthy = context
+                .gateway
+                .executors_info()
+                .iter()
+                .all(|info| info.health != ExecutorHealth::Unhealthy);
+
+            serde_json::to_value(if healthy {
+                ExecutorHealth::Healthy
+            } else {
+                ExecutorHealth::Unhealthy
+            })
+            .unwrap_or_default()
+        }
+        _ => Value::Null,
+    }
+}
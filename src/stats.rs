@@ -0,0 +1,130 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+pub(crate) const DEFAULT_STATS_WINDOW: usize = 100;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutorStats {
+    pub executor: String,
+    pub samples: usize,
+    pub success_rate: Option<f64>,
+    pub p50_latency: Option<Duration>,
+    pub p95_latency: Option<Duration>,
+    pub p99_latency: Option<Duration>,
+    pub active_requests: usize,
+}
+
+#[derive(Default)]
+struct ExecutorWindow {
+    samples: VecDeque<(Duration, bool)>,
+    active_requests: usize,
+}
+
+impl ExecutorWindow {
+    fn stats(&self, executor: &str) -> ExecutorStats {
+        let mut latencies = self
+            .samples
+            .iter()
+            .map(|(latency, _)| *latency)
+            .collect::<Vec<Duration>>();
+        latencies.sort();
+
+        let successes = self.samples.iter().filter(|(_, success)| *success).count();
+
+        ExecutorStats {
+            executor: executor.to_owned(),
+            samples: self.samples.len(),
+            success_rate: if self.samples.is_empty() {
+                None
+            } else {
+                Some(successes as f64 / self.samples.len() as f64)
+            },
+            p50_latency: percentile(&latencies, 0.5),
+            p95_latency: percentile(&latencies, 0.95),
+            p99_latency: percentile(&latencies, 0.99),
+            active_requests: self.active_requests,
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct StatsTracker(Mutex<HashMap<String, ExecutorWindow>>);
+
+impl StatsTracker {
+    pub(crate) fn start<'a>(&'a self, executor: &str, window: usize) -> StatsGuard<'a> {
+        self.lock()
+            .entry(executor.to_owned())
+            .or_default()
+            .active_requests += 1;
+
+        StatsGuard {
+            tracker: self,
+            executor: executor.to_owned(),
+            window,
+            started_at: Instant::now(),
+            success: false,
+        }
+    }
+
+    pub(crate) fn get(&self, executor: &str) -> Option<ExecutorStats> {
+        self.lock()
+            .get(executor)
+            .map(|window| window.stats(executor))
+    }
+
+    pub(crate) fn snapshot(&self) -> Vec<ExecutorStats> {
+        let mut stats = self
+            .lock()
+            .iter()
+            .map(|(executor, window)| window.stats(executor))
+            .collect::<Vec<ExecutorStats>>();
+
+        stats.sort_by(|a, b| a.executor.cmp(&b.executor));
+        stats
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, ExecutorWindow>> {
+        self.0.lock().expect("Stats tracker lock is poisoned.")
+    }
+}
+
+pub(crate) struct StatsGuard<'a> {
+    tracker: &'a StatsTracker,
+    executor: String,
+    window: usize,
+    started_at: Instant,
+    success: bool,
+}
+
+impl StatsGuard<'_> {
+    pub(crate) fn finish<T, E>(mut self, result: &Result<T, E>) {
+        self.success = result.is_ok();
+    }
+}
+
+impl Drop for StatsGuard<'_> {
+    fn drop(&mut self) {
+        let mut windows = self.tracker.lock();
+        let window = windows.entry(self.executor.clone()).or_default();
+
+        window.active_requests = window.active_requests.saturating_sub(1);
+
+        if self.window == 0 {
+            return;
+        }
+
+        while window.samples.len() >= self.window {
+            window.samples.pop_front();
+        }
+
+        window
+            .samples
+            .push_back((self.started_at.elapsed(), self.success));
+    }
+}
+
+fn percentile(latencies: &[Duration], p: f64) -> Option<Duration> {
+    let i = ((latencies.len() as f64 * p).ceil() as usize).max(1) - 1;
+    latencies.get(i).copied()
+}
@@ -0,0 +1,180 @@
+use crate::gateway::GatewaySchema;
+use crate::naming::NamingPolicy;
+use crate::schema::{Schema, Type, TypeKind};
+use crate::static_fields::STATIC_FIELDS_EXECUTOR;
+use graphql_parser::schema::{
+    Definition, Directive, Document, EnumType, EnumValue, Field, TypeDefinition, Value,
+};
+use graphql_parser::Pos;
+use std::collections::HashMap;
+
+const JOIN_DIRECTIVES: &str = r#"directive @join__graph(name: String!, url: String!) on ENUM_VALUE
+
+directive @join__type(graph: join__Graph!, key: String) repeatable on OBJECT | INTERFACE
+
+directive @join__field(graph: join__Graph) repeatable on FIELD_DEFINITION
+"#;
+
+pub(crate) fn supergraph_sdl(
+    mut document: Document<'_, String>,
+    schema: &GatewaySchema,
+    introspections: &HashMap<String, Schema>,
+    naming_policy: &NamingPolicy,
+) -> String {
+    let mut graphs = introspections
+        .keys()
+        .filter(|name| name.as_str() != STATIC_FIELDS_EXECUTOR)
+        .map(|name| name.as_str())
+        .collect::<Vec<&str>>();
+
+    graphs.sort_unstable();
+
+    for definition in document.definitions.iter_mut() {
+        let (kind, name, directives, fields) = match definition {
+            Definition::TypeDefinition(TypeDefinition::Object(object)) => (
+                TypeKind::Object,
+                &object.name,
+                &mut object.directives,
+                &mut object.fields,
+            ),
+            Definition::TypeDefinition(TypeDefinition::Interface(interface)) => (
+                TypeKind::Interface,
+                &interface.name,
+                &mut interface.directives,
+                &mut interface.fields,
+            ),
+            _ => continue,
+        };
+
+        if naming_policy.is_introspection_type(name) {
+            continue;
+        }
+
+        let current_type = schema
+            .2
+            .get(&format!("{}.{}", kind, name))
+            .and_then(|&i| schema.0.types.get(i));
+
+        let current_type = match current_type {
+            Some(current_type) => current_type,
+            _ => continue,
+        };
+
+        let type_graphs = graphs
+            .iter()
+            .filter(|graph| {
+                introspections[**graph]
+                    .types
+                    .iter()
+                    .any(|t| t.kind == kind && t.name() == name)
+            })
+            .copied()
+            .collect::<Vec<&str>>();
+
+        let key = if kind == TypeKind::Object && current_type.is_node() {
+            Some(current_type.key_fields().join(" "))
+        } else {
+            None
+        };
+
+        directives.extend(type_graphs.iter().map(|graph| {
+            let mut arguments = vec![("graph".to_owned(), graph_value(graph))];
+
+            if let Some(key) = &key {
+                arguments.push(("key".to_owned(), Value::String(key.clone())));
+            }
+
+            join_directive("join__type", arguments)
+        }));
+
+        if kind == TypeKind::Object && type_graphs.len() > 1 {
+            for field in fields.iter_mut() {
+                join_fields(schema, current_type, field);
+            }
+        }
+    }
+
+    let index = document.definitions.len().saturating_sub(1);
+    document.definitions.insert(index, graph_enum(&graphs));
+
+    format!("{}\n{}", JOIN_DIRECTIVES, document)
+}
+
+fn join_fields(schema: &GatewaySchema, current_type: &Type, field: &mut Field<'_, String>) {
+    if current_type.key_fields().contains(&field.name.as_str()) {
+        return;
+    }
+
+    let owners = match schema.4.get(&field.name) {
+        Some(owners) if current_type.name() == "Query" => owners.clone(),
+        _ => schema
+            .3
+            .get(&format!("{}.{}", current_type, field.name))
+            .map(|(executor, _)| vec![executor.clone()])
+            .unwrap_or_default(),
+    };
+
+    field.directives.extend(
+        owners
+            .iter()
+            .filter(|owner| owner.as_str() != STATIC_FIELDS_EXECUTOR)
+            .map(|owner| {
+                join_directive(
+                    "join__field",
+                    vec![("graph".to_owned(), graph_value(owner))],
+                )
+            }),
+    );
+}
+
+fn graph_enum<'a>(graphs: &[&str]) -> Definition<'a, String> {
+    Definition::TypeDefinition(TypeDefinition::Enum(EnumType {
+        position: Pos::default(),
+        description: None,
+        name: "join__Graph".to_owned(),
+        directives: vec![],
+        values: graphs
+            .iter()
+            .map(|graph| EnumValue {
+                position: Pos::default(),
+                description: None,
+                name: graph_name(graph),
+                directives: vec![join_directive(
+                    "join__graph",
+                    vec![
+                        ("name".to_owned(), Value::String((*graph).to_owned())),
+                        ("url".to_owned(), Value::String(String::new())),
+                    ],
+                )],
+            })
+            .collect(),
+    }))
+}
+
+fn join_directive<'a>(
+    name: &str,
+    arguments: Vec<(String, Value<'a, String>)>,
+) -> Directive<'a, String> {
+    Directive {
+        position: Pos::default(),
+        name: name.to_owned(),
+        arguments,
+    }
+}
+
+fn graph_value<'a>(graph: &str) -> Value<'a, String> {
+    Value::Enum(graph_name(graph))
+}
+
+fn graph_name(graph: &str) -> String {
+    graph
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
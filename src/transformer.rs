@@ -0,0 +1,15 @@
+use crate::data::Data;
+use graphql_parser::query::Document;
+
+pub trait SelectionTransformer: Send + Sync {
+    fn transform(&self, data: Option<&Data>, document: &mut Document<'_, String>);
+}
+
+impl<F> SelectionTransformer for F
+where
+    F: Fn(Option<&Data>, &mut Document<'_, String>) + Send + Sync,
+{
+    fn transform(&self, data: Option<&Data>, document: &mut Document<'_, String>) {
+        self(data, document)
+    }
+}
@@ -0,0 +1,141 @@
+use crate::naming::{pascal_case, NamingPolicy};
+use crate::schema::{InputValue, Schema, Type, TypeKind};
+
+pub(crate) fn export_typescript(schema: &Schema, naming_policy: &NamingPolicy) -> String {
+    let mut types = schema
+        .types
+        .iter()
+        .filter(|t| !naming_policy.is_introspection_type(t.name()))
+        .collect::<Vec<&Type>>();
+
+    types.sort_by(|a, b| a.name().cmp(b.name()));
+
+    types
+        .into_iter()
+        .filter_map(export_type)
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn export_type(t: &Type) -> Option<String> {
+    let definition = match t.kind {
+        TypeKind::Scalar if scalar(t.name()).is_some() => return None,
+        TypeKind::Scalar => format!("export type {} = any;\n", t.name()),
+        TypeKind::Enum => {
+            let mut values = t
+                .enum_values
+                .iter()
+                .flatten()
+                .map(|value| format!("\"{}\"", value.name))
+                .collect::<Vec<String>>();
+
+            values.sort();
+
+            format!("export type {} = {};\n", t.name(), values.join(" | "))
+        }
+        TypeKind::Union => {
+            let mut possible_types = t
+                .possible_types
+                .iter()
+                .flatten()
+                .map(|possible_type| possible_type.name())
+                .collect::<Vec<&str>>();
+
+            possible_types.sort();
+
+            format!(
+                "export type {} = {};\n",
+                t.name(),
+                possible_types.join(" | ")
+            )
+        }
+        TypeKind::InputObject => {
+            let mut input_fields = t.input_fields.iter().flatten().collect::<Vec<_>>();
+            input_fields.sort_by(|a, b| a.name.cmp(&b.name));
+
+            export_interface(t.name(), None, input_fields.into_iter().map(input))
+        }
+        TypeKind::Object | TypeKind::Interface => {
+            let typename = match t.kind {
+                TypeKind::Object => Some(t.name()),
+                _ => None,
+            };
+
+            let mut fields = t.fields.iter().flatten().collect::<Vec<_>>();
+            fields.sort_by(|a, b| a.name.cmp(&b.name));
+
+            let mut definition = export_interface(
+                t.name(),
+                typename,
+                fields
+                    .iter()
+                    .map(|field| (field.name.clone(), type_ref(&field.field_type, true), false)),
+            );
+
+            for field in fields.iter().filter(|field| !field.args.is_empty()) {
+                definition.push('\n');
+                definition.push_str(&export_interface(
+                    &format!("{}{}Args", t.name(), pascal_case(&field.name)),
+                    None,
+                    field.args.iter().map(input),
+                ));
+            }
+
+            definition
+        }
+        TypeKind::List | TypeKind::NonNull => return None,
+    };
+
+    Some(definition)
+}
+
+fn export_interface<I: Iterator<Item = (String, String, bool)>>(
+    name: &str,
+    typename: Option<&str>,
+    fields: I,
+) -> String {
+    let mut definition = format!("export interface {} {{\n", name);
+
+    if let Some(typename) = typename {
+        definition.push_str(&format!("  __typename?: \"{}\";\n", typename));
+    }
+
+    for (name, field_type, optional) in fields {
+        let optional = if optional { "?" } else { "" };
+        definition.push_str(&format!("  {}{}: {};\n", name, optional, field_type));
+    }
+
+    definition.push_str("}\n");
+    definition
+}
+
+fn input(value: &InputValue) -> (String, String, bool) {
+    (
+        value.name.clone(),
+        type_ref(&value.input_type, true),
+        value.input_type.kind != TypeKind::NonNull || value.default_value.is_some(),
+    )
+}
+
+fn type_ref(t: &Type, nullable: bool) -> String {
+    let name = match t.kind {
+        TypeKind::NonNull => return type_ref(t.of_type(), false),
+        TypeKind::List => format!("Array<{}>", type_ref(t.of_type(), true)),
+        _ => scalar(t.name()).unwrap_or_else(|| t.name()).to_owned(),
+    };
+
+    if nullable {
+        format!("{} | null", name)
+    } else {
+        name
+    }
+}
+
+fn scalar(name: &str) -> Option<&'static str> {
+    match name {
+        "ID" | "String" => Some("string"),
+        "Int" | "Float" => Some("number"),
+        "Boolean" => Some("boolean"),
+        _ => None,
+    }
+}
@@ -0,0 +1,338 @@
+use crate::literals::literal_value;
+use crate::query::{find_operation, QueryError, QueryPosError, QueryResult};
+use crate::schema::{Schema, Type, TypeKind};
+use graphql_parser::query::{Type as AstType, Value as AstValue, VariableDefinition};
+use serde::Serialize;
+use serde_json::{Error as JsonError, Map, Value};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+#[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq)]
+pub struct Variables(Map<String, Value>);
+
+impl Variables {
+    pub fn new() -> Self {
+        Variables::default()
+    }
+
+    pub fn from_serialize<T: Serialize>(value: &T) -> Result<Self, JsonError> {
+        match serde_json::to_value(value)? {
+            Value::Object(map) => Ok(Variables(map)),
+            Value::Null => Ok(Variables::default()),
+            _ => Err(serde::ser::Error::custom("Variables must be an object.")),
+        }
+    }
+
+    pub fn set<K: Into<String>, V: Into<Value>>(mut self, name: K, value: V) -> Self {
+        self.0.insert(name.into(), value.into());
+        self
+    }
+
+    pub fn set_opt<K: Into<String>, V: Into<Value>>(self, name: K, value: Option<V>) -> Self {
+        match value {
+            Some(value) => self.set(name, value),
+            _ => self,
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.0.get(name)
+    }
+
+    pub fn validate<T: AsRef<str>>(&self, query_source: T) -> QueryResult<()> {
+        let document = graphql_parser::parse_query::<String>(query_source.as_ref())?;
+        let (_, _, variable_definitions) = find_operation(&document, None)?;
+        let variables = Value::Object(self.0.clone());
+
+        validate_variables(&variable_definitions, Some(&variables))
+    }
+}
+
+impl From<Variables> for Value {
+    fn from(variables: Variables) -> Value {
+        Value::Object(variables.0)
+    }
+}
+
+impl From<Map<String, Value>> for Variables {
+    fn from(map: Map<String, Value>) -> Variables {
+        Variables(map)
+    }
+}
+
+impl<K: Into<String>, V: Into<Value>> From<HashMap<K, V>> for Variables {
+    fn from(map: HashMap<K, V>) -> Variables {
+        Variables(map.into_iter().map(|(k, v)| (k.into(), v.into())).collect())
+    }
+}
+
+pub(crate) fn validate_variables(
+    variable_definitions: &[VariableDefinition<'_, String>],
+    variables: Option<&Value>,
+) -> QueryResult<()> {
+    let variables = match variables {
+        Some(Value::Object(variables)) => Some(variables),
+        Some(Value::Null) | None => None,
+        _ => return Err(QueryError::InvalidVariables),
+    };
+
+    let errors = variable_definitions
+        .iter()
+        .filter(|variable_definition| {
+            let is_required = matches!(variable_definition.var_type, AstType::NonNullType(_))
+                && variable_definition.default_value.is_none();
+            let value = variables.and_then(|variables| variables.get(&variable_definition.name));
+
+            is_required && value.map(|value| value.is_null()).unwrap_or(true)
+        })
+        .map(|variable_definition| {
+            QueryPosError(
+                variable_definition.position,
+                QueryError::VariableNotProvided(
+                    variable_definition.name.clone(),
+                    variable_definition.var_type.to_string(),
+                ),
+            )
+        })
+        .collect::<Vec<QueryPosError>>();
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(QueryError::Errors(errors))
+    }
+}
+
+pub(crate) fn validate_declared_variables(
+    variable_definitions: &[VariableDefinition<'_, String>],
+    variables: Option<&Value>,
+) -> QueryResult<()> {
+    let variables = match variables {
+        Some(Value::Object(variables)) => variables,
+        _ => return Ok(()),
+    };
+
+    match variables.keys().find(|name| {
+        !variable_definitions
+            .iter()
+            .any(|variable_definition| variable_definition.name == **name)
+    }) {
+        Some(name) => Err(QueryError::UnknownVariable(name.clone())),
+        _ => Ok(()),
+    }
+}
+
+pub(crate) fn with_default_variables(
+    variable_definitions: &[VariableDefinition<'_, String>],
+    variables: Option<&Value>,
+    literals: &[String],
+) -> Option<Value> {
+    let mut variables = match variables {
+        Some(Value::Object(variables)) => variables.clone(),
+        Some(Value::Null) | None => Map::new(),
+        _ => return variables.cloned(),
+    };
+
+    for variable_definition in variable_definitions {
+        if let (false, Some(default_value)) = (
+            variables.contains_key(&variable_definition.name),
+            &variable_definition.default_value,
+        ) {
+            variables.insert(
+                variable_definition.name.clone(),
+                const_to_json(default_value, literals),
+            );
+        }
+    }
+
+    if variables.is_empty() {
+        None
+    } else {
+        Some(Value::Object(variables))
+    }
+}
+
+pub(crate) fn coerce_variables(
+    schema: &Schema,
+    variable_definitions: &[VariableDefinition<'_, String>],
+    variables: Option<&Value>,
+) -> QueryResult<Option<Value>> {
+    let mut variables = match variables {
+        Some(Value::Object(variables)) if !variable_definitions.is_empty() => variables.clone(),
+        _ => return Ok(variables.cloned()),
+    };
+
+    let types = schema
+        .types
+        .iter()
+        .filter_map(|schema_type| Some((schema_type.name.as_deref()?, schema_type)))
+        .collect::<HashMap<&str, &Type>>();
+
+    let mut errors = vec![];
+
+    for variable_definition in variable_definitions {
+        let name = &variable_definition.name;
+        let value = match variables.get_mut(name) {
+            Some(value) => value,
+            _ => continue,
+        };
+
+        match coerce_value(&types, &variable_definition.var_type, value.clone(), name) {
+            Ok(coerced) => *value = coerced,
+            Err(reason) => errors.push(QueryPosError(
+                variable_definition.position,
+                QueryError::InvalidVariableValue(name.clone(), value.to_string(), reason),
+            )),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(Some(Value::Object(variables)))
+    } else {
+        Err(QueryError::Errors(errors))
+    }
+}
+
+fn coerce_value(
+    types: &HashMap<&str, &Type>,
+    var_type: &AstType<'_, String>,
+    value: Value,
+    path: &str,
+) -> Result<Value, String> {
+    match (var_type, value) {
+        (AstType::NonNullType(_), Value::Null) => Err(format!(
+            "Expected non-nullable type \"{}\" not to be null at \"{}\".",
+            var_type, path
+        )),
+        (AstType::NonNullType(of_type), value) => coerce_value(types, of_type, value, path),
+        (_, Value::Null) => Ok(Value::Null),
+        (AstType::ListType(of_type), Value::Array(items)) => items
+            .into_iter()
+            .enumerate()
+            .map(|(i, item)| coerce_value(types, of_type, item, &format!("{}[{}]", path, i)))
+            .collect::<Result<Vec<Value>, String>>()
+            .map(Value::Array),
+        (AstType::ListType(of_type), value) => {
+            coerce_value(types, of_type, value, path).map(|value| Value::Array(vec![value]))
+        }
+        (AstType::NamedType(name), value) => match types.get(name.as_str()) {
+            Some(named_type) => coerce_named_value(types, named_type, value, path),
+            _ => Ok(value),
+        },
+    }
+}
+
+fn coerce_named_value(
+    types: &HashMap<&str, &Type>,
+    named_type: &Type,
+    value: Value,
+    path: &str,
+) -> Result<Value, String> {
+    let is_valid = match (&named_type.kind, named_type.name()) {
+        (TypeKind::Scalar, "Int") => value
+            .as_i64()
+            .map(|value| i32::try_from(value).is_ok())
+            .unwrap_or(false),
+        (TypeKind::Scalar, "Float") => value.is_number(),
+        (TypeKind::Scalar, "String") => value.is_string(),
+        (TypeKind::Scalar, "Boolean") => value.is_boolean(),
+        (TypeKind::Scalar, "ID") => match value.as_i64() {
+            Some(id) => return Ok(Value::String(id.to_string())),
+            _ => value.is_string(),
+        },
+        (TypeKind::Scalar, _) => true,
+        (TypeKind::Enum, _) => value
+            .as_str()
+            .map(|value| {
+                named_type
+                    .enum_values
+                    .iter()
+                    .flatten()
+                    .any(|enum_value| enum_value.name == value)
+            })
+            .unwrap_or(false),
+        (TypeKind::InputObject, _) => {
+            return match value {
+                Value::Object(fields) => coerce_input_object(types, named_type, fields, path),
+                _ => Err(expected_type(named_type, path)),
+            };
+        }
+        _ => false,
+    };
+
+    if is_valid {
+        Ok(value)
+    } else {
+        Err(expected_type(named_type, path))
+    }
+}
+
+fn coerce_input_object(
+    types: &HashMap<&str, &Type>,
+    input_type: &Type,
+    mut fields: Map<String, Value>,
+    path: &str,
+) -> Result<Value, String> {
+    let input_fields = input_type.input_fields.iter().flatten().collect::<Vec<_>>();
+
+    if let Some(name) = fields
+        .keys()
+        .find(|name| input_fields.iter().all(|field| &field.name != *name))
+    {
+        return Err(format!(
+            "Field \"{}\" is not defined by type \"{}\" at \"{}\".",
+            name,
+            input_type.name(),
+            path
+        ));
+    }
+
+    for input_field in input_fields {
+        let field_type = AstType::from(input_field.input_type.clone());
+        let field_path = format!("{}.{}", path, input_field.name);
+
+        match fields.get_mut(&input_field.name) {
+            Some(value) => *value = coerce_value(types, &field_type, value.take(), &field_path)?,
+            _ if matches!(field_type, AstType::NonNullType(_))
+                && input_field.default_value.is_none() =>
+            {
+                return Err(format!(
+                    "Field \"{}\" of required type \"{}\" was not provided at \"{}\".",
+                    input_field.name, field_type, path
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(Value::Object(fields))
+}
+
+fn expected_type(named_type: &Type, path: &str) -> String {
+    format!("Expected type \"{}\" at \"{}\".", named_type.name(), path)
+}
+
+fn const_to_json(value: &AstValue<'_, String>, literals: &[String]) -> Value {
+    match value {
+        AstValue::Int(number) => number.as_i64().map(Value::from).unwrap_or(Value::Null),
+        AstValue::Float(number) => Value::from(*number),
+        AstValue::Enum(value) => {
+            literal_value(value, literals).unwrap_or_else(|| Value::String(value.clone()))
+        }
+        AstValue::String(value) => Value::String(value.clone()),
+        AstValue::Boolean(value) => Value::Bool(*value),
+        AstValue::List(values) => Value::Array(
+            values
+                .iter()
+                .map(|value| const_to_json(value, literals))
+                .collect(),
+        ),
+        AstValue::Object(values) => Value::Object(
+            values
+                .iter()
+                .map(|(name, value)| (name.clone(), const_to_json(value, literals)))
+                .collect(),
+        ),
+        AstValue::Null | AstValue::Variable(_) => Value::Null,
+    }
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription};
+use async_trait::async_trait;
+use common::{inventory, TestExecutor};
+use futures_await_test::async_test;
+use graphql_gateway::{Data, Executor, QueryBuilder};
+use serde_json::{json, Value};
+
+#[derive(Clone)]
+struct TracedExecutor(Box<dyn Executor>);
+
+#[async_trait]
+impl Executor for TracedExecutor {
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    async fn execute(
+        &self,
+        data: Option<&Data>,
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<Value>,
+    ) -> Result<Value, String> {
+        let mut response = self
+            .0
+            .execute(data, query, operation_name, variables)
+            .await?;
+
+        let resolvers = response["data"]["nodes"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .flat_map(|(i, _)| {
+                vec![
+                    json!({ "path": ["nodes", i], "startOffset": 3, "duration": 1 }),
+                    json!({ "path": ["nodes", i, "inStock", "source"], "startOffset": 7, "duration": 1 }),
+                ]
+            })
+            .collect::<Vec<Value>>();
+
+        response["extensions"] = json!({ "tracing": { "execution": { "resolvers": resolvers } } });
+
+        Ok(response)
+    }
+}
+
+#[async_test]
+async fn apollo_tracing() {
+    let query = QueryBuilder::new("query { products { name inStock } }");
+    let gateway = common::gateway().await;
+
+    let response = query.execute_response(&gateway).await;
+    assert!(response.extensions.get("tracing").is_none());
+
+    let gateway = gateway
+        .executor(TracedExecutor(Box::new(TestExecutor::new(
+            "inventory",
+            inventory::Query {},
+            EmptyMutation,
+            EmptySubscription,
+        ))))
+        .apollo_tracing(true);
+
+    let response = query.execute_response(&gateway).await;
+    let tracing = &response.extensions["tracing"];
+
+    assert!(response.result.is_ok());
+    assert_eq!(tracing["version"], json!(1));
+    assert!(tracing["startTime"].as_str().unwrap().ends_with('Z'));
+    assert!(tracing["duration"].as_u64().unwrap() > 0);
+
+    let resolvers = tracing["execution"]["resolvers"].as_array().unwrap();
+    let resolver = |path: Value| {
+        resolvers
+            .iter()
+            .find(|resolver| resolver["path"] == path)
+            .cloned()
+            .unwrap_or_else(|| panic!("Missing resolver for path {}", path))
+    };
+
+    let products = resolver(json!(["products"]));
+    assert_eq!(products["parentType"], json!("Query"));
+    assert_eq!(products["returnType"], json!("[Product!]!"));
+
+    let in_stock = resolver(json!(["products", 1, "inStock"]));
+    assert_eq!(in_stock["parentType"], json!("Product"));
+    assert_eq!(in_stock["fieldName"], json!("inStock"));
+    assert_eq!(in_stock["returnType"], json!("Boolean!"));
+
+    let source = resolver(json!(["products", 1, "inStock", "source"]));
+    assert!(source["startOffset"].as_u64().unwrap() >= 7);
+    assert!(resolver(json!(["products", 0, "inStock", "source"]))["duration"] == json!(1));
+    assert!(!resolvers
+        .iter()
+        .any(|resolver| resolver["path"][0] == json!("nodes")));
+}
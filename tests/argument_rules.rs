@@ -0,0 +1,96 @@
+mod common;
+
+use futures_await_test::async_test;
+use graphql_gateway::{GraphQLResponse, MaxLength, Pattern, QueryBuilder, QueryError, Range};
+use serde_json::{json, Value};
+
+#[async_test]
+async fn argument_rules() {
+    let gateway = common::gateway()
+        .await
+        .argument_rule("User", "sayHello", "name", MaxLength(5))
+        .argument_rule(
+            "User",
+            "reviews",
+            "first",
+            Range {
+                min: Some(1.0),
+                max: Some(10.0),
+            },
+        )
+        .argument_rule(
+            "Mutation",
+            "signIn",
+            "input.email",
+            Pattern::new(r"[^@\s]+@[^@\s]+").unwrap(),
+        )
+        .argument_rule("User", "sayHello", "name", |value: &Value| {
+            match value.as_str() {
+                Some("admin") => Err("is reserved".to_owned()),
+                _ => Ok(()),
+            }
+        });
+
+    let query = QueryBuilder::new(
+        r#"
+            query ($name: String) {
+                users {
+                    sayHello(name: $name)
+                    reviews(first: 20) {
+                        body
+                    }
+                }
+            }
+        "#,
+    )
+    .variables(json!({ "name": "Johnny" }));
+
+    let response = serde_json::to_value(GraphQLResponse(query.execute(&gateway).await)).unwrap();
+
+    assert_eq!(
+        response["errors"],
+        json!([
+            {
+                "message": "Invalid value for argument \"name\" on field \"User.sayHello\": must be at most 5 characters long.",
+                "locations": [{ "line": 4, "column": 21 }],
+                "extensions": { "code": "BAD_USER_INPUT", "field": "User.sayHello", "argument": "name" }
+            },
+            {
+                "message": "Invalid value for argument \"first\" on field \"User.reviews\": must be less than or equal to 10.",
+                "locations": [{ "line": 5, "column": 21 }],
+                "extensions": { "code": "BAD_USER_INPUT", "field": "User.reviews", "argument": "first" }
+            }
+        ])
+    );
+
+    let query = QueryBuilder::new(r#"{ viewer { sayHello(name: "admin") } }"#);
+
+    match query.execute(&gateway).await.unwrap_err() {
+        QueryError::Errors(errors) => assert_eq!(
+            errors[0].1.to_string(),
+            "Invalid value for argument \"name\" on field \"User.sayHello\": is reserved."
+        ),
+        err => panic!("Unexpected error {:?}", err),
+    }
+
+    let query = QueryBuilder::new(r#"{ viewer { sayHello(name: "Jo") } }"#);
+
+    assert!(query.execute(&gateway).await.is_ok());
+
+    let query = QueryBuilder::new(
+        r#"
+            mutation {
+                signIn(input: { email: "john", password: "yep" }) {
+                    id
+                }
+            }
+        "#,
+    );
+
+    let response = serde_json::to_value(GraphQLResponse(query.execute(&gateway).await)).unwrap();
+
+    assert_eq!(
+        response["errors"][0]["message"],
+        json!("Invalid value for argument \"input.email\" on field \"Mutation.signIn\": must match pattern \"[^@\\s]+@[^@\\s]+\".")
+    );
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use futures_await_test::async_test;
+use graphql_gateway::{Authorizer, Data, QueryBuilder, UnauthorizedField};
+use serde_json::json;
+
+struct DenyFields(Vec<&'static str>);
+
+impl Authorizer for DenyFields {
+    fn is_authorized(&self, _data: Option<&Data>, object_type: &str, field: &str) -> bool {
+        !self
+            .0
+            .contains(&format!("{}.{}", object_type, field).as_str())
+    }
+}
+
+#[async_test]
+async fn unauthorized_field() {
+    let query = QueryBuilder::new(
+        r#"
+            query {
+                users {
+                    id
+                    email
+                }
+            }
+        "#,
+    );
+
+    let gateway = common::gateway()
+        .await
+        .authorizer(DenyFields(vec!["User.email", "User.role"]));
+    let response = serde_json::to_value(query.execute_response(&gateway).await).unwrap();
+
+    assert_eq!(
+        response["errors"][0]["message"],
+        json!("Not authorized to query field \"email\" on type \"User\".")
+    );
+    assert_eq!(
+        response["errors"][0]["extensions"]["code"],
+        json!("FORBIDDEN")
+    );
+    assert_eq!(response["extensions"]["errorClass"], json!("AUTH"));
+    assert!(response.get("data").is_none());
+
+    let gateway = common::gateway()
+        .await
+        .authorizer(DenyFields(vec!["User.email", "User.role"]))
+        .unauthorized_field(UnauthorizedField::Omit);
+
+    assert_eq!(
+        query.execute(&gateway).await.unwrap(),
+        json!({
+            "users": [
+                { "id": "VXNlcjow" },
+                { "id": "VXNlcjox" }
+            ]
+        })
+    );
+
+    let gateway = common::gateway()
+        .await
+        .authorizer(DenyFields(vec!["User.email", "User.role"]))
+        .field_unauthorized("User", "email", UnauthorizedField::Null)
+        .field_unauthorized("User", "role", UnauthorizedField::Null);
+    let response = serde_json::to_value(query.execute_response(&gateway).await).unwrap();
+
+    assert_eq!(
+        response["data"],
+        json!({
+            "users": [
+                { "id": "VXNlcjow", "email": null },
+                { "id": "VXNlcjox", "email": null }
+            ]
+        })
+    );
+    assert_eq!(
+        response["errors"][0]["extensions"]["code"],
+        json!("FORBIDDEN")
+    );
+    assert_eq!(
+        response["errors"][0]["locations"],
+        json!([{ "line": 5, "column": 21 }])
+    );
+
+    let query = QueryBuilder::new(
+        r#"
+            query {
+                users {
+                    id
+                    role
+                }
+            }
+        "#,
+    );
+    let response = serde_json::to_value(query.execute_response(&gateway).await).unwrap();
+
+    assert_eq!(
+        response["errors"][0]["extensions"]["code"],
+        json!("FORBIDDEN")
+    );
+    assert!(response.get("data").is_none());
+}
@@ -0,0 +1,46 @@
+mod common;
+
+use futures_await_test::async_test;
+use graphql_gateway::GraphQLBatchPayload;
+use serde_json::json;
+
+#[async_test]
+async fn batch() {
+    let gateway = common::gateway().await.batch_parallelism(2);
+
+    let payload: GraphQLBatchPayload = serde_json::from_value(json!([
+        { "query": "query { users { username } }" },
+        {
+            "query": "query Product($id: ID!) { node(id: $id) { ... on Product { name } } }",
+            "operationName": "Product",
+            "variables": { "id": "UHJvZHVjdDox" }
+        },
+        { "query": "query { unknown }" }
+    ]))
+    .unwrap();
+
+    let response = serde_json::to_value(payload.execute(&gateway).await).unwrap();
+
+    assert_eq!(
+        response[0]["data"],
+        json!({ "users": [{ "username": null }, { "username": "albert" }] })
+    );
+    assert_eq!(
+        response[1]["data"],
+        json!({ "node": { "name": "Product 2" } })
+    );
+    assert_eq!(
+        response[2]["errors"][0]["message"],
+        json!("Cannot query field \"unknown\" on type \"Query\".")
+    );
+
+    let payload: GraphQLBatchPayload =
+        serde_json::from_value(json!({ "query": "query { products { name } }" })).unwrap();
+
+    let response = serde_json::to_value(payload.execute(&gateway).await).unwrap();
+
+    assert_eq!(
+        response["data"],
+        json!({ "products": [{ "name": "Product 1" }, { "name": "Product 2" }] })
+    );
+}
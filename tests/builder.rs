@@ -0,0 +1,344 @@
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription};
+use async_trait::async_trait;
+use common::{account, inventory, product, review, TestExecutor};
+use futures_await_test::async_test;
+use futures_timer::Delay;
+use graphql_gateway::{
+    ConfigError, Data, ErrorClass, ErrorClassifier, Executor, ExecutorOptions, GatewayBuilder,
+    GatewayError, GraphQLResponse, NodeLookup, QueryBuilder, QueryError, RetryPolicy,
+};
+use serde_json::{json, Map, Value};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+struct RejectingClassifier;
+
+impl ErrorClassifier for RejectingClassifier {
+    fn classify(&self, _error: &QueryError) -> ErrorClass {
+        ErrorClass::Rejected
+    }
+}
+
+#[derive(Clone)]
+struct UnstableExecutor {
+    executor: Box<dyn Executor>,
+    failures: usize,
+    delay: Option<Duration>,
+    calls: Arc<AtomicUsize>,
+}
+
+impl UnstableExecutor {
+    fn new<E: Executor + 'static>(executor: E) -> Self {
+        UnstableExecutor {
+            executor: Box::new(executor),
+            failures: 0,
+            delay: None,
+            calls: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+}
+
+#[async_trait]
+impl Executor for UnstableExecutor {
+    fn name(&self) -> &str {
+        self.executor.name()
+    }
+
+    async fn execute(
+        &self,
+        data: Option<&Data>,
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<Value>,
+    ) -> Result<Value, String> {
+        if operation_name.as_deref() != Some("IntrospectionQuery") {
+            if self.calls.fetch_add(1, Ordering::SeqCst) < self.failures {
+                return Err("Connection reset".to_owned());
+            }
+
+            if let Some(delay) = self.delay {
+                Delay::new(delay).await;
+            }
+        }
+
+        self.executor
+            .execute(data, query, operation_name, variables)
+            .await
+    }
+}
+
+fn builder<'a>() -> GatewayBuilder<'a> {
+    GatewayBuilder::new()
+        .executor(TestExecutor::new(
+            "account",
+            account::Query {},
+            account::Mutation {},
+            EmptySubscription,
+        ))
+        .executor(TestExecutor::new(
+            "product",
+            product::Query {},
+            product::Mutation {},
+            EmptySubscription,
+        ))
+        .executor(TestExecutor::new(
+            "review",
+            review::Query {},
+            EmptyMutation,
+            EmptySubscription,
+        ))
+}
+
+fn inventory() -> UnstableExecutor {
+    UnstableExecutor::new(TestExecutor::new(
+        "inventory",
+        inventory::Query {},
+        EmptyMutation,
+        EmptySubscription,
+    ))
+}
+
+#[async_test]
+async fn builder_validation() {
+    let result = builder()
+        .executor_with_options(
+            inventory(),
+            ExecutorOptions::default()
+                .timeout(Duration::from_secs(0))
+                .retry(0, Duration::from_millis(1))
+                .forward_header("authorization")
+                .forward_header("x tenant"),
+        )
+        .executor(inventory())
+        .configure(|gateway| {
+            gateway
+                .node_lookup("inventory", NodeLookup::default().id_type("[ID!"))
+                .directive(
+                    "type Mask { id: ID }",
+                    |_: Option<&Data>, _: &Map<String, Value>, value| Ok(value),
+                )
+        })
+        .max_depth(0)
+        .max_complexity(0)
+        .build()
+        .await;
+
+    match result {
+        Err(GatewayError::InvalidConfiguration(errors)) => assert_eq!(
+            errors,
+            vec![
+                ConfigError::ZeroTimeout("inventory".to_owned()),
+                ConfigError::ZeroRetryAttempts("inventory".to_owned()),
+                ConfigError::InvalidHeaderName("inventory".to_owned(), "x tenant".to_owned()),
+                ConfigError::DuplicateExecutor("inventory".to_owned()),
+                ConfigError::InvalidNodeIdType("inventory".to_owned(), "[ID!".to_owned()),
+                ConfigError::InvalidDirective(
+                    "type Mask { id: ID }".to_owned(),
+                    "Expected a single directive definition.".to_owned()
+                ),
+                ConfigError::ZeroMaxDepth,
+                ConfigError::ZeroMaxComplexity,
+            ]
+        ),
+        _ => panic!("Expected an invalid configuration error"),
+    }
+}
+
+#[async_test]
+async fn builder_executor_options() {
+    let query = QueryBuilder::new("{ products { name inStock } }");
+    let expected = json!({
+        "products": [
+            { "name": "Product 1", "inStock": true },
+            { "name": "Product 2", "inStock": false }
+        ]
+    });
+
+    let mut flaky = inventory();
+    flaky.failures = 2;
+    let calls = flaky.calls.clone();
+
+    let gateway = builder()
+        .executor_with_options(
+            flaky,
+            ExecutorOptions::default().retry(3, Duration::from_millis(1)),
+        )
+        .build()
+        .await
+        .unwrap();
+
+    assert_eq!(query.execute(&gateway).await.unwrap(), expected);
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+    let mut slow = inventory();
+    slow.delay = Some(Duration::from_millis(200));
+
+    let gateway = builder()
+        .executor_with_options(
+            slow,
+            ExecutorOptions::default().timeout(Duration::from_millis(20)),
+        )
+        .build()
+        .await
+        .unwrap();
+
+    let response = serde_json::to_value(GraphQLResponse(query.execute(&gateway).await)).unwrap();
+
+    assert_eq!(
+        response["errors"][0]["message"],
+        json!("Executor \"inventory\" timed out after 20ms")
+    );
+}
+
+#[async_test]
+async fn builder_global_options() {
+    let gateway = builder()
+        .executor(inventory())
+        .max_depth(2)
+        .introspection(false)
+        .build()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        QueryBuilder::new("{ viewer { email } }")
+            .execute(&gateway)
+            .await
+            .unwrap(),
+        json!({ "viewer": { "email": "john@doe.com" } })
+    );
+
+    let response = serde_json::to_value(GraphQLResponse(
+        QueryBuilder::new(
+            "{ viewer { ...Reviews } } fragment Reviews on User { reviews { body } }",
+        )
+        .execute(&gateway)
+        .await,
+    ))
+    .unwrap();
+
+    assert_eq!(
+        response["errors"][0]["message"],
+        json!("Query depth 3 exceeds the maximum depth of 2.")
+    );
+
+    let response = serde_json::to_value(GraphQLResponse(
+        QueryBuilder::new("{ __schema { queryType { name } } }")
+            .execute(&gateway)
+            .await,
+    ))
+    .unwrap();
+
+    assert_eq!(
+        response["errors"][0]["message"],
+        json!("Introspection is disabled.")
+    );
+}
+
+#[async_test]
+async fn executor_resilience() {
+    let retry =
+        RetryPolicy::new(4, Duration::from_millis(10)).max_backoff(Duration::from_millis(25));
+
+    assert_eq!(
+        (1..4)
+            .map(|attempt| retry.delay(attempt))
+            .collect::<Vec<Duration>>(),
+        vec![
+            Duration::from_millis(10),
+            Duration::from_millis(20),
+            Duration::from_millis(25)
+        ]
+    );
+
+    let mut product = UnstableExecutor::new(TestExecutor::new(
+        "product",
+        product::Query {},
+        product::Mutation {},
+        EmptySubscription,
+    ));
+    product.failures = 2;
+    let calls = product.calls.clone();
+
+    let mut slow = inventory();
+    slow.delay = Some(Duration::from_millis(200));
+
+    let gateway = GatewayBuilder::new()
+        .executor(slow)
+        .executor(product)
+        .configure(|gateway| {
+            gateway
+                .executor_options(
+                    "product",
+                    ExecutorOptions::default()
+                        .retry_policy(RetryPolicy::new(3, Duration::from_millis(1))),
+                )
+                .executor_options(
+                    "inventory",
+                    ExecutorOptions::default().timeout(Duration::from_millis(20)),
+                )
+        })
+        .build()
+        .await
+        .unwrap();
+
+    let mutation = QueryBuilder::new(r#"mutation { addProduct(id: "UHJvZHVjdDow") { name } }"#);
+
+    assert!(matches!(
+        mutation.execute(&gateway).await,
+        Err(QueryError::Transport(..))
+    ));
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    let query = QueryBuilder::new("{ products { name } }");
+
+    assert_eq!(
+        query.execute(&gateway).await.unwrap(),
+        json!({
+            "products": [{ "name": "Product 1" }, { "name": "Product 2" }]
+        })
+    );
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+    let query = QueryBuilder::new("{ products { name inStock } }");
+
+    match query.execute(&gateway).await {
+        Err(QueryError::ExecutorTimeout(executor, timeout)) => {
+            assert_eq!((executor.as_str(), timeout), ("inventory", 20))
+        }
+        _ => panic!("Expected an executor timeout"),
+    }
+}
+
+#[async_test]
+async fn executor_retry_classification() {
+    let mut product = UnstableExecutor::new(TestExecutor::new(
+        "product",
+        product::Query {},
+        product::Mutation {},
+        EmptySubscription,
+    ));
+    product.failures = 2;
+    let calls = product.calls.clone();
+
+    let gateway = GatewayBuilder::new()
+        .executor_with_options(
+            product,
+            ExecutorOptions::default().retry(3, Duration::from_millis(1)),
+        )
+        .configure(|gateway| gateway.error_classifier(RejectingClassifier))
+        .build()
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        QueryBuilder::new("{ products { name } }")
+            .execute(&gateway)
+            .await,
+        Err(QueryError::Transport(..))
+    ));
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,210 @@
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription};
+use async_trait::async_trait;
+use common::{inventory, inventory_stock, TestExecutor};
+use futures_await_test::async_test;
+use graphql_gateway::{
+    CircuitBreaker, CircuitState, Clock, Data, ErrorClass, ErrorClassifier, Executor, Gateway,
+    QueryBuilder, QueryError,
+};
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+#[derive(Clone)]
+struct ManualClock(Instant, Arc<Mutex<Duration>>);
+
+impl ManualClock {
+    fn new() -> Self {
+        ManualClock(Instant::now(), Arc::new(Mutex::new(Duration::default())))
+    }
+
+    fn advance(&self, duration: Duration) {
+        *self.1.lock().unwrap() += duration;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.0 + *self.1.lock().unwrap()
+    }
+}
+
+struct RejectingClassifier;
+
+impl ErrorClassifier for RejectingClassifier {
+    fn classify(&self, _error: &QueryError) -> ErrorClass {
+        ErrorClass::Rejected
+    }
+}
+
+#[derive(Clone)]
+struct FailingExecutor {
+    executor: Box<dyn Executor>,
+    failing: Arc<AtomicBool>,
+    calls: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl Executor for FailingExecutor {
+    fn name(&self) -> &str {
+        self.executor.name()
+    }
+
+    async fn execute(
+        &self,
+        data: Option<&Data>,
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<Value>,
+    ) -> Result<Value, String> {
+        if operation_name.as_deref() != Some("IntrospectionQuery") {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+
+            if self.failing.load(Ordering::SeqCst) {
+                return Err("Connection refused".to_owned());
+            }
+        }
+
+        self.executor
+            .execute(data, query, operation_name, variables)
+            .await
+    }
+}
+
+fn circuit(gateway: &Gateway<'_>, name: &str) -> CircuitState {
+    gateway
+        .health()
+        .executors
+        .into_iter()
+        .find(|executor| executor.name == name)
+        .map(|executor| executor.circuit)
+        .unwrap()
+}
+
+#[async_test]
+async fn circuit_breaker() {
+    let failing = Arc::new(AtomicBool::new(true));
+    let calls = Arc::new(AtomicUsize::new(0));
+    let clock = ManualClock::new();
+    let gateway = common::gateway()
+        .await
+        .executor(FailingExecutor {
+            executor: Box::new(TestExecutor::new(
+                "inventory",
+                inventory_stock::Query {},
+                EmptyMutation,
+                EmptySubscription,
+            )),
+            failing: failing.clone(),
+            calls: calls.clone(),
+        })
+        .build()
+        .await
+        .unwrap()
+        .executor_circuit_breaker(
+            "inventory",
+            CircuitBreaker::new()
+                .min_requests(2)
+                .open_duration(Duration::from_secs(30)),
+        )
+        .circuit_breaker_clock(clock.clone());
+
+    let query = QueryBuilder::new("{ products { name quantity } }");
+
+    assert_eq!(circuit(&gateway, "inventory"), CircuitState::Closed);
+    assert!(query.execute(&gateway).await.is_err());
+    assert!(query.execute(&gateway).await.is_err());
+    assert_eq!(circuit(&gateway, "inventory"), CircuitState::Open);
+    assert_eq!(circuit(&gateway, "product"), CircuitState::Closed);
+
+    let response = serde_json::to_value(query.execute_response(&gateway).await).unwrap();
+
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert_eq!(
+        response["data"],
+        json!({
+            "products": [
+                { "name": "Product 1", "quantity": null },
+                { "name": "Product 2", "quantity": null }
+            ]
+        })
+    );
+    assert_eq!(response["errors"].as_array().map(Vec::len), Some(1));
+    assert_eq!(
+        response["errors"][0]["message"],
+        json!("Executor \"inventory\" is unavailable, its circuit breaker is open.")
+    );
+    assert_eq!(
+        response["errors"][0]["extensions"]["code"],
+        json!("CIRCUIT_OPEN")
+    );
+
+    let response = serde_json::to_value(
+        QueryBuilder::new("{ products { name inStock } }")
+            .execute_response(&gateway)
+            .await,
+    )
+    .unwrap();
+
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert_eq!(response["data"], json!(null));
+    assert_eq!(
+        response["errors"][1]["message"],
+        json!("Cannot return null for non-nullable field \"Product.inStock\".")
+    );
+    assert_eq!(
+        response["errors"][1]["path"],
+        json!(["products", 0, "inStock"])
+    );
+
+    failing.store(false, Ordering::SeqCst);
+    clock.advance(Duration::from_secs(29));
+
+    assert_eq!(circuit(&gateway, "inventory"), CircuitState::Open);
+
+    clock.advance(Duration::from_secs(1));
+
+    assert_eq!(circuit(&gateway, "inventory"), CircuitState::HalfOpen);
+    assert_eq!(
+        query.execute(&gateway).await.unwrap(),
+        json!({
+            "products": [
+                { "name": "Product 1", "quantity": 3 },
+                { "name": "Product 2", "quantity": null }
+            ]
+        })
+    );
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+    assert_eq!(circuit(&gateway, "inventory"), CircuitState::Closed);
+}
+
+#[async_test]
+async fn circuit_breaker_definitive_errors() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let gateway = common::gateway()
+        .await
+        .executor(FailingExecutor {
+            executor: Box::new(TestExecutor::new(
+                "inventory",
+                inventory::Query {},
+                EmptyMutation,
+                EmptySubscription,
+            )),
+            failing: Arc::new(AtomicBool::new(true)),
+            calls: calls.clone(),
+        })
+        .executor_circuit_breaker("inventory", CircuitBreaker::new().min_requests(2))
+        .error_classifier(RejectingClassifier);
+
+    let query = QueryBuilder::new("{ products { name inStock } }");
+
+    for _ in 0..3 {
+        assert!(query.execute(&gateway).await.is_err());
+    }
+
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+    assert_eq!(circuit(&gateway, "inventory"), CircuitState::Closed);
+}
@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use async_graphql::http::GQLResponse;
 use async_graphql::{
     EmptyMutation, EmptySubscription, ObjectType, QueryBuilder, Schema, SubscriptionType,
@@ -5,11 +7,15 @@ use async_graphql::{
 };
 use async_trait::async_trait;
 use base64::DecodeError;
-use graphql_gateway::{Data, Executor, Gateway};
+use futures::future;
+use futures::task::Poll;
+use graphql_gateway::{Data, Executor, Gateway, GatewayBuilder};
 use serde_json::Value;
 use std::convert::From;
 use std::num::ParseIntError;
 use std::str::{from_utf8, Utf8Error};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 
 pub enum Error {
     DecodeError(DecodeError),
@@ -86,14 +92,14 @@ where
 }
 
 #[async_trait]
-impl<'a, Q, M, S> Executor for TestExecutor<'static, Q, M, S>
+impl<Q, M, S> Executor for TestExecutor<'static, Q, M, S>
 where
     Q: ObjectType + Send + Sync + 'static,
     M: ObjectType + Send + Sync + 'static,
     S: SubscriptionType + Send + Sync + 'static,
 {
     fn name(&self) -> &str {
-        &self.0
+        self.0
     }
 
     async fn execute(
@@ -119,6 +125,139 @@ where
     }
 }
 
+#[derive(Clone)]
+pub struct CountingExecutor(pub Box<dyn Executor>, pub Arc<AtomicUsize>);
+
+impl CountingExecutor {
+    pub fn new<E: Executor + 'static>(executor: E) -> Self {
+        CountingExecutor(Box::new(executor), Arc::new(AtomicUsize::new(0)))
+    }
+
+    pub fn count(&self) -> usize {
+        self.1.load(Ordering::SeqCst)
+    }
+}
+
+#[async_trait]
+impl Executor for CountingExecutor {
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    async fn execute(
+        &self,
+        ctx: Option<&Data>,
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<Value>,
+    ) -> Result<Value, String> {
+        self.1.fetch_add(1, Ordering::SeqCst);
+        self.0.execute(ctx, query, operation_name, variables).await
+    }
+}
+
+#[derive(Clone)]
+pub struct InFlightExecutor(pub Box<dyn Executor>, pub Arc<(AtomicUsize, AtomicUsize)>);
+
+#[async_trait]
+impl Executor for InFlightExecutor {
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    async fn execute(
+        &self,
+        ctx: Option<&Data>,
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<Value>,
+    ) -> Result<Value, String> {
+        let (in_flight, max_in_flight) = &*self.1;
+        let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        max_in_flight.fetch_max(current, Ordering::SeqCst);
+
+        let mut yielded = false;
+        future::poll_fn(|cx| {
+            if yielded {
+                return Poll::Ready(());
+            }
+
+            yielded = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        })
+        .await;
+
+        let res = self.0.execute(ctx, query, operation_name, variables).await;
+        in_flight.fetch_sub(1, Ordering::SeqCst);
+
+        res
+    }
+}
+
+pub struct Call<'a> {
+    pub executor: &'a str,
+    pub data: Option<&'a Data>,
+    pub query: &'a str,
+    pub operation_name: Option<&'a str>,
+    pub variables: Option<&'a Value>,
+}
+
+type Record<T> = Arc<dyn Fn(&Call) -> Option<T> + Send + Sync>;
+
+pub struct RecordingExecutor<T>(pub Box<dyn Executor>, pub Arc<Mutex<Vec<T>>>, Record<T>);
+
+impl<T: Send + 'static> RecordingExecutor<T> {
+    pub fn new<E, F>(executor: E, calls: Arc<Mutex<Vec<T>>>, record: F) -> Self
+    where
+        E: Executor + 'static,
+        F: Fn(&Call) -> Option<T> + Send + Sync + 'static,
+    {
+        RecordingExecutor(Box::new(executor), calls, Arc::new(record))
+    }
+}
+
+impl RecordingExecutor<String> {
+    pub fn queries<E: Executor + 'static>(executor: E, queries: Arc<Mutex<Vec<String>>>) -> Self {
+        Self::new(executor, queries, |call| Some(call.query.to_owned()))
+    }
+}
+
+impl<T> Clone for RecordingExecutor<T> {
+    fn clone(&self) -> Self {
+        RecordingExecutor(self.0.clone(), self.1.clone(), self.2.clone())
+    }
+}
+
+#[async_trait]
+impl<T: Send + 'static> Executor for RecordingExecutor<T> {
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    async fn execute(
+        &self,
+        data: Option<&Data>,
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<Value>,
+    ) -> Result<Value, String> {
+        let call = Call {
+            executor: self.name(),
+            data,
+            query: &query,
+            operation_name: operation_name.as_deref(),
+            variables: variables.as_ref(),
+        };
+
+        if let Some(record) = (self.2)(&call) {
+            self.1.lock().unwrap().push(record);
+        }
+
+        self.0.execute(data, query, operation_name, variables).await
+    }
+}
+
 pub async fn gateway<'a>() -> Gateway<'a> {
     let account = TestExecutor::new(
         "account",
@@ -139,7 +278,7 @@ pub async fn gateway<'a>() -> Gateway<'a> {
         EmptySubscription,
     );
     let review = TestExecutor::new("review", review::Query {}, EmptyMutation, EmptySubscription);
-    Gateway::default()
+    GatewayBuilder::new()
         .executor(account)
         .executor(inventory)
         .executor(product)
@@ -171,12 +310,12 @@ pub mod account {
 
         #[field]
         async fn email(&self) -> Option<&str> {
-            self.1.as_ref().map(|v| v.as_str())
+            self.1.as_deref()
         }
 
         #[field]
         async fn username(&self) -> Option<&str> {
-            self.2.as_ref().map(|v| v.as_str())
+            self.2.as_deref()
         }
 
         #[field]
@@ -226,7 +365,7 @@ pub mod account {
 
             ids.iter()
                 .map(|node_id| {
-                    let id = match super::from_global_id(&node_id) {
+                    let id = match super::from_global_id(node_id) {
                         Ok((_, id)) => id,
                         _ => return None,
                     };
@@ -241,7 +380,7 @@ pub mod account {
 
         #[field]
         async fn viewer(&self) -> Option<User> {
-            USERS.clone().get(0).cloned()
+            USERS.clone().first().cloned()
         }
 
         #[field]
@@ -261,7 +400,7 @@ pub mod account {
     #[async_graphql::Object]
     impl Mutation {
         async fn sign_in(&self, _input: SignInInput) -> Option<&User> {
-            USERS.get(0)
+            USERS.first()
         }
     }
 }
@@ -321,7 +460,86 @@ pub mod inventory {
 
             ids.iter()
                 .map(|node_id| {
-                    let id = match super::from_global_id(&node_id) {
+                    let id = match super::from_global_id(node_id) {
+                        Ok((_, id)) => id,
+                        _ => return None,
+                    };
+
+                    match node_type.as_str() {
+                        "Product" => PRODUCTS.get(id).map(|u| Node::Product(u.clone())),
+                        _ => None,
+                    }
+                })
+                .collect()
+        }
+    }
+}
+
+pub mod inventory_stock {
+    use async_graphql::ID;
+
+    #[derive(Clone)]
+    pub struct Product(usize, bool);
+
+    #[async_graphql::Object]
+    impl Product {
+        #[field]
+        async fn id(&self) -> ID {
+            super::to_global_id("Product", self.0)
+        }
+
+        #[field]
+        async fn in_stock(&self) -> bool {
+            self.1
+        }
+
+        #[field]
+        async fn quantity(&self) -> Option<i32> {
+            if self.1 {
+                Some(3)
+            } else {
+                None
+            }
+        }
+    }
+
+    lazy_static::lazy_static! {
+        pub static ref PRODUCTS: Vec<Product> = vec![
+            Product(0, true),
+            Product(1, false)
+            ];
+    }
+
+    #[async_graphql::Interface(field(name = "id", type = "ID"))]
+    pub struct Node(Product);
+
+    pub struct Query;
+
+    #[async_graphql::Object]
+    impl Query {
+        #[field]
+        async fn node(&self, id: ID) -> Option<Node> {
+            let (node_type, id) = match super::from_global_id(&id) {
+                Ok((node_type, id)) => (node_type, id),
+                _ => return None,
+            };
+
+            match node_type.as_str() {
+                "Product" => PRODUCTS.clone().get(id).map(|u| Node::Product(u.clone())),
+                _ => None,
+            }
+        }
+
+        #[field]
+        async fn nodes(&self, ids: Vec<ID>) -> Vec<Option<Node>> {
+            let node_type = match super::from_global_id(&ids[0]) {
+                Ok((node_type, _)) => node_type,
+                _ => return vec![],
+            };
+
+            ids.iter()
+                .map(|node_id| {
+                    let id = match super::from_global_id(node_id) {
                         Ok((_, id)) => id,
                         _ => return None,
                     };
@@ -391,7 +609,7 @@ pub mod inventory_updated {
 
             ids.iter()
                 .map(|node_id| {
-                    let id = match super::from_global_id(&node_id) {
+                    let id = match super::from_global_id(node_id) {
                         Ok((_, id)) => id,
                         _ => return None,
                     };
@@ -461,7 +679,7 @@ pub mod product {
 
             ids.iter()
                 .map(|node_id| {
-                    let id = match super::from_global_id(&node_id) {
+                    let id = match super::from_global_id(node_id) {
                         Ok((_, id)) => id,
                         _ => return None,
                     };
@@ -510,11 +728,27 @@ pub mod review {
         }
 
         #[field]
-        async fn reviews(&self) -> Vec<&Review> {
-            REVIEWS.iter().filter(|r| r.1 == self.0).collect()
+        async fn reviews(&self, first: Option<i32>, page: Option<ReviewPage>) -> Vec<&Review> {
+            let (offset, first) = match (page, first) {
+                (Some(page), _) => (page.offset as usize, Some(page.first as usize)),
+                (_, first) => (0, first.map(|first| first as usize)),
+            };
+
+            REVIEWS
+                .iter()
+                .filter(|r| r.1 == self.0)
+                .skip(offset)
+                .take(first.unwrap_or(usize::MAX))
+                .collect()
         }
     }
 
+    #[async_graphql::InputObject]
+    pub struct ReviewPage {
+        pub first: i32,
+        pub offset: i32,
+    }
+
     #[derive(Clone)]
     pub struct Review(usize, usize, usize, String);
 
@@ -596,7 +830,7 @@ pub mod review {
 
             ids.iter()
                 .map(|node_id| {
-                    let id = match super::from_global_id(&node_id) {
+                    let id = match super::from_global_id(node_id) {
                         Ok((_, id)) => id,
                         _ => return None,
                     };
@@ -0,0 +1,96 @@
+mod common;
+
+use async_graphql::EmptySubscription;
+use async_trait::async_trait;
+use common::TestExecutor;
+use futures_await_test::async_test;
+use graphql_gateway::{Compensation, Data, GraphQLResponse, QueryBuilder, QueryError};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+
+mod billing {
+    use async_graphql::FieldResult;
+
+    pub struct Query;
+
+    #[async_graphql::Object]
+    impl Query {
+        #[field]
+        async fn balance(&self) -> i32 {
+            0
+        }
+    }
+
+    pub struct Mutation;
+
+    #[async_graphql::Object]
+    impl Mutation {
+        #[field]
+        async fn charge(&self, amount: i32) -> FieldResult<i32> {
+            Err(format!("Unable to charge {}", amount).into())
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+struct Recorder(Arc<Mutex<Vec<(String, Value)>>>);
+
+#[async_trait]
+impl Compensation for Recorder {
+    async fn compensate(
+        &self,
+        _data: Option<&Data>,
+        field: &str,
+        value: &Value,
+        _error: &QueryError,
+    ) {
+        self.0
+            .lock()
+            .unwrap()
+            .push((field.to_owned(), value.clone()));
+    }
+}
+
+#[async_test]
+async fn compensation() {
+    let billing = TestExecutor::new(
+        "billing",
+        billing::Query {},
+        billing::Mutation {},
+        EmptySubscription,
+    );
+
+    let recorder = Recorder::default();
+    let mut gateway = common::gateway()
+        .await
+        .executor(billing)
+        .compensation("addProduct", recorder.clone())
+        .compensation("signIn", recorder.clone());
+
+    gateway.pull("billing").await.unwrap();
+
+    let query = QueryBuilder::new(
+        r#"
+        mutation {
+            product: addProduct(id: "UHJvZHVjdDow") {
+                id
+            }
+            charge(amount: 10)
+            signIn(input: { email: "john@doe.com", password: "yep" }) {
+                id
+            }
+        }
+        "#,
+    );
+
+    let response = serde_json::to_value(GraphQLResponse(query.execute(&gateway).await)).unwrap();
+
+    assert_eq!(
+        response["errors"][0]["message"],
+        json!("Unable to charge 10")
+    );
+    assert_eq!(
+        *recorder.0.lock().unwrap(),
+        vec![("addProduct".to_owned(), json!({ "id": "UHJvZHVjdDow" }))]
+    );
+}
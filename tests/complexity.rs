@@ -0,0 +1,77 @@
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription};
+use common::{inventory, TestExecutor};
+use futures_await_test::async_test;
+use graphql_gateway::{GraphQLResponse, QueryBuilder};
+use serde_json::json;
+
+const INVENTORY_SDL: &str = r#"
+    interface Node {
+        id: ID!
+    }
+
+    type Product implements Node {
+        id: ID!
+        inStock: Boolean! @cost(weight: 20)
+    }
+
+    type Query {
+        node(id: ID!): Node
+        nodes(ids: [ID!]!): [Node]!
+    }
+"#;
+
+#[async_test]
+async fn max_complexity() {
+    let query = "{ viewer { reviews(first: 10) { body } } }";
+    let gateway = common::gateway().await.max_complexity(10);
+    let response = serde_json::to_value(GraphQLResponse(
+        QueryBuilder::new(query).execute(&gateway).await,
+    ))
+    .unwrap();
+
+    assert_eq!(
+        response["errors"][0]["message"],
+        json!("Query complexity 12 exceeds the maximum complexity of 10.")
+    );
+
+    let gateway = gateway.field_cost("Review.body", 0);
+
+    assert_eq!(
+        QueryBuilder::new(query).execute(&gateway).await.unwrap(),
+        json!({
+            "viewer": {
+                "reviews": [{ "body": "Good product" }, { "body": "Bad product" }]
+            }
+        })
+    );
+}
+
+#[async_test]
+async fn schema_field_cost() {
+    let inventory = TestExecutor::new(
+        "inventory",
+        inventory::Query {},
+        EmptyMutation,
+        EmptySubscription,
+    );
+    let gateway = common::gateway()
+        .await
+        .executor_with_sdl(inventory, INVENTORY_SDL)
+        .max_complexity(10)
+        .build()
+        .await
+        .unwrap();
+    let response = serde_json::to_value(GraphQLResponse(
+        QueryBuilder::new("{ products { name inStock } }")
+            .execute(&gateway)
+            .await,
+    ))
+    .unwrap();
+
+    assert_eq!(
+        response["errors"][0]["message"],
+        json!("Query complexity 22 exceeds the maximum complexity of 10.")
+    );
+}
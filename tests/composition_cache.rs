@@ -0,0 +1,76 @@
+mod common;
+
+use async_graphql::EmptySubscription;
+use async_trait::async_trait;
+use common::{product, TestExecutor};
+use futures_await_test::async_test;
+use graphql_gateway::{Data, Executor, Gateway};
+use serde_json::Value;
+use std::fs;
+
+#[derive(Clone)]
+struct DownExecutor(&'static str);
+
+#[async_trait]
+impl Executor for DownExecutor {
+    fn name(&self) -> &str {
+        self.0
+    }
+
+    async fn execute(
+        &self,
+        _data: Option<&Data>,
+        _query: String,
+        _operation_name: Option<String>,
+        _variables: Option<Value>,
+    ) -> Result<Value, String> {
+        Err("Network is unreachable".to_owned())
+    }
+}
+
+#[async_test]
+async fn composition_cache() {
+    let path = std::env::temp_dir().join(format!(
+        "graphql-gateway-composition-{}.json",
+        std::process::id()
+    ));
+    let _ = fs::remove_file(&path);
+
+    let down = Gateway::default()
+        .executor(DownExecutor("product"))
+        .composition_cache(&path)
+        .build()
+        .await
+        .unwrap();
+
+    assert!(!down.to_sdl().contains("products"));
+    assert!(!path.exists());
+
+    let gateway = Gateway::default()
+        .executor(TestExecutor::new(
+            "product",
+            product::Query {},
+            product::Mutation {},
+            EmptySubscription,
+        ))
+        .composition_cache(&path)
+        .build()
+        .await
+        .unwrap();
+
+    assert!(path.exists());
+    assert_eq!(gateway.health().executors[0].stale_since, None);
+
+    let cached = Gateway::default()
+        .executor(DownExecutor("product"))
+        .composition_cache(&path)
+        .build()
+        .await
+        .unwrap();
+
+    assert!(cached.to_sdl().contains("products"));
+    assert_eq!(cached.to_sdl(), gateway.to_sdl());
+    assert!(cached.health().executors[0].stale_since.is_some());
+
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use futures_await_test::async_test;
+use graphql_gateway::{CompositionLimit, CompositionLimitError, CompositionLimits, GatewayError};
+
+#[async_test]
+async fn composition_limits() {
+    let gateway = common::gateway().await;
+
+    let limited = |limits: CompositionLimits| gateway.clone().composition_limits(limits).build();
+
+    assert!(limited(CompositionLimits::default()).await.is_ok());
+
+    match limited(CompositionLimits {
+        max_fields_per_type: Some(3),
+        ..CompositionLimits::default()
+    })
+    .await
+    {
+        Err(GatewayError::CompositionLimitsExceeded(errors)) => {
+            assert!(errors.contains(&CompositionLimitError {
+                executor: "account".to_owned(),
+                name: Some("User".to_owned()),
+                limit: CompositionLimit::FieldsPerType,
+                count: 5,
+                max: 3,
+            }));
+        }
+        _ => panic!("Expected a composition limits error"),
+    }
+
+    match limited(CompositionLimits {
+        max_types: Some(1),
+        ..CompositionLimits::default()
+    })
+    .await
+    {
+        Err(GatewayError::CompositionLimitsExceeded(errors)) => {
+            let mut executors = errors
+                .iter()
+                .map(|error| {
+                    assert_eq!(error.limit, CompositionLimit::Types);
+                    error.executor.as_str()
+                })
+                .collect::<Vec<&str>>();
+            executors.sort();
+
+            assert_eq!(executors, vec!["account", "inventory", "product", "review"]);
+        }
+        _ => panic!("Expected a composition limits error"),
+    }
+
+    match limited(CompositionLimits {
+        max_enum_values: Some(2),
+        ..CompositionLimits::default()
+    })
+    .await
+    {
+        Err(GatewayError::CompositionLimitsExceeded(errors)) => {
+            assert_eq!(
+                errors,
+                vec![CompositionLimitError {
+                    executor: "account".to_owned(),
+                    name: Some("UserRole".to_owned()),
+                    limit: CompositionLimit::EnumValues,
+                    count: 3,
+                    max: 2,
+                }]
+            );
+        }
+        _ => panic!("Expected a composition limits error"),
+    }
+}
@@ -0,0 +1,67 @@
+mod common;
+
+use async_trait::async_trait;
+use futures_await_test::async_test;
+use graphql_gateway::conformance;
+use graphql_gateway::{Data, Executor, Gateway, QueryBuilder};
+use serde_json::{json, Value};
+
+#[derive(Clone)]
+struct NullLeakExecutor;
+
+#[async_trait]
+impl Executor for NullLeakExecutor {
+    fn name(&self) -> &str {
+        "status"
+    }
+
+    async fn execute(
+        &self,
+        _data: Option<&Data>,
+        _query: String,
+        _operation_name: Option<String>,
+        _variables: Option<Value>,
+    ) -> Result<Value, String> {
+        Ok(json!({
+            "data": { "status": null, "conformanceA": null, "conformanceB": null }
+        }))
+    }
+}
+
+#[async_test]
+async fn conformance_success() {
+    let gateway = common::gateway().await;
+    let report = conformance::run(&gateway).await;
+
+    assert_eq!(report.failures, vec![]);
+    assert!(report.is_success());
+    assert!(report.scenarios > 4);
+}
+
+#[async_test]
+async fn conformance_null_propagation() {
+    let gateway = Gateway::default()
+        .executor_with_sdl(NullLeakExecutor, "type Query { status: String! }")
+        .build()
+        .await
+        .unwrap();
+    let report = conformance::run(&gateway).await;
+
+    assert_eq!(report.failures, vec![]);
+    assert!(report.is_success());
+
+    let response = QueryBuilder::new("query SmokeStatus { status }")
+        .execute_response(&gateway)
+        .await;
+    let response = serde_json::to_value(response).unwrap();
+
+    assert_eq!(response["data"], json!(null));
+    assert_eq!(
+        response["errors"][0]["message"],
+        json!("Cannot return null for non-nullable field \"Query.status\".")
+    );
+    assert_eq!(
+        response["errors"][0]["locations"],
+        json!([{ "line": 1, "column": 21 }])
+    );
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use async_graphql::EmptySubscription;
+use common::{product, RecordingExecutor, TestExecutor};
+use futures_await_test::async_test;
+use graphql_gateway::QueryBuilder;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone)]
+struct Config(&'static str);
+
+#[derive(Clone)]
+struct Token(&'static str);
+
+#[async_test]
+async fn layered_data() {
+    let source = r#"
+        query {
+            products {
+                name
+            }
+        }
+    "#;
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let product = TestExecutor::new(
+        "product",
+        product::Query {},
+        product::Mutation {},
+        EmptySubscription,
+    );
+
+    let gateway = common::gateway()
+        .await
+        .executor(RecordingExecutor::new(product, seen.clone(), |call| {
+            Some((
+                call.data
+                    .and_then(|data| data.get::<Config>())
+                    .map(|config| config.0),
+                call.data
+                    .and_then(|data| data.get::<Token>())
+                    .map(|token| token.0),
+            ))
+        }))
+        .data(Config("global"))
+        .data(Token("global"));
+
+    QueryBuilder::new(source).execute(&gateway).await.unwrap();
+    QueryBuilder::new(source)
+        .data(Token("request"))
+        .execute(&gateway)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        seen.lock().unwrap().as_slice(),
+        [
+            (Some("global"), Some("global")),
+            (Some("global"), Some("request"))
+        ]
+    );
+}
@@ -0,0 +1,153 @@
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription};
+use common::{RecordingExecutor, TestExecutor};
+use futures_await_test::async_test;
+use graphql_gateway::{Gateway, QueryBuilder, QueryError, Range};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+
+mod catalog {
+    #[async_graphql::InputObject]
+    pub struct PageInput {
+        #[field(default = "25")]
+        pub size: i32,
+    }
+
+    pub struct Query;
+
+    #[async_graphql::Object]
+    impl Query {
+        #[field]
+        async fn items(
+            &self,
+            #[arg(default = "3")] first: i32,
+            #[arg(default = "\"name\"")] order: String,
+        ) -> Vec<String> {
+            (0..first).map(|i| format!("{}:{}", order, i)).collect()
+        }
+
+        #[field]
+        async fn page(&self, input: PageInput) -> i32 {
+            input.size
+        }
+    }
+}
+
+#[async_test]
+async fn default_values() {
+    let executor = TestExecutor::new("catalog", catalog::Query, EmptyMutation, EmptySubscription);
+    let gateway = Gateway::default().executor(executor).build().await.unwrap();
+    let sdl = gateway.to_string();
+
+    assert!(sdl.contains("items(first: Int! = 3, order: String! = \"name\"): [String!]!"));
+    assert!(sdl.contains("size: Int! = 25"));
+
+    let response = QueryBuilder::new(
+        "{ __schema { types { name fields { name args { name defaultValue } } } } }",
+    )
+    .execute(&gateway)
+    .await
+    .unwrap();
+
+    let items = response["__schema"]["types"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|schema_type| schema_type["name"] == "Query")
+        .flat_map(|schema_type| schema_type["fields"].as_array().unwrap())
+        .find(|field| field["name"] == "items")
+        .unwrap();
+
+    assert_eq!(
+        items["args"],
+        json!([
+            { "name": "first", "defaultValue": "3" },
+            { "name": "order", "defaultValue": "\"name\"" }
+        ])
+    );
+
+    let gateway = gateway
+        .argument_rule(
+            "Query",
+            "items",
+            "first",
+            Range {
+                min: None,
+                max: Some(2.0),
+            },
+        )
+        .argument_rule(
+            "Query",
+            "page",
+            "input.size",
+            Range {
+                min: None,
+                max: Some(10.0),
+            },
+        );
+
+    assert_eq!(
+        QueryBuilder::new("{ items(first: 2) }")
+            .execute(&gateway)
+            .await
+            .unwrap(),
+        json!({ "items": ["name:0", "name:1"] })
+    );
+
+    let messages = match QueryBuilder::new("{ items page(input: {}) }")
+        .execute(&gateway)
+        .await
+    {
+        Err(QueryError::Errors(errors)) => errors
+            .into_iter()
+            .map(|error| error.1.to_string())
+            .collect::<Vec<String>>(),
+        result => panic!("Unexpected result {:?}", result),
+    };
+
+    assert_eq!(
+        messages,
+        vec![
+            "Invalid value for argument \"first\" on field \"Query.items\": must be less than or equal to 2.",
+            "Invalid value for argument \"input.size\" on field \"Query.page\": must be less than or equal to 10.",
+        ]
+    );
+}
+
+#[async_test]
+async fn default_variables() {
+    let variables = Arc::new(Mutex::new(vec![]));
+    let executor = RecordingExecutor::new(
+        TestExecutor::new("catalog", catalog::Query, EmptyMutation, EmptySubscription),
+        variables.clone(),
+        |call| {
+            if call.operation_name == Some("IntrospectionQuery") {
+                None
+            } else {
+                Some(call.variables.cloned())
+            }
+        },
+    );
+    let gateway = Gateway::default().executor(executor).build().await.unwrap();
+    let source = r#"
+        query Items($first: Int = 2, $order: String = "id", $size: Int) {
+            items(first: $first, order: $order)
+            page(input: { size: $size })
+        }
+    "#;
+
+    assert_eq!(
+        QueryBuilder::new(source)
+            .operation_name("Items")
+            .variables(json!({ "order": "rank", "size": 5 }))
+            .execute(&gateway)
+            .await
+            .unwrap(),
+        json!({ "items": ["rank:0", "rank:1"], "page": 5 })
+    );
+    assert_eq!(
+        variables.lock().unwrap().clone(),
+        vec![Some(json!({ "first": 2, "order": "rank", "size": 5 }))]
+    );
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use async_graphql::EmptySubscription;
+use async_trait::async_trait;
+use common::{product, TestExecutor};
+use futures_await_test::async_test;
+use futures_timer::Delay;
+use graphql_gateway::{Data, Executor, QueryBuilder, RequestId, RequestPhase};
+use serde_json::Value;
+use std::time::Duration;
+
+#[derive(Clone)]
+struct SlowExecutor(Box<dyn Executor>);
+
+#[async_trait]
+impl Executor for SlowExecutor {
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    async fn execute(
+        &self,
+        data: Option<&Data>,
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<Value>,
+    ) -> Result<Value, String> {
+        Delay::new(Duration::from_millis(200)).await;
+        self.0.execute(data, query, operation_name, variables).await
+    }
+}
+
+#[async_test]
+async fn inflight_requests() {
+    let query = QueryBuilder::new(
+        r#"
+            query Products {
+                products {
+                    name
+                }
+            }
+        "#,
+    )
+    .operation_name("Products")
+    .data(RequestId::new("stuck"));
+
+    let product = TestExecutor::new(
+        "product",
+        product::Query {},
+        product::Mutation {},
+        EmptySubscription,
+    );
+    let gateway = common::gateway()
+        .await
+        .executor(SlowExecutor(Box::new(product)));
+
+    let probe = async {
+        Delay::new(Duration::from_millis(50)).await;
+        gateway.inflight_requests()
+    };
+
+    let (result, requests) = futures::join!(query.execute(&gateway), probe);
+
+    assert!(result.is_ok());
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].request_id, "stuck");
+    assert_eq!(requests[0].operation_name.as_deref(), Some("Products"));
+    assert_eq!(requests[0].phase, RequestPhase::Executing);
+    assert_eq!(requests[0].waiting.len(), 1);
+    assert_eq!(requests[0].waiting[0].executor, "product");
+    assert!(requests[0].waiting[0].elapsed_ms >= 40);
+    assert!(gateway.inflight_requests().is_empty());
+}
+
+#[async_test]
+async fn inflight_requests_shared_id() {
+    let query = QueryBuilder::new("query Products { products { name } }")
+        .operation_name("Products")
+        .data(RequestId::new("shared"));
+
+    let product = TestExecutor::new(
+        "product",
+        product::Query {},
+        product::Mutation {},
+        EmptySubscription,
+    );
+    let gateway = common::gateway()
+        .await
+        .executor(SlowExecutor(Box::new(product)));
+
+    let probe = async {
+        Delay::new(Duration::from_millis(50)).await;
+        gateway.inflight_requests()
+    };
+
+    let (first, second, requests) =
+        futures::join!(query.execute(&gateway), query.execute(&gateway), probe);
+
+    assert!(first.is_ok() && second.is_ok());
+    assert_eq!(requests.len(), 2);
+    assert!(requests
+        .iter()
+        .all(|request| request.request_id == "shared" && request.waiting.len() == 1));
+    assert!(gateway.inflight_requests().is_empty());
+}
@@ -0,0 +1,138 @@
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription};
+use common::{inventory, product, CountingExecutor, RecordingExecutor, TestExecutor};
+use futures_await_test::async_test;
+use graphql_gateway::{Data, QueryBuilder, QueryError};
+use serde_json::{json, Map, Value};
+use std::sync::{Arc, Mutex};
+
+#[async_test]
+async fn directives() {
+    let source = r#"
+        query Products($withStock: Boolean!, $skipName: Boolean = true) {
+            products {
+                name @skip(if: $skipName)
+                inStock @include(if: $withStock)
+                ...ProductName @include(if: false)
+                ... on Product @skip(if: false) {
+                    id
+                }
+            }
+        }
+
+        fragment ProductName on Product {
+            name
+        }
+    "#;
+
+    let inventory = CountingExecutor::new(TestExecutor::new(
+        "inventory",
+        inventory::Query {},
+        EmptyMutation,
+        EmptySubscription,
+    ));
+
+    let gateway = common::gateway().await.executor(inventory.clone());
+
+    let query = QueryBuilder::new(source)
+        .operation_name("Products")
+        .variables(json!({ "withStock": false }));
+
+    assert_eq!(
+        query.execute(&gateway).await.unwrap(),
+        json!({
+            "products": [
+                { "id": "UHJvZHVjdDow" },
+                { "id": "UHJvZHVjdDox" }
+            ]
+        })
+    );
+    assert_eq!(inventory.count(), 0);
+
+    let query = QueryBuilder::new(source)
+        .operation_name("Products")
+        .variables(json!({ "withStock": true, "skipName": false }));
+
+    assert_eq!(
+        query.execute(&gateway).await.unwrap(),
+        json!({
+            "products": [
+                { "name": "Product 1", "inStock": true, "id": "UHJvZHVjdDow" },
+                { "name": "Product 2", "inStock": false, "id": "UHJvZHVjdDox" }
+            ]
+        })
+    );
+    assert_eq!(inventory.count(), 1);
+}
+
+fn mask(
+    _data: Option<&Data>,
+    arguments: &Map<String, Value>,
+    value: Value,
+) -> Result<Value, String> {
+    let mask = arguments["char"].as_str().unwrap_or_default();
+
+    Ok(match value {
+        Value::String(value) => Value::from(mask.repeat(value.chars().count())),
+        value => value,
+    })
+}
+
+#[async_test]
+async fn gateway_directives() {
+    let queries = Arc::new(Mutex::new(vec![]));
+    let mut gateway = common::gateway()
+        .await
+        .executor(RecordingExecutor::queries(
+            TestExecutor::new(
+                "product",
+                product::Query {},
+                product::Mutation {},
+                EmptySubscription,
+            ),
+            queries.clone(),
+        ))
+        .directive(r#"directive @mask(char: String = "*") on FIELD"#, mask)
+        .directive(
+            "directive @fail on FIELD",
+            |_: Option<&Data>, _: &Map<String, Value>, _| Err("Unavailable".to_owned()),
+        );
+
+    gateway.pull("product").await.unwrap();
+
+    assert!(gateway
+        .to_sdl()
+        .contains(r#"directive @mask(char: String = "*") on FIELD"#));
+
+    let query = QueryBuilder::new(
+        "query Products($char: String) { products { name @mask(char: $char) inStock @mask } }",
+    )
+    .operation_name("Products")
+    .variables(json!({ "char": "#" }));
+
+    assert_eq!(
+        query.execute(&gateway).await.unwrap(),
+        json!({
+            "products": [
+                { "name": "#########", "inStock": true },
+                { "name": "#########", "inStock": false }
+            ]
+        })
+    );
+
+    let queries = queries.lock().unwrap().clone();
+    assert!(queries
+        .iter()
+        .all(|query| !query.contains("@mask") && !query.contains("$char")));
+
+    let query = QueryBuilder::new("query { products { name @fail } }");
+
+    match query.execute(&gateway).await.unwrap_err() {
+        QueryError::Errors(errors) => assert_eq!(
+            errors[0].1.to_string(),
+            "Directive \"@fail\" failed: Unavailable"
+        ),
+        err => panic!("Unexpected error {:?}", err),
+    }
+}
@@ -0,0 +1,113 @@
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription};
+use common::{inventory, RecordingExecutor, TestExecutor};
+use futures_await_test::async_test;
+use graphql_gateway::{is_injected_variable, DocumentFormat, QueryBuilder};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+
+#[async_test]
+async fn document_format() {
+    let query = QueryBuilder::new(
+        r#"
+            query {
+                products {
+                    name
+                    ...Stock
+                }
+            }
+
+            fragment Stock on Product {
+                inStock
+            }
+        "#,
+    );
+
+    let queries = Arc::new(Mutex::new(Vec::new()));
+    let inventory = TestExecutor::new(
+        "inventory",
+        inventory::Query {},
+        EmptyMutation,
+        EmptySubscription,
+    );
+
+    let gateway = common::gateway()
+        .await
+        .executor(RecordingExecutor::queries(inventory, queries.clone()))
+        .document_format(DocumentFormat::Minified);
+
+    assert_eq!(
+        query.execute(&gateway).await.unwrap(),
+        json!({
+            "products": [
+                { "name": "Product 1", "inStock": true },
+                { "name": "Product 2", "inStock": false }
+            ]
+        })
+    );
+    assert_eq!(
+        queries.lock().unwrap().as_slice(),
+        ["fragment Stock on Product{id inStock}query NodeQuery($__gql_gateway_ids:[ID]!){nodes(ids:$__gql_gateway_ids){...on Product{id...Stock}}}"]
+    );
+
+    let source = r#"query Q($a: [Int] = [1, -2]) { b(c: "", d: "x y") { e @include(if: true) } }"#;
+    let minified = DocumentFormat::Minified.format_source(source);
+
+    assert_eq!(
+        minified,
+        r#"query Q($a:[Int]=[1 -2]){b(c:""d:"x y"){e@include(if:true)}}"#
+    );
+    assert_eq!(
+        DocumentFormat::Pretty.format_source(&minified),
+        DocumentFormat::Pretty.format_source(source)
+    );
+    assert_eq!(
+        DocumentFormat::Pretty.format_source("{ a { b } }"),
+        "{\n  a {\n    b\n  }\n}\n"
+    );
+}
+
+#[async_test]
+async fn injected_variables() {
+    let query = QueryBuilder::new(
+        r#"
+            query Products($__gql_gateway_ids: Boolean!) {
+                products {
+                    name
+                    inStock @include(if: $__gql_gateway_ids)
+                }
+            }
+        "#,
+    )
+    .operation_name("Products")
+    .variables(json!({ "__gql_gateway_ids": true }));
+
+    let queries = Arc::new(Mutex::new(Vec::new()));
+    let inventory = TestExecutor::new(
+        "inventory",
+        inventory::Query {},
+        EmptyMutation,
+        EmptySubscription,
+    );
+
+    let gateway = common::gateway()
+        .await
+        .executor(RecordingExecutor::queries(inventory, queries.clone()))
+        .document_format(DocumentFormat::Minified);
+
+    assert_eq!(
+        query.execute(&gateway).await.unwrap(),
+        json!({
+            "products": [
+                { "name": "Product 1", "inStock": true },
+                { "name": "Product 2", "inStock": false }
+            ]
+        })
+    );
+    assert!(is_injected_variable("__gql_gateway_ids_1"));
+    assert_eq!(
+        queries.lock().unwrap().as_slice(),
+        ["query NodeQuery($__gql_gateway_ids:Boolean!$__gql_gateway_ids_1:[ID]!){nodes(ids:$__gql_gateway_ids_1){...on Product{id inStock@include(if:$__gql_gateway_ids)}}}"]
+    );
+}
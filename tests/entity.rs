@@ -0,0 +1,45 @@
+mod common;
+
+use futures_await_test::async_test;
+use graphql_gateway::{Data, QueryError};
+use serde_json::json;
+
+#[async_test]
+async fn resolve_entity() {
+    let gateway = common::gateway().await;
+
+    let product = gateway
+        .resolve_entity(
+            "Product",
+            "UHJvZHVjdDow",
+            "id name inStock reviews { body }",
+            None,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        product,
+        json!({
+            "id": "UHJvZHVjdDow",
+            "name": "Product 1",
+            "inStock": true,
+            "reviews": [{ "body": "Good product" }, { "body": "Fake description" }]
+        })
+    );
+
+    let missing = gateway
+        .resolve_entity("Product", "UHJvZHVjdDoxMDA=", "name", Some(Data::default()))
+        .await
+        .unwrap();
+
+    assert_eq!(missing, json!(null));
+
+    match gateway
+        .resolve_entity("Query", "UHJvZHVjdDow", "name", None)
+        .await
+    {
+        Err(QueryError::UnknownEntityType(type_name)) => assert_eq!(type_name, "Query"),
+        result => panic!("Unexpected result {:?}", result),
+    }
+}
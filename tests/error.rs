@@ -1,8 +1,39 @@
 mod common;
 
+use async_graphql::EmptySubscription;
+use async_trait::async_trait;
+use common::{product, TestExecutor};
 use futures_await_test::async_test;
-use graphql_gateway::{GraphQLResponse, QueryBuilder, QueryError};
-use serde_json::json;
+use graphql_gateway::{
+    Data, DefaultErrorClassifier, ErrorClass, ErrorClassifier, ErrorPassthrough, Executor,
+    GraphQLResponse, PlannerFlags, QueryBuilder, QueryError,
+};
+use serde_json::{json, Value};
+
+#[derive(Clone)]
+struct UnreachableExecutor(Box<dyn Executor>);
+
+#[async_trait]
+impl Executor for UnreachableExecutor {
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    async fn execute(
+        &self,
+        data: Option<&Data>,
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<Value>,
+    ) -> Result<Value, String> {
+        match operation_name.as_deref() {
+            Some("IntrospectionQuery") => {
+                self.0.execute(data, query, operation_name, variables).await
+            }
+            _ => Err("Connection refused".to_owned()),
+        }
+    }
+}
 
 #[async_test]
 async fn error_not_supported() {
@@ -80,6 +111,53 @@ async fn error_unknown_fragment() {
     );
 }
 
+#[async_test]
+async fn error_duplicate_fragment() {
+    let query = QueryBuilder::new(
+        r#"
+            query {
+                products {
+                    ...ProductDetail
+                }
+            }
+
+            fragment ProductDetail on Product {
+                id
+            }
+
+            fragment ProductDetail on Product {
+                name
+            }
+        "#
+        .to_owned(),
+    );
+
+    let gateway = common::gateway().await;
+    let response = serde_json::to_value(GraphQLResponse(query.execute(&gateway).await)).unwrap();
+
+    assert_eq!(
+        response,
+        json!({
+            "errors": [{
+                "message": "There can be only one fragment named \"ProductDetail\".",
+                "locations": [{ "line": 8, "column": 13 }, { "line": 12, "column": 13 }]
+            }]
+        })
+    );
+
+    let response = query
+        .data(PlannerFlags {
+            allow_duplicate_fragments: true,
+            ..PlannerFlags::default()
+        })
+        .execute(&gateway)
+        .await
+        .unwrap();
+
+    assert!(response["products"][0].get("name").is_some());
+    assert!(response["products"][0].get("id").is_none());
+}
+
 #[async_test]
 async fn error_executor() {
     let response =
@@ -96,3 +174,137 @@ async fn error_executor() {
         })
     );
 }
+
+#[async_test]
+async fn error_classification() {
+    let product = UnreachableExecutor(Box::new(TestExecutor::new(
+        "product",
+        product::Query {},
+        product::Mutation {},
+        EmptySubscription,
+    )));
+
+    let mut gateway = common::gateway().await.executor(product);
+    gateway.pull("product").await.unwrap();
+
+    let response = QueryBuilder::new("query { products { name } }")
+        .execute_response(&gateway)
+        .await;
+
+    assert_eq!(response.extensions["errorClass"], json!("TRANSIENT"));
+    assert_eq!(
+        response.result.unwrap_err().to_string(),
+        "Connection refused"
+    );
+
+    let response = QueryBuilder::new("query { viewer { unknown } }")
+        .execute_response(&gateway)
+        .await;
+
+    assert_eq!(response.extensions["errorClass"], json!("VALIDATION"));
+
+    let response = QueryBuilder::new("query { viewer { username } }")
+        .execute_response(&gateway)
+        .await;
+
+    assert!(response.extensions.get("errorClass").is_none());
+
+    let forbidden = QueryError::Executor(json!({
+        "errors": [{ "message": "Forbidden", "extensions": { "code": "FORBIDDEN" } }]
+    }));
+    let rejected = QueryError::Executor(json!({ "errors": [{ "message": "Out of stock" }] }));
+
+    assert_eq!(
+        DefaultErrorClassifier.classify(&forbidden),
+        ErrorClass::Auth
+    );
+    assert_eq!(
+        DefaultErrorClassifier.classify(&rejected),
+        ErrorClass::Rejected
+    );
+    assert_eq!(
+        DefaultErrorClassifier.classify(&QueryError::ResourceExhausted(2048, 1024)),
+        ErrorClass::Rejected
+    );
+    assert!(!ErrorClass::Rejected.is_retryable());
+    assert!(ErrorClass::Transient.is_retryable());
+}
+
+#[derive(Clone)]
+struct FailingInventory;
+
+#[async_trait]
+impl Executor for FailingInventory {
+    fn name(&self) -> &str {
+        "inventory"
+    }
+
+    async fn execute(
+        &self,
+        _data: Option<&Data>,
+        _query: String,
+        _operation_name: Option<String>,
+        _variables: Option<Value>,
+    ) -> Result<Value, String> {
+        Ok(json!({
+            "data": { "nodes": [{ "id": "UHJvZHVjdDow", "inStock": true }, null] },
+            "errors": [{
+                "message": "Stock service timed out",
+                "locations": [{ "line": 5, "column": 7 }],
+                "path": ["nodes", 1, "inStock"],
+                "extensions": { "code": "STOCK_TIMEOUT" }
+            }]
+        }))
+    }
+}
+
+#[async_test]
+async fn error_passthrough() {
+    let gateway = common::gateway().await.executor(FailingInventory);
+    let query = QueryBuilder::new("query { products { name inStock } }");
+
+    let response = serde_json::to_value(GraphQLResponse(query.execute(&gateway).await)).unwrap();
+
+    assert_eq!(
+        response,
+        json!({
+            "data": null,
+            "errors": [{
+                "message": "Stock service timed out",
+                "locations": [{ "line": 5, "column": 7 }],
+                "path": ["products", 1, "inStock"],
+                "extensions": { "code": "STOCK_TIMEOUT" }
+            }]
+        })
+    );
+
+    let batched = gateway.clone().planner_flags(PlannerFlags {
+        batch_nodes: true,
+        ..PlannerFlags::default()
+    });
+
+    match query.execute(&batched).await {
+        Err(QueryError::Downstream(errors)) => assert_eq!(
+            errors[0].path,
+            Some(vec![json!("products"), json!(1), json!("inStock")])
+        ),
+        result => panic!("Unexpected result {:?}", result),
+    }
+
+    let gateway = gateway.error_passthrough(ErrorPassthrough::Mask);
+
+    match query.execute(&gateway).await {
+        Err(QueryError::Downstream(errors)) => {
+            assert_eq!(errors.len(), 1);
+            assert_eq!(errors[0].executor, "inventory");
+            assert_eq!(errors[0].message, "Downstream service error.");
+            assert_eq!(errors[0].code(), Some("STOCK_TIMEOUT"));
+            assert!(errors[0].locations.is_empty());
+            assert_eq!(
+                errors[0].path,
+                Some(vec![json!("products"), json!(1), json!("inStock")])
+            );
+        }
+        result => panic!("Unexpected result {:?}", result),
+    }
+}
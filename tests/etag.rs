@@ -0,0 +1,42 @@
+mod common;
+
+use futures_await_test::async_test;
+use graphql_gateway::{IfNoneMatch, QueryBuilder};
+
+#[async_test]
+async fn etag() {
+    let source = r#"
+        query {
+            products {
+                name
+            }
+        }
+    "#;
+
+    let gateway = common::gateway().await;
+    let response = QueryBuilder::new(source).execute_response(&gateway).await;
+
+    assert!(response.etag.is_none());
+
+    let gateway = common::gateway().await.etag(true);
+    let response = QueryBuilder::new(source).execute_response(&gateway).await;
+    let etag = response.etag.unwrap();
+
+    assert!(!response.not_modified);
+
+    let response = QueryBuilder::new(source)
+        .data(IfNoneMatch(format!("\"other\", W/{}", etag)))
+        .execute_response(&gateway)
+        .await;
+
+    assert_eq!(response.etag.as_ref(), Some(&etag));
+    assert!(response.not_modified);
+
+    let response = QueryBuilder::new("query { users { id } }")
+        .data(IfNoneMatch(etag.clone()))
+        .execute_response(&gateway)
+        .await;
+
+    assert_ne!(response.etag.as_ref(), Some(&etag));
+    assert!(!response.not_modified);
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use async_graphql::EmptySubscription;
+use async_trait::async_trait;
+use common::{product, CountingExecutor, TestExecutor};
+use futures_await_test::async_test;
+use graphql_gateway::{Data, Executor, QueryBuilder};
+use serde_json::Value;
+
+#[derive(Clone)]
+struct ToggleExecutor {
+    executor: Box<dyn Executor>,
+    enabled: bool,
+}
+
+#[async_trait]
+impl Executor for ToggleExecutor {
+    fn name(&self) -> &str {
+        self.executor.name()
+    }
+
+    async fn execute(
+        &self,
+        data: Option<&Data>,
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<Value>,
+    ) -> Result<Value, String> {
+        if !self.enabled {
+            return Err("Executor is disabled".to_owned());
+        }
+
+        self.executor
+            .execute(data, query, operation_name, variables)
+            .await
+    }
+}
+
+#[async_test]
+async fn executor_as() {
+    let mut gateway = common::gateway().await.executor(ToggleExecutor {
+        executor: Box::new(TestExecutor::new(
+            "product",
+            product::Query {},
+            product::Mutation {},
+            EmptySubscription,
+        )),
+        enabled: true,
+    });
+
+    assert!(
+        gateway
+            .executor_as::<ToggleExecutor>("product")
+            .unwrap()
+            .enabled
+    );
+    assert!(gateway.executor_as::<CountingExecutor>("product").is_none());
+    assert!(gateway.executor_as::<ToggleExecutor>("unknown").is_none());
+
+    let query = QueryBuilder::new("query { products { name } }");
+    assert!(query.execute(&gateway).await.is_ok());
+
+    gateway
+        .executor_as_mut::<ToggleExecutor>("product")
+        .unwrap()
+        .enabled = false;
+
+    assert!(query.execute(&gateway).await.is_err());
+}
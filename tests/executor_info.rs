@@ -0,0 +1,68 @@
+mod common;
+
+use async_trait::async_trait;
+use futures_await_test::async_test;
+use graphql_gateway::{Data, Executor, ExecutorHealth, Gateway};
+use serde_json::Value;
+
+#[derive(Clone)]
+struct UnreachableExecutor;
+
+#[async_trait]
+impl Executor for UnreachableExecutor {
+    fn name(&self) -> &str {
+        "billing"
+    }
+
+    async fn execute(
+        &self,
+        _data: Option<&Data>,
+        _query: String,
+        _operation_name: Option<String>,
+        _variables: Option<Value>,
+    ) -> Result<Value, String> {
+        Err("Connection refused".to_owned())
+    }
+}
+
+#[async_test]
+async fn executors_info() {
+    let gateway = common::gateway().await;
+    let infos = gateway.executors_info();
+
+    assert_eq!(
+        infos
+            .iter()
+            .map(|info| info.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["account", "inventory", "product", "review"]
+    );
+
+    for info in &infos {
+        assert_eq!(info.health, ExecutorHealth::Healthy);
+        assert!(info.schema_hash.is_some());
+        assert!(info.introspected_at.is_some());
+        assert!(info.type_count > 0);
+        assert!(info.field_count > 0);
+        assert_eq!(info.error, None);
+    }
+
+    let rebuilt = common::gateway().await.executors_info();
+
+    assert_eq!(infos[2].schema_hash, rebuilt[2].schema_hash);
+    assert_ne!(infos[0].schema_hash, infos[2].schema_hash);
+
+    let gateway = Gateway::default()
+        .executor(UnreachableExecutor)
+        .build()
+        .await
+        .unwrap();
+    let info = &gateway.executors_info()[0];
+
+    assert_eq!(info.name, "billing");
+    assert_eq!(info.health, ExecutorHealth::Unhealthy);
+    assert_eq!(info.schema_hash, None);
+    assert_eq!(info.type_count, 0);
+    assert_eq!(info.error.as_deref(), Some("Connection refused"));
+    assert!(info.introspected_at.is_some());
+}
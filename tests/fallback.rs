@@ -0,0 +1,420 @@
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription};
+use async_trait::async_trait;
+use common::{account, CountingExecutor, TestExecutor};
+use futures_await_test::async_test;
+use futures_timer::Delay;
+use graphql_gateway::{Data, Executor, ExecutorOptions, GraphQLResponse, QueryBuilder};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+#[derive(Clone)]
+struct NodesDownExecutor(Box<dyn Executor>);
+
+#[async_trait]
+impl Executor for NodesDownExecutor {
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    async fn execute(
+        &self,
+        data: Option<&Data>,
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<Value>,
+    ) -> Result<Value, String> {
+        if query.contains("nodes") {
+            return Err("Service unavailable".to_owned());
+        }
+
+        self.0.execute(data, query, operation_name, variables).await
+    }
+}
+
+#[derive(Clone)]
+struct SlowNodesExecutor(Box<dyn Executor>);
+
+#[async_trait]
+impl Executor for SlowNodesExecutor {
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    async fn execute(
+        &self,
+        data: Option<&Data>,
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<Value>,
+    ) -> Result<Value, String> {
+        if query.contains("nodes") {
+            Delay::new(Duration::from_millis(200)).await;
+        }
+
+        self.0.execute(data, query, operation_name, variables).await
+    }
+}
+
+#[derive(Clone)]
+struct NodesErrorExecutor(Box<dyn Executor>);
+
+#[async_trait]
+impl Executor for NodesErrorExecutor {
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    async fn execute(
+        &self,
+        data: Option<&Data>,
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<Value>,
+    ) -> Result<Value, String> {
+        if query.contains("nodes") {
+            return Ok(json!({ "data": null, "errors": [{ "message": "Nodes failed" }] }));
+        }
+
+        self.0.execute(data, query, operation_name, variables).await
+    }
+}
+
+mod legacy {
+    use async_graphql::ID;
+
+    #[derive(Clone)]
+    pub struct User(usize);
+
+    #[async_graphql::Object]
+    impl User {
+        #[field]
+        async fn id(&self) -> ID {
+            crate::common::to_global_id("User", self.0)
+        }
+
+        #[field]
+        async fn username(&self) -> String {
+            format!("legacy-{}", self.0)
+        }
+
+        #[field]
+        async fn karma(&self) -> i32 {
+            self.0 as i32 * 10 + 5
+        }
+
+        #[field]
+        async fn say_hello(&self, name: String) -> String {
+            format!("Hi, {}", name)
+        }
+    }
+
+    #[async_graphql::Interface(field(name = "id", type = "ID"))]
+    pub struct Node(User);
+
+    pub struct Query;
+
+    #[async_graphql::Object]
+    impl Query {
+        #[field]
+        async fn motd(&self, name: String) -> String {
+            format!("Welcome {}", name)
+        }
+
+        #[field]
+        async fn nodes(&self, ids: Vec<ID>) -> Vec<Option<Node>> {
+            ids.iter()
+                .map(|id| match crate::common::from_global_id(id) {
+                    Ok((node_type, id)) if node_type == "User" => Some(Node::User(User(id))),
+                    _ => None,
+                })
+                .collect()
+        }
+    }
+}
+
+#[async_test]
+async fn field_fallback() {
+    let query = QueryBuilder::new(
+        r#"
+            query {
+                users {
+                    id
+                    name: username
+                }
+            }
+        "#,
+    );
+
+    let legacy = CountingExecutor::new(TestExecutor::new(
+        "legacy",
+        legacy::Query {},
+        EmptyMutation,
+        EmptySubscription,
+    ));
+
+    let gateway = common::gateway()
+        .await
+        .fallback_executor(legacy.clone())
+        .field_fallback("User", "username", "account")
+        .field_fallback("User", "username", "legacy")
+        .field_fallback("User", "username", "legacy");
+
+    assert_eq!(
+        query.execute(&gateway).await.unwrap(),
+        json!({
+            "users": [
+                { "id": "VXNlcjow", "name": "legacy-0" },
+                { "id": "VXNlcjox", "name": "albert" }
+            ]
+        })
+    );
+    assert_eq!(legacy.count(), 1);
+}
+
+#[async_test]
+async fn field_fallback_on_primary_error() {
+    let query = QueryBuilder::new(
+        r#"
+            query {
+                products {
+                    reviews {
+                        author {
+                            id
+                            username
+                        }
+                    }
+                }
+            }
+        "#,
+    );
+
+    let gateway = common::gateway()
+        .await
+        .executor(NodesDownExecutor(Box::new(TestExecutor::new(
+            "account",
+            account::Query {},
+            account::Mutation {},
+            EmptySubscription,
+        ))))
+        .fallback_executor(TestExecutor::new(
+            "legacy",
+            legacy::Query {},
+            EmptyMutation,
+            EmptySubscription,
+        ))
+        .field_fallback("User", "username", "legacy");
+
+    let response = serde_json::to_value(query.execute_response(&gateway).await).unwrap();
+
+    assert_eq!(
+        response["data"],
+        json!({
+            "products": [
+                {
+                    "reviews": [
+                        { "author": { "id": "VXNlcjow", "username": "legacy-0" } },
+                        { "author": { "id": "VXNlcjox", "username": "legacy-1" } }
+                    ]
+                },
+                {
+                    "reviews": [
+                        { "author": { "id": "VXNlcjow", "username": "legacy-0" } }
+                    ]
+                }
+            ]
+        })
+    );
+    assert_eq!(response["errors"].as_array().map(Vec::len), Some(1));
+    assert_eq!(
+        response["errors"][0]["message"],
+        json!("Service unavailable")
+    );
+
+    let response = serde_json::to_value(
+        QueryBuilder::new("{ products { reviews { author { id email } } } }")
+            .execute_response(&gateway)
+            .await,
+    )
+    .unwrap();
+
+    assert_eq!(response["data"], json!(null));
+}
+
+fn account() -> TestExecutor<'static, account::Query, account::Mutation, EmptySubscription> {
+    TestExecutor::new(
+        "account",
+        account::Query {},
+        account::Mutation {},
+        EmptySubscription,
+    )
+}
+
+fn legacy() -> TestExecutor<'static, legacy::Query, EmptyMutation, EmptySubscription> {
+    TestExecutor::new("legacy", legacy::Query {}, EmptyMutation, EmptySubscription)
+}
+
+#[async_test]
+async fn field_fallback_on_primary_failures() {
+    let query = QueryBuilder::new(
+        r#"
+            query {
+                products {
+                    reviews {
+                        author {
+                            username
+                        }
+                    }
+                }
+            }
+        "#,
+    );
+    let expected = json!({
+        "products": [
+            {
+                "reviews": [
+                    { "author": { "username": "legacy-0" } },
+                    { "author": { "username": "legacy-1" } }
+                ]
+            },
+            {
+                "reviews": [
+                    { "author": { "username": "legacy-0" } }
+                ]
+            }
+        ]
+    });
+
+    let gateway = common::gateway()
+        .await
+        .executor(SlowNodesExecutor(Box::new(account())))
+        .executor_options(
+            "account",
+            ExecutorOptions::default().timeout(Duration::from_millis(20)),
+        )
+        .fallback_executor(legacy())
+        .field_fallback("User", "username", "legacy");
+
+    let response = serde_json::to_value(query.execute_response(&gateway).await).unwrap();
+
+    assert_eq!(response["data"], expected);
+    assert_eq!(response["errors"].as_array().map(Vec::len), Some(1));
+
+    let gateway = common::gateway()
+        .await
+        .executor(NodesErrorExecutor(Box::new(account())))
+        .fallback_executor(legacy())
+        .field_fallback("User", "username", "legacy");
+
+    let response = serde_json::to_value(query.execute_response(&gateway).await).unwrap();
+
+    assert_eq!(response["data"], expected);
+    assert_eq!(response["errors"][0]["message"], json!("Nodes failed"));
+}
+
+#[async_test]
+async fn field_fallback_error() {
+    let query = QueryBuilder::new("{ users { id username } }");
+
+    let gateway = common::gateway()
+        .await
+        .fallback_executor(NodesDownExecutor(Box::new(legacy())))
+        .field_fallback("User", "username", "legacy");
+
+    let response = serde_json::to_value(query.execute_response(&gateway).await).unwrap();
+
+    assert_eq!(
+        response["data"],
+        json!({
+            "users": [
+                { "id": "VXNlcjow", "username": null },
+                { "id": "VXNlcjox", "username": "albert" }
+            ]
+        })
+    );
+    assert_eq!(response["errors"].as_array().map(Vec::len), Some(1));
+    assert_eq!(
+        response["errors"][0]["message"],
+        json!("Service unavailable")
+    );
+}
+
+#[async_test]
+async fn field_fallback_variables() {
+    let query = QueryBuilder::new(
+        r#"
+            query Hello($name: String!) {
+                products {
+                    reviews {
+                        author {
+                            sayHello(name: $name)
+                        }
+                    }
+                }
+            }
+        "#,
+    )
+    .operation_name("Hello")
+    .variables(json!({ "name": "john" }));
+
+    let gateway = common::gateway()
+        .await
+        .executor(NodesDownExecutor(Box::new(account())))
+        .fallback_executor(legacy())
+        .field_fallback("User", "sayHello", "legacy");
+
+    let response = serde_json::to_value(query.execute_response(&gateway).await).unwrap();
+
+    assert_eq!(
+        response["data"]["products"][0]["reviews"][0],
+        json!({ "author": { "sayHello": "Hi, john" } })
+    );
+}
+
+#[async_test]
+async fn catch_all_executor() {
+    let query = QueryBuilder::new(
+        r#"
+            query Home($name: String!) {
+                motd(name: $name)
+                users {
+                    id
+                    email
+                    karma
+                }
+            }
+        "#,
+    )
+    .operation_name("Home")
+    .variables(json!({ "name": "john" }));
+
+    let gateway = common::gateway().await;
+    let response = serde_json::to_value(GraphQLResponse(query.execute(&gateway).await)).unwrap();
+
+    assert_eq!(
+        response["errors"][0]["message"],
+        json!("Cannot query field \"motd\" on type \"Query\".")
+    );
+
+    let legacy = CountingExecutor::new(TestExecutor::new(
+        "legacy",
+        legacy::Query {},
+        EmptyMutation,
+        EmptySubscription,
+    ));
+
+    let gateway = gateway.catch_all_executor(legacy.clone());
+
+    assert_eq!(
+        query.execute(&gateway).await.unwrap(),
+        json!({
+            "motd": "Welcome john",
+            "users": [
+                { "id": "VXNlcjow", "email": "john@doe.com", "karma": 5 },
+                { "id": "VXNlcjox", "email": null, "karma": 15 }
+            ]
+        })
+    );
+    assert_eq!(legacy.count(), 2);
+}
@@ -0,0 +1,145 @@
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription};
+use common::TestExecutor;
+use futures_await_test::async_test;
+use graphql_gateway::{Gateway, MergeStrategy, QueryBuilder};
+use serde_json::json;
+
+mod billing {
+    pub struct Notification(i32, &'static str);
+
+    #[async_graphql::Object]
+    impl Notification {
+        #[field]
+        async fn message(&self) -> &str {
+            self.1
+        }
+
+        #[field]
+        async fn created_at(&self) -> i32 {
+            self.0
+        }
+    }
+
+    pub struct Query;
+
+    #[async_graphql::Object]
+    impl Query {
+        #[field]
+        async fn notifications(&self) -> Vec<Notification> {
+            vec![
+                Notification(1, "Invoice paid"),
+                Notification(4, "Card expiring"),
+            ]
+        }
+    }
+}
+
+mod social {
+    pub struct Notification(i32, &'static str);
+
+    #[async_graphql::Object]
+    impl Notification {
+        #[field]
+        async fn message(&self) -> &str {
+            self.1
+        }
+
+        #[field]
+        async fn created_at(&self) -> i32 {
+            self.0
+        }
+    }
+
+    pub struct Query;
+
+    #[async_graphql::Object]
+    impl Query {
+        #[field]
+        async fn notifications(&self) -> Vec<Notification> {
+            vec![Notification(3, "New follower")]
+        }
+
+        #[field]
+        async fn followers(&self) -> i32 {
+            42
+        }
+    }
+}
+
+async fn notifications_gateway<'a>(strategy: MergeStrategy) -> Gateway<'a> {
+    let billing = TestExecutor::new(
+        "billing",
+        billing::Query {},
+        EmptyMutation,
+        EmptySubscription,
+    );
+    let social = TestExecutor::new("social", social::Query {}, EmptyMutation, EmptySubscription);
+
+    Gateway::default()
+        .executor(billing)
+        .executor(social)
+        .fan_out("notifications", strategy)
+        .build()
+        .await
+        .unwrap()
+}
+
+#[async_test]
+async fn fan_out() {
+    let query = QueryBuilder::new(
+        r#"
+            query {
+                followers
+                notifications {
+                    message
+                    createdAt
+                }
+            }
+        "#,
+    );
+
+    let gateway = notifications_gateway(MergeStrategy::Concat).await;
+
+    assert_eq!(
+        query.execute(&gateway).await.unwrap(),
+        json!({
+            "followers": 42,
+            "notifications": [
+                { "message": "Invoice paid", "createdAt": 1 },
+                { "message": "Card expiring", "createdAt": 4 },
+                { "message": "New follower", "createdAt": 3 }
+            ]
+        })
+    );
+
+    let gateway = notifications_gateway(MergeStrategy::sorted_by_desc("createdAt")).await;
+
+    assert_eq!(
+        query.execute(&gateway).await.unwrap(),
+        json!({
+            "followers": 42,
+            "notifications": [
+                { "message": "Card expiring", "createdAt": 4 },
+                { "message": "New follower", "createdAt": 3 },
+                { "message": "Invoice paid", "createdAt": 1 }
+            ]
+        })
+    );
+
+    let billing = TestExecutor::new(
+        "billing",
+        billing::Query {},
+        EmptyMutation,
+        EmptySubscription,
+    );
+    let social = TestExecutor::new("social", social::Query {}, EmptyMutation, EmptySubscription);
+
+    assert!(Gateway::default()
+        .executor(billing)
+        .executor(social)
+        .build()
+        .await
+        .is_err());
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use async_graphql::EmptySubscription;
+use common::{product, RecordingExecutor, TestExecutor};
+use futures_await_test::async_test;
+use graphql_gateway::{PlannerFlags, QueryBuilder};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+
+#[async_test]
+async fn fast_path() {
+    let queries = Arc::new(Mutex::new(Vec::new()));
+    let gateway = common::gateway().await.executor(RecordingExecutor::queries(
+        TestExecutor::new(
+            "product",
+            product::Query {},
+            product::Mutation {},
+            EmptySubscription,
+        ),
+        queries.clone(),
+    ));
+    let source = r#"
+        query {
+            products {
+                ...ProductName
+            }
+        }
+
+        fragment ProductName on Product {
+            name
+        }
+    "#;
+    let expected = json!({
+        "products": [{ "name": "Product 1" }, { "name": "Product 2" }]
+    });
+
+    assert_eq!(
+        QueryBuilder::new(source).execute(&gateway).await.unwrap(),
+        expected
+    );
+    assert!(!queries.lock().unwrap()[0].contains("id"));
+
+    assert_eq!(
+        QueryBuilder::new(source)
+            .data(PlannerFlags {
+                fast_path: false,
+                ..PlannerFlags::default()
+            })
+            .execute(&gateway)
+            .await
+            .unwrap(),
+        expected
+    );
+    assert!(queries.lock().unwrap()[1].contains("id"));
+
+    queries.lock().unwrap().clear();
+
+    let response = QueryBuilder::new("query { products { __typename name inStock } }")
+        .execute(&gateway)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        response,
+        json!({
+            "products": [
+                { "__typename": "Product", "name": "Product 1", "inStock": true },
+                { "__typename": "Product", "name": "Product 2", "inStock": false }
+            ]
+        })
+    );
+    assert!(queries.lock().unwrap()[0].contains("id"));
+}
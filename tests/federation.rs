@@ -0,0 +1,213 @@
+mod common;
+
+use async_trait::async_trait;
+use futures_await_test::async_test;
+use graphql_gateway::{Data, EntityStrategy, Executor, Gateway, QueryBuilder};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+
+const RATING_SDL: &str = r#"
+    extend type Product @key(fields: "id") {
+        id: ID! @external
+        name: String! @external
+        rating: Int!
+    }
+
+    extend type Query {
+        topRating: Int!
+    }
+"#;
+
+#[derive(Clone, Default)]
+struct RatingExecutor(Arc<Mutex<Vec<(String, Value)>>>);
+
+#[async_trait]
+impl Executor for RatingExecutor {
+    fn name(&self) -> &str {
+        "rating"
+    }
+
+    async fn execute(
+        &self,
+        _data: Option<&Data>,
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<Value>,
+    ) -> Result<Value, String> {
+        if operation_name.as_deref() == Some("ServiceQuery") {
+            return Ok(json!({ "data": { "_service": { "sdl": RATING_SDL } } }));
+        }
+
+        let variables = variables.unwrap_or_default();
+        self.0.lock().unwrap().push((query, variables.clone()));
+
+        let entities = variables["__gql_gateway_representations"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default()
+            .into_iter()
+            .enumerate()
+            .map(|(i, representation)| {
+                json!({ "id": representation["id"], "rating": i + 3 })
+            })
+            .collect::<Vec<Value>>();
+
+        Ok(json!({ "data": { "_entities": entities } }))
+    }
+}
+
+#[async_test]
+async fn federation_entities() {
+    let executor = RatingExecutor::default();
+    let mut gateway = common::gateway()
+        .await
+        .executor(executor.clone())
+        .entity_strategy("rating", EntityStrategy::Federation);
+
+    gateway.pull("rating").await.unwrap();
+
+    let sdl = gateway.to_sdl();
+    assert!(sdl.contains("rating: Int!"));
+    assert!(!sdl.contains("_entities"));
+
+    let response = QueryBuilder::new("query { products { name rating } }")
+        .execute(&gateway)
+        .await
+        .unwrap();
+
+    assert_eq!(response["products"][0], json!({ "name": "Product 1", "rating": 3 }));
+    assert_eq!(response["products"][1]["rating"], json!(4));
+
+    let calls = executor.0.lock().unwrap();
+    let (query, variables) = &calls[0];
+
+    assert!(query.contains("_entities(representations: $__gql_gateway_representations)"));
+    assert!(query.contains("[_Any!]!"));
+    assert!(query.contains("... on Product"));
+    assert_eq!(
+        variables["__gql_gateway_representations"][0],
+        json!({ "__typename": "Product", "id": "UHJvZHVjdDow" })
+    );
+}
+
+const STORE_SDL: &str = r#"
+    type Item @key(fields: "sku storeId") {
+        sku: String!
+        storeId: Int!
+        name: String!
+    }
+
+    type Query {
+        items: [Item!]!
+    }
+"#;
+
+const PRICING_SDL: &str = r#"
+    extend type Item @key(fields: "sku storeId") {
+        sku: String! @external
+        storeId: Int! @external
+        price: Int!
+    }
+"#;
+
+#[derive(Clone)]
+struct StoreExecutor;
+
+#[async_trait]
+impl Executor for StoreExecutor {
+    fn name(&self) -> &str {
+        "store"
+    }
+
+    async fn execute(
+        &self,
+        _data: Option<&Data>,
+        _query: String,
+        _operation_name: Option<String>,
+        _variables: Option<Value>,
+    ) -> Result<Value, String> {
+        Ok(json!({
+            "data": {
+                "items": [
+                    { "sku": "A1", "storeId": 1, "name": "Apple" },
+                    { "sku": "A1", "storeId": 2, "name": "Apple" },
+                ]
+            }
+        }))
+    }
+}
+
+#[derive(Clone, Default)]
+struct PricingExecutor(Arc<Mutex<Vec<Value>>>);
+
+#[async_trait]
+impl Executor for PricingExecutor {
+    fn name(&self) -> &str {
+        "pricing"
+    }
+
+    async fn execute(
+        &self,
+        _data: Option<&Data>,
+        _query: String,
+        _operation_name: Option<String>,
+        variables: Option<Value>,
+    ) -> Result<Value, String> {
+        let variables = variables.unwrap_or_default();
+        self.0.lock().unwrap().push(variables.clone());
+
+        let mut entities = variables["__gql_gateway_representations"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|representation| {
+                json!({
+                    "sku": representation["sku"],
+                    "storeId": representation["storeId"],
+                    "price": representation["storeId"].as_i64().unwrap() * 10,
+                })
+            })
+            .collect::<Vec<Value>>();
+        entities.reverse();
+
+        Ok(json!({ "data": { "_entities": entities } }))
+    }
+}
+
+#[async_test]
+async fn federation_composite_keys() {
+    let pricing = PricingExecutor::default();
+    let gateway = Gateway::default()
+        .executor_with_sdl(StoreExecutor, STORE_SDL)
+        .executor_with_sdl(pricing.clone(), PRICING_SDL)
+        .entity_strategy("pricing", EntityStrategy::Federation)
+        .build()
+        .await
+        .unwrap();
+
+    let response = QueryBuilder::new("query { items { name price } }")
+        .execute(&gateway)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        response,
+        json!({
+            "items": [
+                { "name": "Apple", "price": 10 },
+                { "name": "Apple", "price": 20 }
+            ]
+        })
+    );
+
+    let calls = pricing.0.lock().unwrap();
+
+    assert_eq!(
+        calls[0]["__gql_gateway_representations"],
+        json!([
+            { "__typename": "Item", "sku": "A1", "storeId": 1 },
+            { "__typename": "Item", "sku": "A1", "storeId": 2 }
+        ])
+    );
+}
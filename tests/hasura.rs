@@ -0,0 +1,138 @@
+use async_trait::async_trait;
+use futures_await_test::async_test;
+use graphql_gateway::{Data, Executor, Gateway, HasuraExecutor, QueryBuilder, Schema};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+
+const HASURA_SDL: &str = r#"
+    schema {
+        query: query_root
+    }
+
+    type users {
+        id: Int!
+        name: String!
+    }
+
+    type query_root {
+        users(limit: Int): [users!]!
+        users_by_pk(id: Int!): users
+    }
+"#;
+
+const ORDER_SDL: &str = r#"
+    type users @key(fields: "id") {
+        id: Int!
+    }
+
+    type Order {
+        id: ID!
+        buyer: users!
+    }
+
+    type Query {
+        orders: [Order!]!
+    }
+"#;
+
+#[derive(Clone, Default)]
+struct HasuraMock(Arc<Mutex<Vec<Value>>>);
+
+#[async_trait]
+impl Executor for HasuraMock {
+    fn name(&self) -> &str {
+        "hasura"
+    }
+
+    async fn execute(
+        &self,
+        _data: Option<&Data>,
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<Value>,
+    ) -> Result<Value, String> {
+        if operation_name.as_deref() == Some("IntrospectionQuery") {
+            let schema = Schema::from_sdl(HASURA_SDL)?;
+
+            return Ok(json!({ "data": { "__schema": schema } }));
+        }
+
+        self.0
+            .lock()
+            .unwrap()
+            .push(json!({ "query": query, "variables": variables }));
+
+        Ok(json!({
+            "data": {
+                "node_0": { "id": 2, "name": "Bob" },
+                "node_1": null
+            },
+            "errors": [{ "message": "Permission denied", "path": ["node_1"] }]
+        }))
+    }
+}
+
+#[derive(Clone)]
+struct OrderExecutor;
+
+#[async_trait]
+impl Executor for OrderExecutor {
+    fn name(&self) -> &str {
+        "order"
+    }
+
+    async fn execute(
+        &self,
+        _data: Option<&Data>,
+        _query: String,
+        _operation_name: Option<String>,
+        _variables: Option<Value>,
+    ) -> Result<Value, String> {
+        Ok(json!({
+            "data": {
+                "orders": [
+                    { "id": "1", "buyer": { "id": 2 } },
+                    { "id": "2", "buyer": { "id": 3 } }
+                ]
+            }
+        }))
+    }
+}
+
+#[async_test]
+async fn hasura_pk_lookup() {
+    let hasura = HasuraMock::default();
+    let gateway = Gateway::default()
+        .executor(HasuraExecutor::new(hasura.clone()).entity("users", "users_by_pk", "id"))
+        .executor_with_sdl(OrderExecutor, ORDER_SDL)
+        .build()
+        .await
+        .unwrap();
+
+    let sdl = gateway.to_sdl();
+    assert!(sdl.contains("users_by_pk(id: Int!): users"));
+    assert!(!sdl.contains("query_root"));
+
+    let response = QueryBuilder::new("query { orders { id buyer { name } } }")
+        .execute(&gateway)
+        .await;
+
+    match response {
+        Err(graphql_gateway::QueryError::Downstream(errors)) => {
+            assert_eq!(errors[0].message, "Permission denied");
+            assert_eq!(
+                errors[0].path,
+                Some(vec![json!("orders"), json!(1), json!("buyer")])
+            );
+        }
+        result => panic!("Unexpected result {:?}", result),
+    }
+
+    let calls = hasura.0.lock().unwrap();
+    let query = calls[0]["query"].as_str().unwrap();
+
+    assert!(query.contains("node_0: users_by_pk(id: 2)"));
+    assert!(query.contains("node_1: users_by_pk(id: 3)"));
+    assert!(!query.contains("nodes"));
+    assert_eq!(calls[0]["variables"], json!({}));
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription};
+use async_trait::async_trait;
+use common::{inventory, product, TestExecutor};
+use futures_await_test::async_test;
+use graphql_gateway::{Data, Executor, Gateway};
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+#[derive(Clone)]
+struct PartitionedExecutor(Box<dyn Executor>, Arc<AtomicBool>);
+
+#[async_trait]
+impl Executor for PartitionedExecutor {
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    async fn execute(
+        &self,
+        data: Option<&Data>,
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<Value>,
+    ) -> Result<Value, String> {
+        if self.1.load(Ordering::SeqCst) {
+            return Err("Network is unreachable".to_owned());
+        }
+
+        self.0.execute(data, query, operation_name, variables).await
+    }
+}
+
+#[async_test]
+async fn health() {
+    let partitioned = Arc::new(AtomicBool::new(true));
+    let mut gateway = Gateway::default()
+        .executor(TestExecutor::new(
+            "product",
+            product::Query {},
+            product::Mutation {},
+            EmptySubscription,
+        ))
+        .executor(PartitionedExecutor(
+            Box::new(TestExecutor::new(
+                "inventory",
+                inventory::Query {},
+                EmptyMutation,
+                EmptySubscription,
+            )),
+            partitioned.clone(),
+        ))
+        .build()
+        .await
+        .unwrap();
+
+    let health = gateway.health();
+    let product_hash = gateway
+        .executors_info()
+        .into_iter()
+        .find(|info| info.name == "product")
+        .and_then(|info| info.schema_hash);
+
+    assert!(!health.ready);
+    assert_eq!(health.executors[0].name, "inventory");
+    assert_eq!(
+        health.executors[0].error.as_deref(),
+        Some("Network is unreachable")
+    );
+    assert_eq!(health.executors[0].last_introspection, None);
+    assert_eq!(health.executors[0].schema_hash, None);
+    assert_eq!(health.executors[1].name, "product");
+    assert_eq!(health.executors[1].error, None);
+    assert!(health.executors[1].last_introspection.is_some());
+    assert_eq!(health.executors[1].schema_hash, product_hash);
+
+    partitioned.store(false, Ordering::SeqCst);
+    gateway.pull("inventory").await.unwrap();
+
+    let health = gateway.health();
+    let last_introspection = health.executors[0].last_introspection;
+
+    assert!(health.ready);
+    assert!(last_introspection.is_some());
+    assert!(health.executors[0].schema_hash.is_some());
+
+    partitioned.store(true, Ordering::SeqCst);
+    assert!(gateway.pull("inventory").await.is_err());
+
+    let health = serde_json::to_value(gateway.health()).unwrap();
+
+    assert_eq!(health["ready"], json!(true));
+    assert_eq!(
+        health["executors"][0],
+        json!({
+            "name": "inventory",
+            "circuit": "CLOSED",
+            "lastIntrospection": last_introspection,
+            "error": "Network is unreachable",
+            "schemaHash": health["executors"][0]["schemaHash"],
+            "staleSince": null
+        })
+    );
+    assert!(health["executors"][0]["schemaHash"].is_string());
+}
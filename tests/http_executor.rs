@@ -0,0 +1,103 @@
+use graphql_gateway::{Data, Executor, HttpExecutor, HttpHeaders};
+use serde_json::json;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc;
+use std::thread;
+
+fn serve(responses: Vec<(&'static str, &'static str)>) -> (String, mpsc::Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/graphql", listener.local_addr().unwrap());
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        for (status, body) in responses {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+
+                if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = length.trim().parse().unwrap();
+                }
+
+                request.push_str(&line);
+
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut request_body = vec![0; content_length];
+            reader.read_exact(&mut request_body).unwrap();
+            request.push_str(&String::from_utf8(request_body).unwrap());
+            sender.send(request).unwrap();
+
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+        }
+    });
+
+    (url, receiver)
+}
+
+#[test]
+fn http_executor() {
+    let (url, requests) = serve(vec![
+        ("200 OK", r#"{"data":{"viewer":{"id":"1"}}}"#),
+        ("400 Bad Request", r#"{"errors":[{"message":"Bad query"}]}"#),
+        ("502 Bad Gateway", "upstream unavailable"),
+    ]);
+
+    let executor = HttpExecutor::new("account", url.as_str()).header("x-api-key", "secret");
+    let mut data = Data::default();
+    data.insert(HttpHeaders(vec![(
+        "authorization".to_owned(),
+        "Bearer token".to_owned(),
+    )]));
+
+    let mut runtime = tokio::runtime::Builder::new()
+        .basic_scheduler()
+        .enable_all()
+        .build()
+        .unwrap();
+
+    runtime.block_on(async {
+        let res = executor
+            .execute(Some(&data), "{ viewer { id } }".to_owned(), None, None)
+            .await;
+
+        assert_eq!(res.unwrap(), json!({"data": {"viewer": {"id": "1"}}}));
+
+        let request = requests.recv().unwrap().to_lowercase();
+        assert!(request.starts_with("post /graphql"));
+        assert!(request.contains("x-api-key: secret"));
+        assert!(request.contains("authorization: bearer token"));
+        assert!(request.contains(r#""query":"{ viewer { id } }""#));
+
+        let res = executor
+            .execute(None, "{ viewer }".to_owned(), None, None)
+            .await;
+
+        assert_eq!(res.unwrap(), json!({"errors": [{"message": "Bad query"}]}));
+
+        let res = executor
+            .execute(None, "{ viewer { id } }".to_owned(), None, None)
+            .await;
+
+        assert_eq!(
+            res.unwrap_err(),
+            format!("{} responded with HTTP 502 Bad Gateway", url)
+        );
+    });
+}
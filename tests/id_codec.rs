@@ -0,0 +1,134 @@
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription};
+use common::TestExecutor;
+use futures_await_test::async_test;
+use graphql_gateway::{DefaultIdCodec, IdCodec, QueryBuilder};
+use serde_json::json;
+
+mod legacy {
+    use async_graphql::ID;
+
+    pub struct Order(usize, i32);
+
+    #[async_graphql::Object]
+    impl Order {
+        #[field]
+        async fn id(&self) -> ID {
+            ID::from(self.0.to_string())
+        }
+
+        #[field]
+        async fn total(&self) -> i32 {
+            self.1
+        }
+    }
+
+    pub struct Query;
+
+    #[async_graphql::Object]
+    impl Query {
+        #[field]
+        async fn orders(&self) -> Vec<Order> {
+            vec![Order(1, 10), Order(2, 20)]
+        }
+
+        #[field]
+        async fn order(&self, id: ID) -> Option<Order> {
+            match id.as_str() {
+                "1" => Some(Order(1, 10)),
+                "2" => Some(Order(2, 20)),
+                _ => None,
+            }
+        }
+    }
+}
+
+#[async_test]
+async fn id_codec() {
+    let legacy = TestExecutor::new("legacy", legacy::Query {}, EmptyMutation, EmptySubscription);
+    let mut gateway = common::gateway().await.executor(legacy).wrap_ids("legacy");
+
+    gateway.pull("legacy").await.unwrap();
+
+    let order_id = DefaultIdCodec.encode("Order", "legacy", &json!("2"));
+
+    assert_eq!(
+        DefaultIdCodec.decode(&order_id),
+        Some(("Order".to_owned(), "legacy".to_owned(), json!("2")))
+    );
+
+    let query = QueryBuilder::new(
+        r#"
+            query OrderQuery($id: ID!) {
+                orders {
+                    id
+                    total
+                }
+                order(id: $id) {
+                    id
+                    total
+                }
+                products {
+                    id
+                }
+            }
+        "#,
+    )
+    .operation_name("OrderQuery")
+    .variables(json!({ "id": order_id }));
+
+    assert_eq!(
+        query.execute(&gateway).await.unwrap(),
+        json!({
+            "orders": [
+                { "id": DefaultIdCodec.encode("Order", "legacy", &json!("1")), "total": 10 },
+                { "id": order_id, "total": 20 }
+            ],
+            "order": { "id": order_id, "total": 20 },
+            "products": [
+                { "id": "UHJvZHVjdDow" },
+                { "id": "UHJvZHVjdDox" }
+            ]
+        })
+    );
+
+    let query = QueryBuilder::new(format!(
+        r#"
+            query {{
+                order(id: "{}") {{
+                    total
+                }}
+            }}
+        "#,
+        DefaultIdCodec
+            .encode("Order", "legacy", &json!("1"))
+            .as_str()
+            .unwrap()
+    ));
+
+    assert_eq!(
+        query.execute(&gateway).await.unwrap(),
+        json!({ "order": { "total": 10 } })
+    );
+
+    let query = QueryBuilder::new(format!(
+        r#"
+            query {{
+                order(id: "{}") {{
+                    total
+                }}
+            }}
+        "#,
+        DefaultIdCodec
+            .encode("Order", "legacy", &json!("2"))
+            .as_str()
+            .unwrap()
+            .replace('=', "\\u003d")
+    ));
+
+    assert_eq!(
+        query.execute(&gateway).await.unwrap(),
+        json!({ "order": { "total": 20 } })
+    );
+}
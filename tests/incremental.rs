@@ -0,0 +1,225 @@
+mod common;
+
+use futures::stream::StreamExt;
+use futures_await_test::async_test;
+use graphql_gateway::{IncrementalPayload, QueryBuilder};
+use serde_json::{json, Value};
+
+fn payloads(payloads: &[IncrementalPayload]) -> Vec<Value> {
+    payloads
+        .iter()
+        .map(|payload| {
+            let mut payload = serde_json::to_value(payload).unwrap();
+            payload.as_object_mut().unwrap().remove("extensions");
+            payload
+        })
+        .collect()
+}
+
+#[async_test]
+async fn defer() {
+    let gateway = common::gateway().await;
+    let query = QueryBuilder::new(
+        r#"
+            query {
+                products {
+                    name
+                    ... @defer(label: "stock") {
+                        inStock
+                    }
+                }
+            }
+        "#,
+    );
+
+    let chunks = query.execute_stream(&gateway).collect::<Vec<_>>().await;
+
+    assert_eq!(
+        payloads(&chunks),
+        vec![
+            json!({
+                "data": { "products": [{ "name": "Product 1" }, { "name": "Product 2" }] },
+                "hasNext": true
+            }),
+            json!({
+                "incremental": [
+                    { "data": { "inStock": true }, "path": ["products", 0], "label": "stock" },
+                    { "data": { "inStock": false }, "path": ["products", 1], "label": "stock" }
+                ],
+                "hasNext": false
+            })
+        ]
+    );
+}
+
+#[async_test]
+async fn defer_fragment_spread() {
+    let gateway = common::gateway().await;
+    let query = QueryBuilder::new(
+        r#"
+            query Viewer($skip: Boolean!) {
+                viewer {
+                    ...ViewerEmail @defer
+                    ...ViewerRole @defer(if: $skip)
+                }
+            }
+
+            fragment ViewerEmail on User {
+                email
+            }
+
+            fragment ViewerRole on User {
+                role
+            }
+        "#,
+    )
+    .operation_name("Viewer")
+    .variables(json!({ "skip": false }));
+
+    let chunks = query.execute_stream(&gateway).collect::<Vec<_>>().await;
+
+    assert_eq!(
+        payloads(&chunks),
+        vec![
+            json!({ "data": { "viewer": { "role": "ADMIN" } }, "hasNext": true }),
+            json!({
+                "incremental": [{ "data": { "email": "john@doe.com" }, "path": ["viewer"] }],
+                "hasNext": false
+            })
+        ]
+    );
+}
+
+#[async_test]
+async fn stream() {
+    let gateway = common::gateway().await;
+    let query = QueryBuilder::new(
+        r#"
+            query {
+                users @stream(initialCount: 1, label: "users") {
+                    username
+                }
+            }
+        "#,
+    );
+
+    let chunks = query.execute_stream(&gateway).collect::<Vec<_>>().await;
+
+    assert_eq!(
+        payloads(&chunks),
+        vec![
+            json!({ "data": { "users": [{ "username": null }] }, "hasNext": true }),
+            json!({
+                "incremental": [{
+                    "items": [{ "username": "albert" }],
+                    "path": ["users", 1],
+                    "label": "users"
+                }],
+                "hasNext": false
+            })
+        ]
+    );
+
+    let multipart = chunks
+        .iter()
+        .map(IncrementalPayload::to_multipart)
+        .collect::<String>();
+
+    assert!(
+        multipart.starts_with("\r\n---\r\nContent-Type: application/json; charset=utf-8\r\n\r\n{")
+    );
+    assert!(multipart.ends_with("}\r\n-----\r\n"));
+    assert_eq!(multipart.matches("\r\n---\r\n").count(), 2);
+}
+
+#[async_test]
+async fn execute_stream_without_directives() {
+    let gateway = common::gateway().await;
+    let chunks = QueryBuilder::new("query { users { username } }")
+        .execute_stream(&gateway)
+        .collect::<Vec<_>>()
+        .await;
+
+    assert_eq!(
+        payloads(&chunks),
+        vec![json!({
+            "data": { "users": [{ "username": null }, { "username": "albert" }] },
+            "hasNext": false
+        })]
+    );
+
+    let chunks = QueryBuilder::new("query { unknown @stream }")
+        .execute_stream(&gateway)
+        .collect::<Vec<_>>()
+        .await;
+
+    assert_eq!(
+        payloads(&chunks)[0]["errors"][0]["message"],
+        json!("Cannot query field \"unknown\" on type \"Query\".")
+    );
+    assert!(!chunks[0].has_next());
+}
+
+#[async_test]
+async fn mutation_incremental_directives() {
+    let gateway = common::gateway().await;
+    let chunks = QueryBuilder::new(
+        r#"
+            mutation {
+                addProduct(id: "UHJvZHVjdDow") {
+                    name
+                    ... @defer {
+                        inStock
+                    }
+                }
+            }
+        "#,
+    )
+    .execute_stream(&gateway)
+    .collect::<Vec<_>>()
+    .await;
+
+    assert_eq!(
+        payloads(&chunks),
+        vec![json!({
+            "data": { "addProduct": { "name": "Product 1", "inStock": true } },
+            "hasNext": false
+        })]
+    );
+}
+
+#[async_test]
+async fn max_deferred() {
+    let gateway = common::gateway().await.max_deferred(1);
+    let query = QueryBuilder::new(
+        r#"
+            query {
+                viewer {
+                    ... @defer(label: "email") {
+                        email
+                    }
+                    ... @defer(label: "role") {
+                        role
+                    }
+                }
+            }
+        "#,
+    );
+
+    let chunks = query.execute_stream(&gateway).collect::<Vec<_>>().await;
+
+    assert_eq!(
+        payloads(&chunks),
+        vec![
+            json!({ "data": { "viewer": { "role": "ADMIN" } }, "hasNext": true }),
+            json!({
+                "incremental": [{
+                    "data": { "email": "john@doe.com" },
+                    "path": ["viewer"],
+                    "label": "email"
+                }],
+                "hasNext": false
+            })
+        ]
+    );
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription};
+use common::TestExecutor;
+use futures_await_test::async_test;
+use graphql_gateway::{Data, Gateway, QueryBuilder};
+use serde_json::{json, Value};
+
+mod greeting {
+    pub struct Query;
+
+    #[async_graphql::Object]
+    impl Query {
+        #[field]
+        async fn hello(&self, name: String, locale: Option<String>) -> String {
+            match locale.as_deref() {
+                Some("fr") => format!("Bonjour {}", name),
+                _ => format!("Hello {}", name),
+            }
+        }
+    }
+}
+
+mod ledger {
+    pub struct Query;
+
+    #[async_graphql::Object]
+    impl Query {
+        #[field]
+        async fn balance(&self, account: i64) -> String {
+            format!("Balance of {}", account)
+        }
+    }
+}
+
+struct Locale(&'static str);
+
+#[async_test]
+async fn inject_argument() {
+    let gateway = Gateway::default()
+        .executor(TestExecutor::new(
+            "greeting",
+            greeting::Query {},
+            EmptyMutation,
+            EmptySubscription,
+        ))
+        .inject_argument("locale", |data: Option<&Data>| {
+            data.and_then(|data| data.get::<Locale>())
+                .map(|locale| Value::from(locale.0))
+        })
+        .build()
+        .await
+        .unwrap();
+
+    let source = r#"
+        query {
+            hello(name: "Ada")
+            english: hello(name: "Ada", locale: "en")
+        }
+    "#;
+
+    assert_eq!(
+        QueryBuilder::new(source).execute(&gateway).await.unwrap(),
+        json!({ "hello": "Hello Ada", "english": "Hello Ada" })
+    );
+    assert_eq!(
+        QueryBuilder::new(source)
+            .data(Locale("fr"))
+            .execute(&gateway)
+            .await
+            .unwrap(),
+        json!({ "hello": "Bonjour Ada", "english": "Hello Ada" })
+    );
+}
+
+#[async_test]
+async fn inject_argument_precision() {
+    let gateway = Gateway::default()
+        .executor(TestExecutor::new(
+            "ledger",
+            ledger::Query {},
+            EmptyMutation,
+            EmptySubscription,
+        ))
+        .inject_argument("account", |_: Option<&Data>| {
+            Some(Value::from(9_007_199_254_740_993_i64))
+        })
+        .build()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        QueryBuilder::new("{ balance }")
+            .execute(&gateway)
+            .await
+            .unwrap(),
+        json!({ "balance": "Balance of 9007199254740993" })
+    );
+}
@@ -1,9 +1,45 @@
 mod common;
 
+use async_graphql::{EmptyMutation, EmptySubscription};
+use async_trait::async_trait;
+use common::{product, TestExecutor};
 use futures_await_test::async_test;
-use graphql_gateway::{Schema, QueryBuilder, TypeKind};
+use graphql_gateway::{Data, Executor, Gateway, QueryBuilder, Schema, TypeKind};
 use serde_json::{json, Value};
 
+#[derive(Clone)]
+struct Token(&'static str);
+
+#[derive(Clone)]
+struct AuthExecutor(Box<dyn Executor>);
+
+#[async_trait]
+impl Executor for AuthExecutor {
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    async fn execute(
+        &self,
+        data: Option<&Data>,
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<Value>,
+    ) -> Result<Value, String> {
+        let expected = match operation_name.as_deref() {
+            Some("IntrospectionQuery") => "introspection",
+            _ => "request",
+        };
+
+        match data.and_then(|data| data.get::<Token>()) {
+            Some(token) if token.0 == expected => {
+                self.0.execute(data, query, operation_name, variables).await
+            }
+            _ => Err("Unauthorized".to_owned()),
+        }
+    }
+}
+
 #[async_test]
 async fn introspection() {
     let query = QueryBuilder::new(graphql_gateway::INTROSPECTION_QUERY.to_owned())
@@ -31,130 +67,206 @@ async fn introspection() {
 
     let schema: Schema = serde_json::from_value(res["__schema"].clone()).unwrap();
 
-    assert_eq!(
-        schema.types.iter().any(|t| t.kind == TypeKind::Object
-            && t.name == Some("Query".to_owned())
-            && t.fields.as_ref().unwrap().len() == 5
-            && t.fields
-                .as_ref()
-                .unwrap()
-                .iter()
-                .filter(|field| match field.name.as_ref() {
-                    "node" | "nodes" | "products" | "viewer" | "users" => true,
+    assert!(schema.types.iter().any(|t| t.kind == TypeKind::Object
+        && t.name == Some("Query".to_owned())
+        && t.fields.as_ref().unwrap().len() == 5
+        && t.fields
+            .as_ref()
+            .unwrap()
+            .iter()
+            .filter(|field| matches!(
+                field.name.as_ref(),
+                "node" | "nodes" | "products" | "viewer" | "users"
+            ))
+            .count()
+            == 5));
+
+    assert!(schema.types.iter().any(|t| t.kind == TypeKind::Object
+        && t.name == Some("Mutation".to_owned())
+        && t.fields.as_ref().unwrap().len() == 2
+        && t.fields
+            .as_ref()
+            .unwrap()
+            .iter()
+            .filter(|field| matches!(field.name.as_ref(), "addProduct" | "signIn"))
+            .count()
+            == 2));
+
+    assert!(schema.types.iter().any(|t| t.kind == TypeKind::Interface
+        && t.name == Some("Node".to_owned())
+        && t.fields.as_ref().unwrap().len() == 1
+        && t.fields
+            .as_ref()
+            .unwrap()
+            .iter()
+            .any(|field| field.name == "id")
+        && t.possible_types
+            .as_ref()
+            .unwrap()
+            .iter()
+            .filter(|possible_type| {
+                match possible_type.name.as_ref().unwrap().as_ref() {
+                    "User" | "Product" | "Review" => possible_type.kind == TypeKind::Object,
                     _ => false,
-                })
-                .count()
-                == 5),
-        true
-    );
+                }
+            })
+            .count()
+            == 3));
+
+    assert!(schema.types.iter().any(|t| t.kind == TypeKind::Object
+        && t.name == Some("User".to_owned())
+        && t.fields.as_ref().unwrap().len() == 6
+        && t.fields
+            .as_ref()
+            .unwrap()
+            .iter()
+            .filter(|field| matches!(
+                field.name.as_ref(),
+                "id" | "email" | "username" | "reviews" | "sayHello" | "role"
+            ))
+            .count()
+            == 6));
+
+    assert!(schema.types.iter().any(|t| t.kind == TypeKind::Object
+        && t.name == Some("Product".to_owned())
+        && t.fields.as_ref().unwrap().len() == 4
+        && t.fields
+            .as_ref()
+            .unwrap()
+            .iter()
+            .filter(|field| matches!(field.name.as_ref(), "id" | "name" | "reviews" | "inStock"))
+            .count()
+            == 4));
 
+    assert!(schema.types.iter().any(|t| t.kind == TypeKind::Object
+        && t.name == Some("Review".to_owned())
+        && t.fields.as_ref().unwrap().len() == 4
+        && t.fields
+            .as_ref()
+            .unwrap()
+            .iter()
+            .filter(|field| matches!(field.name.as_ref(), "id" | "body" | "author" | "product"))
+            .count()
+            == 4));
+
+    assert!(schema.types.iter().any(|t| t.kind == TypeKind::Enum
+        && t.name == Some("UserRole".to_owned())
+        && t.enum_values.as_ref().unwrap().len() == 3
+        && t.enum_values
+            .as_ref()
+            .unwrap()
+            .iter()
+            .filter(|enum_value| matches!(enum_value.name.as_ref(), "ADMIN" | "STAFF" | "USER"))
+            .count()
+            == 3));
+}
+
+#[async_test]
+async fn introspection_ownership() {
+    let query = QueryBuilder::new(graphql_gateway::INTROSPECTION_QUERY.to_owned())
+        .operation_name("IntrospectionQuery");
+    let gateway = common::gateway().await;
+    let res = query.execute_response(&gateway).await;
+    let ownership = &res.extensions["gateway"];
+
+    assert_eq!(ownership["fieldOwners"]["User.username"], json!("account"));
+    assert_eq!(ownership["fieldOwners"]["User.reviews"], json!("review"));
+    assert_eq!(ownership["fieldOwners"]["Query.products"], json!("product"));
+    assert!(ownership["fieldOwners"].get("__Type.name").is_none());
     assert_eq!(
-        schema.types.iter().any(|t| t.kind == TypeKind::Object
-            && t.name == Some("Mutation".to_owned())
-            && t.fields.as_ref().unwrap().len() == 2
-            && t.fields
-                .as_ref()
-                .unwrap()
-                .iter()
-                .filter(|field| match field.name.as_ref() {
-                    "addProduct" => true,
-                    "signIn" => true,
-                    _ => false,
-                })
-                .count()
-                == 2),
-        true
+        ownership["entities"]["User"],
+        json!({ "keys": ["id"], "executors": ["account", "review"] })
     );
+    assert!(ownership["entities"].get("Query").is_none());
 
-    assert_eq!(
-        schema.types.iter().any(|t| t.kind == TypeKind::Interface
-            && t.name == Some("Node".to_owned())
-            && t.fields.as_ref().unwrap().len() == 1
-            && t.fields
-                .as_ref()
-                .unwrap()
-                .iter()
-                .any(|field| field.name == "id")
-            && t.possible_types
-                .as_ref()
-                .unwrap()
-                .iter()
-                .filter(|possible_type| {
-                    match possible_type.name.as_ref().unwrap().as_ref() {
-                        "User" | "Product" | "Review" => possible_type.kind == TypeKind::Object,
-                        _ => false,
+    let res = QueryBuilder::new("query { products { name } }")
+        .execute_response(&gateway)
+        .await;
+
+    assert!(res.extensions.get("gateway").is_none());
+}
+
+#[async_test]
+async fn introspection_type() {
+    let gateway = common::gateway().await;
+    let res = QueryBuilder::new(
+        r#"
+            query Type($name: String!) {
+                user: __type(name: $name) {
+                    kind
+                    name
+                    fields {
+                        name
                     }
-                })
-                .count()
-                == 3),
-        true
-    );
+                }
+                missing: __type(name: "Missing") {
+                    name
+                }
+                products {
+                    name
+                }
+            }
+        "#,
+    )
+    .operation_name("Type")
+    .variables(json!({ "name": "User" }))
+    .execute(&gateway)
+    .await
+    .unwrap();
 
-    assert_eq!(
-        schema.types.iter().any(|t| t.kind == TypeKind::Object
-            && t.name == Some("User".to_owned())
-            && t.fields.as_ref().unwrap().len() == 6
-            && t.fields
-                .as_ref()
-                .unwrap()
-                .iter()
-                .filter(|field| match field.name.as_ref() {
-                    "id" | "email" | "username" | "reviews" | "sayHello" | "role" => true,
-                    _ => false,
-                })
-                .count()
-                == 6),
-        true
-    );
+    assert_eq!(res["user"]["kind"], json!("OBJECT"));
+    assert_eq!(res["user"]["name"], json!("User"));
+    assert!(res["user"]["fields"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|field| field["name"] == "reviews"));
+    assert_eq!(res["missing"], Value::Null);
+    assert_eq!(res["products"][0], json!({ "name": "Product 1" }));
+}
 
-    assert_eq!(
-        schema.types.iter().any(|t| t.kind == TypeKind::Object
-            && t.name == Some("Product".to_owned())
-            && t.fields.as_ref().unwrap().len() == 4
-            && t.fields
-                .as_ref()
-                .unwrap()
-                .iter()
-                .filter(|field| match field.name.as_ref() {
-                    "id" | "name" | "reviews" | "inStock" => true,
-                    _ => false,
-                })
-                .count()
-                == 4),
-        true
-    );
+#[async_test]
+async fn introspection_data() {
+    let query = QueryBuilder::new(
+        r#"
+            query {
+                products {
+                    name
+                }
+            }
+        "#,
+    )
+    .data(Token("request"));
 
-    assert_eq!(
-        schema.types.iter().any(|t| t.kind == TypeKind::Object
-            && t.name == Some("Review".to_owned())
-            && t.fields.as_ref().unwrap().len() == 4
-            && t.fields
-                .as_ref()
-                .unwrap()
-                .iter()
-                .filter(|field| match field.name.as_ref() {
-                    "id" | "body" | "author" | "product" => true,
-                    _ => false,
-                })
-                .count()
-                == 4),
-        true
-    );
+    let executor = AuthExecutor(Box::new(TestExecutor::new(
+        "product",
+        product::Query {},
+        EmptyMutation,
+        EmptySubscription,
+    )));
+
+    let gateway = Gateway::default()
+        .executor(executor.clone())
+        .build()
+        .await
+        .unwrap();
+
+    assert!(query.execute(&gateway).await.is_err());
+
+    let gateway = Gateway::default()
+        .executor(executor)
+        .introspection_data("product", Token("introspection"))
+        .build()
+        .await
+        .unwrap();
 
     assert_eq!(
-        schema.types.iter().any(|t| t.kind == TypeKind::Enum
-            && t.name == Some("UserRole".to_owned())
-            && t.enum_values.as_ref().unwrap().len() == 3
-            && t.enum_values
-                .as_ref()
-                .unwrap()
-                .iter()
-                .filter(|enum_value| match enum_value.name.as_ref() {
-                    "ADMIN" | "STAFF" | "USER" => true,
-                    _ => false,
-                })
-                .count()
-                == 3),
-        true
+        query.execute(&gateway).await.unwrap(),
+        json!({
+            "products": [
+                { "name": "Product 1" },
+                { "name": "Product 2" }
+            ]
+        })
     );
 }
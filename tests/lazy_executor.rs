@@ -0,0 +1,153 @@
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription};
+use common::{account, inventory, product, review, TestExecutor};
+use futures_await_test::async_test;
+use graphql_gateway::{Executor, ExecutorHealth, Gateway, GraphQLResponse, QueryBuilder};
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+fn gateway<'a>() -> Gateway<'a> {
+    Gateway::default()
+        .executor(TestExecutor::new(
+            "account",
+            account::Query {},
+            account::Mutation {},
+            EmptySubscription,
+        ))
+        .executor(TestExecutor::new(
+            "product",
+            product::Query {},
+            product::Mutation {},
+            EmptySubscription,
+        ))
+        .executor(TestExecutor::new(
+            "review",
+            review::Query {},
+            EmptyMutation,
+            EmptySubscription,
+        ))
+}
+
+fn inventory_factory(
+    created: Arc<AtomicUsize>,
+) -> impl Fn() -> TestExecutor<'static, inventory::Query, EmptyMutation, EmptySubscription> {
+    move || {
+        created.fetch_add(1, Ordering::SeqCst);
+        TestExecutor::new(
+            "inventory",
+            inventory::Query {},
+            EmptyMutation,
+            EmptySubscription,
+        )
+    }
+}
+
+#[async_test]
+async fn lazy_executor() {
+    let created = Arc::new(AtomicUsize::new(0));
+    let mut gateway = gateway()
+        .lazy_executor("inventory", inventory_factory(created.clone()))
+        .build()
+        .await
+        .unwrap();
+
+    assert_eq!(created.load(Ordering::SeqCst), 0);
+
+    let info = gateway
+        .executors_info()
+        .into_iter()
+        .find(|info| info.name == "inventory")
+        .unwrap();
+
+    assert!(!info.initialized);
+    assert_eq!(info.health, ExecutorHealth::Unknown);
+    assert_eq!(info.schema_hash, None);
+
+    let query = QueryBuilder::new("{ products { name inStock } }");
+    let response = serde_json::to_value(GraphQLResponse(query.execute(&gateway).await)).unwrap();
+
+    assert_eq!(
+        response["errors"][0]["message"],
+        json!("Cannot query field \"inStock\" on type \"Product\".")
+    );
+
+    gateway.pull("inventory").await.unwrap();
+
+    assert_eq!(created.load(Ordering::SeqCst), 1);
+
+    assert_eq!(
+        query.execute(&gateway).await.unwrap(),
+        json!({
+            "products": [
+                { "name": "Product 1", "inStock": true },
+                { "name": "Product 2", "inStock": false }
+            ]
+        })
+    );
+
+    let info = gateway
+        .executors_info()
+        .into_iter()
+        .find(|info| info.name == "inventory")
+        .unwrap();
+
+    assert!(info.initialized);
+    assert_eq!(info.health, ExecutorHealth::Healthy);
+    assert_eq!(created.load(Ordering::SeqCst), 1);
+}
+
+#[async_test]
+async fn lazy_executor_with_schema() {
+    let (_, schema) = TestExecutor::new(
+        "inventory",
+        inventory::Query {},
+        EmptyMutation,
+        EmptySubscription,
+    )
+    .introspect(None)
+    .await
+    .unwrap();
+
+    let created = Arc::new(AtomicUsize::new(0));
+    let gateway = gateway()
+        .lazy_executor_with_schema("inventory", schema, inventory_factory(created.clone()))
+        .build()
+        .await
+        .unwrap();
+
+    assert_eq!(created.load(Ordering::SeqCst), 0);
+
+    assert_eq!(
+        QueryBuilder::new("{ products { name } }")
+            .execute(&gateway)
+            .await
+            .unwrap(),
+        json!({
+            "products": [{ "name": "Product 1" }, { "name": "Product 2" }]
+        })
+    );
+
+    assert_eq!(created.load(Ordering::SeqCst), 0);
+
+    let query = QueryBuilder::new("{ products { name inStock } }");
+
+    for _ in 0..2 {
+        assert_eq!(
+            query.execute(&gateway).await.unwrap(),
+            json!({
+                "products": [
+                    { "name": "Product 1", "inStock": true },
+                    { "name": "Product 2", "inStock": false }
+                ]
+            })
+        );
+    }
+
+    assert_eq!(created.load(Ordering::SeqCst), 1);
+    assert!(gateway
+        .executors_info()
+        .iter()
+        .any(|info| info.name == "inventory" && info.initialized && info.schema_hash.is_some()));
+}
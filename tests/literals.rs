@@ -0,0 +1,131 @@
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription};
+use common::{RecordingExecutor, TestExecutor};
+use futures_await_test::async_test;
+use graphql_gateway::{Gateway, QueryBuilder};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+
+mod metrics {
+    use async_graphql::Json;
+    use serde_json::Value;
+
+    pub struct Query;
+
+    #[async_graphql::Object]
+    impl Query {
+        #[field]
+        async fn echo(&self, value: Json<Value>) -> Json<Value> {
+            value
+        }
+    }
+}
+
+#[async_test]
+async fn scalar_literals() {
+    let queries = Arc::new(Mutex::new(Vec::new()));
+    let gateway = Gateway::default()
+        .executor(RecordingExecutor::queries(
+            TestExecutor::new(
+                "metrics",
+                metrics::Query {},
+                EmptyMutation,
+                EmptySubscription,
+            ),
+            queries.clone(),
+        ))
+        .build()
+        .await
+        .unwrap();
+
+    queries.lock().unwrap().clear();
+
+    let query = QueryBuilder::new(
+        r#"
+            query {
+                echo(value: { amount: 1.0, count: 3, name: "caf\u00e9", ratio: -2.50, scaled: 1e3 })
+            }
+        "#,
+    );
+
+    assert_eq!(
+        query.execute(&gateway).await.unwrap(),
+        json!({
+            "echo": {
+                "amount": 1.0,
+                "ratio": -2.5,
+                "scaled": 1000.0,
+                "count": 3,
+                "name": "café"
+            }
+        })
+    );
+    assert!(queries.lock().unwrap()[0].contains(
+        r#"echo(value: {amount: 1.0, count: 3, name: "caf\u00e9", ratio: -2.50, scaled: 1e3})"#
+    ));
+
+    let query = QueryBuilder::new("{ echo(value: 123456789012345678901234567890) }");
+    let _ = query.execute(&gateway).await;
+
+    assert!(queries.lock().unwrap()[1].contains("echo(value: 123456789012345678901234567890)"));
+}
+
+#[async_test]
+async fn string_literals() {
+    let queries = Arc::new(Mutex::new(Vec::new()));
+    let gateway = Gateway::default()
+        .executor(RecordingExecutor::queries(
+            TestExecutor::new(
+                "metrics",
+                metrics::Query {},
+                EmptyMutation,
+                EmptySubscription,
+            ),
+            queries.clone(),
+        ))
+        .build()
+        .await
+        .unwrap();
+
+    queries.lock().unwrap().clear();
+
+    let block = "\"\"\"\n                    Hello \\\"\"\" \"world\"\n                      caf\\u00e9 \\n\"\"\"";
+    let query = QueryBuilder::new(format!(
+        r#"
+            query {{
+                block: echo(value: {})
+                escaped: echo(value: ["\u00e9\u2603", "tab\there", "\"quoted\" \\ \/"])
+            }}
+        "#,
+        block
+    ));
+
+    assert_eq!(
+        query.execute(&gateway).await.unwrap()["escaped"],
+        json!(["é☃", "tab\there", "\"quoted\" \\ /"])
+    );
+
+    let delegated = queries.lock().unwrap()[0].clone();
+
+    assert!(delegated.contains(&format!("block: echo(value: {})", block)));
+    assert!(delegated
+        .contains(r#"escaped: echo(value: ["\u00e9\u2603", "tab\there", "\"quoted\" \\ \/"])"#));
+
+    let query = QueryBuilder::new(
+        r#"
+            query Echo($value: JSON = "caf\u00e9") {
+                echo(value: $value)
+                __type(name: "Q\u0075ery") {
+                    name
+                }
+            }
+        "#,
+    )
+    .operation_name("Echo");
+
+    assert_eq!(
+        query.execute(&gateway).await.unwrap(),
+        json!({ "echo": "café", "__type": { "name": "Query" } })
+    );
+}
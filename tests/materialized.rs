@@ -0,0 +1,205 @@
+mod common;
+
+use async_graphql::EmptySubscription;
+use async_trait::async_trait;
+use common::{product, CountingExecutor, TestExecutor};
+use futures_await_test::async_test;
+use graphql_gateway::{
+    Authorizer, ConfigError, Data, Executor, GatewayBuilder, GatewayError, QueryBuilder,
+};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+struct User(&'static str);
+
+struct AllowAll;
+
+impl Authorizer for AllowAll {
+    fn is_authorized(&self, _data: Option<&Data>, _object_type: &str, _field: &str) -> bool {
+        true
+    }
+}
+
+#[derive(Clone)]
+struct PersonalizedExecutor(Box<dyn Executor>);
+
+#[async_trait]
+impl Executor for PersonalizedExecutor {
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    async fn execute(
+        &self,
+        data: Option<&Data>,
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<Value>,
+    ) -> Result<Value, String> {
+        let mut value = self
+            .0
+            .execute(data, query, operation_name, variables)
+            .await?;
+        let user = data.and_then(|data| data.get::<User>()).map(|user| user.0);
+
+        if let (Some(user), Some(Value::Array(products))) =
+            (user, value.pointer_mut("/data/products"))
+        {
+            for product in products {
+                if let Some(Value::String(name)) = product.get_mut("name") {
+                    *name = format!("{} for {}", name, user);
+                }
+            }
+        }
+
+        Ok(value)
+    }
+}
+
+#[async_test]
+async fn materialized_view() {
+    let source = r#"
+        query Products {
+            products {
+                name
+            }
+        }
+    "#;
+
+    let product = CountingExecutor::new(TestExecutor::new(
+        "product",
+        product::Query {},
+        product::Mutation {},
+        EmptySubscription,
+    ));
+
+    let mut gateway = common::gateway()
+        .await
+        .executor(product.clone())
+        .materialized_view(
+            "products",
+            QueryBuilder::new(source).operation_name("Products"),
+            Duration::from_secs(60),
+        );
+
+    gateway.pull("product").await.unwrap();
+
+    let count = product.count();
+    let expected = json!({
+        "products": [
+            { "name": "Product 1" },
+            { "name": "Product 2" }
+        ]
+    });
+
+    let query =
+        QueryBuilder::new("query Products { products { name } }").operation_name("Products");
+
+    for _ in 0..3 {
+        let response = query.execute_response(&gateway).await;
+
+        assert_eq!(
+            response.extensions["materialized"]["name"],
+            json!("products")
+        );
+        assert_eq!(response.result.unwrap(), expected);
+    }
+
+    assert_eq!(product.count(), count + 1);
+    assert!(gateway.refresh_materialized_views().await.is_empty());
+
+    let response = QueryBuilder::new(source).execute_response(&gateway).await;
+
+    assert!(response.extensions.get("materialized").is_none());
+    assert_eq!(response.result.unwrap(), expected);
+    assert_eq!(product.count(), count + 2);
+}
+
+#[async_test]
+async fn materialized_view_per_user_data() {
+    let source = "query Products { products { name } }";
+    let product = CountingExecutor::new(PersonalizedExecutor(Box::new(TestExecutor::new(
+        "product",
+        product::Query {},
+        product::Mutation {},
+        EmptySubscription,
+    ))));
+
+    let mut gateway = common::gateway()
+        .await
+        .executor(product.clone())
+        .materialized_view(
+            "products",
+            QueryBuilder::new(source).operation_name("Products"),
+            Duration::from_secs(60),
+        );
+
+    gateway.pull("product").await.unwrap();
+
+    let response = QueryBuilder::new(source)
+        .operation_name("Products")
+        .execute_response(&gateway)
+        .await;
+
+    assert_eq!(
+        response.extensions["materialized"]["name"],
+        json!("products")
+    );
+
+    let count = product.count();
+
+    for user in &["john", "albert"] {
+        let response = QueryBuilder::new(source)
+            .operation_name("Products")
+            .data(User(user))
+            .execute_response(&gateway)
+            .await;
+
+        assert!(response.extensions.get("materialized").is_none());
+        assert_eq!(
+            response.result.unwrap(),
+            json!({
+                "products": [
+                    { "name": format!("Product 1 for {}", user) },
+                    { "name": format!("Product 2 for {}", user) }
+                ]
+            })
+        );
+    }
+
+    assert_eq!(product.count(), count + 2);
+
+    let gateway = gateway.authorizer(AllowAll);
+    let response = QueryBuilder::new(source)
+        .operation_name("Products")
+        .execute_response(&gateway)
+        .await;
+
+    assert!(response.extensions.get("materialized").is_none());
+    assert_eq!(product.count(), count + 3);
+}
+
+#[async_test]
+async fn materialized_view_mutation() {
+    let result = GatewayBuilder::new()
+        .configure(|gateway| {
+            gateway.materialized_view(
+                "sign_in",
+                QueryBuilder::new("mutation { signIn(input: { email: \"a\", password: \"b\" }) }"),
+                Duration::from_secs(60),
+            )
+        })
+        .build()
+        .await;
+
+    match result {
+        Err(GatewayError::InvalidConfiguration(errors)) => assert_eq!(
+            errors,
+            vec![ConfigError::InvalidMaterializedView(
+                "sign_in".to_owned(),
+                "Materialized views only support query operations.".to_owned()
+            )]
+        ),
+        _ => panic!("Expected an invalid configuration error."),
+    }
+}
@@ -0,0 +1,44 @@
+mod common;
+
+use futures_await_test::async_test;
+use graphql_gateway::QueryBuilder;
+use serde_json::json;
+
+#[async_test]
+async fn memory_limit() {
+    let query = QueryBuilder::new(
+        r#"
+            query {
+                products {
+                    name
+                }
+            }
+        "#,
+    );
+
+    let gateway = common::gateway().await;
+    let response = query.execute_response(&gateway).await;
+    let used = response.extensions["memory"]["used"].as_u64().unwrap();
+
+    assert!(used > 0);
+    assert_eq!(response.extensions["memory"]["limit"], json!(null));
+    assert_eq!(
+        response.result.unwrap(),
+        json!({
+            "products": [
+                { "name": "Product 1" },
+                { "name": "Product 2" }
+            ]
+        })
+    );
+
+    let gateway = common::gateway().await.memory_limit(used as usize - 1);
+    let response = serde_json::to_value(query.execute_response(&gateway).await).unwrap();
+
+    assert_eq!(
+        response["errors"][0]["extensions"]["code"],
+        json!("RESOURCE_EXHAUSTED")
+    );
+    assert_eq!(response["extensions"]["memory"]["limit"], json!(used - 1));
+    assert!(response.get("data").is_none());
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use futures_await_test::async_test;
+use graphql_gateway::{GraphQLResponse, Mode, QueryBuilder};
+use serde_json::json;
+use std::time::Duration;
+
+#[async_test]
+async fn mode_read_only() {
+    let query = QueryBuilder::new(
+        r#"
+            query {
+                products {
+                    name
+                }
+            }
+        "#,
+    );
+    let mutation = QueryBuilder::new(
+        r#"
+            mutation {
+                addProduct(id: "UHJvZHVjdDow") {
+                    name
+                }
+            }
+        "#,
+    );
+
+    let gateway = common::gateway().await;
+    gateway.set_mode(Mode::ReadOnly);
+
+    assert_eq!(gateway.mode(), Mode::ReadOnly);
+    assert!(query.execute(&gateway).await.is_ok());
+
+    let response = serde_json::to_value(GraphQLResponse(mutation.execute(&gateway).await)).unwrap();
+
+    assert_eq!(
+        response,
+        json!({
+            "errors": [{
+                "message": "Gateway is in read-only mode, mutations are not allowed.",
+                "locations": [{ "line": 0, "column": 0 }],
+                "extensions": { "code": "READ_ONLY", "retryAfter": null }
+            }]
+        })
+    );
+
+    gateway.set_mode(Mode::Normal);
+
+    assert!(mutation.execute(&gateway).await.is_ok());
+}
+
+#[async_test]
+async fn mode_maintenance() {
+    let query = QueryBuilder::new(
+        r#"
+            query {
+                products {
+                    name
+                }
+            }
+        "#,
+    );
+
+    let gateway = common::gateway().await;
+    gateway.set_mode_with(
+        Mode::Maintenance,
+        Some("Back soon.".to_owned()),
+        Some(Duration::from_secs(120)),
+    );
+
+    let response = serde_json::to_value(GraphQLResponse(query.execute(&gateway).await)).unwrap();
+
+    assert_eq!(
+        response,
+        json!({
+            "errors": [{
+                "message": "Back soon.",
+                "locations": [{ "line": 0, "column": 0 }],
+                "extensions": { "code": "MAINTENANCE", "retryAfter": 120 }
+            }]
+        })
+    );
+}
@@ -1,8 +1,11 @@
 mod common;
 
+use async_graphql::{EmptyMutation, EmptySubscription};
+use common::{account, inventory, product, Call, RecordingExecutor, TestExecutor};
 use futures_await_test::async_test;
-use graphql_gateway::QueryBuilder;
+use graphql_gateway::{GatewayBuilder, QueryBuilder};
 use serde_json::json;
+use std::sync::{Arc, Mutex};
 
 #[async_test]
 async fn mutation() {
@@ -41,3 +44,100 @@ async fn mutation() {
         })
     );
 }
+
+fn executor_name(call: &Call) -> Option<String> {
+    if call.query.contains("__schema") {
+        None
+    } else {
+        Some(call.executor.to_owned())
+    }
+}
+
+#[async_test]
+async fn mutation_ordering() {
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let account = TestExecutor::new(
+        "account",
+        account::Query {},
+        account::Mutation {},
+        EmptySubscription,
+    );
+    let inventory = TestExecutor::new(
+        "inventory",
+        inventory::Query {},
+        EmptyMutation,
+        EmptySubscription,
+    );
+    let product = TestExecutor::new(
+        "product",
+        product::Query {},
+        product::Mutation {},
+        EmptySubscription,
+    );
+    let gateway = GatewayBuilder::new()
+        .executor(RecordingExecutor::new(
+            account,
+            calls.clone(),
+            executor_name,
+        ))
+        .executor(RecordingExecutor::new(
+            inventory,
+            calls.clone(),
+            executor_name,
+        ))
+        .executor(RecordingExecutor::new(
+            product,
+            calls.clone(),
+            executor_name,
+        ))
+        .build()
+        .await
+        .unwrap();
+    let query = QueryBuilder::new(
+        r#"
+        mutation {
+            first: addProduct(id: "UHJvZHVjdDow") {
+                name
+                inStock
+            }
+            signIn(input: { email: "john@doe.com", password: "yep" }) {
+                email
+            }
+            second: addProduct(id: "UHJvZHVjdDox") {
+                name
+            }
+        }
+        "#,
+    );
+
+    calls.lock().unwrap().clear();
+
+    assert_eq!(
+        query.execute(&gateway).await.unwrap(),
+        json!({
+            "first": { "name": "Product 1", "inStock": true },
+            "signIn": { "email": "john@doe.com" },
+            "second": { "name": "Product 2" }
+        })
+    );
+    assert_eq!(
+        *calls.lock().unwrap(),
+        vec!["product", "inventory", "account", "product"]
+    );
+
+    let plan = query.plan(&gateway).unwrap();
+
+    assert!(!plan.parallel_root);
+    assert_eq!(
+        plan.steps
+            .iter()
+            .map(|step| (step.executor.as_str(), step.path.join("."), step.depends_on))
+            .collect::<Vec<_>>(),
+        vec![
+            ("product", "".to_owned(), None),
+            ("inventory", "first".to_owned(), Some(0)),
+            ("account", "".to_owned(), None),
+            ("product", "".to_owned(), None)
+        ]
+    );
+}
@@ -0,0 +1,95 @@
+mod common;
+
+use futures_await_test::async_test;
+use graphql_gateway::{GatewayError, NamingError, NamingPolicy, NamingViolation, Schema};
+use serde_json::json;
+
+fn schema(type_name: &str, field_name: &str) -> Schema {
+    serde_json::from_value(json!({
+        "description": null,
+        "queryType": { "kind": "OBJECT", "name": "Query" },
+        "mutationType": null,
+        "subscriptionType": null,
+        "directives": [],
+        "types": [{
+            "kind": "OBJECT",
+            "name": type_name,
+            "description": null,
+            "interfaces": [],
+            "possibleTypes": null,
+            "enumValues": null,
+            "inputFields": null,
+            "ofType": null,
+            "fields": [{
+                "name": field_name,
+                "description": null,
+                "args": [],
+                "type": { "kind": "SCALAR", "name": "String", "ofType": null },
+                "isDeprecated": false,
+                "deprecationReason": null
+            }]
+        }]
+    }))
+    .unwrap()
+}
+
+fn naming_error(name: &str, violation: NamingViolation) -> NamingError {
+    NamingError {
+        executor: "legacy".to_owned(),
+        name: name.to_owned(),
+        violation,
+    }
+}
+
+#[async_test]
+async fn naming_policy() {
+    let gateway = common::gateway().await;
+
+    assert!(gateway
+        .validate("legacy", schema("Legacy", "value"))
+        .is_ok());
+
+    match gateway
+        .validate("legacy", schema("__Legacy", "__value"))
+        .unwrap_err()
+    {
+        GatewayError::InvalidNames(errors) => assert_eq!(
+            errors,
+            vec![
+                naming_error("__Legacy", NamingViolation::ReservedPrefix),
+                naming_error("__Legacy.__value", NamingViolation::ReservedPrefix),
+            ]
+        ),
+        _ => panic!("Expected an invalid names error"),
+    };
+
+    match gateway
+        .validate("legacy", schema("ID", "legacy-value"))
+        .unwrap_err()
+    {
+        GatewayError::InvalidNames(errors) => assert_eq!(
+            errors,
+            vec![
+                naming_error("ID", NamingViolation::BuiltinScalarConflict),
+                naming_error("ID.legacy-value", NamingViolation::InvalidCharacters),
+            ]
+        ),
+        _ => panic!("Expected an invalid names error"),
+    };
+
+    let gateway = gateway.naming_policy(NamingPolicy::default().reserved_prefix("legacy_"));
+
+    match gateway
+        .validate("legacy", schema("Legacy", "legacy_value"))
+        .unwrap_err()
+    {
+        GatewayError::InvalidNames(errors) => assert_eq!(
+            errors,
+            vec![naming_error(
+                "Legacy.legacy_value",
+                NamingViolation::ReservedPrefix
+            )]
+        ),
+        _ => panic!("Expected an invalid names error"),
+    };
+}
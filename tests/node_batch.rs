@@ -0,0 +1,55 @@
+mod common;
+
+use async_graphql::EmptySubscription;
+use common::{account, CountingExecutor, TestExecutor};
+use futures_await_test::async_test;
+use graphql_gateway::{PlannerFlags, QueryBuilder};
+
+#[async_test]
+async fn node_batch() {
+    let source = r#"
+        query {
+            products {
+                name
+                reviews {
+                    body
+                    author {
+                        username
+                    }
+                }
+            }
+        }
+    "#;
+
+    let account = CountingExecutor::new(TestExecutor::new(
+        "account",
+        account::Query {},
+        account::Mutation {},
+        EmptySubscription,
+    ));
+
+    let gateway = common::gateway().await.executor(account.clone());
+
+    let unbatched = QueryBuilder::new(source)
+        .data(PlannerFlags {
+            batch_nodes: false,
+            ..PlannerFlags::default()
+        })
+        .execute(&gateway)
+        .await
+        .unwrap();
+    let unbatched_count = account.count();
+
+    let batched = QueryBuilder::new(source).execute(&gateway).await.unwrap();
+
+    assert_eq!(batched, unbatched);
+    assert!(unbatched_count > 1);
+    assert_eq!(account.count() - unbatched_count, 1);
+
+    let gateway = gateway.list_parallelism(1);
+    let count = account.count();
+    let batched = QueryBuilder::new(source).execute(&gateway).await.unwrap();
+
+    assert_eq!(batched, unbatched);
+    assert_eq!(account.count() - count, 1);
+}
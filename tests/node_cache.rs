@@ -0,0 +1,189 @@
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription};
+use async_trait::async_trait;
+use common::{inventory, product, to_global_id, CountingExecutor, TestExecutor};
+use futures_await_test::async_test;
+use graphql_gateway::{Authorizer, Data, EntityInvalidator, Executor, HeaderPolicy, QueryBuilder};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+struct AllowAll;
+
+impl Authorizer for AllowAll {
+    fn is_authorized(&self, _data: Option<&Data>, _object_type: &str, _field: &str) -> bool {
+        true
+    }
+}
+
+#[derive(Clone)]
+struct InvalidatingExecutor(Box<dyn Executor>);
+
+#[async_trait]
+impl Executor for InvalidatingExecutor {
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    async fn execute(
+        &self,
+        data: Option<&Data>,
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<Value>,
+    ) -> Result<Value, String> {
+        if let Some(invalidator) = data.and_then(|data| data.get::<EntityInvalidator>()) {
+            invalidator.invalidate("Product", to_global_id("Product", 0).to_string());
+        }
+
+        self.0.execute(data, query, operation_name, variables).await
+    }
+}
+
+#[async_test]
+async fn node_cache() {
+    let query = QueryBuilder::new(
+        r#"
+            query {
+                products {
+                    name
+                    inStock
+                }
+            }
+        "#,
+    );
+
+    let inventory = CountingExecutor::new(TestExecutor::new(
+        "inventory",
+        inventory::Query {},
+        EmptyMutation,
+        EmptySubscription,
+    ));
+
+    let mut gateway = common::gateway()
+        .await
+        .executor(inventory.clone())
+        .node_cache_ttl(Duration::from_secs(60));
+
+    gateway.pull("inventory").await.unwrap();
+
+    let expected = json!({
+        "products": [
+            { "name": "Product 1", "inStock": true },
+            { "name": "Product 2", "inStock": false }
+        ]
+    });
+
+    let count = inventory.count();
+
+    assert_eq!(query.execute(&gateway).await.unwrap(), expected);
+    assert_eq!(inventory.count(), count + 1);
+
+    assert_eq!(query.execute(&gateway).await.unwrap(), expected);
+    assert_eq!(inventory.count(), count + 1);
+
+    let gateway = gateway.node_cache_ttl(Duration::from_secs(0));
+
+    assert_eq!(query.execute(&gateway).await.unwrap(), expected);
+    assert_eq!(inventory.count(), count + 2);
+}
+
+#[async_test]
+async fn node_cache_invalidation() {
+    let query = QueryBuilder::new("{ products { name inStock } }");
+    let inventory = CountingExecutor::new(TestExecutor::new(
+        "inventory",
+        inventory::Query {},
+        EmptyMutation,
+        EmptySubscription,
+    ));
+
+    let mut gateway = common::gateway()
+        .await
+        .executor(inventory.clone())
+        .node_cache_ttl(Duration::from_secs(0))
+        .node_cache_type_ttl("Product", Duration::from_secs(60));
+
+    gateway.pull("inventory").await.unwrap();
+
+    let count = inventory.count();
+
+    query.execute(&gateway).await.unwrap();
+    query.execute(&gateway).await.unwrap();
+    assert_eq!(inventory.count(), count + 1);
+
+    gateway.invalidate_entity("Product", to_global_id("Product", 1).to_string());
+    query.execute(&gateway).await.unwrap();
+    assert_eq!(inventory.count(), count + 2);
+
+    let mut gateway = gateway.executor(InvalidatingExecutor(Box::new(TestExecutor::new(
+        "product",
+        product::Query {},
+        product::Mutation {},
+        EmptySubscription,
+    ))));
+
+    gateway.pull("product").await.unwrap();
+
+    query.execute(&gateway).await.unwrap();
+    query.execute(&gateway).await.unwrap();
+    assert_eq!(inventory.count(), count + 4);
+}
+
+#[async_test]
+async fn node_cache_bypass() {
+    let query = QueryBuilder::new("{ products { name inStock } }");
+    let inventory = CountingExecutor::new(TestExecutor::new(
+        "inventory",
+        inventory::Query {},
+        EmptyMutation,
+        EmptySubscription,
+    ));
+
+    let mut gateway = common::gateway()
+        .await
+        .executor(inventory.clone())
+        .node_cache_ttl(Duration::from_secs(60))
+        .node_cache_executor_ttl("inventory", Duration::from_secs(0));
+
+    gateway.pull("inventory").await.unwrap();
+
+    let count = inventory.count();
+
+    query.execute(&gateway).await.unwrap();
+    query.execute(&gateway).await.unwrap();
+    assert_eq!(inventory.count(), count + 2);
+
+    let gateway = gateway
+        .node_cache_executor_ttl("inventory", Duration::from_secs(60))
+        .authorizer(AllowAll);
+
+    query.execute(&gateway).await.unwrap();
+    query.execute(&gateway).await.unwrap();
+    assert_eq!(inventory.count(), count + 4);
+}
+
+#[async_test]
+async fn node_cache_forwarded_headers() {
+    let query = QueryBuilder::new("{ products { name inStock } }");
+    let inventory = CountingExecutor::new(TestExecutor::new(
+        "inventory",
+        inventory::Query {},
+        EmptyMutation,
+        EmptySubscription,
+    ));
+
+    let mut gateway = common::gateway()
+        .await
+        .executor(inventory.clone())
+        .node_cache_ttl(Duration::from_secs(60))
+        .executor_header_policy("inventory", HeaderPolicy::new().allow("authorization"));
+
+    gateway.pull("inventory").await.unwrap();
+
+    let count = inventory.count();
+
+    query.execute(&gateway).await.unwrap();
+    query.execute(&gateway).await.unwrap();
+    assert_eq!(inventory.count(), count + 2);
+}
@@ -0,0 +1,168 @@
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription};
+use common::{account, product, review, RecordingExecutor, TestExecutor};
+use futures_await_test::async_test;
+use graphql_gateway::{Gateway, NodeLookup, QueryBuilder};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+
+mod inventory {
+    use async_graphql::ID;
+
+    #[derive(Clone)]
+    pub struct Product(usize, bool);
+
+    #[async_graphql::Object]
+    impl Product {
+        #[field]
+        async fn id(&self) -> ID {
+            super::common::to_global_id("Product", self.0)
+        }
+
+        #[field]
+        async fn in_stock(&self) -> bool {
+            self.1
+        }
+    }
+
+    lazy_static::lazy_static! {
+        pub static ref PRODUCTS: Vec<Product> = vec![
+            Product(0, true),
+            Product(1, false)
+            ];
+    }
+
+    #[async_graphql::Interface(field(name = "id", type = "ID"))]
+    pub struct Node(Product);
+
+    pub struct Query;
+
+    #[async_graphql::Object]
+    impl Query {
+        #[field]
+        async fn node(&self, id: ID) -> Option<Node> {
+            let (_, id) = super::common::from_global_id(&id).ok()?;
+
+            PRODUCTS.get(id).map(|p| Node::Product(p.clone()))
+        }
+
+        #[field]
+        async fn nodes(&self, ids: Vec<ID>) -> Vec<Option<Product>> {
+            ids.iter()
+                .map(|id| {
+                    let (_, id) = super::common::from_global_id(id).ok()?;
+
+                    PRODUCTS.get(id).cloned()
+                })
+                .collect()
+        }
+
+        #[field]
+        async fn nodes_by_ids(&self, keys: Vec<ID>) -> Vec<Option<Product>> {
+            keys.iter()
+                .map(|id| {
+                    let (_, id) = super::common::from_global_id(id).ok()?;
+
+                    PRODUCTS.get(id).cloned()
+                })
+                .collect()
+        }
+    }
+}
+
+fn gateway<'a>(queries: Arc<Mutex<Vec<String>>>) -> Gateway<'a> {
+    Gateway::default()
+        .executor(TestExecutor::new(
+            "account",
+            account::Query {},
+            account::Mutation {},
+            EmptySubscription,
+        ))
+        .executor(RecordingExecutor::new(
+            TestExecutor::new(
+                "inventory",
+                inventory::Query {},
+                EmptyMutation,
+                EmptySubscription,
+            ),
+            queries,
+            |call| {
+                if call.operation_name == Some("NodeQuery") {
+                    Some(call.query.to_owned())
+                } else {
+                    None
+                }
+            },
+        ))
+        .executor(TestExecutor::new(
+            "product",
+            product::Query {},
+            product::Mutation {},
+            EmptySubscription,
+        ))
+        .executor(TestExecutor::new(
+            "review",
+            review::Query {},
+            EmptyMutation,
+            EmptySubscription,
+        ))
+}
+
+#[async_test]
+async fn concrete_nodes_lookup() {
+    let queries = Arc::new(Mutex::new(vec![]));
+    let gateway = gateway(queries.clone()).build().await.unwrap();
+
+    assert_eq!(
+        QueryBuilder::new("{ products { name inStock } }")
+            .execute(&gateway)
+            .await
+            .unwrap(),
+        json!({
+            "products": [
+                { "name": "Product 1", "inStock": true },
+                { "name": "Product 2", "inStock": false }
+            ]
+        })
+    );
+
+    let queries = queries.lock().unwrap();
+
+    assert_eq!(queries.len(), 1);
+    assert!(!queries[0].contains("on Product"));
+}
+
+#[async_test]
+async fn custom_nodes_lookup() {
+    let queries = Arc::new(Mutex::new(vec![]));
+    let gateway = gateway(queries.clone())
+        .node_lookup(
+            "inventory",
+            NodeLookup::new("nodesByIds")
+                .argument("keys")
+                .id_type("[ID!]!"),
+        )
+        .build()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        QueryBuilder::new("{ products { name inStock } }")
+            .execute(&gateway)
+            .await
+            .unwrap(),
+        json!({
+            "products": [
+                { "name": "Product 1", "inStock": true },
+                { "name": "Product 2", "inStock": false }
+            ]
+        })
+    );
+
+    let queries = queries.lock().unwrap();
+
+    assert_eq!(queries.len(), 1);
+    assert!(queries[0].contains("[ID!]!"));
+    assert!(queries[0].contains("nodesByIds(keys: $__gql_gateway_keys)"));
+}
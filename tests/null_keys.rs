@@ -0,0 +1,96 @@
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription};
+use async_trait::async_trait;
+use common::{inventory_stock, product, TestExecutor};
+use futures_await_test::async_test;
+use graphql_gateway::{Data, Executor, QueryBuilder};
+use serde_json::{json, Value};
+
+#[derive(Clone)]
+struct NullIdExecutor(Box<dyn Executor>);
+
+#[async_trait]
+impl Executor for NullIdExecutor {
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    async fn execute(
+        &self,
+        data: Option<&Data>,
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<Value>,
+    ) -> Result<Value, String> {
+        let mut response = self.0.execute(data, query, operation_name, variables).await?;
+
+        if let Some(product) = response["data"]["products"].get_mut(1) {
+            product["id"] = Value::Null;
+        }
+
+        Ok(response)
+    }
+}
+
+fn product() -> NullIdExecutor {
+    NullIdExecutor(Box::new(TestExecutor::new(
+        "product",
+        product::Query {},
+        product::Mutation {},
+        EmptySubscription,
+    )))
+}
+
+#[async_test]
+async fn skip_null_keys() {
+    let query = QueryBuilder::new("query { products { name quantity } }");
+    let gateway = common::gateway()
+        .await
+        .executor(product())
+        .executor(TestExecutor::new(
+            "inventory",
+            inventory_stock::Query {},
+            EmptyMutation,
+            EmptySubscription,
+        ))
+        .build()
+        .await
+        .unwrap();
+
+    assert!(query.execute(&gateway).await.is_err());
+
+    let gateway = gateway.skip_null_keys(true);
+    let response = query.execute_response(&gateway).await;
+
+    assert_eq!(
+        response.result.unwrap(),
+        json!({
+            "products": [
+                { "name": "Product 1", "quantity": 3 },
+                { "name": "Product 2", "quantity": null }
+            ]
+        })
+    );
+    assert_eq!(
+        response.extensions["warnings"],
+        json!([{
+            "message": "Skipped enrichment of \"Product\" with a null id.",
+            "path": ["products", 1]
+        }])
+    );
+
+    let response = QueryBuilder::new("query { products { name inStock } }")
+        .execute_response(&gateway)
+        .await;
+
+    assert_eq!(response.result.unwrap(), Value::Null);
+    assert_eq!(
+        response
+            .errors
+            .iter()
+            .map(|e| e.1.to_string())
+            .collect::<Vec<_>>(),
+        vec!["Cannot return null for non-nullable field \"Product.inStock\".".to_owned()]
+    );
+}
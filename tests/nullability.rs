@@ -0,0 +1,107 @@
+mod common;
+
+use async_trait::async_trait;
+use futures_await_test::async_test;
+use graphql_gateway::{Data, Executor, Gateway, QueryBuilder, QueryError};
+use serde_json::{json, Value};
+
+#[derive(Clone)]
+struct NullExecutor;
+
+#[async_trait]
+impl Executor for NullExecutor {
+    fn name(&self) -> &str {
+        "null"
+    }
+
+    async fn execute(
+        &self,
+        _data: Option<&Data>,
+        _query: String,
+        _operation_name: Option<String>,
+        _variables: Option<Value>,
+    ) -> Result<Value, String> {
+        Ok(json!({
+            "data": {
+                "viewer": { "name": null, "nickname": null },
+                "items": [{ "name": "first" }, { "name": null }],
+                "entries": [{ "name": "first" }, { "name": null }, { "name": null }],
+                "nickname": null
+            }
+        }))
+    }
+}
+
+#[async_test]
+async fn null_propagation() {
+    let gateway = Gateway::default()
+        .executor_with_sdl(
+            NullExecutor,
+            r#"
+                type Query {
+                    viewer: User
+                    items: [Item!]
+                    entries: [Item]!
+                    nickname: String
+                }
+
+                type User {
+                    name: String!
+                    nickname: String
+                }
+
+                type Item {
+                    name: String!
+                }
+            "#,
+        )
+        .build()
+        .await
+        .unwrap();
+
+    let response = QueryBuilder::new(
+        "query { viewer { name nickname } items { name } entries { name } nickname }",
+    )
+    .execute_response(&gateway)
+    .await;
+
+    assert_eq!(
+        response.result.unwrap(),
+        json!({
+            "viewer": null,
+            "items": null,
+            "entries": [{ "name": "first" }, null, null],
+            "nickname": null
+        })
+    );
+    assert_eq!(
+        response
+            .errors
+            .iter()
+            .map(|e| e.1.to_string())
+            .collect::<Vec<_>>(),
+        vec![
+            "Cannot return null for non-nullable field \"User.name\".".to_owned(),
+            "Cannot return null for non-nullable field \"Item.name\".".to_owned(),
+            "Cannot return null for non-nullable field \"Item.name\".".to_owned(),
+            "Cannot return null for non-nullable field \"Item.name\".".to_owned(),
+        ]
+    );
+
+    assert_eq!(
+        response
+            .errors
+            .iter()
+            .map(|e| match &e.1 {
+                QueryError::NonNullField(_, _, path) => json!(path),
+                _ => Value::Null,
+            })
+            .collect::<Vec<_>>(),
+        vec![
+            json!(["viewer", "name"]),
+            json!(["items", 1, "name"]),
+            json!(["entries", 1, "name"]),
+            json!(["entries", 2, "name"]),
+        ]
+    );
+}
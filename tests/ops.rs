@@ -0,0 +1,113 @@
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription};
+use async_trait::async_trait;
+use common::{account, inventory, inventory_updated, product, review, TestExecutor};
+use futures_await_test::async_test;
+use graphql_gateway::ops::{self, SchemaChange};
+use graphql_gateway::{Data, Executor, GatewayBuilder, GatewayError, GraphQLResponse};
+use serde_json::{json, Value};
+
+#[derive(Clone)]
+struct OfflineExecutor;
+
+#[async_trait]
+impl Executor for OfflineExecutor {
+    fn name(&self) -> &str {
+        "offline"
+    }
+
+    async fn execute(
+        &self,
+        _data: Option<&Data>,
+        _query: String,
+        _operation_name: Option<String>,
+        _variables: Option<Value>,
+    ) -> Result<Value, String> {
+        Err("Connection refused".to_owned())
+    }
+}
+
+fn builder<'a>() -> GatewayBuilder<'a> {
+    GatewayBuilder::new()
+        .executor(TestExecutor::new(
+            "account",
+            account::Query {},
+            account::Mutation {},
+            EmptySubscription,
+        ))
+        .executor(TestExecutor::new(
+            "product",
+            product::Query {},
+            product::Mutation {},
+            EmptySubscription,
+        ))
+        .executor(TestExecutor::new(
+            "review",
+            review::Query {},
+            EmptyMutation,
+            EmptySubscription,
+        ))
+}
+
+#[async_test]
+async fn compose_and_diff() {
+    let previous = ops::compose(builder().executor(TestExecutor::new(
+        "inventory",
+        inventory::Query {},
+        EmptyMutation,
+        EmptySubscription,
+    )))
+    .await
+    .unwrap();
+    let next = ops::compose(builder().executor(TestExecutor::new(
+        "inventory",
+        inventory_updated::Query {},
+        EmptyMutation,
+        EmptySubscription,
+    )))
+    .await
+    .unwrap();
+
+    assert!(ops::print_sdl(&previous).contains("inStock: Boolean!"));
+
+    let changes = ops::diff(&previous, &next);
+
+    assert_eq!(
+        changes,
+        vec![
+            SchemaChange::FieldRemoved {
+                coordinate: "Product.inStock".to_owned()
+            },
+            SchemaChange::FieldAdded {
+                coordinate: "Product.delivered".to_owned()
+            },
+        ]
+    );
+    assert!(changes[0].is_breaking());
+    assert_eq!(changes[0].to_string(), "Field Product.inStock was removed");
+    assert!(ops::diff(&next, &next).is_empty());
+
+    match ops::compose(builder().executor(OfflineExecutor)).await {
+        Err(GatewayError::CompositionFailed(errors)) => {
+            assert_eq!(errors[0].0, "offline");
+        }
+        _ => panic!("Expected a composition error"),
+    }
+}
+
+#[async_test]
+async fn check_query() {
+    let gateway = ops::compose(builder()).await.unwrap();
+    let parsed = ops::check_query(&gateway, "query Viewer { viewer { email } }").unwrap();
+
+    assert_eq!(parsed.operation_name.as_deref(), Some("Viewer"));
+
+    assert_eq!(
+        serde_json::to_value(GraphQLResponse(
+            ops::check_query(&gateway, "{ viewer { unknown } }").map(|_| json!(null))
+        ))
+        .unwrap()["errors"][0]["message"],
+        json!("Cannot query field \"unknown\" on type \"User\".")
+    );
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use futures_await_test::async_test;
+use graphql_gateway::{
+    sha256_hex, GraphQLBatchPayload, PersistedQueries, QueryBuilder, QueryError,
+};
+use serde_json::json;
+
+const PRODUCTS_QUERY: &str = "query Products { products { name } }";
+
+#[async_test]
+async fn persisted_queries() {
+    let manifest = json!({
+        "format": "apollo-persisted-query-manifest",
+        "version": 1,
+        "operations": [
+            { "id": "products", "name": "Products", "type": "query", "body": PRODUCTS_QUERY }
+        ]
+    });
+    let queries = PersistedQueries::from_json(&manifest.to_string()).unwrap();
+    let gateway = common::gateway().await.persisted_queries(queries);
+
+    assert_eq!(
+        QueryBuilder::new(PRODUCTS_QUERY)
+            .execute(&gateway)
+            .await
+            .unwrap(),
+        json!({ "products": [{ "name": "Product 1" }, { "name": "Product 2" }] })
+    );
+
+    match QueryBuilder::new("query { users { username } }")
+        .execute(&gateway)
+        .await
+    {
+        Err(QueryError::QueryNotAllowed) => {}
+        result => panic!("Unexpected result {:?}", result),
+    }
+
+    let payload: GraphQLBatchPayload = serde_json::from_value(json!([
+        { "extensions": { "persistedQuery": { "version": 1, "sha256Hash": "products" } } },
+        { "extensions": { "persistedQuery": { "version": 1, "sha256Hash": "unknown" } } },
+        { "query": "query { users { username } }" }
+    ]))
+    .unwrap();
+
+    let response = serde_json::to_value(payload.execute(&gateway).await).unwrap();
+
+    assert_eq!(
+        response[0]["data"],
+        json!({ "products": [{ "name": "Product 1" }, { "name": "Product 2" }] })
+    );
+    assert_eq!(
+        response[1]["errors"][0]["extensions"]["code"],
+        json!("PERSISTED_QUERY_NOT_FOUND")
+    );
+    assert_eq!(
+        response[2]["errors"][0],
+        json!({
+            "message": "Query is not in the persisted query allowlist.",
+            "locations": [{ "line": 0, "column": 0 }],
+            "extensions": { "code": "QUERY_NOT_ALLOWED" }
+        })
+    );
+}
+
+#[test]
+fn persisted_queries_manifest() {
+    let hash = sha256_hex(PRODUCTS_QUERY);
+    let queries =
+        PersistedQueries::from_json(&json!({ hash.clone(): PRODUCTS_QUERY }).to_string()).unwrap();
+
+    assert_eq!(queries.len(), 1);
+    assert!(PersistedQueries::from_json("[]").is_err());
+    assert_eq!(PersistedQueries::new().query(PRODUCTS_QUERY).len(), 1);
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription};
+use common::{inventory_updated, TestExecutor};
+use futures_await_test::async_test;
+use graphql_gateway::{PlanCacheStats, QueryBuilder};
+use serde_json::json;
+
+#[async_test]
+async fn plan_cache() {
+    let gateway = common::gateway().await.plan_cache(16);
+    let query = QueryBuilder::new("query { products { name inStock } users { username } }");
+
+    let first = query.execute(&gateway).await.unwrap();
+    let second = query.execute(&gateway).await.unwrap();
+
+    assert_eq!(first, second);
+    assert_eq!(
+        second,
+        json!({
+            "products": [
+                { "name": "Product 1", "inStock": true },
+                { "name": "Product 2", "inStock": false }
+            ],
+            "users": [{ "username": null }, { "username": "albert" }]
+        })
+    );
+    assert_eq!(
+        gateway.plan_cache_stats(),
+        Some(PlanCacheStats {
+            entries: 1,
+            hits: 1,
+            misses: 1
+        })
+    );
+    assert_eq!(common::gateway().await.plan_cache_stats(), None);
+}
+
+#[async_test]
+async fn plan_cache_variables() {
+    let gateway = common::gateway().await.plan_cache(16);
+    let query =
+        "query Products($stock: Boolean!) { products { name inStock @include(if: $stock) } }";
+
+    assert_eq!(
+        QueryBuilder::new(query)
+            .operation_name("Products")
+            .variables(json!({ "stock": true }))
+            .execute(&gateway)
+            .await
+            .unwrap(),
+        json!({
+            "products": [
+                { "name": "Product 1", "inStock": true },
+                { "name": "Product 2", "inStock": false }
+            ]
+        })
+    );
+    assert_eq!(
+        QueryBuilder::new(query)
+            .operation_name("Products")
+            .variables(json!({ "stock": false }))
+            .execute(&gateway)
+            .await
+            .unwrap(),
+        json!({ "products": [{ "name": "Product 1" }, { "name": "Product 2" }] })
+    );
+    assert_eq!(gateway.plan_cache_stats().unwrap().hits, 1);
+}
+
+#[async_test]
+async fn plan_cache_pull() {
+    let query = QueryBuilder::new("query { products { name } }");
+    let gateway = common::gateway().await.plan_cache(16);
+
+    query.execute(&gateway).await.unwrap();
+    assert_eq!(gateway.plan_cache_stats().unwrap().entries, 1);
+
+    let inventory = TestExecutor::new(
+        "inventory",
+        inventory_updated::Query {},
+        EmptyMutation,
+        EmptySubscription,
+    );
+    let mut gateway = gateway.executor(inventory);
+
+    gateway.pull("inventory").await.unwrap();
+    assert_eq!(gateway.plan_cache_stats().unwrap().entries, 0);
+
+    query.execute(&gateway).await.unwrap();
+    assert_eq!(
+        gateway.plan_cache_stats(),
+        Some(PlanCacheStats {
+            entries: 1,
+            hits: 0,
+            misses: 2
+        })
+    );
+}
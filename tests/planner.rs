@@ -0,0 +1,305 @@
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription};
+use common::{account, inventory, product, CountingExecutor, InFlightExecutor, TestExecutor};
+use futures_await_test::async_test;
+use graphql_gateway::{
+    GatewayBuilder, GraphQLResponse, ParsedQuery, PlanStepKind, PlannerFlags, QueryBuilder,
+};
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[async_test]
+async fn planner_flags() {
+    let source = r#"
+        query {
+            products {
+                name
+                inStock
+            }
+        }
+    "#;
+
+    let expected = json!({
+        "products": [
+            { "name": "Product 1", "inStock": true },
+            { "name": "Product 2", "inStock": false }
+        ]
+    });
+
+    let inventory = CountingExecutor::new(TestExecutor::new(
+        "inventory",
+        inventory::Query {},
+        EmptyMutation,
+        EmptySubscription,
+    ));
+
+    let mut gateway = common::gateway()
+        .await
+        .executor(inventory.clone())
+        .planner_flags(PlannerFlags {
+            parallel_root: true,
+            ..PlannerFlags::default()
+        });
+
+    gateway.pull("inventory").await.unwrap();
+
+    let query = QueryBuilder::new(source);
+
+    assert_eq!(query.execute(&gateway).await.unwrap(), expected);
+    assert_eq!(inventory.count(), 2);
+
+    let query = QueryBuilder::new(source).data(PlannerFlags {
+        batch_nodes: false,
+        ..PlannerFlags::default()
+    });
+
+    assert_eq!(query.execute(&gateway).await.unwrap(), expected);
+    assert_eq!(inventory.count(), 4);
+
+    let query = QueryBuilder::new(source).variables(json!({ "first": 1 }));
+
+    assert_eq!(query.execute(&gateway).await.unwrap(), expected);
+
+    let query = query.data(PlannerFlags {
+        strict_validation: true,
+        ..PlannerFlags::default()
+    });
+
+    assert_eq!(
+        query.execute(&gateway).await.unwrap_err().to_string(),
+        "Variable \"$first\" is not defined by the operation."
+    );
+}
+
+#[async_test]
+async fn query_plan() {
+    let inventory = CountingExecutor::new(TestExecutor::new(
+        "inventory",
+        inventory::Query {},
+        EmptyMutation,
+        EmptySubscription,
+    ));
+
+    let gateway = common::gateway().await.executor(inventory.clone());
+    let query = QueryBuilder::new(
+        r#"
+            query {
+                products {
+                    name
+                    inStock
+                    reviews {
+                        body
+                        author {
+                            username
+                        }
+                    }
+                }
+            }
+        "#,
+    );
+
+    let plan = query.plan(&gateway).unwrap();
+
+    assert_eq!(inventory.count(), 0);
+    assert!(plan.parallel_root);
+    assert_eq!(
+        plan.executors(),
+        vec!["product", "inventory", "review", "account"]
+    );
+    assert_eq!(
+        plan.steps
+            .iter()
+            .map(|step| (
+                step.kind,
+                step.type_name.as_str(),
+                step.path.join("."),
+                step.depends_on
+            ))
+            .collect::<Vec<_>>(),
+        vec![
+            (PlanStepKind::Root, "Query", "".to_owned(), None),
+            (
+                PlanStepKind::Node,
+                "Product",
+                "products".to_owned(),
+                Some(0)
+            ),
+            (
+                PlanStepKind::Node,
+                "Product",
+                "products".to_owned(),
+                Some(0)
+            ),
+            (
+                PlanStepKind::Node,
+                "User",
+                "products.reviews.author".to_owned(),
+                Some(2)
+            ),
+        ]
+    );
+    assert_eq!(
+        plan.steps[0].document,
+        "query {\n  products {\n    id\n    name\n  }\n}\n"
+    );
+    assert_eq!(
+        plan.dependents(2)
+            .iter()
+            .map(|step| step.executor.as_str())
+            .collect::<Vec<_>>(),
+        vec!["account"]
+    );
+
+    let plan = QueryBuilder::new(
+        r#"
+            mutation {
+                signIn(input: { email: "john@doe.com", password: "yep" }) {
+                    id
+                }
+                addProduct(id: "Product:1") {
+                    id
+                }
+            }
+        "#,
+    )
+    .data(PlannerFlags {
+        parallel_root: true,
+        ..PlannerFlags::default()
+    })
+    .plan(&gateway)
+    .unwrap();
+
+    assert!(!plan.parallel_root);
+    assert_eq!(plan.executors(), vec!["account", "product"]);
+}
+
+#[async_test]
+async fn query_phases() {
+    let gateway = common::gateway().await;
+    let query = QueryBuilder::new(
+        r#"
+            query Products($first: Int) {
+                products {
+                    name
+                }
+                viewer {
+                    ...ViewerFields
+                }
+            }
+
+            fragment ViewerFields on User {
+                email
+                reviews(first: $first) {
+                    body
+                }
+            }
+        "#,
+    );
+
+    assert_eq!(
+        query.parse().unwrap(),
+        ParsedQuery {
+            operation_type: "Query".to_owned(),
+            operation_name: Some("Products".to_owned()),
+            variables: vec!["first".to_owned()],
+            fragments: vec!["ViewerFields".to_owned()],
+            root_fields: vec!["products".to_owned(), "viewer".to_owned()],
+        }
+    );
+
+    assert!(query.validate(&gateway).is_ok());
+    assert_eq!(
+        serde_json::to_value(GraphQLResponse(
+            QueryBuilder::new("{ products { price } }")
+                .validate(&gateway)
+                .map(|_| json!(null))
+        ))
+        .unwrap()["errors"][0]["message"],
+        json!("Cannot query field \"price\" on type \"Product\".")
+    );
+
+    let query = query.variables(json!({ "first": 1 }));
+    let plan = query.plan(&gateway).unwrap();
+
+    assert_eq!(
+        query.execute_plan(&gateway, &plan).await.unwrap(),
+        json!({
+            "products": [{ "name": "Product 1" }, { "name": "Product 2" }],
+            "viewer": { "email": "john@doe.com", "reviews": [{ "body": "Good product" }] }
+        })
+    );
+
+    let stale = QueryBuilder::new("{ viewer { email } }")
+        .plan(&gateway)
+        .unwrap();
+
+    assert_eq!(
+        query
+            .execute_plan(&gateway, &stale)
+            .await
+            .unwrap_err()
+            .to_string(),
+        "Query plan is stale, plan the operation again."
+    );
+}
+
+#[async_test]
+async fn parallel_root() {
+    let counters = Arc::new((AtomicUsize::new(0), AtomicUsize::new(0)));
+    let account = TestExecutor::new(
+        "account",
+        account::Query {},
+        account::Mutation {},
+        EmptySubscription,
+    );
+    let product = TestExecutor::new(
+        "product",
+        product::Query {},
+        product::Mutation {},
+        EmptySubscription,
+    );
+    let gateway = GatewayBuilder::new()
+        .executor(InFlightExecutor(Box::new(account), counters.clone()))
+        .executor(InFlightExecutor(Box::new(product), counters.clone()))
+        .build()
+        .await
+        .unwrap();
+    let query = QueryBuilder::new("query { users { username } products { name } }");
+    let expected = json!({
+        "users": [{ "username": null }, { "username": "albert" }],
+        "products": [{ "name": "Product 1" }, { "name": "Product 2" }]
+    });
+
+    counters.1.store(0, Ordering::SeqCst);
+    assert_eq!(query.execute(&gateway).await.unwrap(), expected);
+    assert_eq!(counters.1.load(Ordering::SeqCst), 2);
+
+    let query = query.data(PlannerFlags {
+        parallel_root: false,
+        ..PlannerFlags::default()
+    });
+
+    counters.1.store(0, Ordering::SeqCst);
+    assert_eq!(query.execute(&gateway).await.unwrap(), expected);
+    assert_eq!(counters.1.load(Ordering::SeqCst), 1);
+
+    counters.1.store(0, Ordering::SeqCst);
+    QueryBuilder::new(
+        r#"
+            mutation {
+                signIn(input: { email: "john@doe.com", password: "yep" }) {
+                    id
+                }
+                addProduct(id: "Product:1") {
+                    id
+                }
+            }
+        "#,
+    )
+    .execute(&gateway)
+    .await
+    .unwrap();
+    assert_eq!(counters.1.load(Ordering::SeqCst), 1);
+}
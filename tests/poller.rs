@@ -0,0 +1,125 @@
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription};
+use async_trait::async_trait;
+use common::{account, inventory, inventory_updated, product, review, TestExecutor};
+use futures::StreamExt;
+use futures_await_test::async_test;
+use graphql_gateway::{Data, Executor, Gateway, QueryBuilder};
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[derive(Clone)]
+struct DeployingExecutor(Arc<AtomicBool>);
+
+#[async_trait]
+impl Executor for DeployingExecutor {
+    fn name(&self) -> &str {
+        "inventory"
+    }
+
+    async fn execute(
+        &self,
+        data: Option<&Data>,
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<Value>,
+    ) -> Result<Value, String> {
+        if self.0.load(Ordering::SeqCst) {
+            TestExecutor::new(
+                "inventory",
+                inventory_updated::Query {},
+                EmptyMutation,
+                EmptySubscription,
+            )
+            .execute(data, query, operation_name, variables)
+            .await
+        } else {
+            TestExecutor::new(
+                "inventory",
+                inventory::Query {},
+                EmptyMutation,
+                EmptySubscription,
+            )
+            .execute(data, query, operation_name, variables)
+            .await
+        }
+    }
+}
+
+async fn gateway(deployed: Arc<AtomicBool>) -> Gateway<'static> {
+    Gateway::default()
+        .executor(TestExecutor::new(
+            "account",
+            account::Query {},
+            account::Mutation {},
+            EmptySubscription,
+        ))
+        .executor(DeployingExecutor(deployed))
+        .executor(TestExecutor::new(
+            "product",
+            product::Query {},
+            product::Mutation {},
+            EmptySubscription,
+        ))
+        .executor(TestExecutor::new(
+            "review",
+            review::Query {},
+            EmptyMutation,
+            EmptySubscription,
+        ))
+        .build()
+        .await
+        .unwrap()
+}
+
+#[async_test]
+async fn spawn_poller() {
+    let deployed = Arc::new(AtomicBool::new(false));
+    let handle = gateway(deployed.clone())
+        .await
+        .spawn_poller(Duration::from_millis(10), |task| {
+            thread::spawn(move || futures::executor::block_on(task));
+        });
+    let mut changes = handle.schema_changes();
+
+    assert_eq!(
+        QueryBuilder::new("{ products { name inStock } }")
+            .execute(&handle.load())
+            .await
+            .unwrap(),
+        json!({
+            "products": [
+                { "name": "Product 1", "inStock": true },
+                { "name": "Product 2", "inStock": false }
+            ]
+        })
+    );
+
+    deployed.store(true, Ordering::SeqCst);
+
+    let change = changes.next().await.unwrap();
+
+    assert_eq!(change.executor, "inventory");
+    assert!(change.previous_hash.is_some());
+    assert!(change.hash.is_some());
+    assert_ne!(change.previous_hash, change.hash);
+
+    assert_eq!(
+        QueryBuilder::new("{ products { name delivered } }")
+            .execute(&handle.load())
+            .await
+            .unwrap(),
+        json!({
+            "products": [
+                { "name": "Product 1", "delivered": true },
+                { "name": "Product 2", "delivered": false }
+            ]
+        })
+    );
+
+    assert!(handle.poll().await.is_empty());
+}
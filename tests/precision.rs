@@ -0,0 +1,102 @@
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription, ScalarType, Value as GqlValue};
+use async_trait::async_trait;
+use common::TestExecutor;
+use futures_await_test::async_test;
+use graphql_gateway::{Data, Executor, Gateway, GraphQLPayload, GraphQLResponse};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+
+pub struct Decimal(String);
+
+#[async_graphql::Scalar]
+impl ScalarType for Decimal {
+    fn type_name() -> &'static str {
+        "Decimal"
+    }
+
+    fn parse(value: &GqlValue) -> Option<Self> {
+        match value {
+            GqlValue::String(value) => Some(Decimal(value.clone())),
+            GqlValue::Float(value) => Some(Decimal(value.to_string())),
+            _ => None,
+        }
+    }
+
+    fn to_json(&self) -> async_graphql::Result<Value> {
+        Ok(Value::String(self.0.clone()))
+    }
+}
+
+mod ledger {
+    use super::Decimal;
+
+    pub struct Query;
+
+    #[async_graphql::Object]
+    impl Query {
+        #[field]
+        async fn convert(&self, amount: Decimal) -> Decimal {
+            amount
+        }
+    }
+}
+
+#[derive(Clone)]
+struct EchoExecutor(Box<dyn Executor>, Arc<Mutex<Vec<String>>>);
+
+#[async_trait]
+impl Executor for EchoExecutor {
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    async fn execute(
+        &self,
+        data: Option<&Data>,
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<Value>,
+    ) -> Result<Value, String> {
+        if operation_name.as_deref() == Some("IntrospectionQuery") {
+            return self.0.execute(data, query, operation_name, variables).await;
+        }
+
+        let variables = variables.unwrap_or_default();
+        self.1.lock().unwrap().push(variables.to_string());
+
+        Ok(json!({ "data": { "convert": variables["amount"] } }))
+    }
+}
+
+#[async_test]
+async fn arbitrary_precision() {
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let ledger = TestExecutor::new("ledger", ledger::Query {}, EmptyMutation, EmptySubscription);
+    let gateway = Gateway::default()
+        .executor(EchoExecutor(Box::new(ledger), requests.clone()))
+        .build()
+        .await
+        .unwrap();
+
+    let payload: GraphQLPayload = serde_json::from_str(
+        r#"{
+            "query": "query Convert($amount: Decimal) { convert(amount: $amount) }",
+            "operationName": "Convert",
+            "variables": { "amount": 12345678901234567890.123456789 }
+        }"#,
+    )
+    .unwrap();
+
+    let response = GraphQLResponse(payload.to_query_builder().execute(&gateway).await);
+
+    assert_eq!(
+        requests.lock().unwrap().as_slice(),
+        [r#"{"amount":12345678901234567890.123456789}"#]
+    );
+    assert_eq!(
+        serde_json::to_string(&response).unwrap(),
+        r#"{"data":{"convert":12345678901234567890.123456789}}"#
+    );
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription};
+use common::{inventory_updated, TestExecutor};
+use futures_await_test::async_test;
+use graphql_gateway::{PlanCacheStats, PreparedQuery, QueryBuilder};
+use serde_json::json;
+
+#[async_test]
+async fn prepared_query() {
+    let gateway = common::gateway().await;
+    let prepared = PreparedQuery::prepare(
+        &gateway,
+        "query Product($id: ID!) { node(id: $id) { ... on Product { name } } }",
+    )
+    .unwrap();
+
+    let first = prepared
+        .query()
+        .operation_name("Product")
+        .variables(json!({ "id": "UHJvZHVjdDow" }))
+        .execute(&gateway)
+        .await
+        .unwrap();
+    let second = QueryBuilder::from_prepared(&prepared)
+        .operation_name("Product")
+        .variables(json!({ "id": "UHJvZHVjdDox" }))
+        .execute(&gateway)
+        .await
+        .unwrap();
+
+    assert_eq!(first, json!({ "node": { "name": "Product 1" } }));
+    assert_eq!(second, json!({ "node": { "name": "Product 2" } }));
+    assert_eq!(
+        prepared.plan_cache_stats(),
+        PlanCacheStats {
+            entries: 1,
+            hits: 1,
+            misses: 1
+        }
+    );
+    assert_eq!(gateway.plan_cache_stats(), None);
+}
+
+#[async_test]
+async fn prepared_query_errors() {
+    let gateway = common::gateway().await;
+
+    assert_eq!(
+        PreparedQuery::prepare(&gateway, "{ products {")
+            .err()
+            .map(|e| e.to_string().starts_with("Parse error")),
+        Some(true)
+    );
+    assert_eq!(
+        PreparedQuery::prepare(
+            &gateway,
+            "{ products { ...Name } } fragment Name on Product { name } fragment Name on Product { name }"
+        )
+        .err()
+        .map(|e| e.to_string()),
+        Some("Query errors.".to_owned())
+    );
+
+    let prepared = PreparedQuery::prepare(&gateway, "{ unknown }").unwrap();
+
+    assert_eq!(
+        prepared
+            .query()
+            .execute(&gateway)
+            .await
+            .err()
+            .map(|e| e.to_string()),
+        Some("Query errors.".to_owned())
+    );
+}
+
+#[async_test]
+async fn prepared_query_pull() {
+    let gateway = common::gateway().await;
+    let prepared = PreparedQuery::prepare(&gateway, "{ products { name } }").unwrap();
+
+    prepared.query().execute(&gateway).await.unwrap();
+
+    let inventory = TestExecutor::new(
+        "inventory",
+        inventory_updated::Query {},
+        EmptyMutation,
+        EmptySubscription,
+    );
+    let mut gateway = gateway.executor(inventory);
+
+    gateway.pull("inventory").await.unwrap();
+
+    assert_eq!(
+        prepared.query().execute(&gateway).await.unwrap(),
+        json!({ "products": [{ "name": "Product 1" }, { "name": "Product 2" }] })
+    );
+    assert_eq!(
+        prepared.plan_cache_stats(),
+        PlanCacheStats {
+            entries: 2,
+            hits: 0,
+            misses: 2
+        }
+    );
+}
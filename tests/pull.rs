@@ -3,7 +3,7 @@ mod common;
 use async_graphql::{EmptyMutation, EmptySubscription};
 use common::{account, inventory, inventory_updated, TestExecutor};
 use futures_await_test::async_test;
-use graphql_gateway::{Executor, GatewayError, QueryBuilder, GraphQLResponse};
+use graphql_gateway::{Executor, GatewayError, GraphQLResponse, QueryBuilder};
 use serde_json::json;
 
 #[async_test]
@@ -140,8 +140,8 @@ async fn validate() {
         EmptySubscription,
     );
 
-    let (name, schema) = inventory_updated.introspect().await.unwrap();
-    assert_eq!(gateway.validate(name, schema).is_ok(), true);
+    let (name, schema) = inventory_updated.introspect(None).await.unwrap();
+    assert!(gateway.validate(name, schema).is_ok());
 }
 
 #[async_test]
@@ -155,40 +155,22 @@ async fn validate_failed() {
         EmptySubscription,
     );
 
-    let (name, schema) = account.introspect().await.unwrap();
+    let (name, schema) = account.introspect(None).await.unwrap();
 
     match gateway.validate(name, schema).unwrap_err() {
         GatewayError::DuplicateObjectFields(fields) => {
-            assert_eq!(
-                fields.iter().any(|(_, _, key)| key == "Object.Query.users"),
-                true
-            );
-            assert_eq!(
-                fields
-                    .iter()
-                    .any(|(_, _, key)| key == "Object.Query.viewer"),
-                true
-            );
-            assert_eq!(
-                fields.iter().any(|(_, _, key)| key == "Object.User.email"),
-                true
-            );
-            assert_eq!(
-                fields
-                    .iter()
-                    .any(|(_, _, key)| key == "Object.User.username"),
-                true
-            );
-            assert_eq!(
-                fields.iter().any(|(_, _, key)| key == "Object.User.role"),
-                true
-            );
-            assert_eq!(
-                fields
-                    .iter()
-                    .any(|(_, _, key)| key == "Object.User.sayHello"),
-                true
-            );
+            assert!(fields.iter().any(|(_, _, key)| key == "Object.Query.users"));
+            assert!(fields
+                .iter()
+                .any(|(_, _, key)| key == "Object.Query.viewer"));
+            assert!(fields.iter().any(|(_, _, key)| key == "Object.User.email"));
+            assert!(fields
+                .iter()
+                .any(|(_, _, key)| key == "Object.User.username"));
+            assert!(fields.iter().any(|(_, _, key)| key == "Object.User.role"));
+            assert!(fields
+                .iter()
+                .any(|(_, _, key)| key == "Object.User.sayHello"));
         }
         _ => panic!("thread 'validate' panicked at 'Excepted an duplicate error"),
     };
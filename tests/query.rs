@@ -1,8 +1,12 @@
 mod common;
 
+use async_graphql::{EmptyMutation, EmptySubscription};
+use common::{product, review, InFlightExecutor, TestExecutor};
 use futures_await_test::async_test;
-use graphql_gateway::QueryBuilder;
+use graphql_gateway::{PlannerFlags, QueryBuilder, QueryError};
 use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
 #[async_test]
 async fn query() {
@@ -266,3 +270,329 @@ async fn query_node() {
         })
     );
 }
+
+#[async_test]
+async fn query_node_fragments() {
+    let query = QueryBuilder::new(
+        r#"
+            query {
+                node(id: "UmV2aWV3OjA=") {
+                    id
+                    ...ReviewFields
+                }
+                nodes(ids: ["UHJvZHVjdDow", "UHJvZHVjdDox"]) {
+                    ...ProductFields
+                }
+            }
+
+            fragment ProductFields on Product {
+                id
+                name
+                inStock
+            }
+
+            fragment ReviewFields on Review {
+                body
+                product {
+                    ...ProductFields
+                }
+            }
+        "#
+        .to_owned(),
+    );
+
+    let gateway = common::gateway().await;
+
+    assert_eq!(
+        query.execute(&gateway).await.unwrap(),
+        json!({
+            "node": {
+                "id": "UmV2aWV3OjA=",
+                "body": "Good product",
+                "product": {
+                    "id": "UHJvZHVjdDow",
+                    "name": "Product 1",
+                    "inStock": true
+                }
+            },
+            "nodes": [
+                {
+                    "id": "UHJvZHVjdDow",
+                    "name": "Product 1",
+                    "inStock": true
+                },
+                {
+                    "id": "UHJvZHVjdDox",
+                    "name": "Product 2",
+                    "inStock": false
+                }
+            ]
+        })
+    );
+}
+
+#[async_test]
+async fn query_list_parallelism() {
+    let query = QueryBuilder::new(
+        r#"
+            query {
+                users {
+                    username
+                    reviews {
+                        body
+                        product {
+                            name
+                        }
+                    }
+                }
+            }
+        "#
+        .to_owned(),
+    )
+    .data(PlannerFlags {
+        batch_nodes: false,
+        ..PlannerFlags::default()
+    });
+
+    let counters = Arc::new((AtomicUsize::new(0), AtomicUsize::new(0)));
+    let gateway = common::gateway()
+        .await
+        .executor(InFlightExecutor(
+            Box::new(TestExecutor::new(
+                "product",
+                product::Query {},
+                product::Mutation {},
+                EmptySubscription,
+            )),
+            counters.clone(),
+        ))
+        .executor(InFlightExecutor(
+            Box::new(TestExecutor::new(
+                "review",
+                review::Query {},
+                EmptyMutation,
+                EmptySubscription,
+            )),
+            counters.clone(),
+        ))
+        .list_parallelism(1);
+
+    assert_eq!(
+        query.execute(&gateway).await.unwrap(),
+        json!({
+            "users": [
+                {
+                    "username": null,
+                    "reviews": [
+                        { "body": "Good product", "product": { "name": "Product 1" } },
+                        { "body": "Bad product", "product": { "name": "Product 2" } }
+                    ]
+                },
+                {
+                    "username": "albert",
+                    "reviews": [
+                        { "body": "Fake description", "product": { "name": "Product 1" } }
+                    ]
+                }
+            ]
+        })
+    );
+    assert_eq!(counters.1.load(Ordering::SeqCst), 1);
+}
+
+#[async_test]
+async fn query_node_nested_arguments() {
+    let query = QueryBuilder::new(
+        r#"
+            query UsersQuery($first: Int, $offset: Int!, $unused: String) {
+                users {
+                    username
+                    first: reviews(first: $first) {
+                        body
+                    }
+                    literal: reviews(first: 1) {
+                        body
+                    }
+                    paged: reviews(page: { first: 1, offset: $offset }) {
+                        body
+                    }
+                }
+            }
+        "#,
+    )
+    .operation_name("UsersQuery")
+    .variables(json!({ "first": 1, "offset": 1, "unused": "yes" }));
+
+    let gateway = common::gateway().await;
+
+    assert_eq!(
+        query.execute(&gateway).await.unwrap(),
+        json!({
+            "users": [
+                {
+                    "username": null,
+                    "first": [{ "body": "Good product" }],
+                    "literal": [{ "body": "Good product" }],
+                    "paged": [{ "body": "Bad product" }]
+                },
+                {
+                    "username": "albert",
+                    "first": [{ "body": "Fake description" }],
+                    "literal": [{ "body": "Fake description" }],
+                    "paged": []
+                }
+            ]
+        })
+    );
+}
+
+#[async_test]
+async fn query_operation_name() {
+    let source = r#"
+        query Products {
+            products {
+                name
+            }
+        }
+
+        query Users {
+            users {
+                username
+            }
+        }
+    "#;
+
+    let gateway = common::gateway().await;
+
+    assert_eq!(
+        QueryBuilder::new(source)
+            .operation_name("Users")
+            .execute(&gateway)
+            .await
+            .unwrap(),
+        json!({ "users": [{ "username": null }, { "username": "albert" }] })
+    );
+
+    assert_eq!(
+        QueryBuilder::new(source)
+            .execute(&gateway)
+            .await
+            .unwrap_err()
+            .to_string(),
+        "Must provide operation name if query contains multiple operations."
+    );
+
+    assert_eq!(
+        QueryBuilder::new(source)
+            .operation_name("Reviews")
+            .execute(&gateway)
+            .await
+            .unwrap_err()
+            .to_string(),
+        "Unknown operation named \"Reviews\"."
+    );
+
+    assert_eq!(
+        QueryBuilder::new(format!("{} query Users {{ users {{ email }} }}", source))
+            .operation_name("Users")
+            .execute(&gateway)
+            .await
+            .unwrap_err()
+            .to_string(),
+        "There can be only one operation named \"Users\"."
+    );
+}
+
+#[async_test]
+async fn query_untyped_inline_fragment() {
+    let query = QueryBuilder::new(
+        r#"
+            query {
+                products {
+                    name
+                    ... @include(if: true) {
+                        inStock
+                    }
+                    ... @skip(if: true) {
+                        reviews {
+                            body
+                        }
+                    }
+                    ... {
+                        id
+                    }
+                }
+                ... {
+                    users {
+                        username
+                    }
+                }
+            }
+        "#,
+    );
+
+    let gateway = common::gateway().await;
+
+    assert_eq!(
+        query.execute(&gateway).await.unwrap(),
+        json!({
+            "products": [
+                { "name": "Product 1", "inStock": true, "id": "UHJvZHVjdDow" },
+                { "name": "Product 2", "inStock": false, "id": "UHJvZHVjdDox" }
+            ],
+            "users": [{ "username": null }, { "username": "albert" }]
+        })
+    );
+}
+
+#[async_test]
+async fn query_merged_fields() {
+    let query = QueryBuilder::new(
+        r#"
+            query {
+                viewer {
+                    email
+                }
+                viewer {
+                    reviews {
+                        body
+                    }
+                }
+                viewer {
+                    email
+                    reviews {
+                        product {
+                            name
+                        }
+                    }
+                }
+            }
+        "#,
+    );
+
+    let gateway = common::gateway().await;
+
+    assert_eq!(
+        query.execute(&gateway).await.unwrap(),
+        json!({
+            "viewer": {
+                "email": "john@doe.com",
+                "reviews": [
+                    { "body": "Good product", "product": { "name": "Product 1" } },
+                    { "body": "Bad product", "product": { "name": "Product 2" } }
+                ]
+            }
+        })
+    );
+
+    let error = QueryBuilder::new("query { users { id } users: products { id } }")
+        .execute(&gateway)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        QueryError::Errors(errors)
+            if matches!(&errors[0].1, QueryError::FieldsConflict(name) if name == "users")
+    ));
+}
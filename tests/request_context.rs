@@ -0,0 +1,86 @@
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription};
+use common::{inventory, product, Call, RecordingExecutor, TestExecutor};
+use futures_await_test::async_test;
+use graphql_gateway::{HeaderPolicy, QueryBuilder, RequestContext};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+
+fn record_context(call: &Call) -> Option<Value> {
+    let context = call.data?.get::<RequestContext>()?;
+
+    Some(json!({
+        "executor": call.executor,
+        "headers": context.headers,
+        "metadata": context.metadata,
+    }))
+}
+
+#[async_test]
+async fn request_context() {
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let gateway = common::gateway()
+        .await
+        .executor(RecordingExecutor::new(
+            TestExecutor::new(
+                "product",
+                product::Query {},
+                product::Mutation {},
+                EmptySubscription,
+            ),
+            calls.clone(),
+            record_context,
+        ))
+        .executor(RecordingExecutor::new(
+            TestExecutor::new(
+                "inventory",
+                inventory::Query {},
+                EmptyMutation,
+                EmptySubscription,
+            ),
+            calls.clone(),
+            record_context,
+        ))
+        .header_policy(
+            HeaderPolicy::new()
+                .allow("Authorization")
+                .rename("x-request-id", "x-trace-id")
+                .inject("x-gateway", "graphql-gateway"),
+        )
+        .executor_header_policy("inventory", HeaderPolicy::new().allow("x-request-id"));
+
+    let context = RequestContext::new()
+        .header("authorization", "Bearer token")
+        .header("cookie", "session=1")
+        .header("X-Request-Id", "abc")
+        .metadata("tenant", "acme");
+
+    assert_eq!(context.get_header("x-request-id"), Some("abc"));
+
+    QueryBuilder::new("query { products { name inStock } }")
+        .request_context(context)
+        .execute(&gateway)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        *calls.lock().unwrap(),
+        vec![
+            json!({
+                "executor": "product",
+                "headers": [
+                    ["authorization", "Bearer token"],
+                    ["x-trace-id", "abc"],
+                    ["x-gateway", "graphql-gateway"]
+                ],
+                "metadata": { "tenant": "acme" }
+            }),
+            json!({
+                "executor": "inventory",
+                "headers": [["X-Request-Id", "abc"]],
+                "metadata": { "tenant": "acme" }
+            })
+        ]
+    );
+}
@@ -0,0 +1,47 @@
+mod common;
+
+use async_graphql::EmptySubscription;
+use common::{product, RecordingExecutor, TestExecutor};
+use futures_await_test::async_test;
+use graphql_gateway::{GraphQLPayload, RequestExtensions};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+
+#[async_test]
+async fn request_extensions() {
+    let extensions = Arc::new(Mutex::new(Vec::new()));
+    let product = RecordingExecutor::new(
+        TestExecutor::new(
+            "product",
+            product::Query {},
+            product::Mutation {},
+            EmptySubscription,
+        ),
+        extensions.clone(),
+        |call| Some(call.data?.get::<RequestExtensions>()?.0.clone()),
+    );
+
+    let gateway = common::gateway().await.executor(product);
+
+    let payload: GraphQLPayload =
+        serde_json::from_str(r#"{ "query": "query { products { name } }" }"#).unwrap();
+
+    assert!(payload.extensions.is_none());
+    payload.to_query_builder().execute(&gateway).await.unwrap();
+    assert!(extensions.lock().unwrap().is_empty());
+
+    let payload: GraphQLPayload = serde_json::from_str(
+        r#"{
+            "query": "query { products { name } }",
+            "extensions": { "persistedQuery": { "version": 1 } }
+        }"#,
+    )
+    .unwrap();
+
+    payload.to_query_builder().execute(&gateway).await.unwrap();
+
+    assert_eq!(
+        *extensions.lock().unwrap(),
+        vec![json!({ "persistedQuery": { "version": 1 } })]
+    );
+}
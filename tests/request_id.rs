@@ -0,0 +1,50 @@
+mod common;
+
+use async_graphql::EmptySubscription;
+use common::{product, RecordingExecutor, TestExecutor};
+use futures_await_test::async_test;
+use graphql_gateway::{QueryBuilder, RequestId};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+
+#[async_test]
+async fn request_id() {
+    let request_ids = Arc::new(Mutex::new(Vec::new()));
+    let product = RecordingExecutor::new(
+        TestExecutor::new(
+            "product",
+            product::Query {},
+            product::Mutation {},
+            EmptySubscription,
+        ),
+        request_ids.clone(),
+        |call| call.data?.get::<RequestId>().cloned(),
+    );
+
+    let gateway = common::gateway().await.executor(product);
+    let query = QueryBuilder::new("query { products { name } }");
+    let response = query.execute_response(&gateway).await;
+    let request_id = response.extensions["requestId"]
+        .as_str()
+        .unwrap()
+        .to_owned();
+
+    assert_eq!(request_id.len(), 32);
+    assert_eq!(
+        *request_ids.lock().unwrap(),
+        vec![RequestId::new(request_id.clone())]
+    );
+
+    let response = query.execute_response(&gateway).await;
+
+    assert_ne!(response.extensions["requestId"], json!(request_id));
+
+    let query = QueryBuilder::new("query { products { unknown } }").data(RequestId::new("abc"));
+    let response = serde_json::to_value(query.execute_response(&gateway).await).unwrap();
+
+    assert_eq!(response["extensions"]["requestId"], json!("abc"));
+    assert_eq!(
+        response["errors"][0]["extensions"]["requestId"],
+        json!("abc")
+    );
+}
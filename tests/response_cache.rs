@@ -0,0 +1,174 @@
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription};
+use common::{inventory, CountingExecutor, TestExecutor};
+use futures_await_test::async_test;
+use graphql_gateway::{
+    Authorizer, Data, HeaderPolicy, LruResponseCache, QueryBuilder, ResponseCache, ResponseCacheKey,
+};
+use serde_json::json;
+use std::time::Duration;
+
+struct AllowAll;
+
+impl Authorizer for AllowAll {
+    fn is_authorized(&self, _data: Option<&Data>, _object_type: &str, _field: &str) -> bool {
+        true
+    }
+}
+
+#[async_test]
+async fn response_cache() {
+    let source = r#"
+        query {
+            first: products {
+                name
+                inStock
+            }
+            second: products {
+                name
+                inStock
+            }
+        }
+    "#;
+
+    let inventory = CountingExecutor::new(TestExecutor::new(
+        "inventory",
+        inventory::Query {},
+        EmptyMutation,
+        EmptySubscription,
+    ));
+
+    let gateway = common::gateway().await.executor(inventory.clone());
+    let products = json!([
+        { "name": "Product 1", "inStock": true },
+        { "name": "Product 2", "inStock": false }
+    ]);
+    let expected = json!({ "first": products, "second": products });
+
+    let query = QueryBuilder::new(source);
+
+    assert_eq!(query.execute(&gateway).await.unwrap(), expected);
+    assert_eq!(inventory.count(), 1);
+
+    let query = QueryBuilder::new(source).response_cache(false);
+
+    assert_eq!(query.execute(&gateway).await.unwrap(), expected);
+    assert_eq!(inventory.count(), 3);
+}
+
+#[async_test]
+async fn shared_response_cache() {
+    let source = "{ products { name inStock } }";
+
+    let inventory = CountingExecutor::new(TestExecutor::new(
+        "inventory",
+        inventory::Query {},
+        EmptyMutation,
+        EmptySubscription,
+    ));
+
+    let gateway = common::gateway()
+        .await
+        .executor(inventory.clone())
+        .response_cache(LruResponseCache::new(16), Duration::from_secs(60));
+
+    let expected = json!({
+        "products": [
+            { "name": "Product 1", "inStock": true },
+            { "name": "Product 2", "inStock": false }
+        ]
+    });
+
+    for _ in 0..3 {
+        assert_eq!(
+            QueryBuilder::new(source).execute(&gateway).await.unwrap(),
+            expected
+        );
+    }
+
+    assert_eq!(inventory.count(), 1);
+
+    let gateway = gateway.response_cache_ttl("inventory", Duration::from_secs(0));
+
+    QueryBuilder::new(source).execute(&gateway).await.unwrap();
+
+    assert_eq!(inventory.count(), 2);
+
+    let cache = LruResponseCache::new(2);
+    let key = |query: &str| ResponseCacheKey {
+        executor: "inventory".to_owned(),
+        query: query.to_owned(),
+        variables: String::new(),
+    };
+
+    cache.insert(key("a"), json!(1), Duration::from_secs(60));
+    cache.insert(key("b"), json!(2), Duration::from_secs(60));
+    assert_eq!(cache.get(&key("a")), Some(json!(1)));
+
+    cache.insert(key("c"), json!(3), Duration::from_secs(60));
+    assert_eq!(cache.get(&key("b")), None);
+    assert_eq!(cache.get(&key("a")), Some(json!(1)));
+
+    cache.insert(key("d"), json!(4), Duration::from_secs(0));
+    assert_eq!(cache.get(&key("d")), None);
+    assert_eq!(cache.len(), 2);
+}
+
+#[async_test]
+async fn shared_response_cache_string_literals() {
+    let gateway = common::gateway()
+        .await
+        .response_cache(LruResponseCache::new(16), Duration::from_secs(60));
+
+    for name in &["a  b", "a b"] {
+        let query = QueryBuilder::new(format!(r#"{{ viewer {{ sayHello(name: "{}") }} }}"#, name));
+
+        assert_eq!(
+            query.execute(&gateway).await.unwrap(),
+            json!({ "viewer": { "sayHello": format!("Hello, {}", name) } })
+        );
+    }
+}
+
+#[async_test]
+async fn shared_response_cache_per_user() {
+    let source = "{ products { name inStock } }";
+
+    let inventory = CountingExecutor::new(TestExecutor::new(
+        "inventory",
+        inventory::Query {},
+        EmptyMutation,
+        EmptySubscription,
+    ));
+
+    let gateway = common::gateway()
+        .await
+        .executor(inventory.clone())
+        .response_cache(LruResponseCache::new(16), Duration::from_secs(60));
+
+    let authorized = gateway.clone().authorizer(AllowAll);
+
+    QueryBuilder::new(source)
+        .execute(&authorized)
+        .await
+        .unwrap();
+    QueryBuilder::new(source)
+        .execute(&authorized)
+        .await
+        .unwrap();
+    assert_eq!(inventory.count(), 2);
+
+    let propagating =
+        gateway.executor_header_policy("inventory", HeaderPolicy::new().allow("authorization"));
+
+    QueryBuilder::new(source)
+        .execute(&propagating)
+        .await
+        .unwrap();
+    QueryBuilder::new(source)
+        .execute(&propagating)
+        .await
+        .unwrap();
+    assert_eq!(inventory.count(), 4);
+}
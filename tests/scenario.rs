@@ -0,0 +1,20 @@
+use futures_await_test::async_test;
+use graphql_gateway::Scenario;
+use std::fs;
+
+#[async_test]
+async fn scenarios() {
+    let mut paths = fs::read_dir("tests/scenarios")
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect::<Vec<_>>();
+
+    paths.sort();
+
+    for path in paths {
+        let scenario = Scenario::load(&path).unwrap();
+        let outcome = scenario.check().await;
+
+        assert!(outcome.is_ok(), "{}", outcome.unwrap_err());
+    }
+}
@@ -0,0 +1,120 @@
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription};
+use async_trait::async_trait;
+use common::{account, inventory, product, review, TestExecutor};
+use futures_await_test::async_test;
+use graphql_gateway::{Data, Executor, Gateway, QueryBuilder, RegistrySource};
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+#[derive(Clone)]
+struct OfflineExecutor(Box<dyn Executor>, Arc<AtomicBool>);
+
+#[async_trait]
+impl Executor for OfflineExecutor {
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    async fn execute(
+        &self,
+        data: Option<&Data>,
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<Value>,
+    ) -> Result<Value, String> {
+        if !self.1.load(Ordering::SeqCst) {
+            return Err("Connection refused".to_owned());
+        }
+
+        self.0.execute(data, query, operation_name, variables).await
+    }
+}
+
+fn executors() -> Vec<Box<dyn Executor>> {
+    vec![
+        Box::new(TestExecutor::new(
+            "account",
+            account::Query {},
+            account::Mutation {},
+            EmptySubscription,
+        )),
+        Box::new(TestExecutor::new(
+            "inventory",
+            inventory::Query {},
+            EmptyMutation,
+            EmptySubscription,
+        )),
+        Box::new(TestExecutor::new(
+            "product",
+            product::Query {},
+            product::Mutation {},
+            EmptySubscription,
+        )),
+        Box::new(TestExecutor::new(
+            "review",
+            review::Query {},
+            EmptyMutation,
+            EmptySubscription,
+        )),
+    ]
+}
+
+#[async_test]
+async fn registry_schema_source() {
+    let mut registry = Map::new();
+
+    for executor in executors().into_iter().filter(|e| e.name() != "review") {
+        let (name, schema) = executor.introspect(None).await.unwrap();
+        registry.insert(name, serde_json::to_value(schema).unwrap());
+    }
+
+    let path = std::env::temp_dir().join(format!("registry-{}.json", std::process::id()));
+    fs::write(&path, Value::Object(registry).to_string()).unwrap();
+
+    let online = Arc::new(AtomicBool::new(false));
+    let mut gateway = Gateway::default().schema_source(RegistrySource::file(&path));
+
+    for executor in executors() {
+        gateway = gateway.executor(OfflineExecutor(executor, online.clone()));
+    }
+
+    let gateway = gateway.build().await.unwrap();
+
+    assert_eq!(
+        gateway
+            .executors_info()
+            .iter()
+            .map(|info| (info.name.as_str(), info.error.as_deref()))
+            .collect::<Vec<_>>(),
+        vec![
+            ("account", None),
+            ("inventory", None),
+            ("product", None),
+            (
+                "review",
+                Some("Executor \"review\" is not in the schema registry.")
+            ),
+        ]
+    );
+
+    online.store(true, Ordering::SeqCst);
+
+    assert_eq!(
+        QueryBuilder::new("{ products { name inStock } }")
+            .execute(&gateway)
+            .await
+            .unwrap(),
+        json!({
+            "products": [
+                { "name": "Product 1", "inStock": true },
+                { "name": "Product 2", "inStock": false }
+            ]
+        })
+    );
+
+    fs::remove_file(&path).unwrap();
+}
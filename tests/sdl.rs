@@ -0,0 +1,327 @@
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription};
+use async_trait::async_trait;
+use common::{inventory, TestExecutor};
+use futures_await_test::async_test;
+use graphql_gateway::{Data, Executor, Gateway, QueryBuilder, Schema, TypeKind};
+use serde_json::{json, Value};
+
+const INVENTORY_SDL: &str = r#"
+    interface Node {
+        id: ID!
+    }
+
+    type Product implements Node {
+        id: ID!
+        inStock: Boolean!
+    }
+
+    type Query {
+        node(id: ID!): Node
+        nodes(ids: [ID!]!): [Node]!
+    }
+
+    extend type Query {
+        restock(limit: Int = 10): Boolean @deprecated
+    }
+"#;
+
+#[derive(Clone)]
+struct NoIntrospectionExecutor(Box<dyn Executor>);
+
+#[async_trait]
+impl Executor for NoIntrospectionExecutor {
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    async fn execute(
+        &self,
+        data: Option<&Data>,
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<Value>,
+    ) -> Result<Value, String> {
+        if operation_name.as_deref() == Some("IntrospectionQuery") {
+            return Err("Introspection is disabled.".to_owned());
+        }
+
+        self.0.execute(data, query, operation_name, variables).await
+    }
+}
+
+#[derive(Clone)]
+struct CatalogExecutor;
+
+#[async_trait]
+impl Executor for CatalogExecutor {
+    fn name(&self) -> &str {
+        "catalog"
+    }
+
+    async fn execute(
+        &self,
+        _data: Option<&Data>,
+        _query: String,
+        _operation_name: Option<String>,
+        _variables: Option<Value>,
+    ) -> Result<Value, String> {
+        let decimal = json!({ "kind": "SCALAR", "name": "Decimal", "ofType": null });
+
+        Ok(json!({
+            "data": {
+                "__schema": {
+                    "description": null,
+                    "queryType": { "kind": "OBJECT", "name": "Query" },
+                    "mutationType": null,
+                    "subscriptionType": null,
+                    "directives": [
+                        {
+                            "name": "skip",
+                            "description": null,
+                            "locations": ["FIELD", "FRAGMENT_SPREAD", "INLINE_FRAGMENT"],
+                            "args": [{
+                                "name": "if",
+                                "description": null,
+                                "type": {
+                                    "kind": "NON_NULL",
+                                    "name": null,
+                                    "ofType": { "kind": "SCALAR", "name": "Boolean", "ofType": null }
+                                },
+                                "defaultValue": null
+                            }]
+                        },
+                        {
+                            "name": "cacheControl",
+                            "description": "Caches the field.",
+                            "locations": ["FIELD_DEFINITION", "OBJECT"],
+                            "args": [{
+                                "name": "maxAge",
+                                "description": null,
+                                "type": { "kind": "SCALAR", "name": "Int", "ofType": null },
+                                "defaultValue": null
+                            }]
+                        }
+                    ],
+                    "types": [
+                        {
+                            "kind": "OBJECT",
+                            "name": "Query",
+                            "description": "Catalog queries.",
+                            "interfaces": [],
+                            "possibleTypes": null,
+                            "enumValues": null,
+                            "inputFields": null,
+                            "ofType": null,
+                            "fields": [
+                                {
+                                    "name": "price",
+                                    "description": "Price of the product.",
+                                    "args": [],
+                                    "type": decimal,
+                                    "isDeprecated": false,
+                                    "deprecationReason": null
+                                },
+                                {
+                                    "name": "cost",
+                                    "description": null,
+                                    "args": [],
+                                    "type": decimal,
+                                    "isDeprecated": true,
+                                    "deprecationReason": "Use price instead."
+                                },
+                                {
+                                    "name": "status",
+                                    "description": null,
+                                    "args": [],
+                                    "type": { "kind": "ENUM", "name": "Status", "ofType": null },
+                                    "isDeprecated": false,
+                                    "deprecationReason": null
+                                }
+                            ]
+                        },
+                        {
+                            "kind": "ENUM",
+                            "name": "Status",
+                            "description": null,
+                            "interfaces": null,
+                            "possibleTypes": null,
+                            "fields": null,
+                            "inputFields": null,
+                            "ofType": null,
+                            "enumValues": [
+                                { "name": "ACTIVE", "description": null, "isDeprecated": false, "deprecationReason": null },
+                                { "name": "LEGACY", "description": null, "isDeprecated": true, "deprecationReason": null }
+                            ]
+                        },
+                        {
+                            "kind": "SCALAR",
+                            "name": "Decimal",
+                            "description": "Arbitrary precision decimal.",
+                            "interfaces": null,
+                            "possibleTypes": null,
+                            "fields": null,
+                            "enumValues": null,
+                            "inputFields": null,
+                            "ofType": null
+                        },
+                        {
+                            "kind": "SCALAR",
+                            "name": "String",
+                            "description": null,
+                            "interfaces": null,
+                            "possibleTypes": null,
+                            "fields": null,
+                            "enumValues": null,
+                            "inputFields": null,
+                            "ofType": null
+                        }
+                    ]
+                }
+            }
+        }))
+    }
+}
+
+#[async_test]
+async fn to_sdl() {
+    let gateway = Gateway::default()
+        .executor(CatalogExecutor)
+        .build()
+        .await
+        .unwrap();
+    let sdl = gateway.to_sdl();
+
+    assert!(graphql_parser::parse_schema::<String>(&sdl).is_ok());
+    assert_eq!(
+        sdl,
+        r#""Caches the field."
+directive @cacheControl(maxAge: Int) on FIELD_DEFINITION | OBJECT
+
+"Catalog queries."
+type Query {
+  "Price of the product."
+  price: Decimal
+  cost: Decimal @deprecated(reason: "Use price instead.")
+  status: Status
+}
+
+enum Status {
+  ACTIVE
+  LEGACY @deprecated
+}
+
+"Arbitrary precision decimal."
+scalar Decimal
+
+schema {
+  query: Query
+}
+"#
+    );
+    assert!(!gateway.to_string().contains("scalar Decimal"));
+}
+
+#[test]
+fn schema_from_sdl() {
+    let schema = Schema::from_sdl(INVENTORY_SDL).unwrap();
+    let query = schema.types.iter().find(|t| t.name() == "Query").unwrap();
+    let restock = query
+        .fields
+        .iter()
+        .flatten()
+        .find(|field| field.name == "restock")
+        .unwrap();
+    let node = schema.types.iter().find(|t| t.name() == "Node").unwrap();
+
+    assert_eq!(schema.query_type.unwrap().name(), "Query");
+    assert!(schema.mutation_type.is_none());
+    assert!(restock.is_deprecated);
+    assert_eq!(
+        restock.deprecation_reason.as_deref(),
+        Some("No longer supported")
+    );
+    assert_eq!(restock.args[0].default_value.as_deref(), Some("10"));
+    assert_eq!(
+        node.possible_types
+            .iter()
+            .flatten()
+            .map(|t| (t.kind.clone(), t.name()))
+            .collect::<Vec<_>>(),
+        vec![(TypeKind::Object, "Product")]
+    );
+    assert!(schema.types.iter().any(|t| t.name() == "__Schema"));
+    assert_eq!(
+        Schema::from_sdl("type Query { product: Product }").unwrap_err(),
+        "Unknown type \"Product\"."
+    );
+}
+
+#[async_test]
+async fn executor_with_sdl() {
+    let inventory = NoIntrospectionExecutor(Box::new(TestExecutor::new(
+        "inventory",
+        inventory::Query {},
+        EmptyMutation,
+        EmptySubscription,
+    )));
+    let gateway = common::gateway()
+        .await
+        .executor_with_sdl(inventory.clone(), INVENTORY_SDL)
+        .build()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        QueryBuilder::new("{ products { name inStock } }")
+            .execute(&gateway)
+            .await
+            .unwrap(),
+        json!({
+            "products": [
+                { "name": "Product 1", "inStock": true },
+                { "name": "Product 2", "inStock": false }
+            ]
+        })
+    );
+
+    let gateway = Gateway::default()
+        .executor_with_sdl(inventory, INVENTORY_SDL)
+        .build()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        QueryBuilder::new("{ __schema { queryType { name } } }")
+            .execute(&gateway)
+            .await
+            .unwrap(),
+        json!({ "__schema": { "queryType": { "name": "Query" } } })
+    );
+}
+
+#[async_test]
+async fn to_supergraph_sdl() {
+    let gateway = common::gateway().await.build().await.unwrap();
+    let sdl = gateway.to_supergraph_sdl();
+
+    assert!(sdl.starts_with("directive @join__graph(name: String!, url: String!) on ENUM_VALUE"));
+    assert!(sdl.contains("type Product implements Node @join__type(graph: INVENTORY, key: \"id\") @join__type(graph: PRODUCT, key: \"id\") @join__type(graph: REVIEW, key: \"id\") {"));
+    assert!(sdl.contains("  id: ID!\n"));
+    assert!(sdl.contains("  inStock: Boolean! @join__field(graph: INVENTORY)\n"));
+    assert!(sdl.contains("  reviews: [Review!]! @join__field(graph: REVIEW)\n"));
+    assert!(sdl.contains("type Review implements Node @join__type(graph: REVIEW, key: \"id\") {"));
+    assert!(sdl.contains("  author: User\n"));
+    assert!(sdl.contains("interface Node @join__type(graph: ACCOUNT) @join__type(graph: INVENTORY) @join__type(graph: PRODUCT) @join__type(graph: REVIEW) {\n  id: ID!\n}"));
+    assert!(sdl.contains("  viewer: User @join__field(graph: ACCOUNT)\n"));
+    assert!(sdl.contains(
+        r#"enum join__Graph {
+  ACCOUNT @join__graph(name: "account", url: "")
+  INVENTORY @join__graph(name: "inventory", url: "")
+  PRODUCT @join__graph(name: "product", url: "")
+  REVIEW @join__graph(name: "review", url: "")
+}"#
+    ));
+    assert!(!gateway.to_sdl().contains("join__"));
+}
@@ -0,0 +1,166 @@
+mod common;
+
+use axum::body::{to_bytes, Body};
+use axum::http::{header, Request, StatusCode};
+use futures_await_test::async_test;
+use graphql_gateway::{graphiql, graphiql_source, router, GatewayHandle, MULTIPART_CONTENT_TYPE};
+use serde_json::{json, Value};
+use tower::ServiceExt;
+
+async fn send(app: axum::Router, request: Request<Body>) -> (StatusCode, Value) {
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+fn post(body: Value) -> Request<Body> {
+    Request::post("/graphql")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+#[async_test]
+async fn server() {
+    let app = router("/graphql", GatewayHandle::new(common::gateway().await));
+
+    let (status, body) = send(
+        app.clone(),
+        Request::get("/graphql?query=query%20Products(%24id%3A%20ID!)%20%7B%20node(id%3A%20%24id)%20%7B%20...%20on%20Product%20%7B%20id%20%7D%20%7D%20products%20%7B%20name%20%7D%20%7D&operationName=Products&variables=%7B%22id%22%3A%22UHJvZHVjdDow%22%7D")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body["data"],
+        json!({
+            "node": { "id": "UHJvZHVjdDow" },
+            "products": [{ "name": "Product 1" }, { "name": "Product 2" }]
+        })
+    );
+
+    let (status, body) = send(
+        app.clone(),
+        Request::get("/graphql?query=query%20Product(%24id%3A%20ID!)%20%7B%20node(id%3A%20%24id)%20%7B%20...%20on%20Product%20%7B%20id%20%7D%20%7D%20%7D&operationName=Product&variables=%7B%22id%22%3A%22UHJvZHVjdDow%22%7D")
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"], json!({ "node": { "id": "UHJvZHVjdDow" } }));
+
+    let (status, _) = send(
+        app.clone(),
+        Request::get(
+            "/graphql?query=mutation%20%7B%20addProduct(id%3A%20%221%22)%20%7B%20id%20%7D%20%7D",
+        )
+        .body(Body::empty())
+        .unwrap(),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::METHOD_NOT_ALLOWED);
+
+    let (status, body) = send(
+        app.clone(),
+        post(json!({ "query": "query { users { username } }" })),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body["data"],
+        json!({ "users": [{ "username": null }, { "username": "albert" }] })
+    );
+
+    let (status, body) = send(
+        app,
+        post(json!([
+            { "query": "query { users { username } }" },
+            { "query": "query { products { name } }" }
+        ])),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body[0]["data"]["users"][1], json!({ "username": "albert" }));
+    assert_eq!(
+        body[1]["data"]["products"][0],
+        json!({ "name": "Product 1" })
+    );
+}
+
+#[async_test]
+async fn server_graphiql() {
+    let app = router("/graphql", GatewayHandle::new(common::gateway().await))
+        .merge(graphiql("/", "/graphql"));
+
+    let response = app
+        .oneshot(Request::get("/").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers()[header::CONTENT_TYPE]
+        .to_str()
+        .unwrap()
+        .starts_with("text/html"));
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+
+    assert!(body.contains(r#"GraphiQL.createFetcher({ url: "/graphql" })"#));
+    assert!(graphiql_source("/</script>").contains(r#"url: "/<\/script>""#));
+}
+
+#[async_test]
+async fn server_multipart() {
+    let app = router("/graphql", GatewayHandle::new(common::gateway().await));
+
+    let response = app
+        .oneshot(
+            Request::post("/graphql")
+                .header(header::CONTENT_TYPE, "application/json")
+                .header(header::ACCEPT, "multipart/mixed")
+                .body(Body::from(
+                    json!({
+                        "query": "query { users @stream(initialCount: 1) { username } }"
+                    })
+                    .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[header::CONTENT_TYPE],
+        MULTIPART_CONTENT_TYPE
+    );
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    let parts = body
+        .trim_end_matches("\r\n-----\r\n")
+        .split("\r\n---\r\n")
+        .skip(1)
+        .map(|part| serde_json::from_str(part.split("\r\n\r\n").nth(1).unwrap()).unwrap())
+        .collect::<Vec<Value>>();
+
+    assert_eq!(parts.len(), 2);
+    assert_eq!(parts[0]["data"], json!({ "users": [{ "username": null }] }));
+    assert_eq!(parts[0]["hasNext"], json!(true));
+    assert_eq!(
+        parts[1],
+        json!({
+            "incremental": [{ "items": [{ "username": "albert" }], "path": ["users", 1] }],
+            "hasNext": false
+        })
+    );
+}
@@ -0,0 +1,115 @@
+mod common;
+
+use futures_await_test::async_test;
+use graphql_gateway::{
+    operation_hash, operation_signature, sha256_hex, stats_report_key, OperationUsage, QueryBuilder,
+};
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn signatures() {
+    let cases = vec![
+        ("{ user { name } }", "{user{name}}"),
+        ("query { user { name } }", "{user{name}}"),
+        ("query OpName { user { name } }", "query OpName{user{name}}"),
+        (
+            r#"query OpName { user { name(apple: [[10]], cat: ENUM_VALUE, bag: {input: "value"}) } }"#,
+            "query OpName{user{name(apple:[],bag:{},cat:ENUM_VALUE)}}",
+        ),
+        (
+            "query OpName($c: Int!, $a: [[Boolean!]!], $b: EnumType) { user { name(apple: $a, cat: $c, bag: $b) } }",
+            "query OpName($a:[[Boolean!]!],$b:EnumType,$c:Int!){user{name(apple:$a,bag:$b,cat:$c)}}",
+        ),
+        (
+            r#"
+            { user { name ...Bar } }
+            fragment Bar on User { asd }
+            fragment Baz on User { jkl }
+            "#,
+            "fragment Bar on User{asd}{user{name...Bar}}",
+        ),
+        (
+            r#"
+            query Foo($b: Int, $a: Boolean) {
+                user(name: "hello", age: 5) {
+                    ...Bar
+                    ... on User {
+                        hello
+                        bee
+                    }
+                    tz
+                    aliased: name
+                }
+            }
+
+            fragment Baz on User { asd }
+            fragment Bar on User { age @skip(if: $a) ...Nested }
+            fragment Nested on User { blah }
+            "#,
+            r#"fragment Bar on User{age@skip(if:$a)...Nested}fragment Nested on User{blah}query Foo($a:Boolean,$b:Int){user(age:0,name:""){name tz...Bar...on User{bee hello}}}"#,
+        ),
+        (
+            "{ ledger(amount: 12345678901234567890.123456789) }",
+            "{ledger(amount:0)}",
+        ),
+    ];
+
+    for (query, signature) in cases {
+        assert_eq!(operation_signature(query, None).unwrap(), signature);
+    }
+}
+
+#[test]
+fn signature_hashes() {
+    let query = "query Products { products { b: name inStock } } query Other { a }";
+
+    assert_eq!(
+        stats_report_key(query, Some("Products")).unwrap(),
+        "# Products\nquery Products{products{inStock name}}"
+    );
+    assert_eq!(
+        stats_report_key("{ products { name } }", None).unwrap(),
+        "# -\n{products{name}}"
+    );
+    assert_eq!(
+        operation_hash(query, Some("Products")).unwrap(),
+        sha256_hex("query Products{products{inStock name}}")
+    );
+    assert_eq!(
+        sha256_hex("{__typename}"),
+        "ecf4edb46db40b5132295c0291d62fb65d6759a9eedfa4d5d612dd5ec54a6b38"
+    );
+    assert!(operation_signature(query, None).is_err());
+}
+
+#[async_test]
+async fn usage_reporter() {
+    let reports = Arc::new(Mutex::new(Vec::<OperationUsage>::new()));
+    let recorded = reports.clone();
+    let gateway = common::gateway()
+        .await
+        .usage_reporter(move |usage: &OperationUsage| recorded.lock().unwrap().push(usage.clone()));
+
+    QueryBuilder::new("query Products { products { name } }")
+        .execute(&gateway)
+        .await
+        .unwrap();
+    QueryBuilder::new("{ products { unknown } }")
+        .execute(&gateway)
+        .await
+        .unwrap_err();
+
+    let reports = reports.lock().unwrap();
+
+    assert_eq!(
+        reports[0].stats_report_key,
+        "# Products\nquery Products{products{name}}"
+    );
+    assert_eq!(
+        reports[0].operation_hash,
+        sha256_hex("query Products{products{name}}")
+    );
+    assert!(!reports[0].has_errors);
+    assert_eq!(reports[1].stats_report_key, "# -\n{products{unknown}}");
+    assert!(reports[1].has_errors);
+}
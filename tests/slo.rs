@@ -0,0 +1,100 @@
+mod common;
+
+use futures_await_test::async_test;
+use graphql_gateway::{QueryBuilder, Slo, SloTarget, SloViolation};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[async_test]
+async fn slo() {
+    let violations = Arc::new(Mutex::new(Vec::new()));
+    let alerts = violations.clone();
+
+    let gateway = common::gateway()
+        .await
+        .slo(
+            SloTarget::Operation("Products".to_owned()),
+            Slo {
+                p95_latency: Some(Duration::from_nanos(1)),
+                max_size: None,
+            },
+        )
+        .slo(
+            SloTarget::RootField("users".to_owned()),
+            Slo {
+                p95_latency: None,
+                max_size: Some(32),
+            },
+        )
+        .slo(
+            SloTarget::RootField("viewer".to_owned()),
+            Slo {
+                p95_latency: None,
+                max_size: Some(1024),
+            },
+        )
+        .slo_alert(move |violation: &SloViolation| {
+            alerts.lock().unwrap().push(violation.clone());
+        });
+
+    let products =
+        QueryBuilder::new("query Products { products { name } }").operation_name("Products");
+
+    products.execute(&gateway).await.unwrap();
+    products.execute(&gateway).await.unwrap();
+
+    {
+        let violations = violations.lock().unwrap();
+
+        assert_eq!(violations.len(), 1);
+
+        match &violations[0] {
+            SloViolation::Latency {
+                target, threshold, ..
+            } => {
+                assert_eq!(target, &SloTarget::Operation("Products".to_owned()));
+                assert_eq!(threshold, &Duration::from_nanos(1));
+            }
+            violation => panic!("Unexpected violation {:?}", violation),
+        }
+    }
+
+    QueryBuilder::new("{ all: users { email username } viewer { email } }")
+        .execute(&gateway)
+        .await
+        .unwrap();
+
+    {
+        let violations = violations.lock().unwrap();
+
+        assert_eq!(violations.len(), 2);
+
+        match &violations[1] {
+            SloViolation::Size {
+                target,
+                size,
+                threshold,
+            } => {
+                assert_eq!(target, &SloTarget::RootField("users".to_owned()));
+                assert!(size > threshold);
+                assert_eq!(threshold, &32);
+            }
+            violation => panic!("Unexpected violation {:?}", violation),
+        }
+    }
+
+    let status = gateway.slo_status();
+
+    assert_eq!(status.len(), 3);
+    assert_eq!(
+        status[0].target,
+        SloTarget::Operation("Products".to_owned())
+    );
+    assert_eq!(status[0].samples, 2);
+    assert!(status[0].violating);
+    assert!(status[1].violating);
+    assert_eq!(status[2].target, SloTarget::RootField("viewer".to_owned()));
+    assert_eq!(status[2].samples, 1);
+    assert!(status[2].last_size.is_some());
+    assert!(!status[2].violating);
+}
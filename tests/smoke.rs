@@ -0,0 +1,25 @@
+mod common;
+
+use futures_await_test::async_test;
+use graphql_gateway::QueryBuilder;
+
+#[async_test]
+async fn generate_smoke_queries() {
+    let gateway = common::gateway().await;
+    let queries = gateway.generate_smoke_queries(2, 2);
+
+    assert_eq!(
+        queries,
+        vec![
+            "query SmokeProducts { products { id inStock name reviews { body id } } }",
+            "query SmokeUsers { users { email id reviews { body id } role username } }",
+        ]
+    );
+
+    for query in queries {
+        QueryBuilder::new(query).execute(&gateway).await.unwrap();
+    }
+
+    assert_eq!(gateway.generate_smoke_queries(0, 10), Vec::<String>::new());
+    assert_eq!(gateway.generate_smoke_queries(1, 10).len(), 3);
+}
@@ -0,0 +1,140 @@
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription};
+use async_trait::async_trait;
+use common::{account, inventory, inventory_updated, product, review, TestExecutor};
+use futures_await_test::async_test;
+use graphql_gateway::{Data, Executor, Gateway, GatewayError, GatewayHandle, QueryBuilder};
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+#[derive(Clone)]
+struct DeployingExecutor(Arc<AtomicBool>);
+
+#[async_trait]
+impl Executor for DeployingExecutor {
+    fn name(&self) -> &str {
+        "inventory"
+    }
+
+    async fn execute(
+        &self,
+        data: Option<&Data>,
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<Value>,
+    ) -> Result<Value, String> {
+        if self.0.load(Ordering::SeqCst) {
+            TestExecutor::new(
+                "inventory",
+                inventory_updated::Query {},
+                EmptyMutation,
+                EmptySubscription,
+            )
+            .execute(data, query, operation_name, variables)
+            .await
+        } else {
+            TestExecutor::new(
+                "inventory",
+                inventory::Query {},
+                EmptyMutation,
+                EmptySubscription,
+            )
+            .execute(data, query, operation_name, variables)
+            .await
+        }
+    }
+}
+
+async fn gateway(deployed: Arc<AtomicBool>) -> Gateway<'static> {
+    Gateway::default()
+        .executor(TestExecutor::new(
+            "account",
+            account::Query {},
+            account::Mutation {},
+            EmptySubscription,
+        ))
+        .executor(DeployingExecutor(deployed))
+        .executor(TestExecutor::new(
+            "product",
+            product::Query {},
+            product::Mutation {},
+            EmptySubscription,
+        ))
+        .executor(TestExecutor::new(
+            "review",
+            review::Query {},
+            EmptyMutation,
+            EmptySubscription,
+        ))
+        .schema_history(3)
+        .build()
+        .await
+        .unwrap()
+}
+
+#[async_test]
+async fn schema_rollback() {
+    let deployed = Arc::new(AtomicBool::new(false));
+    let mut gateway = gateway(deployed.clone()).await;
+    let initial_hash = gateway.schema_snapshots()[0].hash.clone();
+
+    deployed.store(true, Ordering::SeqCst);
+    gateway.pull("inventory").await.unwrap();
+    gateway.pull("inventory").await.unwrap();
+
+    assert_eq!(gateway.schema_snapshots().len(), 2);
+    assert_eq!(
+        QueryBuilder::new("{ products { name delivered } }")
+            .execute(&gateway)
+            .await
+            .unwrap(),
+        json!({
+            "products": [
+                { "name": "Product 1", "delivered": true },
+                { "name": "Product 2", "delivered": false }
+            ]
+        })
+    );
+
+    gateway.rollback(1).unwrap();
+    gateway.pull("inventory").await.unwrap();
+
+    assert_eq!(
+        gateway
+            .schema_snapshots()
+            .iter()
+            .map(|snapshot| snapshot.hash.as_str())
+            .collect::<Vec<_>>(),
+        vec![initial_hash.as_str()]
+    );
+    assert!(gateway.to_string().contains("inStock"));
+    assert!(!gateway.to_string().contains("delivered"));
+
+    match gateway.rollback(1).unwrap_err() {
+        GatewayError::UnknownSnapshot(snapshot) => assert_eq!(snapshot, "1"),
+        _ => panic!("Expected an unknown snapshot error"),
+    }
+}
+
+#[async_test]
+async fn handle_rollback_to() {
+    let deployed = Arc::new(AtomicBool::new(false));
+    let handle = GatewayHandle::new(gateway(deployed.clone()).await);
+    let initial_hash = handle.load().schema_snapshots()[0].hash.clone();
+
+    deployed.store(true, Ordering::SeqCst);
+
+    let changes = handle.poll().await;
+    assert_eq!(changes.len(), 1);
+
+    let rollback = handle.rollback_to(&initial_hash).unwrap();
+
+    assert_eq!(rollback.len(), 1);
+    assert_eq!(rollback[0].executor, "inventory");
+    assert_eq!(rollback[0].previous_hash, changes[0].hash);
+    assert_eq!(rollback[0].hash, changes[0].previous_hash);
+    assert!(handle.poll().await.is_empty());
+    assert!(handle.load().to_string().contains("inStock"));
+}
@@ -0,0 +1,45 @@
+mod common;
+
+use futures_await_test::async_test;
+use graphql_gateway::{ExecutorHealth, QueryBuilder};
+use serde_json::json;
+
+#[async_test]
+async fn static_fields() {
+    let gateway = common::gateway().await;
+
+    assert!(QueryBuilder::new("{ _gatewayVersion }")
+        .execute(&gateway)
+        .await
+        .is_err());
+
+    let gateway = gateway.static_fields(true).build().await.unwrap();
+    let res = QueryBuilder::new(
+        r#"
+            query {
+                version: _gatewayVersion
+                _schemaHash
+                _health
+                products {
+                    name
+                }
+            }
+        "#,
+    )
+    .execute(&gateway)
+    .await
+    .unwrap();
+
+    assert_eq!(res["version"], json!(env!("CARGO_PKG_VERSION")));
+    assert_eq!(res["_schemaHash"].as_str().map(str::len), Some(16));
+    assert_eq!(res["_health"], json!(ExecutorHealth::Healthy));
+    assert_eq!(res["products"][0], json!({ "name": "Product 1" }));
+    assert!(gateway.to_sdl().contains("_schemaHash: String!"));
+
+    let res = QueryBuilder::new("{ _schemaHash }")
+        .execute(&gateway)
+        .await
+        .unwrap();
+
+    assert_eq!(res["_schemaHash"].as_str().map(str::len), Some(16));
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use async_graphql::EmptySubscription;
+use async_trait::async_trait;
+use common::{product, TestExecutor};
+use futures_await_test::async_test;
+use futures_timer::Delay;
+use graphql_gateway::{Data, Executor, QueryBuilder};
+use serde_json::Value;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Clone)]
+struct FlakyExecutor(Box<dyn Executor>, Arc<AtomicUsize>);
+
+#[async_trait]
+impl Executor for FlakyExecutor {
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    async fn execute(
+        &self,
+        data: Option<&Data>,
+        query: String,
+        operation_name: Option<String>,
+        variables: Option<Value>,
+    ) -> Result<Value, String> {
+        Delay::new(Duration::from_millis(100)).await;
+
+        if self.1.fetch_add(1, Ordering::SeqCst) % 2 == 1 {
+            return Err("Connection refused".to_owned());
+        }
+
+        self.0.execute(data, query, operation_name, variables).await
+    }
+}
+
+#[async_test]
+async fn executor_stats() {
+    let product = TestExecutor::new(
+        "product",
+        product::Query {},
+        product::Mutation {},
+        EmptySubscription,
+    );
+    let gateway = common::gateway()
+        .await
+        .stats_window(4)
+        .executor(FlakyExecutor(
+            Box::new(product),
+            Arc::new(AtomicUsize::new(0)),
+        ));
+    let query = QueryBuilder::new("{ products { name } }");
+
+    assert_eq!(gateway.executor_stats("product"), None);
+
+    let probe = async {
+        Delay::new(Duration::from_millis(50)).await;
+        gateway.executor_stats("product")
+    };
+
+    let (result, stats) = futures::join!(query.execute(&gateway), probe);
+
+    assert!(result.is_ok());
+
+    let stats = stats.unwrap();
+    assert_eq!(stats.active_requests, 1);
+    assert_eq!(stats.samples, 0);
+    assert_eq!(stats.success_rate, None);
+
+    for _ in 0..5 {
+        let _ = query.execute(&gateway).await;
+    }
+
+    let stats = gateway.executor_stats("product").unwrap();
+    assert_eq!(stats.executor, "product");
+    assert_eq!(stats.active_requests, 0);
+    assert_eq!(stats.samples, 4);
+    assert_eq!(stats.success_rate, Some(0.5));
+    assert!(stats.p50_latency.unwrap() >= Duration::from_millis(100));
+    assert!(stats.p99_latency >= stats.p95_latency);
+    assert!(stats.p95_latency >= stats.p50_latency);
+
+    assert_eq!(
+        gateway
+            .executors_stats()
+            .iter()
+            .map(|stats| stats.executor.as_str())
+            .collect::<Vec<&str>>(),
+        vec!["product"]
+    );
+}
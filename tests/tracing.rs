@@ -0,0 +1,93 @@
+mod common;
+
+use futures_await_test::async_test;
+use graphql_gateway::QueryBuilder;
+use serde_json::{json, Map, Value};
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+#[derive(Clone, Default)]
+struct SpanRecorder(Arc<Mutex<Vec<Value>>>);
+
+struct FieldVisitor(Map<String, Value>);
+
+impl Visit for FieldVisitor {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_owned(), json!(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_owned(), json!(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0
+            .insert(field.name().to_owned(), json!(format!("{:?}", value)));
+    }
+}
+
+impl Subscriber for SpanRecorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut visitor = FieldVisitor(Map::new());
+        visitor
+            .0
+            .insert("name".to_owned(), json!(span.metadata().name()));
+        span.record(&mut visitor);
+
+        let mut spans = self.0.lock().unwrap();
+        spans.push(Value::Object(visitor.0));
+
+        Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, _event: &Event<'_>) {}
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+#[async_test]
+async fn executor_spans() {
+    let recorder = SpanRecorder::default();
+    let gateway = common::gateway().await;
+    let _guard = tracing::subscriber::set_default(recorder.clone());
+
+    QueryBuilder::new("query Products { products { name inStock } }")
+        .operation_name("Products")
+        .execute(&gateway)
+        .await
+        .unwrap();
+
+    let spans = recorder.0.lock().unwrap();
+
+    assert_eq!(spans[0]["name"], json!("query"));
+    assert_eq!(spans[0]["operation_name"], json!("Products"));
+    assert!(spans
+        .iter()
+        .any(|span| span["name"] == json!("resolve_executors")
+            && span["object_type"] == json!("Query")));
+
+    let executors = spans
+        .iter()
+        .filter(|span| span["name"] == json!("executor"))
+        .collect::<Vec<&Value>>();
+
+    assert_eq!(executors[0]["executor"], json!("product"));
+    assert_eq!(executors[1]["executor"], json!("inventory"));
+    assert!(executors
+        .iter()
+        .all(|span| span["operation_name"] == json!("Products")
+            && span["query_size"].as_u64().unwrap() > 0));
+}
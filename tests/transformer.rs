@@ -0,0 +1,123 @@
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription};
+use common::{inventory, RecordingExecutor, TestExecutor};
+use futures_await_test::async_test;
+use graphql_gateway::{Data, DocumentFormat, QueryBuilder};
+use graphql_parser::query::{
+    Definition, Document, Field, OperationDefinition, Selection, SelectionSet,
+};
+use graphql_parser::Pos;
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+
+fn selection_sets<'a, 'b>(
+    document: &'b mut Document<'a, String>,
+) -> Vec<&'b mut Vec<Selection<'a, String>>> {
+    document
+        .definitions
+        .iter_mut()
+        .filter_map(|definition| match definition {
+            Definition::Operation(OperationDefinition::Query(query)) => {
+                Some(&mut query.selection_set.items)
+            }
+            Definition::Fragment(fragment) => Some(&mut fragment.selection_set.items),
+            _ => None,
+        })
+        .collect()
+}
+
+fn drop_directives(selections: &mut [Selection<'_, String>]) {
+    for selection in selections {
+        match selection {
+            Selection::Field(field) => {
+                field.directives.clear();
+                drop_directives(&mut field.selection_set.items);
+            }
+            Selection::InlineFragment(fragment) => {
+                fragment.directives.clear();
+                drop_directives(&mut fragment.selection_set.items);
+            }
+            Selection::FragmentSpread(spread) => spread.directives.clear(),
+        }
+    }
+}
+
+fn request_typename(selections: &mut [Selection<'_, String>]) {
+    for selection in selections {
+        match selection {
+            Selection::Field(field) => request_typename(&mut field.selection_set.items),
+            Selection::InlineFragment(fragment) => {
+                fragment.selection_set.items.push(Selection::Field(Field {
+                    position: Pos::default(),
+                    alias: None,
+                    name: "__typename".to_owned(),
+                    arguments: vec![],
+                    directives: vec![],
+                    selection_set: SelectionSet {
+                        span: (Pos::default(), Pos::default()),
+                        items: vec![],
+                    },
+                }));
+            }
+            Selection::FragmentSpread(_) => {}
+        }
+    }
+}
+
+#[async_test]
+async fn selection_transformer() {
+    let query = QueryBuilder::new(
+        r#"
+            query {
+                products {
+                    name @include(if: true)
+                    inStock @include(if: true)
+                }
+            }
+        "#,
+    );
+
+    let queries = Arc::new(Mutex::new(Vec::new()));
+    let inventory = TestExecutor::new(
+        "inventory",
+        inventory::Query {},
+        EmptyMutation,
+        EmptySubscription,
+    );
+
+    let gateway = common::gateway()
+        .await
+        .executor(RecordingExecutor::queries(inventory, queries.clone()))
+        .document_format(DocumentFormat::Minified)
+        .selection_transformer(
+            "inventory",
+            |_: Option<&Data>, document: &mut Document<'_, String>| {
+                for selections in selection_sets(document) {
+                    drop_directives(selections);
+                }
+            },
+        )
+        .selection_transformer(
+            "inventory",
+            |_: Option<&Data>, document: &mut Document<'_, String>| {
+                for selections in selection_sets(document) {
+                    request_typename(selections);
+                }
+            },
+        );
+
+    assert_eq!(
+        query.execute(&gateway).await.unwrap(),
+        json!({
+            "products": [
+                { "name": "Product 1", "inStock": true },
+                { "name": "Product 2", "inStock": false }
+            ]
+        })
+    );
+    assert_eq!(
+        queries.lock().unwrap().as_slice(),
+        ["query NodeQuery($__gql_gateway_ids:[ID]!){nodes(ids:$__gql_gateway_ids){...on Product{id inStock __typename}}}"]
+    );
+}
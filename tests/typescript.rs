@@ -0,0 +1,43 @@
+mod common;
+
+use futures_await_test::async_test;
+
+#[async_test]
+async fn export_typescript() {
+    let gateway = common::gateway().await;
+    let typescript = gateway.export_typescript();
+
+    assert!(typescript.contains(
+        r#"export interface User {
+  __typename?: "User";
+  email: string | null;
+  id: string;
+  reviews: Array<Review>;
+  role: UserRole;
+  sayHello: string;
+  username: string | null;
+}
+"#
+    ));
+    assert!(typescript.contains(r#"export type UserRole = "ADMIN" | "STAFF" | "USER";"#));
+    assert!(typescript.contains(
+        r#"export interface UserSayHelloArgs {
+  name: string;
+}
+"#
+    ));
+    assert!(typescript.contains(
+        r#"export interface SignInInput {
+  email: string;
+  password: string;
+}
+"#
+    ));
+    assert!(typescript.contains(
+        r#"export interface Node {
+  id: string;
+}
+"#
+    ));
+    assert!(!typescript.contains("__Schema"));
+}
@@ -0,0 +1,149 @@
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription};
+use common::TestExecutor;
+use futures_await_test::async_test;
+use graphql_gateway::{PlanStepKind, QueryBuilder};
+use serde_json::json;
+
+mod search {
+    use async_graphql::ID;
+
+    #[derive(Clone)]
+    pub struct Product(usize);
+
+    #[async_graphql::Object]
+    impl Product {
+        #[field]
+        async fn id(&self) -> ID {
+            super::common::to_global_id("Product", self.0)
+        }
+    }
+
+    #[derive(Clone)]
+    pub struct User(usize);
+
+    #[async_graphql::Object]
+    impl User {
+        #[field]
+        async fn id(&self) -> ID {
+            super::common::to_global_id("User", self.0)
+        }
+    }
+
+    #[async_graphql::Interface(field(name = "id", type = "ID"))]
+    pub struct Node(Product, User);
+
+    #[async_graphql::Union]
+    pub struct SearchResult(Product, User);
+
+    pub struct Query;
+
+    #[async_graphql::Object]
+    impl Query {
+        #[field]
+        async fn node(&self, _id: ID) -> Option<Node> {
+            None
+        }
+
+        #[field]
+        async fn nodes(&self, ids: Vec<ID>) -> Vec<Option<Node>> {
+            ids.iter().map(|_| None).collect()
+        }
+
+        #[field]
+        async fn search(&self) -> Vec<SearchResult> {
+            vec![Product(1).into(), User(0).into(), Product(0).into()]
+        }
+    }
+}
+
+#[async_test]
+async fn union() {
+    let search = TestExecutor::new("search", search::Query, EmptyMutation, EmptySubscription);
+    let mut gateway = common::gateway().await.executor(search);
+
+    gateway.pull("search").await.unwrap();
+
+    let query = QueryBuilder::new(
+        r#"
+            query {
+                search {
+                    ... on Product {
+                        name
+                        inStock
+                    }
+                    ...UserFields
+                }
+            }
+
+            fragment UserFields on User {
+                email
+            }
+        "#,
+    );
+
+    let plan = query.plan(&gateway).unwrap();
+
+    assert_eq!(
+        plan.executors(),
+        vec!["search", "product", "inventory", "account"]
+    );
+    assert!(plan.steps[1..]
+        .iter()
+        .all(|step| step.kind == PlanStepKind::Node && step.depends_on == Some(0)));
+
+    assert_eq!(
+        query.execute(&gateway).await.unwrap(),
+        json!({
+            "search": [
+                { "name": "Product 2", "inStock": false },
+                { "email": "john@doe.com" },
+                { "name": "Product 1", "inStock": true }
+            ]
+        })
+    );
+}
+
+#[async_test]
+async fn union_typename() {
+    let search = TestExecutor::new("search", search::Query, EmptyMutation, EmptySubscription);
+    let mut gateway = common::gateway().await.executor(search);
+
+    gateway.pull("search").await.unwrap();
+
+    let query = QueryBuilder::new(
+        r#"
+            query {
+                __typename
+                search {
+                    kind: __typename
+                    ... on Product {
+                        __typename
+                        name
+                    }
+                }
+                products {
+                    __typename
+                    name
+                }
+            }
+        "#,
+    );
+
+    assert_eq!(
+        query.execute(&gateway).await.unwrap(),
+        json!({
+            "__typename": "Query",
+            "search": [
+                { "kind": "Product", "__typename": "Product", "name": "Product 2" },
+                { "kind": "User" },
+                { "kind": "Product", "__typename": "Product", "name": "Product 1" }
+            ],
+            "products": [
+                { "__typename": "Product", "name": "Product 1" },
+                { "__typename": "Product", "name": "Product 2" }
+            ]
+        })
+    );
+}
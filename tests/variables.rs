@@ -0,0 +1,145 @@
+mod common;
+
+use futures_await_test::async_test;
+use graphql_gateway::{GraphQLResponse, QueryBuilder, Variables};
+use serde_json::json;
+use std::collections::HashMap;
+
+#[derive(serde::Serialize)]
+struct NodeVariables {
+    id: String,
+    name: String,
+}
+
+#[async_test]
+async fn variables_builder() {
+    let source = r#"
+        query NodeQuery($id: ID!, $name: String!) {
+            node(id: $id) {
+                ...on Review {
+                    body
+                    author {
+                        sayHello(name: $name)
+                    }
+                }
+            }
+        }
+    "#;
+
+    let variables = Variables::new()
+        .set("id", "UmV2aWV3OjA=")
+        .set_opt("name", Some("john"))
+        .set_opt::<_, String>("unused", None);
+
+    assert!(variables.get("unused").is_none());
+    assert!(variables.validate(source).is_ok());
+
+    let struct_variables = Variables::from_serialize(&NodeVariables {
+        id: "UmV2aWV3OjA=".to_owned(),
+        name: "john".to_owned(),
+    })
+    .unwrap();
+
+    assert_eq!(struct_variables, variables);
+
+    let mut map = HashMap::new();
+    map.insert("id", "UmV2aWV3OjA=");
+    map.insert("name", "john");
+
+    assert_eq!(Variables::from(map), variables);
+
+    let gateway = common::gateway().await;
+    let query = QueryBuilder::new(source)
+        .operation_name("NodeQuery")
+        .variables(variables);
+
+    assert_eq!(
+        query.execute(&gateway).await.unwrap(),
+        json!({
+            "node": {
+                "body": "Good product",
+                "author": {
+                    "sayHello": "Hello, john"
+                }
+            }
+        })
+    );
+}
+
+#[async_test]
+async fn variables_not_provided() {
+    let source = r#"
+        query NodeQuery($id: ID!, $name: String = "john") {
+            node(id: $id) {
+                id
+            }
+        }
+    "#;
+
+    assert!(Variables::new()
+        .set("name", "john")
+        .validate(source)
+        .is_err());
+
+    let gateway = common::gateway().await;
+    let query = QueryBuilder::new(source).variables(Variables::new());
+    let response = serde_json::to_value(GraphQLResponse(query.execute(&gateway).await)).unwrap();
+
+    assert_eq!(
+        response,
+        json!({
+            "errors": [{ "message": "Variable \"$id\" of required type \"ID!\" was not provided.", "locations": [{ "line": 2, "column": 25 }] }]
+        })
+    );
+}
+
+#[async_test]
+async fn variables_coercion() {
+    let gateway = common::gateway().await;
+    let source = r#"
+        query Reviews($first: Int, $page: ReviewPage) {
+            viewer {
+                reviews(first: $first, page: $page) {
+                    body
+                }
+            }
+        }
+    "#;
+    let query = QueryBuilder::new(source).variables(json!({ "first": 1, "page": null }));
+
+    assert_eq!(
+        query.execute(&gateway).await.unwrap(),
+        json!({ "viewer": { "reviews": [{ "body": "Good product" }] } })
+    );
+
+    let query = QueryBuilder::new(source).variables(
+        Variables::new()
+            .set("first", "10")
+            .set("page", json!({ "first": 1, "limit": 1 })),
+    );
+    let response = serde_json::to_value(GraphQLResponse(query.execute(&gateway).await)).unwrap();
+
+    assert_eq!(
+        response,
+        json!({
+            "errors": [
+                {
+                    "message": "Variable \"$first\" got invalid value \"10\"; Expected type \"Int\" at \"first\".",
+                    "locations": [{ "line": 2, "column": 23 }]
+                },
+                {
+                    "message": "Variable \"$page\" got invalid value {\"first\":1,\"limit\":1}; Field \"limit\" is not defined by type \"ReviewPage\" at \"page\".",
+                    "locations": [{ "line": 2, "column": 36 }]
+                }
+            ]
+        })
+    );
+
+    let query = QueryBuilder::new(source).variables(json!({ "page": { "first": 1 } }));
+    let response = serde_json::to_value(GraphQLResponse(query.execute(&gateway).await)).unwrap();
+
+    assert_eq!(
+        response["errors"][0]["message"],
+        json!("Variable \"$page\" got invalid value {\"first\":1}; Field \"offset\" of required type \"Int!\" was not provided at \"page\".")
+    );
+}
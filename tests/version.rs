@@ -0,0 +1,112 @@
+mod common;
+
+use async_graphql::{EmptyMutation, EmptySubscription};
+use common::{inventory_updated, TestExecutor};
+use futures_await_test::async_test;
+use graphql_gateway::{GraphQLResponse, QueryBuilder};
+use serde_json::json;
+
+#[async_test]
+async fn version() {
+    let inventory = TestExecutor::new(
+        "inventory",
+        inventory_updated::Query {},
+        EmptyMutation,
+        EmptySubscription,
+    );
+
+    let gateway = common::gateway()
+        .await
+        .executor_version("v2", inventory)
+        .deprecate_version("v1", "Use v2 instead.")
+        .build()
+        .await
+        .unwrap();
+
+    let source = r#"
+        query {
+            node(id: "UHJvZHVjdDow") {
+                ... on Product {
+                    name
+                    delivered
+                }
+            }
+        }
+    "#;
+
+    let response = serde_json::to_value(GraphQLResponse(
+        QueryBuilder::new(source).execute(&gateway).await,
+    ))
+    .unwrap();
+
+    assert_eq!(
+        response,
+        json!({
+            "errors": [{ "message": "Cannot query field \"delivered\" on type \"Product\".", "locations": [{ "line": 6, "column": 21 }] }]
+        })
+    );
+
+    assert_eq!(
+        QueryBuilder::new(source)
+            .version("v2")
+            .execute(&gateway)
+            .await
+            .unwrap(),
+        json!({
+            "node": {
+                "name": "Product 1",
+                "delivered": true
+            }
+        })
+    );
+
+    assert_eq!(gateway.version_deprecation("v1"), Some("Use v2 instead."));
+    assert_eq!(gateway.version_deprecation("v2"), None);
+
+    let response = serde_json::to_value(GraphQLResponse(
+        QueryBuilder::new(source)
+            .version("v3")
+            .execute(&gateway)
+            .await,
+    ))
+    .unwrap();
+
+    assert_eq!(
+        response,
+        json!({
+            "errors": [{ "message": "Unknown version \"v3\".", "locations": [{ "line": 0, "column": 0 }] }]
+        })
+    );
+}
+
+#[async_test]
+async fn deprecated_version() {
+    let gateway = common::gateway()
+        .await
+        .executor_version(
+            "v1",
+            TestExecutor::new(
+                "inventory",
+                inventory_updated::Query {},
+                EmptyMutation,
+                EmptySubscription,
+            ),
+        )
+        .deprecate_version("v1", "Use v2 instead.")
+        .build()
+        .await
+        .unwrap();
+
+    let query = QueryBuilder::new("{ products { name } }");
+
+    let response = serde_json::to_value(query.execute_response(&gateway).await).unwrap();
+    assert_eq!(response["extensions"]["deprecation"], json!(null));
+
+    let response =
+        serde_json::to_value(query.version("v1").execute_response(&gateway).await).unwrap();
+    assert_eq!(
+        response["extensions"]["deprecation"],
+        json!({ "version": "v1", "reason": "Use v2 instead." })
+    );
+    assert!(response["data"]["products"].is_array());
+}